@@ -11,11 +11,37 @@ use crate::{
 };
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "EFloat64Raw"))]
 pub struct EFloat64 {
     pub upper_bound: f64,
     pub lower_bound: f64,
 }
 
+// Deserialized intervals must contain a number, so NaN bounds and bounds in the wrong order are rejected.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct EFloat64Raw {
+    upper_bound: f64,
+    lower_bound: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<EFloat64Raw> for EFloat64 {
+    type Error = crate::algebra_error::AlgebraError;
+
+    fn try_from(raw: EFloat64Raw) -> AlgebraResult<EFloat64> {
+        if raw.lower_bound.is_nan() || raw.upper_bound.is_nan() || raw.lower_bound > raw.upper_bound
+        {
+            return Err(crate::algebra_error::AlgebraError::new(format!(
+                "Interval [{}, {}] is empty",
+                raw.lower_bound, raw.upper_bound
+            )));
+        }
+        Ok(EFloat64::new(raw.upper_bound, raw.lower_bound))
+    }
+}
+
 const TWO_PI: f64 = 2.0 * PI;
 
 impl EFloat64 {
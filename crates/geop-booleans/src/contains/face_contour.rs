@@ -16,7 +16,10 @@ pub enum FaceContourContains {
 // Checks if a contour is completely on the surface of a face. Then it checks if the contour is inside, outside, or wiggly.
 pub fn face_contour_contains(face: &Face, contour: &Contour) -> FaceContourContains {
     for edge in contour.edges.iter() {
-        if !(curve_surface_intersection(&edge.curve, &*face.surface).is_curve()) {
+        // Curves that cannot be intersected with the surface do not lie on it
+        if !curve_surface_intersection(&edge.curve, face.surface())
+            .is_ok_and(|intersection| intersection.is_curve())
+        {
             return FaceContourContains::NotSameSurface;
         }
     }
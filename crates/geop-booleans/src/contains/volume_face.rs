@@ -1,6 +1,12 @@
+use geop_geometry::{color::Category10Color, point::Point};
 use geop_topology::{
-    contains::volume_point::{volume_point_contains, VolumePointContains},
+    contains::{
+        face_point::{face_point_contains, FacePointContains},
+        volume_point::{volume_point_contains, VolumePointContains},
+    },
     topology::{face::Face, volume::Volume},
+    topology_error::{TopologyError, TopologyResult, WithContext},
+    topology_scene::TopologyScene,
 };
 
 pub enum VolumeFaceContains {
@@ -10,21 +16,51 @@ pub enum VolumeFaceContains {
     Outside,
 }
 
-pub fn volume_face_contains(volume: &Volume, face: &Face) -> VolumeFaceContains {
-    let p = face.inner_point();
-    println!("Face {}", face);
-    println!("Point {:?}", p);
-    match volume_point_contains(volume, p) {
-        VolumePointContains::Inside => VolumeFaceContains::Inside,
-        VolumePointContains::OnFace(face2) => {
-            if face.normal(p).dot(face2.normal(p)) > 0.0 {
-                VolumeFaceContains::BoundarySameNormals
-            } else {
-                VolumeFaceContains::BoundaryDifferentNormals
+// Points inside of the face, starting with its inner point, followed by the midpoints between the midpoints of its edges.
+fn sample_points(face: &Face) -> Vec<Point> {
+    let mut points = vec![face.inner_point()];
+    let edges = face.all_edges();
+    for (i, e1) in edges.iter().enumerate() {
+        for e2 in edges.iter().skip(i + 1) {
+            let p = face
+                .edge_from_to(e1.get_midpoint(), e2.get_midpoint())
+                .get_midpoint();
+            if face_point_contains(face, p) == FacePointContains::Inside {
+                points.push(p);
             }
         }
-        VolumePointContains::OnEdge(_) => panic!("Should not happen"),
-        VolumePointContains::OnPoint(_) => panic!("Should not happen"),
-        VolumePointContains::Outside => VolumeFaceContains::Outside,
     }
+    points
+}
+
+// The face is expected to be split by the boundary of the volume already, so any point inside of the face can be used.
+// Points that end up on an edge or a corner of the volume, e.g. for faces that touch along an edge, are skipped.
+pub fn volume_face_contains(volume: &Volume, face: &Face) -> TopologyResult<VolumeFaceContains> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            "Check if volume contains face".to_string(),
+            TopologyScene::with_faces(vec![(face.clone(), Category10Color::Red)]),
+        )
+    };
+    for p in sample_points(face) {
+        match volume_point_contains(volume, p).with_context(&context)? {
+            VolumePointContains::Inside => return Ok(VolumeFaceContains::Inside),
+            VolumePointContains::OnFace(face2) => {
+                let alignment = face.normal(p).dot(face2.normal(p));
+                if alignment > 0.0 {
+                    return Ok(VolumeFaceContains::BoundarySameNormals);
+                }
+                if alignment < 0.0 {
+                    return Ok(VolumeFaceContains::BoundaryDifferentNormals);
+                }
+                // The faces touch tangentially at this point only
+            }
+            VolumePointContains::OnEdge(_) | VolumePointContains::OnPoint(_) => {}
+            VolumePointContains::Outside => return Ok(VolumeFaceContains::Outside),
+        }
+    }
+    Err(TopologyError::new(
+        "Face only touches the volume at edges and corners".to_string(),
+    ))
+    .with_context(&context)
 }
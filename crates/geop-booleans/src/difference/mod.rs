@@ -1 +1,2 @@
 pub mod face_face;
+pub mod volume_volume;
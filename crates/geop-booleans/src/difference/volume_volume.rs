@@ -0,0 +1,81 @@
+use geop_topology::{
+    failure_dump::dump_on_failure,
+    topology::{face::Face, volume::Volume},
+    topology_error::TopologyResult,
+};
+
+use crate::{
+    remesh::volume::{normalize_volumes, split_volumes, volume_remesh, VolumeSplit},
+    replay::boolean_inputs,
+};
+
+// Removes the other volume from this one. The faces of the other volume that are inside of this one bound the
+// result, so they are flipped. If the other volume is completely inside, it becomes a cavity.
+pub fn volume_difference(
+    volume_self: &Volume,
+    volume_other: &Volume,
+) -> TopologyResult<Vec<Volume>> {
+    dump_on_failure(
+        "volume_difference",
+        || boolean_inputs(volume_self, volume_other),
+        || {
+            split_volumes(volume_self, volume_other).and_then(|mut splits| {
+                let faces = splits
+                    .drain(..)
+                    .filter_map(|split| match split {
+                        VolumeSplit::AinB(_) => None,
+                        VolumeSplit::AonBSameSide(_) => None,
+                        VolumeSplit::AonBOpSide(face) => Some(face),
+                        VolumeSplit::AoutB(face) => Some(face),
+                        VolumeSplit::BinA(face) => Some(face.flip()),
+                        VolumeSplit::BonASameSide(_) => None,
+                        VolumeSplit::BonAOpSide(_) => None,
+                        VolumeSplit::BoutA(_) => None,
+                    })
+                    .collect::<Vec<Face>>();
+                normalize_volumes(volume_remesh(faces))
+            })
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::point::Point;
+
+    use crate::test_fixtures::{cube, is_inside, pipe_tee};
+
+    use super::*;
+
+    #[test]
+    fn test_volume_difference() {
+        // A pocket in the face at x = 1
+        let a = cube(2.0, Point::zero());
+        let b = cube(1.0, Point::from_f64(1.0, 0.2, 0.3));
+        let difference = volume_difference(&a, &b).unwrap();
+        assert_eq!(difference.len(), 1);
+        assert_eq!(difference[0].boundary().faces().len(), 11);
+
+        // A cube inside of the other one becomes a cavity with normals pointing into the cavity
+        let c = cube(0.5, Point::zero());
+        let difference = volume_difference(&a, &c).unwrap();
+        assert_eq!(difference.len(), 1);
+        assert_eq!(difference[0].boundary().faces().len(), 6);
+        assert_eq!(difference[0].cavities().len(), 1);
+        let face = &difference[0].cavities()[0].faces()[0];
+        let p = face.inner_point();
+        assert!(face.normal(p).dot(p) < 0.0);
+    }
+
+    #[test]
+    fn test_volume_difference_pipe_tee() {
+        // Drilling the thin cylinder through the thick one leaves a hole with a wall curved in two directions
+        let (thick, thin) = pipe_tee();
+        let difference = volume_difference(&thick, &thin).unwrap();
+        assert_eq!(difference.len(), 1);
+        assert_eq!(difference[0].boundary().faces().len(), 4);
+        assert!(!is_inside(&difference[0], Point::zero()));
+        assert!(is_inside(&difference[0], Point::from_f64(0.0, 0.75, 0.0)));
+        assert!(is_inside(&difference[0], Point::from_f64(1.5, 0.0, 0.0)));
+    }
+}
@@ -9,6 +9,7 @@ use crate::remesh::edge::{edge_remesh, edge_split, EdgeRemesh};
 use geop_topology::{
     contains::edge_point::{edge_point_contains, EdgePointContains},
     topology::edge::Edge,
+    topology_error::{TopologyError, TopologyResult},
 };
 // Intersect between start1/2 and end1/2. Returns None if there is no intersection.
 // Keep in mind that all curves are treated as infinite lines, such that start after end means that the line starts, goes to +infinity, goes to -infinty and then ends.
@@ -20,8 +21,13 @@ pub enum EdgeEdgeIntersection {
     Edges(Vec<Edge>),
 }
 
-pub fn edge_edge_intersection(edge_self: &Edge, edge_other: &Edge) -> EdgeEdgeIntersection {
-    match curve_curve_intersection(&edge_self.curve, &edge_other.curve) {
+pub fn edge_edge_intersection(
+    edge_self: &Edge,
+    edge_other: &Edge,
+) -> TopologyResult<EdgeEdgeIntersection> {
+    let intersection = curve_curve_intersection(&edge_self.curve, &edge_other.curve)
+        .map_err(TopologyError::from)?;
+    Ok(match intersection {
         CurveCurveIntersection::Curve(_) => {
             let same_dir = edge_self
                 .curve
@@ -105,5 +111,5 @@ pub fn edge_edge_intersection(edge_self: &Edge, edge_other: &Edge) -> EdgeEdgeIn
             }
         }
         CurveCurveIntersection::None => EdgeEdgeIntersection::None,
-    }
+    })
 }
@@ -2,38 +2,48 @@ use geop_geometry::{
     curve_surface_intersection::curve_surface::{
         curve_surface_intersection, CurveSurfaceIntersection,
     },
-    curves::CurveLike,
+    curves::{curve::Curve, CurveLike},
     point::Point,
 };
 
 use geop_topology::{
     contains::face_point::{face_point_contains, FacePointContains},
     topology::{edge::Edge, face::Face},
+    topology_error::{TopologyError, TopologyResult},
 };
 
 use super::edge_edge::{edge_edge_intersection, EdgeEdgeIntersection};
 
+fn is_closed(curve: &Curve) -> bool {
+    match curve {
+        Curve::Circle(_) | Curve::Ellipse(_) | Curve::SampledCurve(_) => true,
+        Curve::Line(_) | Curve::Helix(_) | Curve::Nurbs(_) => false,
+    }
+}
+
 pub enum FaceEdgeIntersection {
     None,
     Points(Vec<Point>),
     Edges(Vec<Edge>),
 }
 
-pub fn face_edge_intersection(face: &Face, edge: &Edge) -> FaceEdgeIntersection {
-    match curve_surface_intersection(&edge.curve, &face.surface) {
-        CurveSurfaceIntersection::Points(mut points) => FaceEdgeIntersection::Points(
+pub fn face_edge_intersection(face: &Face, edge: &Edge) -> TopologyResult<FaceEdgeIntersection> {
+    let intersection =
+        curve_surface_intersection(&edge.curve, face.surface()).map_err(TopologyError::from)?;
+    match intersection {
+        CurveSurfaceIntersection::Points(mut points) => Ok(FaceEdgeIntersection::Points(
             points
                 .drain(..)
                 .filter(|p| face_point_contains(face, *p) == FacePointContains::Inside)
                 .collect(),
-        ),
+        )),
         CurveSurfaceIntersection::Curve(curve) => {
             let mut points = Vec::<Option<Point>>::new();
             points.push(edge.start);
             points.push(edge.end);
 
             for e in face.all_edges().iter() {
-                match edge_edge_intersection(edge, e) {
+                match edge_edge_intersection(edge, e)? {
                     EdgeEdgeIntersection::Edges(es) => {
                         for e in es.iter() {
                             points.push(e.start);
@@ -52,7 +62,30 @@ pub fn face_edge_intersection(face: &Face, edge: &Edge) -> FaceEdgeIntersection
             // Now sort the points and remove duplicates
             let mut points = curve.sort(points);
             points.dedup();
-            points.push(None); // Add a None to the end to close the loop
+            if is_closed(&curve) && edge.start.is_none() && edge.end.is_none() {
+                // A closed curve is split into pieces between consecutive points, wrapping around at the end
+                points.retain(|p| p.is_some());
+                if points.len() < 2 {
+                    points = vec![None, None];
+                } else {
+                    points.push(points[0]);
+                }
+            } else if is_closed(&curve) {
+                points.push(None); // Add a None to the end to close the loop
+            } else if !matches!(curve, Curve::Nurbs(_)) {
+                // The pieces lie between the points within the edge, and extend to infinity where the edge is unbounded.
+                // A nurbs curve is bounded, so its sorted points already start and end at the ends of the curve.
+                points.retain(|p| match p {
+                    Some(p) => curve.between(*p, edge.start, edge.end).unwrap(),
+                    None => false,
+                });
+                if edge.start.is_none() {
+                    points.insert(0, None);
+                }
+                if edge.end.is_none() {
+                    points.push(None);
+                }
+            }
 
             let mut edges = Vec::<Edge>::new();
             for (p1, p2) in points.iter().zip(points.iter().skip(1)) {
@@ -62,8 +95,8 @@ pub fn face_edge_intersection(face: &Face, edge: &Edge) -> FaceEdgeIntersection
                 }
             }
 
-            return FaceEdgeIntersection::Edges(edges);
+            Ok(FaceEdgeIntersection::Edges(edges))
         }
-        CurveSurfaceIntersection::None => FaceEdgeIntersection::None,
+        CurveSurfaceIntersection::None => Ok(FaceEdgeIntersection::None),
     }
 }
@@ -7,20 +7,28 @@ use geop_geometry::{
 
 use geop_topology::{
     contains::face_point::{face_point_contains, FacePointContains},
+    failure_dump::dump_on_failure,
     topology::{edge::Edge, face::Face},
+    topology_error::{TopologyError, TopologyResult},
 };
 
-use crate::remesh::face::{face_remesh, face_split, normalize_faces, FaceSplit};
+use crate::{
+    remesh::face::{face_remesh, face_split, normalize_faces, FaceSplit},
+    replay::{face_inputs, surface_inputs},
+};
 
 use super::face_edge::{face_edge_intersection, FaceEdgeIntersection};
 
-pub fn face_face_same_surface_intersection(face_self: &Face, face_other: &Face) -> Vec<Face> {
+pub fn face_face_same_surface_intersection(
+    face_self: &Face,
+    face_other: &Face,
+) -> TopologyResult<Vec<Face>> {
     assert!(
-        face_self.surface == face_other.surface,
+        face_self.surface() == face_other.surface(),
         "Faces must have the same surface",
     );
 
-    let edges = face_split(face_self, face_other)
+    let edges = face_split(face_self, face_other)?
         .drain(..)
         .filter(|mode| match mode {
             FaceSplit::AinB(_) => true,
@@ -35,7 +43,7 @@ pub fn face_face_same_surface_intersection(face_self: &Face, face_other: &Face)
         .collect::<Vec<FaceSplit>>();
 
     let contours = face_remesh(edges);
-    return normalize_faces(contours, face_self.surface.clone());
+    Ok(normalize_faces(contours, face_self.surface().clone()))
 }
 
 pub enum FaceFaceIntersection {
@@ -67,8 +75,27 @@ pub enum FaceFaceIntersection {
 //     todo!()
 // }
 
-pub fn face_face_intersection(face_self: &Face, face_other: &Face) -> FaceFaceIntersection {
-    match surface_surface_intersection(&face_self.surface, &face_other.surface) {
+pub fn face_face_intersection(
+    face_self: &Face,
+    face_other: &Face,
+) -> TopologyResult<FaceFaceIntersection> {
+    dump_on_failure(
+        "face_face_intersection",
+        || face_inputs(face_self, face_other),
+        || intersect_faces(face_self, face_other),
+    )
+}
+
+fn intersect_faces(face_self: &Face, face_other: &Face) -> TopologyResult<FaceFaceIntersection> {
+    let intersection = dump_on_failure(
+        "surface_surface_intersection",
+        || surface_inputs(face_self.surface(), face_other.surface()),
+        || {
+            surface_surface_intersection(face_self.surface(), face_other.surface())
+                .map_err(TopologyError::from)
+        },
+    )?;
+    Ok(match intersection {
         FaceSurfaceIntersection::None => FaceFaceIntersection::None,
         FaceSurfaceIntersection::CurvesAndPoints(curves, points) => {
             let mut points = points
@@ -82,7 +109,7 @@ pub fn face_face_intersection(face_self: &Face, face_other: &Face) -> FaceFaceIn
 
             let mut edges = Vec::<Edge>::new();
             for curve in curves.iter() {
-                match face_edge_intersection(face_self, &Edge::from_curve(curve.clone())) {
+                match face_edge_intersection(face_self, &Edge::from_curve(curve.clone()))? {
                     FaceEdgeIntersection::Points(ps) => {
                         for p in ps.iter() {
                             if face_point_contains(face_other, *p) == FacePointContains::Inside {
@@ -92,7 +119,7 @@ pub fn face_face_intersection(face_self: &Face, face_other: &Face) -> FaceFaceIn
                     }
                     FaceEdgeIntersection::Edges(es) => {
                         for e in es.iter() {
-                            match face_edge_intersection(face_other, e) {
+                            match face_edge_intersection(face_other, e)? {
                                 FaceEdgeIntersection::Points(ps) => {
                                     points.extend(ps);
                                 }
@@ -110,16 +137,16 @@ pub fn face_face_intersection(face_self: &Face, face_other: &Face) -> FaceFaceIn
             FaceFaceIntersection::EdgesAndPoints(points, edges)
         }
         FaceSurfaceIntersection::Surface(_surface) => {
-            if face_self.surface == face_other.surface {
+            if face_self.surface() == face_other.surface() {
                 FaceFaceIntersection::Faces(face_face_same_surface_intersection(
                     face_self, face_other,
-                ))
+                )?)
             } else {
                 FaceFaceIntersection::Faces(face_face_same_surface_intersection(
                     face_self,
                     &face_other.flip(),
-                ))
+                )?)
             }
         }
-    }
+    })
 }
@@ -1,3 +1,4 @@
 pub mod edge_edge;
 pub mod face_edge;
 pub mod face_face;
+pub mod volume_volume;
@@ -0,0 +1,65 @@
+use geop_topology::{
+    failure_dump::dump_on_failure,
+    topology::{face::Face, volume::Volume},
+    topology_error::TopologyResult,
+};
+
+use crate::{
+    remesh::volume::{normalize_volumes, split_volumes, volume_remesh, VolumeSplit},
+    replay::boolean_inputs,
+};
+
+// The common part of both volumes, which is empty if they do not overlap.
+pub fn volume_intersection(
+    volume_self: &Volume,
+    volume_other: &Volume,
+) -> TopologyResult<Vec<Volume>> {
+    dump_on_failure(
+        "volume_intersection",
+        || boolean_inputs(volume_self, volume_other),
+        || {
+            split_volumes(volume_self, volume_other).and_then(|mut splits| {
+                let faces = splits
+                    .drain(..)
+                    .filter_map(|split| match split {
+                        VolumeSplit::AinB(face) => Some(face),
+                        VolumeSplit::AonBSameSide(face) => Some(face),
+                        VolumeSplit::AonBOpSide(_) => None,
+                        VolumeSplit::AoutB(_) => None,
+                        VolumeSplit::BinA(face) => Some(face),
+                        VolumeSplit::BonASameSide(_) => None,
+                        VolumeSplit::BonAOpSide(_) => None,
+                        VolumeSplit::BoutA(_) => None,
+                    })
+                    .collect::<Vec<Face>>();
+                normalize_volumes(volume_remesh(faces))
+            })
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::point::Point;
+
+    use crate::test_fixtures::cube;
+
+    use super::*;
+
+    #[test]
+    fn test_volume_intersection() {
+        // The part of the small cube with x < 1
+        let a = cube(2.0, Point::zero());
+        let b = cube(1.0, Point::from_f64(1.0, 0.2, 0.3));
+        let intersection = volume_intersection(&a, &b).unwrap();
+        assert_eq!(intersection.len(), 1);
+        assert_eq!(intersection[0].boundary().faces().len(), 6);
+        for face in intersection[0].boundary().faces().iter() {
+            let p = face.inner_point();
+            assert!(p.x >= 0.5 && p.x <= 1.0);
+        }
+
+        let c = cube(1.0, Point::from_f64(4.0, 0.0, 0.0));
+        assert!(volume_intersection(&a, &c).unwrap().is_empty());
+    }
+}
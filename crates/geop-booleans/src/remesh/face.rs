@@ -4,7 +4,10 @@ use std::{
 };
 
 use geop_geometry::surfaces::surface::Surface;
-use geop_topology::topology::{contour::Contour, edge::Edge, face::Face};
+use geop_topology::{
+    topology::{bvh::edge_bvh, contour::Contour, edge::Edge, face::Face},
+    topology_error::TopologyResult,
+};
 
 use crate::{
     contains::{
@@ -17,30 +20,32 @@ use crate::{
 
 use geop_geometry::point::Point;
 
-pub fn face_split_points(face_self: &Face, face_other: &Face) -> Vec<Point> {
+pub fn face_split_points(face_self: &Face, face_other: &Face) -> TopologyResult<Vec<Point>> {
     let mut intersections = Vec::<Point>::new();
-    for es in face_self.all_edges().iter() {
-        for eo in face_other.all_edges().iter() {
-            match edge_edge_intersection(&es, &eo) {
-                EdgeEdgeIntersection::Points(points) => {
-                    intersections.extend(points);
-                }
-                EdgeEdgeIntersection::Edges(edges) => {
-                    for edge in edges {
-                        if let Some(p) = edge.start {
-                            intersections.push(p);
-                        }
-                        if let Some(p) = edge.end {
-                            intersections.push(p);
-                        }
+    let edges_self = face_self.all_edges();
+    let edges_other = face_other.all_edges();
+    // Only edges with overlapping bounding boxes can intersect
+    for (i, j) in edge_bvh(&edges_self).query_pairs(&edge_bvh(&edges_other)) {
+        let (es, eo) = (&edges_self[i], &edges_other[j]);
+        match edge_edge_intersection(es, eo)? {
+            EdgeEdgeIntersection::Points(points) => {
+                intersections.extend(points);
+            }
+            EdgeEdgeIntersection::Edges(edges) => {
+                for edge in edges {
+                    if let Some(p) = edge.start {
+                        intersections.push(p);
+                    }
+                    if let Some(p) = edge.end {
+                        intersections.push(p);
                     }
                 }
-                EdgeEdgeIntersection::None => {}
             }
+            EdgeEdgeIntersection::None => {}
         }
     }
 
-    intersections
+    Ok(intersections)
 }
 
 #[derive(Debug)]
@@ -55,8 +60,8 @@ pub enum FaceSplit {
     BoutA(Edge),
 }
 
-pub fn face_split(face_self: &Face, face_other: &Face) -> Vec<FaceSplit> {
-    assert!(face_self.surface == face_other.surface);
+pub fn face_split(face_self: &Face, face_other: &Face) -> TopologyResult<Vec<FaceSplit>> {
+    assert!(face_self.surface() == face_other.surface());
     println!("face_split");
     println!("Face_self {:}", face_self);
     println!("Face_other {:}", face_other);
@@ -64,7 +69,7 @@ pub fn face_split(face_self: &Face, face_other: &Face) -> Vec<FaceSplit> {
     // debug_data::add_face(face_self.clone(), DebugColor::Red);
     // debug_data::add_face(face_other.clone(), DebugColor::Blue);
 
-    let intersections = face_split_points(face_self, face_other);
+    let intersections = face_split_points(face_self, face_other)?;
 
     // println!("intersections: {:}", intersections.len());
     // for point in intersections.iter() {
@@ -100,7 +105,7 @@ pub fn face_split(face_self: &Face, face_other: &Face) -> Vec<FaceSplit> {
         println!("Edge: {:?}", edge);
     }
 
-    res
+    Ok(res)
 }
 
 pub fn face_remesh(mut edges_intermediate: Vec<FaceSplit>) -> Vec<Contour> {
@@ -203,16 +208,14 @@ impl ContourHierarchy {
 
     pub fn as_faces(&self, surface: Rc<Surface>) -> Vec<Face> {
         let mut faces = Vec::<Face>::new();
-        let mut face = Face::new(vec![self.contour.clone()], surface.clone());
-        // if let Some(mut face) = face {
+        let mut boundaries = vec![self.contour.clone()];
         for child in self.children.iter() {
-            face.boundaries.push(child.contour.clone());
+            boundaries.push(child.contour.clone());
             for child2 in child.children.iter() {
                 faces.extend(child2.as_faces(surface.clone()));
             }
         }
-        faces.push(face);
-        // }
+        faces.push(Face::new(boundaries, surface));
         faces
     }
 }
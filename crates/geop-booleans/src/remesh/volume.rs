@@ -1,29 +1,51 @@
 use crate::{
     contains::volume_face::{volume_face_contains, VolumeFaceContains},
     intersections::face_face::{face_face_intersection, FaceFaceIntersection},
+    replay::boolean_inputs,
     split_if_necessary::edge_split_face::split_faces_by_edges_if_necessary,
 };
-use geop_topology::topology::{edge::Edge, face::Face, volume::Volume};
+use geop_geometry::point::Point;
+use geop_topology::{
+    contains::{
+        edge_point::{edge_point_contains, EdgePointContains},
+        volume_point::{volume_point_contains, VolumePointContains},
+    },
+    failure_dump::dump_on_failure,
+    topology::{bvh::edge_bvh, edge::Edge, face::Face, shell::Shell, volume::Volume},
+    topology_error::{TopologyError, TopologyResult},
+};
 
 // Points are ignored for now.
-pub fn volume_split_edges(volume_self: &Volume, volume_other: &Volume) -> Vec<Edge> {
+pub fn volume_split_edges(
+    volume_self: &Volume,
+    volume_other: &Volume,
+) -> TopologyResult<Vec<Edge>> {
     let mut edges = Vec::<Edge>::new();
-    for face_self in volume_self.all_faces().iter() {
-        for face_other in volume_other.all_faces().iter() {
-            match face_face_intersection(face_self, face_other) {
-                FaceFaceIntersection::EdgesAndPoints(_points, new_edges) => {
-                    edges.extend(new_edges);
-                }
-                FaceFaceIntersection::Faces(faces) => {
-                    for edge in faces.into_iter().flat_map(|face| face.all_edges()) {
-                        edges.push(edge);
-                    }
+    // Volumes whose bounding boxes are apart cannot intersect
+    if let (Ok(box_self), Ok(box_other)) = (volume_self.bounding_box(), volume_other.bounding_box())
+    {
+        if !box_self.intersects(&box_other) {
+            return Ok(edges);
+        }
+    }
+    let faces_self = volume_self.all_faces();
+    let faces_other = volume_other.all_faces();
+    // Only faces with overlapping bounding boxes can intersect
+    for (i, j) in volume_self.face_bvh().query_pairs(volume_other.face_bvh()) {
+        let (face_self, face_other) = (&faces_self[i], &faces_other[j]);
+        match face_face_intersection(face_self, face_other)? {
+            FaceFaceIntersection::EdgesAndPoints(_points, new_edges) => {
+                edges.extend(new_edges);
+            }
+            FaceFaceIntersection::Faces(faces) => {
+                for edge in faces.into_iter().flat_map(|face| face.all_edges()) {
+                    edges.push(edge);
                 }
-                FaceFaceIntersection::None => {}
             }
+            FaceFaceIntersection::None => {}
         }
     }
-    edges
+    Ok(edges)
 }
 
 #[derive(Debug)]
@@ -53,29 +75,206 @@ impl VolumeSplit {
     }
 }
 
-pub fn volume_split(volume_self: &Volume, volume_other: &Volume) -> Vec<VolumeSplit> {
-    let intersections = volume_split_edges(volume_self, volume_other);
+pub fn volume_split(
+    volume_self: &Volume,
+    volume_other: &Volume,
+) -> TopologyResult<Vec<VolumeSplit>> {
+    dump_on_failure(
+        "volume_split",
+        || boolean_inputs(volume_self, volume_other),
+        || split_volumes(volume_self, volume_other),
+    )
+}
+
+// The split without a failure dump, for the boolean operations that dump their own inputs.
+pub(crate) fn split_volumes(
+    volume_self: &Volume,
+    volume_other: &Volume,
+) -> TopologyResult<Vec<VolumeSplit>> {
+    let intersections = volume_split_edges(volume_self, volume_other)?;
 
     let faces_self = split_faces_by_edges_if_necessary(volume_self.all_faces(), &intersections);
     let faces_other = split_faces_by_edges_if_necessary(volume_other.all_faces(), &intersections);
 
-    faces_self
-        .into_iter()
-        .map(|face| match volume_face_contains(volume_other, &face) {
+    let mut splits = Vec::<VolumeSplit>::new();
+    for face in faces_self {
+        splits.push(match volume_face_contains(volume_other, &face)? {
             VolumeFaceContains::Inside => VolumeSplit::AinB(face),
             VolumeFaceContains::BoundarySameNormals => VolumeSplit::AonBSameSide(face),
             VolumeFaceContains::BoundaryDifferentNormals => VolumeSplit::AonBOpSide(face),
             VolumeFaceContains::Outside => VolumeSplit::AoutB(face),
-        })
-        .chain(
-            faces_other
+        });
+    }
+    for face in faces_other {
+        splits.push(match volume_face_contains(volume_self, &face)? {
+            VolumeFaceContains::Inside => VolumeSplit::BinA(face),
+            VolumeFaceContains::BoundarySameNormals => VolumeSplit::BonASameSide(face),
+            VolumeFaceContains::BoundaryDifferentNormals => VolumeSplit::BonAOpSide(face),
+            VolumeFaceContains::Outside => VolumeSplit::BoutA(face),
+        });
+    }
+    Ok(splits)
+}
+
+// Direction from a point on the boundary of the face into the face.
+fn into_face(face: &Face, p: Point) -> Point {
+    face.normal(p)
+        .cross(*face.boundary_tangent(p).expect_on_edge())
+}
+
+// If more than two faces meet at an edge, e.g. for volumes that touch along an edge, the face is connected to the
+// face that comes first when turning around the edge towards the material behind the face.
+fn radial_partner(faces: &[Face], i: usize, around: &[usize], edge: &Edge, p: Point) -> usize {
+    let tangent = edge.tangent(p);
+    let direction = into_face(&faces[i], p);
+    let sign = match tangent.cross(direction).dot(faces[i].normal(p)) > 0.0 {
+        true => -1.0,
+        false => 1.0,
+    };
+    let angle = |j: usize| {
+        let angle = sign
+            * direction
+                .angle2(into_face(&faces[j], p), tangent)
+                .expect("Directions into faces are not zero")
+                .to_f64();
+        match angle <= 0.0 {
+            true => angle + 2.0 * std::f64::consts::PI,
+            false => angle,
+        }
+    };
+    *around
+        .iter()
+        .min_by(|a, b| angle(**a).total_cmp(&angle(**b)))
+        .expect("There are other faces at the edge")
+}
+
+// Groups faces that are connected via their edges into shells. The edges do not have to match exactly,
+// because a face may have been split at a point that the neighbouring face does not know about.
+pub fn volume_remesh(faces: Vec<Face>) -> Vec<Shell> {
+    let n = faces.len();
+    let mut neighbours = vec![Vec::<usize>::new(); n];
+    let (edges, face_of): (Vec<Edge>, Vec<usize>) = faces
+        .iter()
+        .enumerate()
+        .flat_map(|(i, face)| face.all_edges().into_iter().map(move |edge| (edge, i)))
+        .unzip();
+    let bvh = edge_bvh(&edges);
+    for i in 0..n {
+        for edge in faces[i].all_edges() {
+            let p = edge.get_midpoint();
+            let mut around = bvh
+                .query_point(p)
                 .into_iter()
-                .map(|face| match volume_face_contains(volume_self, &face) {
-                    VolumeFaceContains::Inside => VolumeSplit::BinA(face),
-                    VolumeFaceContains::BoundarySameNormals => VolumeSplit::BonASameSide(face),
-                    VolumeFaceContains::BoundaryDifferentNormals => VolumeSplit::BonAOpSide(face),
-                    VolumeFaceContains::Outside => VolumeSplit::BoutA(face),
-                }),
-        )
-        .collect()
+                .filter(|k| {
+                    face_of[*k] != i
+                        && edge_point_contains(&edges[*k], p) == EdgePointContains::Inside
+                })
+                .map(|k| face_of[k])
+                .collect::<Vec<usize>>();
+            around.sort_unstable();
+            around.dedup();
+            let partner = match around.len() {
+                0 => continue,
+                1 => around[0],
+                _ => radial_partner(&faces, i, &around, &edge, p),
+            };
+            neighbours[i].push(partner);
+            neighbours[partner].push(i);
+        }
+    }
+
+    let mut shell_of = vec![None; n];
+    let mut shells = Vec::<Vec<Face>>::new();
+    for start in 0..n {
+        if shell_of[start].is_some() {
+            continue;
+        }
+        let mut stack = vec![start];
+        shell_of[start] = Some(shells.len());
+        let mut shell = Vec::<Face>::new();
+        while let Some(i) = stack.pop() {
+            shell.push(faces[i].clone());
+            for j in neighbours[i].iter() {
+                if shell_of[*j].is_none() {
+                    shell_of[*j] = Some(shells.len());
+                    stack.push(*j);
+                }
+            }
+        }
+        shells.push(shell);
+    }
+    shells.into_iter().map(Shell::new).collect()
+}
+
+// A closed shell whose normals point inwards bounds a cavity. Containment is decided by the normals of the shell,
+// so a point far away from the shell is inside of it in that case.
+fn is_cavity(shell: &Shell) -> TopologyResult<bool> {
+    // The box is cached on the shell, so the volume below shares it
+    let bounding_box = shell.bounding_box()?;
+    let far =
+        bounding_box.max + (bounding_box.max - bounding_box.min) + Point::from_f64(1.0, 1.0, 1.0);
+    let volume = Volume::new(shell.clone(), vec![]);
+    Ok(matches!(
+        volume_point_contains(&volume, far)?,
+        VolumePointContains::Inside
+    ))
+}
+
+fn shell_contains(shell: &Shell, other: &Shell) -> TopologyResult<bool> {
+    let point = other.faces()[0]
+        .try_inner_point()
+        .ok_or_else(|| TopologyError::new("Face of the shell has no inner point".to_string()))?;
+    let volume = Volume::new(shell.clone(), vec![]);
+    Ok(matches!(
+        volume_point_contains(&volume, point)?,
+        VolumePointContains::Inside
+    ))
+}
+
+// Builds volumes from closed shells. Every cavity belongs to the innermost boundary that contains it.
+pub fn normalize_volumes(shells: Vec<Shell>) -> TopologyResult<Vec<Volume>> {
+    let mut boundaries = Vec::<Shell>::new();
+    let mut cavities = Vec::<Shell>::new();
+    for shell in shells {
+        match is_cavity(&shell)? {
+            true => cavities.push(shell),
+            false => boundaries.push(shell),
+        }
+    }
+
+    // Depth of nesting, i.e. the number of boundaries that contain a boundary
+    let mut depths = Vec::<usize>::new();
+    for (i, shell) in boundaries.iter().enumerate() {
+        let mut depth = 0;
+        for (j, other) in boundaries.iter().enumerate() {
+            if i != j && shell_contains(other, shell)? {
+                depth += 1;
+            }
+        }
+        depths.push(depth);
+    }
+
+    let mut volumes = boundaries
+        .into_iter()
+        .map(|boundary| Volume::new(boundary, vec![]))
+        .collect::<Vec<Volume>>();
+    for cavity in cavities {
+        let mut parent: Option<usize> = None;
+        for i in 0..volumes.len() {
+            if shell_contains(volumes[i].boundary(), &cavity)?
+                && parent.is_none_or(|parent| depths[i] > depths[parent])
+            {
+                parent = Some(i);
+            }
+        }
+        match parent {
+            Some(i) => volumes[i].push_cavity(cavity),
+            None => {
+                return Err(TopologyError::new(
+                    "Cavity is not inside of any volume".to_string(),
+                ))
+            }
+        }
+    }
+    Ok(volumes)
 }
@@ -0,0 +1,215 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    surface_surface_intersection::surface_surface::{
+        surface_surface_intersection, FaceSurfaceIntersection,
+    },
+    surfaces::surface::Surface,
+};
+use geop_topology::{
+    failure_dump::FailureDump,
+    topology::{
+        edge::Edge,
+        face::Face,
+        scene::{Color, Scene},
+        volume::Volume,
+    },
+    topology_error::{TopologyError, TopologyResult},
+};
+
+use crate::{
+    difference::volume_volume::volume_difference,
+    intersections::{
+        face_face::{face_face_intersection, FaceFaceIntersection},
+        volume_volume::volume_intersection,
+    },
+    remesh::volume::volume_split,
+    union::volume::volume_union,
+};
+
+// The inputs of a boolean operation as they are written to failure dumps.
+pub fn boolean_inputs(volume_self: &Volume, volume_other: &Volume) -> Scene {
+    Scene::new(
+        vec![
+            (volume_self.clone(), Color::red()),
+            (volume_other.clone(), Color::blue()),
+        ],
+        vec![],
+        vec![],
+        vec![],
+    )
+}
+
+// The inputs of an intersection of two faces as they are written to failure dumps.
+pub fn face_inputs(face_self: &Face, face_other: &Face) -> Scene {
+    Scene::new(
+        vec![],
+        vec![
+            (face_self.clone(), Color::red()),
+            (face_other.clone(), Color::blue()),
+        ],
+        vec![],
+        vec![],
+    )
+}
+
+// Surfaces are written as faces without boundaries.
+pub fn surface_inputs(surface_self: &Rc<Surface>, surface_other: &Rc<Surface>) -> Scene {
+    face_inputs(
+        &Face::new_unchecked(vec![], surface_self.clone()),
+        &Face::new_unchecked(vec![], surface_other.clone()),
+    )
+}
+
+fn volumes(volumes: Vec<Volume>) -> Scene {
+    Scene::new(
+        volumes
+            .into_iter()
+            .map(|volume| (volume, Color::gray()))
+            .collect(),
+        vec![],
+        vec![],
+        vec![],
+    )
+}
+
+// Runs the failed operation of the dump again, e.g. in a regression test, and returns its result as a scene.
+pub fn replay(dump: &FailureDump) -> TopologyResult<Scene> {
+    let operation = dump.operation.as_str();
+    match operation {
+        "volume_union" | "volume_difference" | "volume_intersection" | "volume_split" => {
+            let (volume_self, volume_other) = match dump.inputs.volumes.as_slice() {
+                [(volume_self, _), (volume_other, _)] => (volume_self, volume_other),
+                _ => {
+                    return Err(TopologyError::new(format!(
+                        "The dump of {} does not contain two volumes",
+                        operation
+                    )))
+                }
+            };
+            match operation {
+                "volume_union" => volume_union(volume_self, volume_other).map(volumes),
+                "volume_difference" => volume_difference(volume_self, volume_other).map(volumes),
+                "volume_intersection" => {
+                    volume_intersection(volume_self, volume_other).map(volumes)
+                }
+                _ => volume_split(volume_self, volume_other).map(|splits| {
+                    Scene::new(
+                        vec![],
+                        splits
+                            .iter()
+                            .map(|split| (split.face().clone(), Color::gray()))
+                            .collect(),
+                        vec![],
+                        vec![],
+                    )
+                }),
+            }
+        }
+        "face_face_intersection" | "surface_surface_intersection" => {
+            let (face_self, face_other) = match dump.inputs.faces.as_slice() {
+                [(face_self, _), (face_other, _)] => (face_self, face_other),
+                _ => {
+                    return Err(TopologyError::new(format!(
+                        "The dump of {} does not contain two faces",
+                        operation
+                    )))
+                }
+            };
+            let faces = |faces: Vec<Face>| faces.into_iter().map(|f| (f, Color::gray())).collect();
+            let edges = |edges: Vec<Edge>| edges.into_iter().map(|e| (e, Color::black())).collect();
+            let points = |points: Vec<_>| points.into_iter().map(|p| (p, Color::black())).collect();
+            if operation == "face_face_intersection" {
+                return Ok(match face_face_intersection(face_self, face_other)? {
+                    FaceFaceIntersection::None => Scene::empty(),
+                    FaceFaceIntersection::EdgesAndPoints(ps, es) => {
+                        Scene::new(vec![], vec![], edges(es), points(ps))
+                    }
+                    FaceFaceIntersection::Faces(fs) => {
+                        Scene::new(vec![], faces(fs), vec![], vec![])
+                    }
+                });
+            }
+            Ok(
+                match surface_surface_intersection(face_self.surface(), face_other.surface())? {
+                    FaceSurfaceIntersection::None => Scene::empty(),
+                    FaceSurfaceIntersection::CurvesAndPoints(curves, ps) => Scene::new(
+                        vec![],
+                        vec![],
+                        edges(curves.into_iter().map(Edge::from_curve).collect()),
+                        points(ps),
+                    ),
+                    FaceSurfaceIntersection::Surface(surface) => Scene::new(
+                        vec![],
+                        faces(vec![Face::new_unchecked(vec![], Rc::new(surface))]),
+                        vec![],
+                        vec![],
+                    ),
+                },
+            )
+        }
+        operation => Err(TopologyError::new(format!("Cannot replay {}", operation))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::{efloat::EFloat64, point::Point, transforms::Transform};
+    use geop_topology::primitive_objects::volumes::cube::primitive_cube;
+
+    use super::*;
+
+    #[test]
+    fn test_replay() {
+        let a = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let b = a.transform(Transform::from_translation(Point::from_f64(1.0, 0.0, 0.0)));
+        let dump = FailureDump {
+            operation: "volume_intersection".to_string(),
+            inputs: boolean_inputs(&a, &b),
+            errors: vec![],
+        };
+        let intersection = replay(&dump).unwrap();
+        assert_eq!(intersection.volumes.len(), 1);
+        assert_eq!(intersection.volumes[0].0.boundary().faces().len(), 6);
+
+        let dump = FailureDump {
+            operation: "face_face_intersection".to_string(),
+            inputs: face_inputs(&a.all_faces()[0], &b.all_faces()[0]),
+            errors: vec![],
+        };
+        assert!(replay(&dump).is_ok());
+
+        let dump = FailureDump {
+            operation: "volume_fillet".to_string(),
+            ..dump
+        };
+        assert!(replay(&dump).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_replay_written_dump() {
+        use geop_topology::failure_dump::{load_failure_dump, write_failure_dump};
+
+        // The dump is written into a directory of this test, without the global dump directory
+        let directory = std::env::temp_dir().join(format!("geop-replay-{}", std::process::id()));
+        let a = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let b = a.transform(Transform::from_translation(Point::from_f64(1.0, 0.0, 0.0)));
+        let error = TopologyError::new("Failed in the field".to_string());
+        let dump = FailureDump::new("volume_union", boolean_inputs(&a, &b), &error);
+        let path = write_failure_dump(&dump, directory.clone())
+            .unwrap()
+            .unwrap();
+
+        let dump = load_failure_dump(&path).unwrap();
+        assert_eq!(dump.errors.last().unwrap().message, "Failed in the field");
+        let union = replay(&dump).unwrap();
+        let expected = volume_union(&a, &b).unwrap();
+        assert_eq!(union.volumes.len(), 1);
+        assert_eq!(
+            union.volumes[0].0.boundary().faces().len(),
+            expected[0].boundary().faces().len()
+        );
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
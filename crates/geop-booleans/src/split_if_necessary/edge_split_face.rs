@@ -3,8 +3,15 @@
 //     split_if_necessary::point_split_edge::split_contours_by_points_if_necessary,
 // };
 
+use std::collections::VecDeque;
+
+use geop_geometry::point::Point;
 use geop_topology::{
-    contains::{contour_point::contour_point_contains, edge_point::EdgePointContains},
+    contains::{
+        contour_point::contour_point_contains,
+        edge_point::EdgePointContains,
+        face_point::{face_point_contains, FacePointContains},
+    },
     topology::{contour::Contour, edge::Edge, face::Face},
 };
 
@@ -18,13 +25,17 @@ use super::point_split_edge::split_contours_by_points_if_necessary;
 pub fn split_face_by_edge_if_necessary(face: &Face, edge: &Edge) -> Vec<Face> {
     match face_edge_contains(face, edge) {
         FaceEdgeContains::Inside => {
+            if edge.start.is_none() && edge.end.is_none() {
+                // A closed edge, e.g. the intersection loop of two pipes
+                return split_face_by_loop(face, vec![edge.clone()]);
+            }
             if edge.start.is_none() || edge.end.is_none() {
                 todo!("Not yet implemented. Edge should have start and end points");
             }
 
             let split_points = vec![edge.start.unwrap(), edge.end.unwrap()];
 
-            let contours = face.boundaries.clone();
+            let contours = face.boundaries().to_vec();
             let contours = split_contours_by_points_if_necessary(contours, &split_points);
             let start_contour =
                 contours
@@ -128,7 +139,7 @@ pub fn split_face_by_edge_if_necessary(face: &Face, edge: &Edge) -> Vec<Face> {
                     }
                 },
             }
-            return normalize_faces(new_contours, face.surface.clone());
+            return normalize_faces(new_contours, face.surface().clone());
         }
         FaceEdgeContains::Outside => {
             vec![face.clone()]
@@ -145,12 +156,158 @@ pub fn split_face_by_edge_if_necessary(face: &Face, edge: &Edge) -> Vec<Face> {
     }
 }
 
+// Joins the edges that meet at their end points into chains, such that e.g. a loop of intersection edges is cut out
+// of the face at once. Chains are not continued through points on the boundary of the face.
+fn chain_edges(face: &Face, edges: Vec<Edge>) -> Vec<Vec<Edge>> {
+    let on_boundary = |p: &Point| {
+        face.boundaries()
+            .iter()
+            .any(|contour| contour_point_contains(contour, *p) != EdgePointContains::Outside)
+    };
+    let mut chains = Vec::<Vec<Edge>>::new();
+    let mut remaining = edges;
+    while let Some(edge) = remaining.pop() {
+        // The same intersection edge can be found multiple times
+        remaining.retain(|other| *other != edge);
+        let mut chain = VecDeque::from([edge]);
+        loop {
+            let end = chain.back().unwrap().end;
+            let start = chain.front().unwrap().start;
+            if end.is_some() && end == start {
+                break;
+            }
+            if let Some(end) = end.filter(|p| !on_boundary(p)) {
+                if let Some(i) = remaining
+                    .iter()
+                    .position(|e| e.start == Some(end) || e.end == Some(end))
+                {
+                    let next = remaining.remove(i);
+                    chain.push_back(match next.start == Some(end) {
+                        true => next,
+                        false => next.flip(),
+                    });
+                    continue;
+                }
+            }
+            if let Some(start) = start.filter(|p| !on_boundary(p)) {
+                if let Some(i) = remaining
+                    .iter()
+                    .position(|e| e.start == Some(start) || e.end == Some(start))
+                {
+                    let previous = remaining.remove(i);
+                    chain.push_front(match previous.end == Some(start) {
+                        true => previous,
+                        false => previous.flip(),
+                    });
+                    continue;
+                }
+            }
+            break;
+        }
+        chains.push(chain.into());
+    }
+    chains
+}
+
+// Splits the face by a loop inside of it. The loop either cuts a hole into the face and bounds a new face,
+// or, e.g. on a cylinder that it runs around, separates the boundaries of the face into two faces.
+// Each side gets the boundaries that lie on it, and the side to the left of the loop runs along it.
+fn split_face_by_loop(face: &Face, edges: Vec<Edge>) -> Vec<Face> {
+    let reversed = edges.iter().rev().map(|e| e.flip()).collect::<Vec<Edge>>();
+    let loop_face = Face::new_unchecked(vec![Contour::new(edges.clone())], face.surface().clone());
+    let mut left = vec![Contour::new(edges)];
+    let mut right = vec![Contour::new(reversed)];
+    for contour in face.boundaries().iter() {
+        match face_point_contains(&loop_face, contour.edges[0].get_midpoint()) {
+            FacePointContains::Inside => left.push(contour.clone()),
+            _ => right.push(contour.clone()),
+        }
+    }
+    vec![
+        Face::new(right, face.surface().clone()),
+        Face::new(left, face.surface().clone()),
+    ]
+}
+
+// Splits the face by a chain of edges, which either forms a loop inside of the face or connects two points of the boundary.
+pub fn split_face_by_chain_if_necessary(face: &Face, chain: &[Edge]) -> Vec<Face> {
+    if chain.len() == 1 {
+        return split_face_by_edge_if_necessary(face, &chain[0]);
+    }
+    if chain
+        .iter()
+        .any(|edge| face_edge_contains(face, edge) != FaceEdgeContains::Inside)
+    {
+        return vec![face.clone()];
+    }
+    let start = chain[0].start.unwrap();
+    let end = chain[chain.len() - 1].end.unwrap();
+    let reversed = chain.iter().rev().map(|e| e.flip()).collect::<Vec<Edge>>();
+
+    if start == end {
+        return split_face_by_loop(face, chain.to_vec());
+    }
+
+    let contours =
+        split_contours_by_points_if_necessary(face.boundaries().to_vec(), &vec![start, end]);
+    let contour_at = |p: Point| {
+        contours
+            .iter()
+            .position(|c| matches!(contour_point_contains(c, p), EdgePointContains::OnPoint(_)))
+    };
+    let mut new_contours = Vec::<Contour>::new();
+    match (contour_at(start), contour_at(end)) {
+        (Some(i), Some(j)) if i == j => {
+            let mut edges = contours[i].get_subcurve(end, start);
+            edges.extend(chain.iter().cloned());
+            new_contours.push(Contour::new(edges));
+            let mut edges = contours[i].get_subcurve(start, end);
+            edges.extend(reversed);
+            new_contours.push(Contour::new(edges));
+            for (k, contour) in contours.iter().enumerate() {
+                if k != i {
+                    new_contours.push(contour.clone());
+                }
+            }
+        }
+        (Some(i), Some(j)) => {
+            let mut edges = contours[i].get_subcurve_single_point(start);
+            edges.extend(chain.iter().cloned());
+            edges.extend(contours[j].get_subcurve_single_point(end));
+            edges.extend(reversed);
+            new_contours.push(Contour::new(edges));
+            for (k, contour) in contours.iter().enumerate() {
+                if k != i && k != j {
+                    new_contours.push(contour.clone());
+                }
+            }
+        }
+        _ => {
+            // Dangling chains are split edge by edge
+            let mut result = vec![face.clone()];
+            for edge in chain {
+                result = result
+                    .iter()
+                    .flat_map(|face| split_face_by_edge_if_necessary(face, edge))
+                    .collect();
+            }
+            return result;
+        }
+    }
+    normalize_faces(new_contours, face.surface().clone())
+}
+
 pub fn split_face_by_edges_if_necessary(face: &Face, edges: &[Edge]) -> Vec<Face> {
+    let inside = edges
+        .iter()
+        .filter(|edge| face_edge_contains(face, edge) == FaceEdgeContains::Inside)
+        .cloned()
+        .collect::<Vec<Edge>>();
     let mut result = vec![face.clone()];
-    for c in edges {
+    for chain in chain_edges(face, inside) {
         let mut new_result = Vec::<Face>::new();
         for face in result.iter() {
-            new_result.extend(split_face_by_edge_if_necessary(face, c));
+            new_result.extend(split_face_by_chain_if_necessary(face, &chain));
         }
         result = new_result;
     }
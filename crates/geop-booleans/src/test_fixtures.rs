@@ -0,0 +1,78 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{plane::Plane, surface::Surface},
+    transforms::Transform,
+};
+use geop_topology::{
+    contains::volume_point::{volume_point_contains, VolumePointContains},
+    operations::{extrude::extrude, revolve::revolve},
+    primitive_objects::{edges::circle::primitive_circle, volumes::cube::primitive_cube},
+    topology::{contour::Contour, face::Face, volume::Volume},
+};
+
+// An axis aligned cube with the given edge length around the center.
+pub fn cube(size: f64, center: Point) -> Volume {
+    let size = EFloat64::from(size);
+    primitive_cube(size, size, size).transform(Transform::from_translation(center))
+}
+
+// A cylinder of the given radius along the axis from start to start + axis. u and v span the start disc, such that u x v = -axis.
+fn cylinder(start: Point, axis: Point, u: Point, v: Point, radius: f64) -> Volume {
+    let normal = -axis.normalize().unwrap();
+    let disc = Face::new(
+        vec![Contour::new(vec![primitive_circle(
+            start,
+            normal,
+            EFloat64::from(radius),
+        )])],
+        Rc::new(Surface::Plane(Plane::new(start, u, v))),
+    );
+    extrude(disc, axis)
+}
+
+// A pipe tee: a thin cylinder along z pierces a thick one along x, and sticks out on both sides.
+pub fn pipe_tee() -> (Volume, Volume) {
+    let thick = cylinder(
+        Point::from_f64(-2.0, 0.0, 0.0),
+        Point::from_f64(4.0, 0.0, 0.0),
+        Point::unit_z(),
+        Point::unit_y(),
+        1.0,
+    );
+    let thin = cylinder(
+        Point::from_f64(0.0, 0.0, -2.0),
+        Point::from_f64(0.0, 0.0, 4.0),
+        Point::unit_y(),
+        Point::unit_x(),
+        0.5,
+    );
+    (thick, thin)
+}
+
+// A ring torus around the z axis, made by revolving a disc in the xz plane. The disc has the normal -y, like its boundary.
+pub fn torus(major_radius: f64, minor_radius: f64) -> Volume {
+    let center = Point::from_f64(major_radius, 0.0, 0.0);
+    let disc = Face::new(
+        vec![Contour::new(vec![primitive_circle(
+            center,
+            -Point::unit_y(),
+            EFloat64::from(minor_radius),
+        )])],
+        Rc::new(Surface::Plane(Plane::new(
+            center,
+            Point::unit_x(),
+            Point::unit_z(),
+        ))),
+    );
+    revolve(disc, Point::zero(), Point::unit_z(), EFloat64::two_pi()).unwrap()
+}
+
+pub fn is_inside(volume: &Volume, p: Point) -> bool {
+    matches!(
+        volume_point_contains(volume, p).unwrap(),
+        VolumePointContains::Inside
+    )
+}
@@ -1,13 +1,13 @@
 use crate::remesh::face::{face_remesh, face_split, normalize_faces, FaceSplit};
-use geop_topology::topology::face::Face;
+use geop_topology::{topology::face::Face, topology_error::TopologyResult};
 
-pub fn face_face_union(face_self: &Face, face_other: &Face) -> Vec<Face> {
+pub fn face_face_union(face_self: &Face, face_other: &Face) -> TopologyResult<Vec<Face>> {
     assert!(
-        face_self.surface == face_other.surface,
+        face_self.surface() == face_other.surface(),
         "Faces must have the same surface",
     );
 
-    let edges = face_split(face_self, face_other)
+    let edges = face_split(face_self, face_other)?
         .drain(..)
         .filter(|mode| match mode {
             FaceSplit::AinB(_) => false,
@@ -22,5 +22,5 @@ pub fn face_face_union(face_self: &Face, face_other: &Face) -> Vec<Face> {
         .collect::<Vec<FaceSplit>>();
 
     let contours = face_remesh(edges);
-    return normalize_faces(contours, face_self.surface.clone());
+    Ok(normalize_faces(contours, face_self.surface().clone()))
 }
@@ -0,0 +1,103 @@
+use geop_topology::{
+    failure_dump::dump_on_failure,
+    topology::{face::Face, volume::Volume},
+    topology_error::TopologyResult,
+};
+
+use crate::{
+    remesh::volume::{normalize_volumes, split_volumes, volume_remesh, VolumeSplit},
+    replay::boolean_inputs,
+};
+
+// The union can fall apart into several volumes if the volumes do not touch.
+pub fn volume_union(volume_self: &Volume, volume_other: &Volume) -> TopologyResult<Vec<Volume>> {
+    dump_on_failure(
+        "volume_union",
+        || boolean_inputs(volume_self, volume_other),
+        || {
+            split_volumes(volume_self, volume_other).and_then(|mut splits| {
+                let faces = splits
+                    .drain(..)
+                    .filter_map(|split| match split {
+                        VolumeSplit::AinB(_) => None,
+                        VolumeSplit::AonBSameSide(face) => Some(face),
+                        VolumeSplit::AonBOpSide(_) => None,
+                        VolumeSplit::AoutB(face) => Some(face),
+                        VolumeSplit::BinA(_) => None,
+                        VolumeSplit::BonASameSide(_) => None,
+                        VolumeSplit::BonAOpSide(_) => None,
+                        VolumeSplit::BoutA(face) => Some(face),
+                    })
+                    .collect::<Vec<Face>>();
+                normalize_volumes(volume_remesh(faces))
+            })
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::point::Point;
+
+    use crate::test_fixtures::{cube, is_inside, pipe_tee, torus};
+
+    use super::*;
+
+    #[test]
+    fn test_volume_union() {
+        // The small cube sticks out of the face at x = 1, which gets a hole
+        let a = cube(2.0, Point::zero());
+        let b = cube(1.0, Point::from_f64(1.0, 0.2, 0.3));
+        let union = volume_union(&a, &b).unwrap();
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].boundary().faces().len(), 11);
+        assert!(union[0].cavities().is_empty());
+
+        // Disjoint volumes stay separate
+        let c = cube(1.0, Point::from_f64(4.0, 0.0, 0.0));
+        assert_eq!(volume_union(&a, &c).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_volume_union_touching() {
+        // Cubes that share a face are merged and the shared face disappears
+        let a = cube(2.0, Point::zero());
+        let b = cube(2.0, Point::from_f64(2.0, 0.0, 0.0));
+        let union = volume_union(&a, &b).unwrap();
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].boundary().faces().len(), 10);
+
+        // Cubes that only share an edge or a corner stay separate
+        let c = cube(2.0, Point::from_f64(2.0, 2.0, 0.0));
+        assert_eq!(volume_union(&a, &c).unwrap().len(), 2);
+        let d = cube(2.0, Point::from_f64(2.0, 2.0, 2.0));
+        assert_eq!(volume_union(&a, &d).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_volume_union_pipe_tee() {
+        // The thin cylinder is cut where it enters the thick one, which gets a hole on either side
+        let (thick, thin) = pipe_tee();
+        let union = volume_union(&thick, &thin).unwrap();
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].boundary().faces().len(), 7);
+        assert!(is_inside(&union[0], Point::from_f64(0.0, 0.0, 1.5)));
+        assert!(is_inside(&union[0], Point::from_f64(1.5, 0.0, 0.0)));
+        assert!(!is_inside(&union[0], Point::from_f64(0.6, 0.0, 1.5)));
+    }
+
+    #[test]
+    fn test_volume_union_torus() {
+        // The torus sticks out of the top face of the cube, which is cut by the inner and the outer circle of the torus
+        let a = cube(10.0, Point::from_f64(0.0, 0.0, -4.8));
+        let b = torus(3.0, 0.5);
+        let union = volume_union(&a, &b).unwrap();
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].boundary().faces().len(), 8);
+        assert!(is_inside(&union[0], Point::from_f64(3.0, 0.0, 0.4)));
+        assert!(is_inside(&union[0], Point::from_f64(0.0, -3.0, 0.4)));
+        assert!(is_inside(&union[0], Point::from_f64(0.0, 0.0, 0.1)));
+        assert!(!is_inside(&union[0], Point::from_f64(0.0, 0.0, 0.4)));
+        assert!(!is_inside(&union[0], Point::from_f64(4.0, 0.0, 0.4)));
+    }
+}
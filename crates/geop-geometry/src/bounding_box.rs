@@ -4,6 +4,9 @@ use geop_algebra::efloat::EFloat64;
 
 use crate::point::Point;
 
+// Margin that keeps rays which graze the bounding box from being culled.
+const EPSILON: f64 = 1e-9;
+
 #[derive(Debug, Clone)]
 pub struct BoundingBox {
     pub min: Point,
@@ -33,6 +36,33 @@ impl BoundingBox {
         x_overlap && y_overlap && z_overlap
     }
 
+    // Checks if the ray from the origin in the given direction passes through the bounding box, using the slab method.
+    pub fn intersects_ray(&self, origin: Point, direction: Point) -> bool {
+        let mut t_min: f64 = 0.0;
+        let mut t_max = f64::INFINITY;
+        for (min, max, o, d) in [
+            (self.min.x, self.max.x, origin.x, direction.x),
+            (self.min.y, self.max.y, origin.y, direction.y),
+            (self.min.z, self.max.z, origin.z, direction.z),
+        ] {
+            let (min, max) = (min.lower_bound - EPSILON, max.upper_bound + EPSILON);
+            let (o, d) = (o.to_f64(), d.to_f64());
+            if d.abs() < EPSILON {
+                if o < min || o > max {
+                    return false;
+                }
+                continue;
+            }
+            let (t1, t2) = ((min - o) / d, (max - o) / d);
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+            if t_min > t_max {
+                return false;
+            }
+        }
+        true
+    }
+
     pub fn max_size(&self) -> EFloat64 {
         let diff = self.max - self.min;
         diff.x.max(diff.y).max(diff.z)
@@ -47,6 +77,19 @@ impl BoundingBox {
         self.max.z = self.max.z.max(p.z);
     }
 
+    // The bounding box of a circle with the given center, normal and radius.
+    pub fn with_circle(center: Point, normal: Point, radius: EFloat64) -> BoundingBox {
+        let extent = |n: EFloat64| {
+            (EFloat64::one() - n * n)
+                .max(EFloat64::zero())
+                .sqrt()
+                .unwrap_or(EFloat64::one())
+                * radius
+        };
+        let extent = Point::new(extent(normal.x), extent(normal.y), extent(normal.z));
+        BoundingBox::new(center - extent, center + extent)
+    }
+
     pub fn with_2_points(interval_self_1: Point, interval_self_2: Point) -> BoundingBox {
         let mut bounding_box = BoundingBox::new(interval_self_1, interval_self_1);
         bounding_box.add_point(interval_self_2);
@@ -0,0 +1,266 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{bounding_box::BoundingBox, point::Point};
+
+// Leaves with at most this many items are not split further.
+const LEAF_SIZE: usize = 4;
+// Margin by which the bounding boxes are widened, so that points with small numerical errors are not missed.
+const MARGIN: f64 = 1e-9;
+
+#[derive(Debug, Clone)]
+enum BvhNode {
+    Leaf {
+        bounding_box: BoundingBox,
+        items: Vec<(usize, BoundingBox)>,
+    },
+    Inner {
+        bounding_box: BoundingBox,
+        left: usize,
+        right: usize,
+    },
+}
+
+impl BvhNode {
+    fn bounding_box(&self) -> &BoundingBox {
+        match self {
+            BvhNode::Leaf { bounding_box, .. } => bounding_box,
+            BvhNode::Inner { bounding_box, .. } => bounding_box,
+        }
+    }
+}
+
+// A bounding volume hierarchy over items that are identified by their index.
+// Items without a bounding box, like unbounded curves, are returned by every query.
+#[derive(Debug, Clone)]
+pub struct Bvh {
+    nodes: Vec<BvhNode>,
+    root: Option<usize>,
+    unbounded: Vec<usize>,
+}
+
+impl Bvh {
+    pub fn new(bounding_boxes: Vec<Option<BoundingBox>>) -> Bvh {
+        let mut bounded = Vec::<(usize, BoundingBox)>::new();
+        let mut unbounded = Vec::<usize>::new();
+        for (i, bounding_box) in bounding_boxes.into_iter().enumerate() {
+            match bounding_box {
+                Some(bounding_box) => {
+                    let margin = Point::ones() * EFloat64::from(MARGIN);
+                    bounded.push((
+                        i,
+                        BoundingBox::new(bounding_box.min - margin, bounding_box.max + margin),
+                    ))
+                }
+                None => unbounded.push(i),
+            }
+        }
+        let mut bvh = Bvh {
+            nodes: Vec::new(),
+            root: None,
+            unbounded,
+        };
+        if !bounded.is_empty() {
+            bvh.root = Some(bvh.build(bounded));
+        }
+        bvh
+    }
+
+    // Splits the items at the median of their centers along the axis where the centers are spread the most.
+    fn build(&mut self, mut items: Vec<(usize, BoundingBox)>) -> usize {
+        let mut bounding_box = items[0].1.clone();
+        for (_, b) in items.iter().skip(1) {
+            bounding_box.add_point(b.min);
+            bounding_box.add_point(b.max);
+        }
+        if items.len() <= LEAF_SIZE {
+            self.nodes.push(BvhNode::Leaf {
+                bounding_box,
+                items,
+            });
+            return self.nodes.len() - 1;
+        }
+
+        let center = |b: &BoundingBox| {
+            let c = b.min + b.max;
+            [c.x.to_f64(), c.y.to_f64(), c.z.to_f64()]
+        };
+        let mut low = [f64::INFINITY; 3];
+        let mut high = [f64::NEG_INFINITY; 3];
+        for (_, b) in items.iter() {
+            let c = center(b);
+            for k in 0..3 {
+                low[k] = low[k].min(c[k]);
+                high[k] = high[k].max(c[k]);
+            }
+        }
+        let axis = (0..3)
+            .max_by(|a, b| (high[*a] - low[*a]).total_cmp(&(high[*b] - low[*b])))
+            .unwrap();
+        items.sort_by(|(_, a), (_, b)| center(a)[axis].total_cmp(&center(b)[axis]));
+        let right_items = items.split_off(items.len() / 2);
+
+        let left = self.build(items);
+        let right = self.build(right_items);
+        self.nodes.push(BvhNode::Inner {
+            bounding_box,
+            left,
+            right,
+        });
+        self.nodes.len() - 1
+    }
+
+    // Collects the items of all leaves that pass the test, descending only into nodes that pass it.
+    fn query_with(&self, test: impl Fn(&BoundingBox) -> bool) -> Vec<usize> {
+        let mut result = self.unbounded.clone();
+        let mut stack = self.root.into_iter().collect::<Vec<usize>>();
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            if !test(node.bounding_box()) {
+                continue;
+            }
+            match node {
+                BvhNode::Leaf { items, .. } => {
+                    result.extend(items.iter().filter(|(_, b)| test(b)).map(|(i, _)| *i))
+                }
+                BvhNode::Inner { left, right, .. } => stack.extend([*left, *right]),
+            }
+        }
+        result.sort();
+        result
+    }
+
+    // The items whose bounding boxes intersect the given one.
+    pub fn query(&self, bounding_box: &BoundingBox) -> Vec<usize> {
+        self.query_with(|b| b.intersects(bounding_box))
+    }
+
+    // The items whose bounding boxes contain the point.
+    pub fn query_point(&self, point: Point) -> Vec<usize> {
+        self.query(&BoundingBox::new(point, point))
+    }
+
+    // The items whose bounding boxes are hit by the ray from the origin in the given direction.
+    pub fn query_ray(&self, origin: Point, direction: Point) -> Vec<usize> {
+        self.query_with(|b| b.intersects_ray(origin, direction))
+    }
+
+    // All pairs of items of this and the other hierarchy whose bounding boxes intersect.
+    pub fn query_pairs(&self, other: &Bvh) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::<(usize, usize)>::new();
+        if let (Some(a), Some(b)) = (self.root, other.root) {
+            let mut stack = vec![(a, b)];
+            while let Some((a, b)) = stack.pop() {
+                let (node_a, node_b) = (&self.nodes[a], &other.nodes[b]);
+                if !node_a.bounding_box().intersects(node_b.bounding_box()) {
+                    continue;
+                }
+                match (node_a, node_b) {
+                    (
+                        BvhNode::Leaf { items: items_a, .. },
+                        BvhNode::Leaf { items: items_b, .. },
+                    ) => {
+                        for (i, box_a) in items_a.iter() {
+                            for (j, box_b) in items_b.iter() {
+                                if box_a.intersects(box_b) {
+                                    pairs.push((*i, *j));
+                                }
+                            }
+                        }
+                    }
+                    (BvhNode::Inner { left, right, .. }, BvhNode::Leaf { .. }) => {
+                        stack.extend([(*left, b), (*right, b)]);
+                    }
+                    (_, BvhNode::Inner { left, right, .. }) => {
+                        stack.extend([(a, *left), (a, *right)]);
+                    }
+                }
+            }
+        }
+        // Items without bounding boxes are paired with everything
+        let all_self = (0..self.len()).collect::<Vec<usize>>();
+        let all_other = (0..other.len()).collect::<Vec<usize>>();
+        for i in self.unbounded.iter() {
+            pairs.extend(all_other.iter().map(|j| (*i, *j)));
+        }
+        for j in other.unbounded.iter() {
+            pairs.extend(
+                all_self
+                    .iter()
+                    .filter(|i| !self.unbounded.contains(i))
+                    .map(|i| (*i, *j)),
+            );
+        }
+        pairs.sort();
+        pairs
+    }
+
+    // The number of items in the hierarchy.
+    pub fn len(&self) -> usize {
+        let bounded = self
+            .nodes
+            .iter()
+            .map(|node| match node {
+                BvhNode::Leaf { items, .. } => items.len(),
+                BvhNode::Inner { .. } => 0,
+            })
+            .sum::<usize>();
+        bounded + self.unbounded.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_box(x: f64, y: f64, z: f64) -> Option<BoundingBox> {
+        Some(BoundingBox::new(
+            Point::from_f64(x, y, z),
+            Point::from_f64(x + 1.0, y + 1.0, z + 1.0),
+        ))
+    }
+
+    #[test]
+    fn test_query() {
+        let boxes = (0..20)
+            .map(|i| unit_box(2.0 * i as f64, 0.0, 0.0))
+            .collect();
+        let bvh = Bvh::new(boxes);
+        assert_eq!(bvh.len(), 20);
+        assert_eq!(bvh.query_point(Point::from_f64(10.5, 0.5, 0.5)), vec![5]);
+        assert_eq!(
+            bvh.query(&BoundingBox::new(
+                Point::from_f64(3.5, 0.0, 0.0),
+                Point::from_f64(8.5, 1.0, 1.0)
+            )),
+            vec![2, 3, 4]
+        );
+        assert!(bvh.query_point(Point::from_f64(1.5, 0.5, 0.5)).is_empty());
+        assert_eq!(
+            bvh.query_ray(Point::from_f64(14.5, 5.0, 0.5), -Point::unit_y()),
+            vec![7]
+        );
+    }
+
+    #[test]
+    fn test_query_pairs() {
+        let a = Bvh::new(
+            (0..10)
+                .map(|i| unit_box(2.0 * i as f64, 0.0, 0.0))
+                .collect(),
+        );
+        let b = Bvh::new(vec![
+            unit_box(4.5, 0.5, 0.5),
+            None,
+            unit_box(30.0, 0.0, 0.0),
+        ]);
+        let pairs = b.query_pairs(&a);
+        assert_eq!(pairs.len(), 1 + 10);
+        assert!(pairs.contains(&(0, 2)));
+        assert!(!pairs.contains(&(0, 3)));
+        assert!(!pairs.iter().any(|(i, _)| *i == 2));
+    }
+}
@@ -21,10 +21,17 @@ pub fn circle_line_intersection(circle: &Circle, line: &Line) -> CircleLineInter
         return CircleLineIntersection::None;
     }
 
-    assert!(
-        circle.normal.is_perpendicular(line.direction),
-        "3D circle-line intersection is not implemented yet"
-    );
+    // A line that is not in the plane of the circle crosses the plane in a single point
+    if !circle.normal.is_perpendicular(line.direction) {
+        let distance = (circle.normal.dot(circle.basis - line.basis)
+            / circle.normal.dot(line.direction))
+        .unwrap();
+        let point = line.basis + distance * line.direction;
+        if ((point - circle.basis).norm() - circle.radius.norm()) == 0.0 {
+            return CircleLineIntersection::OnePoint(point);
+        }
+        return CircleLineIntersection::None;
+    }
 
     let v = circle.basis - line.basis;
     let dir = line.direction.normalize().unwrap();
@@ -72,4 +79,25 @@ mod tests {
             _ => panic!("Expected two point intersection"),
         }
     }
+
+    #[test]
+    fn test_circle_line_intersection_skew() {
+        let c = Circle::try_new(Point::zero(), Point::unit_z(), EFloat64::one()).unwrap();
+        let direction = Point::from_f64(1.0, 0.0, 1.0).normalize().unwrap();
+        let l = Line::new(Point::from_f64(0.0, 0.0, -1.0), direction).unwrap();
+        match circle_line_intersection(&c, &l) {
+            CircleLineIntersection::OnePoint(p) => assert_eq!(p, Point::unit_x()),
+            _ => panic!("Expected one point intersection"),
+        }
+
+        let l = Line::new(
+            Point::from_f64(0.0, 0.0, -1.0),
+            Point::ones().normalize().unwrap(),
+        )
+        .unwrap();
+        match circle_line_intersection(&c, &l) {
+            CircleLineIntersection::None => {}
+            _ => panic!("Expected no intersection"),
+        }
+    }
 }
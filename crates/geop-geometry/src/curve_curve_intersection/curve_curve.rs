@@ -1,12 +1,23 @@
-use crate::{curves::curve::Curve, point::Point};
+use crate::{
+    color::Category10Color,
+    curves::{
+        curve::Curve, ellipse::Ellipse, nurb::NurbsCurve, sampled_curve::SampledCurve, CurveLike,
+    },
+    geometry_error::{GeometryError, GeometryResult},
+    geometry_scene::GeometryScene,
+    point::Point,
+};
 
 use super::{
     circle_circle::{circle_circle_intersection, CircleCircleIntersection},
     circle_line::{circle_line_intersection, CircleLineIntersection},
+    curve_curve_intersection_sampled,
     ellipse_ellipse::{ellipse_ellipse_intersection, EllipseEllipseIntersection},
     helix_circle::{helix_circle_intersection, HelixCircleIntersection},
     helix_line::{helix_line_intersection, HelixLineIntersection},
     line_line::{line_line_intersection, LineLineIntersection},
+    nurbs_curve::{nurbs_curve_intersection, NurbsCurveIntersection},
+    sampled_curve_curve::{sampled_curve_curve_intersection, SampledCurveCurveIntersection},
 };
 
 // This represents an infinite point array, [basis + n * extend_dir | n in Z]. This is for example used for the helix-line intersection.
@@ -24,8 +35,47 @@ pub enum CurveCurveIntersection {
     Curve(Curve),
 }
 
-pub fn curve_curve_intersection(edge_self: &Curve, edge_other: &Curve) -> CurveCurveIntersection {
-    match edge_self {
+fn sampled_curve_curve(
+    sampled: &SampledCurve,
+    edge_other: &dyn CurveLike,
+) -> CurveCurveIntersection {
+    match sampled_curve_curve_intersection(sampled, edge_other) {
+        SampledCurveCurveIntersection::None => CurveCurveIntersection::None,
+        SampledCurveCurveIntersection::Points(points) => {
+            CurveCurveIntersection::FinitePoints(points)
+        }
+        SampledCurveCurveIntersection::Curve(sampled) => {
+            CurveCurveIntersection::Curve(Curve::SampledCurve(sampled))
+        }
+    }
+}
+
+fn nurbs_curve(nurbs: &NurbsCurve, edge_other: &Curve) -> CurveCurveIntersection {
+    match nurbs_curve_intersection(nurbs, edge_other) {
+        NurbsCurveIntersection::None => CurveCurveIntersection::None,
+        NurbsCurveIntersection::Points(points) => CurveCurveIntersection::FinitePoints(points),
+        NurbsCurveIntersection::Curve(nurbs) => CurveCurveIntersection::Curve(Curve::Nurbs(nurbs)),
+    }
+}
+
+// An ellipse is bounded, so it can be intersected with any other curve by sampling it.
+fn ellipse_curve(ellipse: &Ellipse, edge_other: &dyn CurveLike) -> CurveCurveIntersection {
+    match curve_curve_intersection_sampled(
+        &|t| ellipse.interpolate(None, None, t).unwrap(),
+        edge_other,
+    ) {
+        None => CurveCurveIntersection::Curve(Curve::Ellipse(ellipse.clone())),
+        Some(points) if points.is_empty() => CurveCurveIntersection::None,
+        Some(points) => CurveCurveIntersection::FinitePoints(points),
+    }
+}
+
+// Returns an error for curves that cannot be intersected yet, e.g. two different helices.
+pub fn curve_curve_intersection(
+    edge_self: &Curve,
+    edge_other: &Curve,
+) -> GeometryResult<CurveCurveIntersection> {
+    Ok(match edge_self {
         Curve::Line(line) => match edge_other {
             Curve::Line(other_line) => match line_line_intersection(line, other_line) {
                 LineLineIntersection::None => CurveCurveIntersection::None,
@@ -41,7 +91,7 @@ pub fn curve_curve_intersection(edge_self: &Curve, edge_other: &Curve) -> CurveC
                     CurveCurveIntersection::FinitePoints(vec![p1, p2])
                 }
             },
-            Curve::Ellipse(_) => todo!("Implement this"),
+            Curve::Ellipse(other_ellipse) => ellipse_curve(other_ellipse, line),
             Curve::Helix(helix) => match helix_line_intersection(helix, line) {
                 HelixLineIntersection::PointArray(point_array) => {
                     CurveCurveIntersection::InfiniteDiscretePoints(point_array)
@@ -50,6 +100,8 @@ pub fn curve_curve_intersection(edge_self: &Curve, edge_other: &Curve) -> CurveC
                 HelixLineIntersection::OnePoint(p) => CurveCurveIntersection::FinitePoints(vec![p]),
                 HelixLineIntersection::None => CurveCurveIntersection::None,
             },
+            Curve::SampledCurve(other_sampled) => sampled_curve_curve(other_sampled, line),
+            Curve::Nurbs(other_nurbs) => nurbs_curve(other_nurbs, edge_self),
         },
         Curve::Circle(circle) => match edge_other {
             Curve::Line(other_line) => match circle_line_intersection(circle, other_line) {
@@ -73,7 +125,7 @@ pub fn curve_curve_intersection(edge_self: &Curve, edge_other: &Curve) -> CurveC
                     CurveCurveIntersection::Curve(Curve::Circle(c))
                 }
             },
-            Curve::Ellipse(_) => todo!("Implement this"),
+            Curve::Ellipse(other_ellipse) => ellipse_curve(other_ellipse, circle),
             Curve::Helix(helix) => match helix_circle_intersection(helix, circle) {
                 HelixCircleIntersection::TwoPoints(p1, p2) => {
                     CurveCurveIntersection::FinitePoints(vec![p1, p2])
@@ -83,10 +135,12 @@ pub fn curve_curve_intersection(edge_self: &Curve, edge_other: &Curve) -> CurveC
                 }
                 HelixCircleIntersection::None => CurveCurveIntersection::None,
             },
+            Curve::SampledCurve(other_sampled) => sampled_curve_curve(other_sampled, circle),
+            Curve::Nurbs(other_nurbs) => nurbs_curve(other_nurbs, edge_self),
         },
         Curve::Ellipse(ellipse) => match edge_other {
-            Curve::Line(_) => todo!("Implement this"),
-            Curve::Circle(_) => todo!("Implement this"),
+            Curve::Line(other_line) => ellipse_curve(ellipse, other_line),
+            Curve::Circle(other_circle) => ellipse_curve(ellipse, other_circle),
             Curve::Ellipse(other_ellipse) => {
                 match ellipse_ellipse_intersection(ellipse, other_ellipse) {
                     EllipseEllipseIntersection::Ellipse(ellipse) => {
@@ -107,8 +161,90 @@ pub fn curve_curve_intersection(edge_self: &Curve, edge_other: &Curve) -> CurveC
                     EllipseEllipseIntersection::None => CurveCurveIntersection::None,
                 }
             }
-            Curve::Helix(_) => todo!("Implement this"),
+            Curve::Helix(other_helix) => ellipse_curve(ellipse, other_helix),
+            Curve::SampledCurve(other_sampled) => sampled_curve_curve(other_sampled, ellipse),
+            Curve::Nurbs(other_nurbs) => nurbs_curve(other_nurbs, edge_self),
         },
-        Curve::Helix(_) => todo!("Implement this"),
+        Curve::Helix(helix) => match edge_other {
+            Curve::Line(_) | Curve::Circle(_) | Curve::Ellipse(_) => {
+                return curve_curve_intersection(edge_other, edge_self);
+            }
+            Curve::Helix(other_helix) if helix == other_helix => {
+                CurveCurveIntersection::Curve(edge_self.clone())
+            }
+            Curve::Helix(_) => {
+                return Err(GeometryError::new(
+                    "Intersections of different helices are not supported".to_string(),
+                )
+                .with_context_scene(
+                    "Intersect two helices".to_string(),
+                    GeometryScene::with_curves(vec![
+                        (edge_self.clone(), Category10Color::Red),
+                        (edge_other.clone(), Category10Color::Blue),
+                    ]),
+                ));
+            }
+            Curve::SampledCurve(other_sampled) => sampled_curve_curve(other_sampled, helix),
+            Curve::Nurbs(other_nurbs) => nurbs_curve(other_nurbs, edge_self),
+        },
+        Curve::SampledCurve(sampled) => sampled_curve_curve(sampled, edge_other),
+        Curve::Nurbs(nurbs) => nurbs_curve(nurbs, edge_other),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::{helix::Helix, line::Line};
+
+    fn ellipse() -> Curve {
+        Curve::Ellipse(
+            Ellipse::try_new(
+                Point::zero(),
+                Point::unit_z(),
+                Point::from_f64(2.0, 0.0, 0.0),
+                Point::from_f64(0.0, 1.0, 0.0),
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_ellipse_line_intersection() {
+        let line = Curve::Line(Line::new(Point::zero(), Point::unit_x()).unwrap());
+        for intersection in [
+            curve_curve_intersection(&ellipse(), &line).unwrap(),
+            curve_curve_intersection(&line, &ellipse()).unwrap(),
+        ] {
+            match intersection {
+                CurveCurveIntersection::FinitePoints(points) => {
+                    assert_eq!(points.len(), 2);
+                    assert!(points.contains(&Point::from_f64(2.0, 0.0, 0.0)));
+                    assert!(points.contains(&Point::from_f64(-2.0, 0.0, 0.0)));
+                }
+                _ => panic!("Expected two points"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_helix_helix_intersection() {
+        let helix = Curve::Helix(Helix::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::unit_x(),
+            true,
+        ));
+        let other = Curve::Helix(Helix::new(
+            Point::from_f64(0.5, 0.0, 0.0),
+            Point::unit_z(),
+            Point::unit_x(),
+            true,
+        ));
+        assert_eq!(
+            curve_curve_intersection(&helix, &helix).unwrap(),
+            CurveCurveIntersection::Curve(helix.clone())
+        );
+        assert!(curve_curve_intersection(&helix, &other).is_err());
     }
 }
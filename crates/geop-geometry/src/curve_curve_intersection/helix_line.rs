@@ -14,21 +14,14 @@ pub enum HelixLineIntersection {
 
 pub fn helix_line_intersection(helix: &Helix, line: &Line) -> HelixLineIntersection {
     if line.direction.is_parallel(helix.pitch) {
+        let axis = helix.pitch.normalize().unwrap();
         let distance = line.basis - helix.basis;
-        let distance = distance - distance.dot(helix.pitch) * helix.pitch;
+        let distance = distance - distance.dot(axis) * axis;
         let radius = distance.norm();
         if (radius - helix.radius.norm()) == 0.0 {
-            let line_base_projected = line.basis - helix.basis;
-            let line_base_projected =
-                line_base_projected - line_base_projected.dot(helix.pitch) * helix.pitch;
-            let angle = helix
-                .radius
-                .angle2(line_base_projected, helix.pitch)
-                .unwrap();
-            let first_point = helix.basis + helix.radius * angle.cos() + helix.pitch * angle.sin();
-            assert!(helix.on_curve(first_point));
+            // The line meets the helix once per turn
+            let first_point = helix.project(line.basis);
             assert!(line.on_curve(first_point));
-            assert!(helix.on_curve(first_point + helix.pitch));
             assert!(line.on_curve(first_point + helix.pitch));
             let point_array = PointArray {
                 basis: first_point,
@@ -13,17 +13,19 @@ pub fn line_line_intersection(a: &Line, b: &Line) -> LineLineIntersection {
     let p1 = a.basis;
     let p2 = b.basis;
 
-    if v1.is_parallel(v2) {
-        if (p1 - p2).is_parallel(v1) {
-            return LineLineIntersection::Line(Line::new(p1, v1).unwrap());
-        } else {
-            return LineLineIntersection::None;
-        }
-    }
-
     let cross_product = v1.cross(v2);
     let t = (p2 - p1).cross(v2).dot(cross_product) / cross_product.norm_sq();
-    let t = t.unwrap();
+    // Nearly parallel lines, whose cross product is within the error bounds of zero, are treated as parallel
+    let t = match (v1.is_parallel(v2), t) {
+        (false, Ok(t)) => t,
+        _ => {
+            if (p1 - p2).is_parallel(v1) {
+                return LineLineIntersection::Line(Line::new(p1, v1).unwrap());
+            } else {
+                return LineLineIntersection::None;
+            }
+        }
+    };
     let p = p1 + v1 * t;
 
     if (p - p1).is_parallel(v1) && (p - p2).is_parallel(v2) {
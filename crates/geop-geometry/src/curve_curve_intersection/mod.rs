@@ -7,3 +7,63 @@ pub mod helix_circle;
 pub mod helix_line;
 pub mod line_line;
 pub mod numerical;
+pub mod nurbs_curve;
+pub mod sampled_curve_curve;
+
+use crate::{curves::CurveLike, point::Point};
+
+const SAMPLES: usize = 256;
+const GOLDEN_SECTION_ITERATIONS: usize = 80;
+const PRECISION: f64 = 1e-8;
+
+fn distance(other: &dyn CurveLike, p: Point) -> f64 {
+    (p - other.project(p)).norm().to_f64()
+}
+
+fn push_unique(points: &mut Vec<Point>, p: Point) {
+    if !points.iter().any(|q| (p - *q).norm() <= 10.0 * PRECISION) {
+        points.push(p);
+    }
+}
+
+// Intersects a curve, given by a parametrization over [0, 1], with any other curve by minimizing the distance to the other curve
+// along samples. Returns None if all samples lie on the other curve.
+pub fn curve_curve_intersection_sampled(
+    at: &dyn Fn(f64) -> Point,
+    other: &dyn CurveLike,
+) -> Option<Vec<Point>> {
+    let distances = (0..=SAMPLES)
+        .map(|i| distance(other, at(i as f64 / SAMPLES as f64)))
+        .collect::<Vec<f64>>();
+    if distances.iter().all(|d| *d <= PRECISION) {
+        return None;
+    }
+
+    let mut points = Vec::<Point>::new();
+    for i in 0..=SAMPLES {
+        // Only local minima of the distance can be intersections
+        if (i > 0 && distances[i - 1] < distances[i])
+            || (i < SAMPLES && distances[i + 1] < distances[i])
+        {
+            continue;
+        }
+
+        let ratio = (5.0_f64.sqrt() - 1.0) / 2.0;
+        let mut lo = i.saturating_sub(1) as f64 / SAMPLES as f64;
+        let mut hi = (i + 1).min(SAMPLES) as f64 / SAMPLES as f64;
+        for _ in 0..GOLDEN_SECTION_ITERATIONS {
+            let t1 = hi - ratio * (hi - lo);
+            let t2 = lo + ratio * (hi - lo);
+            if distance(other, at(t1)) < distance(other, at(t2)) {
+                hi = t2;
+            } else {
+                lo = t1;
+            }
+        }
+        let p = at((lo + hi) / 2.0);
+        if distance(other, p) <= PRECISION {
+            push_unique(&mut points, other.project(p));
+        }
+    }
+    Some(points)
+}
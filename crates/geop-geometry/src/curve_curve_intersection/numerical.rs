@@ -125,6 +125,26 @@ pub fn curve_curve_intersection_numerical(
         (other_p1, other_p0),
         0,
     ));
+    unique_points(result)
+}
+
+// Finds the intersection between the parts of two curves between the given start and end points. They have to be intersecting only at a finite number of points.
+pub fn curve_curve_intersection_numerical_bounded(
+    edge_self: &dyn CurveLike,
+    interval_self: (Point, Point),
+    edge_other: &dyn CurveLike,
+    interval_other: (Point, Point),
+) -> Vec<Point> {
+    unique_points(curve_curve_intersection_numerical_iteration(
+        edge_self,
+        edge_other,
+        interval_self,
+        interval_other,
+        0,
+    ))
+}
+
+fn unique_points(result: Vec<Point>) -> Vec<Point> {
     // Filter out duplicate points
     let mut unique_points = Vec::new();
     for p in result {
@@ -0,0 +1,191 @@
+use crate::{
+    curves::{curve::Curve, nurb::NurbsCurve, CurveLike},
+    point::Point,
+};
+
+use super::{curve_curve_intersection_sampled, push_unique, GOLDEN_SECTION_ITERATIONS, PRECISION};
+
+pub enum NurbsCurveIntersection {
+    Curve(NurbsCurve),
+    Points(Vec<Point>),
+    None,
+}
+
+fn sample_polyline(curve: &NurbsCurve) -> Vec<(f64, [f64; 3])> {
+    curve
+        .samples()
+        .into_iter()
+        .map(|t| {
+            let p = curve.evaluate64(t).0;
+            (t, [p.x.to_f64(), p.y.to_f64(), p.z.to_f64()])
+        })
+        .collect()
+}
+
+// Checks if the bounding boxes of two segments overlap, after growing them by the given margin.
+fn segments_close(a: ([f64; 3], [f64; 3]), b: ([f64; 3], [f64; 3]), margin: f64) -> bool {
+    (0..3).all(|c| {
+        a.0[c].min(a.1[c]) - margin <= b.0[c].max(b.1[c])
+            && b.0[c].min(b.1[c]) - margin <= a.0[c].max(a.1[c])
+    })
+}
+
+// Two nurbs curves are intersected by finding close segments of their sample polylines,
+// which are refined with Gauss-Newton iterations on both parameters.
+fn nurbs_nurbs_intersection(nurbs: &NurbsCurve, other: &NurbsCurve) -> NurbsCurveIntersection {
+    let (s0, s1) = nurbs.domain();
+    let (t0, t1) = other.domain();
+    let samples_self = sample_polyline(nurbs);
+    let samples_other = sample_polyline(other);
+    let length = |a: [f64; 3], b: [f64; 3]| {
+        ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+    };
+
+    let mut points = Vec::<Point>::new();
+    for a in samples_self.windows(2) {
+        for b in samples_other.windows(2) {
+            let margin = length(a[0].1, a[1].1).max(length(b[0].1, b[1].1)) / 2.0;
+            if !segments_close((a[0].1, a[1].1), (b[0].1, b[1].1), margin) {
+                continue;
+            }
+
+            let (mut s, mut t) = ((a[0].0 + a[1].0) / 2.0, (b[0].0 + b[1].0) / 2.0);
+            for _ in 0..GOLDEN_SECTION_ITERATIONS {
+                let (p, dp) = nurbs.evaluate64(s);
+                let (q, dq) = other.evaluate64(t);
+                let r = p - q;
+                let (a, b, c) = (
+                    dp.dot(dp).to_f64(),
+                    -dp.dot(dq).to_f64(),
+                    dq.dot(dq).to_f64(),
+                );
+                let (g_s, g_t) = (dp.dot(r).to_f64(), -dq.dot(r).to_f64());
+                let det = a * c - b * b;
+                if det <= f64::EPSILON * a * c {
+                    break;
+                }
+                let s_new = (s - (c * g_s - b * g_t) / det).clamp(s0, s1);
+                let t_new = (t - (a * g_t - b * g_s) / det).clamp(t0, t1);
+                let converged = (s_new - s).abs() <= 1e-15 * (s1 - s0)
+                    && (t_new - t).abs() <= 1e-15 * (t1 - t0);
+                (s, t) = (s_new, t_new);
+                if converged {
+                    break;
+                }
+            }
+            let p = nurbs.point_at(s);
+            if (p - other.point_at(t)).norm() <= PRECISION {
+                push_unique(&mut points, p);
+            }
+        }
+    }
+
+    if points.is_empty() {
+        return NurbsCurveIntersection::None;
+    }
+    NurbsCurveIntersection::Points(points)
+}
+
+// Intersects a nurbs curve with any other curve by minimizing the distance to the other curve along the nurbs curve.
+pub fn nurbs_curve_intersection(nurbs: &NurbsCurve, other: &Curve) -> NurbsCurveIntersection {
+    let at = |t: f64| nurbs.interpolate(None, None, t).unwrap();
+
+    if let Curve::Nurbs(other_nurbs) = other {
+        if nurbs == other_nurbs {
+            return NurbsCurveIntersection::Curve(nurbs.clone());
+        }
+        return nurbs_nurbs_intersection(nurbs, other_nurbs);
+    }
+
+    match curve_curve_intersection_sampled(&at, other) {
+        None => NurbsCurveIntersection::Curve(nurbs.clone()),
+        Some(points) if points.is_empty() => NurbsCurveIntersection::None,
+        Some(points) => NurbsCurveIntersection::Points(points),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geop_algebra::efloat::EFloat64;
+
+    use crate::curves::line::Line;
+
+    fn parabola() -> NurbsCurve {
+        NurbsCurve::try_new(
+            2,
+            vec![
+                Point::from_f64(-1.0, 0.0, 0.0),
+                Point::from_f64(0.0, -2.0, 0.0),
+                Point::from_f64(1.0, 0.0, 0.0),
+            ],
+            vec![EFloat64::one(); 3],
+            vec![
+                EFloat64::zero(),
+                EFloat64::zero(),
+                EFloat64::zero(),
+                EFloat64::one(),
+                EFloat64::one(),
+                EFloat64::one(),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_nurbs_line_intersection() {
+        // The parabola y = x^2 - 1 for x in [-1, 1]
+        let curve = parabola();
+
+        let line = Line::new(Point::zero(), Point::unit_x()).unwrap();
+        match nurbs_curve_intersection(&curve, &Curve::Line(line.clone())) {
+            NurbsCurveIntersection::Points(points) => {
+                assert_eq!(points.len(), 2);
+                for p in points {
+                    assert!(line.on_curve(p));
+                    assert!(curve.on_curve(p));
+                    assert!((p.x.abs().to_f64() - 1.0).abs() < 1e-6);
+                }
+            }
+            _ => panic!("Intersection should be two points"),
+        }
+
+        let line = Line::new(Point::from_f64(0.0, -2.0, 0.0), Point::unit_x()).unwrap();
+        match nurbs_curve_intersection(&curve, &Curve::Line(line)) {
+            NurbsCurveIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+    }
+
+    #[test]
+    fn test_nurbs_nurbs_intersection() {
+        let curve = parabola();
+        // The parabola y = 1/2 - x^2 for x in [-1, 1], which crosses the first one twice
+        let other = NurbsCurve::try_new(
+            2,
+            vec![
+                Point::from_f64(-1.0, -0.5, 0.0),
+                Point::from_f64(0.0, 1.5, 0.0),
+                Point::from_f64(1.0, -0.5, 0.0),
+            ],
+            vec![EFloat64::one(); 3],
+            curve.knot_vector.clone(),
+        )
+        .unwrap();
+        match nurbs_curve_intersection(&curve, &Curve::Nurbs(other.clone())) {
+            NurbsCurveIntersection::Points(points) => {
+                assert_eq!(points.len(), 2);
+                for p in points {
+                    assert!(curve.on_curve(p));
+                    assert!(other.on_curve(p));
+                }
+            }
+            _ => panic!("Intersection should be two points"),
+        }
+
+        match nurbs_curve_intersection(&curve, &Curve::Nurbs(curve.clone())) {
+            NurbsCurveIntersection::Curve(_) => {}
+            _ => panic!("Intersection should be the curve"),
+        }
+    }
+}
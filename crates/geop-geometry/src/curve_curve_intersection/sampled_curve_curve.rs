@@ -0,0 +1,115 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curves::{sampled_curve::SampledCurve, CurveLike},
+    point::Point,
+};
+
+pub enum SampledCurveCurveIntersection {
+    Curve(SampledCurve),
+    Points(Vec<Point>),
+    None,
+}
+
+const GOLDEN_SECTION_ITERATIONS: usize = 40;
+
+// Distance between the centers of the intervals, as curves with uncertain parameters would otherwise seem further away.
+fn distance(other: &dyn CurveLike, p: Point) -> f64 {
+    let d = p - other.project(p);
+    (d.x.to_f64().powi(2) + d.y.to_f64().powi(2) + d.z.to_f64().powi(2)).sqrt()
+}
+
+// Intersects a sampled curve with any other curve by minimizing the distance to the other curve along every segment.
+// Intersection points are taken from the other curve, such that they are exact on it and within the error bound of the sampled curve.
+pub fn sampled_curve_curve_intersection(
+    curve: &SampledCurve,
+    other: &dyn CurveLike,
+) -> SampledCurveCurveIntersection {
+    let distances = curve
+        .points
+        .iter()
+        .map(|p| distance(other, *p))
+        .collect::<Vec<f64>>();
+
+    if distances.iter().all(|d| *d <= curve.error_bound) {
+        return SampledCurveCurveIntersection::Curve(curve.clone());
+    }
+
+    let n = curve.points.len();
+    let mut points = Vec::<Point>::new();
+    for i in 0..n {
+        let (a, b) = (curve.points[i], curve.points[(i + 1) % n]);
+        let (d_a, d_b) = (distances[i], distances[(i + 1) % n]);
+
+        // The distance is 1-Lipschitz, so this is a lower bound for the distance along the segment
+        let length = (b - a).norm().to_f64();
+        if (d_a + d_b - length) / 2.0 > curve.error_bound {
+            continue;
+        }
+
+        let at = |t: f64| a + (b - a) * EFloat64::from(t);
+        let ratio = (5.0_f64.sqrt() - 1.0) / 2.0;
+        let (mut lo, mut hi) = (0.0, 1.0);
+        for _ in 0..GOLDEN_SECTION_ITERATIONS {
+            let t1 = hi - ratio * (hi - lo);
+            let t2 = lo + ratio * (hi - lo);
+            if distance(other, at(t1)) < distance(other, at(t2)) {
+                hi = t2;
+            } else {
+                lo = t1;
+            }
+        }
+        let t = (lo + hi) / 2.0;
+        if distance(other, at(t)) > curve.error_bound {
+            continue;
+        }
+
+        let p = other.project(at(t));
+        if !points
+            .iter()
+            .any(|q| (p - *q).norm() <= 2.0 * curve.error_bound)
+        {
+            points.push(p);
+        }
+    }
+
+    if points.is_empty() {
+        return SampledCurveCurveIntersection::None;
+    }
+    SampledCurveCurveIntersection::Points(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::line::Line;
+
+    #[test]
+    fn test_sampled_curve_line_intersection() {
+        let points = (0..256)
+            .map(|i| {
+                let angle = 2.0 * std::f64::consts::PI * i as f64 / 256.0;
+                Point::from_f64(angle.cos(), angle.sin(), 0.0)
+            })
+            .collect();
+        let curve = SampledCurve::try_new(points, 1e-3).unwrap();
+
+        let line = Line::new(Point::from_f64(0.3, 0.0, 0.0), Point::unit_y()).unwrap();
+        match sampled_curve_curve_intersection(&curve, &line) {
+            SampledCurveCurveIntersection::Points(points) => {
+                assert_eq!(points.len(), 2);
+                for p in points {
+                    assert!(line.on_curve(p));
+                    assert!(curve.on_curve(p));
+                }
+            }
+            _ => panic!("Intersection should be two points"),
+        }
+
+        let line = Line::new(Point::from_f64(0.0, 0.0, 1.0), Point::unit_y()).unwrap();
+        match sampled_curve_curve_intersection(&curve, &line) {
+            SampledCurveCurveIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{
+    curves::{circle::Circle, CurveLike},
+    point::Point,
+    surfaces::cone::Cone,
+};
+
+use super::curve_surface_intersection_sampled;
+
+pub enum CircleConeIntersection {
+    Circle(Circle),
+    Points(Vec<Point>),
+    None,
+}
+
+pub fn circle_cone_intersection(circle: &Circle, cone: &Cone) -> CircleConeIntersection {
+    // A circle around the axis is either a circle of latitude of the cone or does not touch it
+    let offset = circle.basis - cone.apex;
+    if circle.normal.is_parallel(cone.axis) && offset.cross(cone.axis).norm() == 0.0 {
+        let height = offset.dot(cone.axis);
+        if height >= 0.0
+            && (circle.radius.norm() * cone.half_angle.cos() - height * cone.half_angle.sin())
+                == 0.0
+        {
+            return CircleConeIntersection::Circle(circle.clone());
+        }
+        return CircleConeIntersection::None;
+    }
+
+    // In general, the circle crosses the cone in up to four points
+    match curve_surface_intersection_sampled(&|t| circle.interpolate(None, None, t).unwrap(), cone)
+    {
+        None => CircleConeIntersection::Circle(circle.clone()),
+        Some(points) if points.is_empty() => CircleConeIntersection::None,
+        Some(points) => CircleConeIntersection::Points(points),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use super::*;
+    use crate::surfaces::SurfaceLike;
+
+    #[test]
+    fn test_circle_cone_intersection() {
+        let cone = Cone::try_new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(std::f64::consts::FRAC_PI_4),
+            true,
+        )
+        .unwrap();
+
+        let circle = Circle::try_new(
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::unit_z(),
+            EFloat64::two(),
+        )
+        .unwrap();
+        match circle_cone_intersection(&circle, &cone) {
+            CircleConeIntersection::Circle(_) => {}
+            _ => panic!("Intersection should be the circle"),
+        }
+
+        let circle = Circle::try_new(
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::unit_z(),
+            EFloat64::one(),
+        )
+        .unwrap();
+        match circle_cone_intersection(&circle, &cone) {
+            CircleConeIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+
+        // A vertical circle around the axis crosses both sides of the cone twice
+        let circle = Circle::try_new(
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::unit_y(),
+            EFloat64::from(1.5),
+        )
+        .unwrap();
+        match circle_cone_intersection(&circle, &cone) {
+            CircleConeIntersection::Points(points) => {
+                assert_eq!(points.len(), 4);
+                for p in points {
+                    assert!(cone.on_surface(p));
+                    assert!((p.x.abs() - p.z).abs() < 1e-8);
+                }
+            }
+            _ => panic!("Intersection should be four points"),
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curves::{circle::Circle, CurveLike},
+    point::Point,
+    surfaces::torus::Torus,
+};
+
+use super::curve_surface_intersection_sampled;
+
+pub enum CircleTorusIntersection {
+    Circle(Circle),
+    Points(Vec<Point>),
+    None,
+}
+
+pub fn circle_torus_intersection(circle: &Circle, torus: &Torus) -> CircleTorusIntersection {
+    let offset = circle.basis - torus.basis;
+    let radius = circle.radius.norm();
+
+    // A circle around the axis is a circle of latitude of the torus or does not touch it
+    if circle.normal.is_parallel(torus.axis) && offset.cross(torus.axis).norm() == 0.0 {
+        let height = offset.dot(torus.axis);
+        let width_sq = torus.minor_radius * torus.minor_radius - height * height;
+        if width_sq < 0.0 {
+            return CircleTorusIntersection::None;
+        }
+        let width = width_sq.max(EFloat64::zero()).sqrt().unwrap();
+        if (radius - torus.major_radius - width) == 0.0
+            || (radius - torus.major_radius + width) == 0.0
+        {
+            return CircleTorusIntersection::Circle(circle.clone());
+        }
+        return CircleTorusIntersection::None;
+    }
+
+    // A meridian circle around the center of the tube
+    let height = offset.dot(torus.axis);
+    let radial = offset - height * torus.axis;
+    if height == 0.0
+        && (radial.norm() - torus.major_radius) == 0.0
+        && circle.normal.dot(radial) == 0.0
+        && circle.normal.dot(torus.axis) == 0.0
+        && (radius - torus.minor_radius) == 0.0
+    {
+        return CircleTorusIntersection::Circle(circle.clone());
+    }
+
+    match curve_surface_intersection_sampled(&|t| circle.interpolate(None, None, t).unwrap(), torus)
+    {
+        None => CircleTorusIntersection::Circle(circle.clone()),
+        Some(points) if points.is_empty() => CircleTorusIntersection::None,
+        Some(points) => CircleTorusIntersection::Points(points),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::SurfaceLike;
+
+    fn torus() -> Torus {
+        Torus::try_new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(2.0),
+            EFloat64::from(0.5),
+            true,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_circle_torus_intersection_circles() {
+        let torus = torus();
+        for (z, r) in [(0.0, 2.5), (0.0, 1.5), (0.5, 2.0), (0.3, 2.4)] {
+            let circle = Circle::try_new(
+                Point::from_f64(0.0, 0.0, z),
+                Point::unit_z(),
+                EFloat64::from(r),
+            )
+            .unwrap();
+            match circle_torus_intersection(&circle, &torus) {
+                CircleTorusIntersection::Circle(_) => {}
+                _ => panic!("Intersection should be the circle at z = {}", z),
+            }
+        }
+
+        let circle = Circle::try_new(
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_y(),
+            EFloat64::from(0.5),
+        )
+        .unwrap();
+        match circle_torus_intersection(&circle, &torus) {
+            CircleTorusIntersection::Circle(_) => {}
+            _ => panic!("Intersection should be the meridian"),
+        }
+
+        let circle = Circle::try_new(
+            Point::from_f64(0.0, 0.0, 1.0),
+            Point::unit_z(),
+            EFloat64::two(),
+        )
+        .unwrap();
+        match circle_torus_intersection(&circle, &torus) {
+            CircleTorusIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+    }
+
+    #[test]
+    fn test_circle_torus_intersection_points() {
+        // A circle in the plane x = 2 enters and leaves the tube on both sides
+        let torus = torus();
+        let circle = Circle::try_new(
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_x(),
+            EFloat64::one(),
+        )
+        .unwrap();
+        match circle_torus_intersection(&circle, &torus) {
+            CircleTorusIntersection::Points(points) => {
+                assert_eq!(points.len(), 4);
+                for p in points {
+                    assert!(torus.on_surface(p));
+                }
+            }
+            _ => panic!("Intersection should be four points"),
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curves::{curve::Curve, CurveLike},
+    point::Point,
+    surfaces::nurbs::NurbsSurface,
+};
+
+use super::{curve_surface_intersection_sampled, helix_surface_intersection_sampled};
+
+pub enum CurveNurbsSurfaceIntersection {
+    Curve(Curve),
+    Points(Vec<Point>),
+    None,
+}
+
+// Intersects a curve with a nurbs surface by sampling the curve. A nurbs surface lies in the convex hull of its control points,
+// so a line or a helix is only sampled where it passes the control points.
+pub fn curve_nurbs_surface_intersection(
+    curve: &Curve,
+    surface: &NurbsSurface,
+) -> CurveNurbsSurfaceIntersection {
+    let points = match curve {
+        Curve::Line(line) => {
+            let heights = surface
+                .control_points
+                .iter()
+                .flatten()
+                .map(|p| (*p - line.basis).dot(line.direction).to_f64())
+                .collect::<Vec<f64>>();
+            let mut t0 = heights.iter().cloned().fold(f64::INFINITY, f64::min);
+            let mut t1 = heights.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            if t1 <= t0 {
+                (t0, t1) = (t0 - 1.0, t1 + 1.0);
+            }
+            curve_surface_intersection_sampled(
+                &|t| line.basis + line.direction * EFloat64::from(t0 + t * (t1 - t0)),
+                surface,
+            )
+        }
+        Curve::Helix(helix) => helix_surface_intersection_sampled(
+            helix,
+            surface,
+            &surface
+                .control_points
+                .iter()
+                .flatten()
+                .cloned()
+                .collect::<Vec<Point>>(),
+        ),
+        _ => curve_surface_intersection_sampled(
+            &|t| curve.interpolate(None, None, t).unwrap(),
+            surface,
+        ),
+    };
+    match points {
+        None => CurveNurbsSurfaceIntersection::Curve(curve.clone()),
+        Some(points) if points.is_empty() => CurveNurbsSurfaceIntersection::None,
+        Some(points) => CurveNurbsSurfaceIntersection::Points(points),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curves::{circle::Circle, helix::Helix, line::Line},
+        surfaces::SurfaceLike,
+    };
+
+    // The saddle z = x * y over [-1, 1] x [-1, 1] as a bilinear patch.
+    fn saddle() -> NurbsSurface {
+        NurbsSurface::try_new(
+            1,
+            1,
+            vec![
+                vec![
+                    Point::from_f64(-1.0, -1.0, 1.0),
+                    Point::from_f64(-1.0, 1.0, -1.0),
+                ],
+                vec![
+                    Point::from_f64(1.0, -1.0, -1.0),
+                    Point::from_f64(1.0, 1.0, 1.0),
+                ],
+            ],
+            vec![vec![EFloat64::one(); 2]; 2],
+            vec![
+                EFloat64::zero(),
+                EFloat64::zero(),
+                EFloat64::one(),
+                EFloat64::one(),
+            ],
+            vec![
+                EFloat64::zero(),
+                EFloat64::zero(),
+                EFloat64::one(),
+                EFloat64::one(),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_line_nurbs_surface_intersection() {
+        let surface = saddle();
+        let line = Line::new(Point::from_f64(0.5, 0.5, 3.0), -Point::unit_z()).unwrap();
+        match curve_nurbs_surface_intersection(&Curve::Line(line), &surface) {
+            CurveNurbsSurfaceIntersection::Points(points) => {
+                assert_eq!(points.len(), 1);
+                assert!((points[0] - Point::from_f64(0.5, 0.5, 0.25)).norm() < 1e-8);
+            }
+            _ => panic!("Intersection should be one point"),
+        }
+
+        // The patch is bounded, so a line beside it does not hit it
+        let line = Line::new(Point::from_f64(2.0, 0.5, 3.0), -Point::unit_z()).unwrap();
+        match curve_nurbs_surface_intersection(&Curve::Line(line), &surface) {
+            CurveNurbsSurfaceIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+
+        // The saddle contains the x axis
+        let line = Line::new(Point::zero(), Point::unit_x()).unwrap();
+        match curve_nurbs_surface_intersection(&Curve::Line(line), &surface) {
+            CurveNurbsSurfaceIntersection::Curve(_) => {}
+            _ => panic!("Intersection should be the line"),
+        }
+    }
+
+    #[test]
+    fn test_circle_nurbs_surface_intersection() {
+        // A circle of radius 1/2 around the z axis crosses the saddle in four points
+        let surface = saddle();
+        let circle = Circle::try_new(Point::zero(), Point::unit_z(), EFloat64::from(0.5)).unwrap();
+        match curve_nurbs_surface_intersection(&Curve::Circle(circle), &surface) {
+            CurveNurbsSurfaceIntersection::Points(points) => {
+                assert_eq!(points.len(), 4);
+                for p in points {
+                    assert!(surface.on_surface(p));
+                    assert!(p.z.abs() < 1e-8);
+                }
+            }
+            _ => panic!("Intersection should be four points"),
+        }
+    }
+
+    #[test]
+    fn test_helix_nurbs_surface_intersection() {
+        // Along a helix of radius 1/2 around the z axis, the saddle stays within |z| <= 1/8. The helix rises by 1/2 per turn,
+        // so it passes the saddle at z = 0 and once more on either side.
+        let surface = saddle();
+        let helix = Helix::new(
+            Point::zero(),
+            Point::from_f64(0.0, 0.0, 0.5),
+            Point::from_f64(0.5, 0.0, 0.0),
+            true,
+        );
+        match curve_nurbs_surface_intersection(&Curve::Helix(helix.clone()), &surface) {
+            CurveNurbsSurfaceIntersection::Points(points) => {
+                assert_eq!(points.len(), 3);
+                for p in points {
+                    assert!(surface.on_surface(p));
+                    assert!(helix.on_curve(p));
+                    assert!(p.z.abs() <= 0.125 + 1e-8);
+                }
+            }
+            _ => panic!("Intersection should be points"),
+        }
+
+        // A helix that winds around beside the patch does not hit it
+        let helix = Helix::new(
+            Point::from_f64(3.0, 0.0, 0.0),
+            Point::from_f64(0.0, 0.0, 0.5),
+            Point::from_f64(0.5, 0.0, 0.0),
+            true,
+        );
+        match curve_nurbs_surface_intersection(&Curve::Helix(helix), &surface) {
+            CurveNurbsSurfaceIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+    }
+}
@@ -1,12 +1,29 @@
-use crate::{curves::curve::Curve, point::Point, surfaces::surface::Surface};
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    color::Category10Color,
+    curves::{curve::Curve, helix::Helix, CurveLike},
+    geometry_error::{GeometryError, GeometryResult},
+    geometry_scene::GeometryScene,
+    point::Point,
+    surfaces::{nurbs::NurbsSurface, surface::Surface, SurfaceLike},
+};
 
 use super::{
+    circle_cone::{circle_cone_intersection, CircleConeIntersection},
     circle_cylinder::{circle_cylinder_intersection, CircleCylinderIntersection},
     circle_plane::{circle_plane_intersection, CirclePlaneIntersection},
     circle_sphere::{circle_sphere_intersection, CircleSphereIntersection},
+    circle_torus::{circle_torus_intersection, CircleTorusIntersection},
+    curve_nurbs_surface::{curve_nurbs_surface_intersection, CurveNurbsSurfaceIntersection},
+    curve_surface_intersection_sampled, helix_surface_intersection_sampled,
+    line_cone::{line_cone_intersection, LineConeIntersection},
     line_cylinder::{line_cylinder_intersection, CylinderLineIntersection},
     line_plane::{line_plane_intersection, LinePlaneIntersection},
     line_sphere::{line_sphere_intersection, LineSphereIntersection},
+    line_torus::{line_torus_intersection, LineTorusIntersection},
+    nurbs_curve_surface::{nurbs_curve_surface_intersection, NurbsCurveSurfaceIntersection},
+    sampled_curve_surface::{sampled_curve_surface_intersection, SampledCurveSurfaceIntersection},
 };
 
 pub enum CurveSurfaceIntersection {
@@ -38,8 +55,43 @@ impl CurveSurfaceIntersection {
     }
 }
 
-pub fn curve_surface_intersection(curve: &Curve, surface: &Surface) -> CurveSurfaceIntersection {
-    match curve {
+fn curve_nurbs_surface(curve: &Curve, surface: &NurbsSurface) -> CurveSurfaceIntersection {
+    match curve_nurbs_surface_intersection(curve, surface) {
+        CurveNurbsSurfaceIntersection::Curve(curve) => CurveSurfaceIntersection::Curve(curve),
+        CurveNurbsSurfaceIntersection::Points(points) => CurveSurfaceIntersection::Points(points),
+        CurveNurbsSurfaceIntersection::None => CurveSurfaceIntersection::None,
+    }
+}
+
+fn sampled(curve: &Curve, points: Option<Vec<Point>>) -> CurveSurfaceIntersection {
+    match points {
+        None => CurveSurfaceIntersection::Curve(curve.clone()),
+        Some(points) if points.is_empty() => CurveSurfaceIntersection::None,
+        Some(points) => CurveSurfaceIntersection::Points(points),
+    }
+}
+
+// A helix is sampled in the turns that pass a ball around the surface.
+fn helix_bounded_surface(
+    helix: &Helix,
+    surface: &Surface,
+    center: Point,
+    radius: EFloat64,
+) -> GeometryResult<CurveSurfaceIntersection> {
+    let direction = helix.pitch.normalize()?;
+    let hull = [center + direction * radius, center - direction * radius];
+    Ok(sampled(
+        &Curve::Helix(helix.clone()),
+        helix_surface_intersection_sampled(helix, surface, &hull),
+    ))
+}
+
+// Returns an error for curves and surfaces that cannot be intersected yet, e.g. a helix and an unbounded surface.
+pub fn curve_surface_intersection(
+    curve: &Curve,
+    surface: &Surface,
+) -> GeometryResult<CurveSurfaceIntersection> {
+    Ok(match curve {
         Curve::Line(line) => match surface {
             Surface::Plane(plane) => match line_plane_intersection(line, plane) {
                 LinePlaneIntersection::Line(line) => {
@@ -71,6 +123,23 @@ pub fn curve_surface_intersection(curve: &Curve, surface: &Surface) -> CurveSurf
                 }
                 CylinderLineIntersection::None => CurveSurfaceIntersection::None,
             },
+            Surface::Cone(cone) => match line_cone_intersection(line, cone) {
+                LineConeIntersection::Line(line) => {
+                    CurveSurfaceIntersection::Curve(Curve::Line(line))
+                }
+                LineConeIntersection::TwoPoints(point1, point2) => {
+                    CurveSurfaceIntersection::Points(vec![point1, point2])
+                }
+                LineConeIntersection::OnePoint(point) => {
+                    CurveSurfaceIntersection::Points(vec![point])
+                }
+                LineConeIntersection::None => CurveSurfaceIntersection::None,
+            },
+            Surface::Torus(torus) => match line_torus_intersection(line, torus) {
+                LineTorusIntersection::Points(points) => CurveSurfaceIntersection::Points(points),
+                LineTorusIntersection::None => CurveSurfaceIntersection::None,
+            },
+            Surface::Nurbs(nurbs) => curve_nurbs_surface(curve, nurbs),
         },
         Curve::Circle(circle) => match surface {
             Surface::Plane(plane) => match circle_plane_intersection(circle, plane) {
@@ -107,8 +176,149 @@ pub fn curve_surface_intersection(curve: &Curve, surface: &Surface) -> CurveSurf
                 }
                 CircleCylinderIntersection::None => CurveSurfaceIntersection::None,
             },
+            Surface::Cone(cone) => match circle_cone_intersection(circle, cone) {
+                CircleConeIntersection::Circle(circle) => {
+                    CurveSurfaceIntersection::Curve(Curve::Circle(circle))
+                }
+                CircleConeIntersection::Points(points) => CurveSurfaceIntersection::Points(points),
+                CircleConeIntersection::None => CurveSurfaceIntersection::None,
+            },
+            Surface::Torus(torus) => match circle_torus_intersection(circle, torus) {
+                CircleTorusIntersection::Circle(circle) => {
+                    CurveSurfaceIntersection::Curve(Curve::Circle(circle))
+                }
+                CircleTorusIntersection::Points(points) => CurveSurfaceIntersection::Points(points),
+                CircleTorusIntersection::None => CurveSurfaceIntersection::None,
+            },
+            Surface::Nurbs(nurbs) => curve_nurbs_surface(curve, nurbs),
+        },
+        Curve::Ellipse(ellipse) => match surface {
+            Surface::Nurbs(nurbs) => curve_nurbs_surface(curve, nurbs),
+            _ => sampled(
+                curve,
+                curve_surface_intersection_sampled(
+                    &|t| ellipse.interpolate(None, None, t).unwrap(),
+                    surface,
+                ),
+            ),
+        },
+        Curve::Helix(helix) => match surface {
+            Surface::Sphere(sphere) => {
+                return helix_bounded_surface(helix, surface, sphere.basis, sphere.radius);
+            }
+            Surface::Torus(torus) => {
+                return helix_bounded_surface(
+                    helix,
+                    surface,
+                    torus.basis,
+                    torus.major_radius + torus.minor_radius,
+                );
+            }
+            Surface::Nurbs(nurbs) => curve_nurbs_surface(curve, nurbs),
+            // A helix lies on a cylinder if the axes are parallel and three points of a turn are on it
+            Surface::Cylinder(cylinder)
+                if helix.pitch.cross(cylinder.extend_dir).norm() == 0.0
+                    && [0.0, 0.25, 0.5]
+                        .iter()
+                        .all(|t| cylinder.on_surface(helix.point_at(*t))) =>
+            {
+                CurveSurfaceIntersection::Curve(curve.clone())
+            }
+            Surface::Plane(_) | Surface::Cylinder(_) | Surface::Cone(_) => {
+                return Err(GeometryError::new(
+                    "Intersections of helices with unbounded surfaces are not supported"
+                        .to_string(),
+                )
+                .with_context_scene(
+                    "Intersect a helix with a surface".to_string(),
+                    GeometryScene {
+                        points: vec![],
+                        curves: vec![(curve.clone(), Category10Color::Red)],
+                        surfaces: vec![(surface.clone(), Category10Color::Blue)],
+                    },
+                ));
+            }
+        },
+        Curve::SampledCurve(sampled) => {
+            match sampled_curve_surface_intersection(sampled, surface) {
+                SampledCurveSurfaceIntersection::Curve(sampled) => {
+                    CurveSurfaceIntersection::Curve(Curve::SampledCurve(sampled))
+                }
+                SampledCurveSurfaceIntersection::Points(points) => {
+                    CurveSurfaceIntersection::Points(points)
+                }
+                SampledCurveSurfaceIntersection::None => CurveSurfaceIntersection::None,
+            }
+        }
+        Curve::Nurbs(nurbs) => match nurbs_curve_surface_intersection(nurbs, surface) {
+            NurbsCurveSurfaceIntersection::Curve(nurbs) => {
+                CurveSurfaceIntersection::Curve(Curve::Nurbs(nurbs))
+            }
+            NurbsCurveSurfaceIntersection::Points(points) => {
+                CurveSurfaceIntersection::Points(points)
+            }
+            NurbsCurveSurfaceIntersection::None => CurveSurfaceIntersection::None,
         },
-        Curve::Ellipse(_) => todo!("Implement this"),
-        Curve::Helix(_) => todo!("Implement this"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curves::ellipse::Ellipse,
+        surfaces::{cylinder::Cylinder, plane::Plane, sphere::Sphere},
+    };
+
+    #[test]
+    fn test_ellipse_sphere_intersection() {
+        let ellipse = Curve::Ellipse(
+            Ellipse::try_new(
+                Point::zero(),
+                Point::unit_z(),
+                Point::from_f64(2.0, 0.0, 0.0),
+                Point::from_f64(0.0, 0.5, 0.0),
+            )
+            .unwrap(),
+        );
+        let sphere = Surface::Sphere(Sphere::new(Point::zero(), EFloat64::one(), true));
+        match curve_surface_intersection(&ellipse, &sphere).unwrap() {
+            CurveSurfaceIntersection::Points(points) => {
+                assert_eq!(points.len(), 4);
+                assert!(points.iter().all(|p| sphere.on_surface(*p)));
+            }
+            _ => panic!("Expected four points"),
+        }
+    }
+
+    #[test]
+    fn test_helix_surface_intersection() {
+        let helix = Curve::Helix(Helix::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::unit_x(),
+            true,
+        ));
+        let sphere = Surface::Sphere(Sphere::new(Point::zero(), EFloat64::two(), true));
+        match curve_surface_intersection(&helix, &sphere).unwrap() {
+            CurveSurfaceIntersection::Points(points) => {
+                assert_eq!(points.len(), 2);
+                assert!(points.iter().all(|p| sphere.on_surface(*p)));
+            }
+            _ => panic!("Expected two points"),
+        }
+
+        let cylinder = Surface::Cylinder(Cylinder::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::one(),
+            true,
+        ));
+        assert!(curve_surface_intersection(&helix, &cylinder)
+            .unwrap()
+            .is_curve());
+
+        let plane = Surface::Plane(Plane::new(Point::zero(), Point::unit_x(), Point::unit_y()));
+        assert!(curve_surface_intersection(&helix, &plane).is_err());
     }
 }
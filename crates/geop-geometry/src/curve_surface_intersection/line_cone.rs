@@ -0,0 +1,128 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{curves::line::Line, point::Point, surfaces::cone::Cone};
+
+pub enum LineConeIntersection {
+    Line(Line),
+    TwoPoints(Point, Point),
+    OnePoint(Point),
+    None,
+}
+
+pub fn line_cone_intersection(line: &Line, cone: &Cone) -> LineConeIntersection {
+    // A point p is on the double cone if ((p - apex) * axis)^2 = cos^2 * |p - apex|^2.
+    // For p = basis + t * direction this is the quadratic a * t^2 + b * t + c = 0.
+    let w = line.basis - cone.apex;
+    let v = line.direction;
+    let d = cone.axis;
+    let cos_sq = cone.half_angle.cos().square();
+    let a = v.dot(d).square() - cos_sq * v.norm_sq();
+    let b = EFloat64::two() * (v.dot(d) * w.dot(d) - cos_sq * v.dot(w));
+    let c = w.dot(d).square() - cos_sq * w.norm_sq();
+
+    // The line is a generator of the cone
+    if a == 0.0 && b == 0.0 && c == 0.0 {
+        return LineConeIntersection::Line(line.clone());
+    }
+
+    let candidates = if a == 0.0 {
+        // The line is parallel to a generator and hits the cone at most once
+        if b == 0.0 {
+            vec![]
+        } else {
+            vec![(-c / b).unwrap()]
+        }
+    } else {
+        let discriminant = b * b - EFloat64::from(4.0) * a * c;
+        if discriminant < 0.0 {
+            vec![]
+        } else if discriminant == 0.0 {
+            vec![(-b / (EFloat64::two() * a)).unwrap()]
+        } else {
+            let root = discriminant.sqrt().unwrap();
+            vec![
+                ((-b + root) / (EFloat64::two() * a)).unwrap(),
+                ((-b - root) / (EFloat64::two() * a)).unwrap(),
+            ]
+        }
+    };
+
+    // Only the nappe in direction of the axis belongs to the cone
+    let points = candidates
+        .into_iter()
+        .map(|t| line.basis + v * t)
+        .filter(|p| (*p - cone.apex).dot(d) >= 0.0)
+        .collect::<Vec<Point>>();
+    match points.as_slice() {
+        [] => LineConeIntersection::None,
+        [p] => LineConeIntersection::OnePoint(*p),
+        [p, q] => LineConeIntersection::TwoPoints(*p, *q),
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::SurfaceLike;
+
+    fn cone() -> Cone {
+        Cone::try_new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(std::f64::consts::FRAC_PI_4),
+            true,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_line_cone_intersection() {
+        let cone = cone();
+        let line = Line::new(Point::from_f64(0.0, 0.0, 1.0), Point::unit_x()).unwrap();
+        match line_cone_intersection(&line, &cone) {
+            LineConeIntersection::TwoPoints(p, q) => {
+                assert!(p == Point::from_f64(1.0, 0.0, 1.0) || q == Point::from_f64(1.0, 0.0, 1.0));
+                assert!(
+                    p == Point::from_f64(-1.0, 0.0, 1.0) || q == Point::from_f64(-1.0, 0.0, 1.0)
+                );
+            }
+            _ => panic!("Intersection should be two points"),
+        }
+
+        // The axis only touches the apex, the other nappe is not part of the cone
+        let line = Line::new(Point::zero(), Point::unit_z()).unwrap();
+        match line_cone_intersection(&line, &cone) {
+            LineConeIntersection::OnePoint(p) => assert_eq!(p, Point::zero()),
+            _ => panic!("Intersection should be the apex"),
+        }
+
+        let line = Line::new(Point::from_f64(0.0, 0.0, -1.0), Point::unit_x()).unwrap();
+        match line_cone_intersection(&line, &cone) {
+            LineConeIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+
+        // Parallel to a generator
+        let direction = Point::from_f64(1.0, 0.0, 1.0).normalize().unwrap();
+        let line = Line::new(Point::from_f64(-1.0, 0.0, 0.0), direction).unwrap();
+        match line_cone_intersection(&line, &cone) {
+            LineConeIntersection::OnePoint(p) => {
+                assert!(cone.on_surface(p));
+                assert_eq!(p, Point::from_f64(-0.5, 0.0, 0.5));
+            }
+            _ => panic!("Intersection should be one point"),
+        }
+    }
+
+    #[test]
+    fn test_line_cone_intersection_generator() {
+        let cone = cone();
+        let direction = Point::from_f64(0.0, 1.0, 1.0).normalize().unwrap();
+        let line = Line::new(Point::from_f64(0.0, 2.0, 2.0), direction).unwrap();
+        match line_cone_intersection(&line, &cone) {
+            LineConeIntersection::Line(_) => {}
+            _ => panic!("Intersection should be the line"),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{curves::line::Line, point::Point, surfaces::torus::Torus};
+
+use super::curve_surface_intersection_sampled;
+
+pub enum LineTorusIntersection {
+    Points(Vec<Point>),
+    None,
+}
+
+// The intersection is a quartic equation, so the line is sampled where it passes the bounding sphere of the torus.
+pub fn line_torus_intersection(line: &Line, torus: &Torus) -> LineTorusIntersection {
+    let outer_radius = torus.major_radius + torus.minor_radius;
+    let closest = (torus.basis - line.basis).dot(line.direction);
+    let distance_sq = (line.basis + line.direction * closest - torus.basis).norm_sq();
+    let half_length_sq = outer_radius * outer_radius - distance_sq;
+    if half_length_sq < 0.0 {
+        return LineTorusIntersection::None;
+    }
+    let half_length = half_length_sq.max(EFloat64::zero()).sqrt().unwrap();
+    let start = line.basis + line.direction * (closest - half_length);
+    let span = line.direction * (EFloat64::two() * half_length);
+
+    match curve_surface_intersection_sampled(&|t| start + span * EFloat64::from(t), torus) {
+        Some(points) if !points.is_empty() => LineTorusIntersection::Points(points),
+        _ => LineTorusIntersection::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_torus_intersection() {
+        let torus = Torus::try_new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(2.0),
+            EFloat64::from(0.5),
+            true,
+        )
+        .unwrap();
+
+        let line = Line::new(Point::zero(), Point::unit_x()).unwrap();
+        match line_torus_intersection(&line, &torus) {
+            LineTorusIntersection::Points(points) => {
+                assert_eq!(points.len(), 4);
+                for x in [-2.5, -1.5, 1.5, 2.5] {
+                    assert!(points
+                        .iter()
+                        .any(|p| (*p - Point::from_f64(x, 0.0, 0.0)).norm() < 1e-8));
+                }
+            }
+            _ => panic!("Intersection should be four points"),
+        }
+
+        // Through the hole
+        let line = Line::new(Point::zero(), Point::unit_z()).unwrap();
+        match line_torus_intersection(&line, &torus) {
+            LineTorusIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+
+        let line = Line::new(Point::from_f64(2.0, -5.0, 0.0), Point::unit_y()).unwrap();
+        match line_torus_intersection(&line, &torus) {
+            LineTorusIntersection::Points(points) => assert_eq!(points.len(), 2),
+            _ => panic!("Intersection should be two points"),
+        }
+    }
+}
@@ -1,14 +1,25 @@
 use geop_algebra::efloat::EFloat64;
 
-use crate::{curves::CurveLike, point::Point, surfaces::SurfaceLike};
+use crate::{
+    curves::{helix::Helix, CurveLike},
+    point::Point,
+    surfaces::SurfaceLike,
+};
 
+pub mod circle_cone;
 pub mod circle_cylinder;
 pub mod circle_plane;
 pub mod circle_sphere;
+pub mod circle_torus;
+pub mod curve_nurbs_surface;
 pub mod curve_surface;
+pub mod line_cone;
 pub mod line_cylinder;
 pub mod line_plane;
 pub mod line_sphere;
+pub mod line_torus;
+pub mod nurbs_curve_surface;
+pub mod sampled_curve_surface;
 
 pub fn curve_surface_intersection_numerical(
     curve: &dyn CurveLike,
@@ -39,3 +50,102 @@ pub fn curve_surface_intersection_numerical(
 
     result
 }
+
+const SAMPLES: usize = 256;
+const BISECTION_ITERATIONS: usize = 60;
+const PRECISION: f64 = 1e-8;
+
+// Distance of p to the surface, positive on the side the normal points to.
+fn signed_distance(surface: &dyn SurfaceLike, p: Point) -> f64 {
+    let q = surface.project(p);
+    (p - q).dot(surface.normal(q)).to_f64()
+}
+
+// Intersects a curve, given by a parametrization over [0, 1], with a surface by finding sign changes of the signed distance along samples.
+// Returns None if all samples lie on the surface. Tangential contacts without a sign change are not found.
+pub fn curve_surface_intersection_sampled(
+    at: &dyn Fn(f64) -> Point,
+    surface: &dyn SurfaceLike,
+) -> Option<Vec<Point>> {
+    let distances = (0..=SAMPLES)
+        .map(|i| signed_distance(surface, at(i as f64 / SAMPLES as f64)))
+        .collect::<Vec<f64>>();
+
+    if distances.iter().all(|d| d.abs() <= PRECISION) {
+        return None;
+    }
+
+    let mut points = Vec::<Point>::new();
+    for i in 0..=SAMPLES {
+        let p = if distances[i].abs() <= PRECISION {
+            at(i as f64 / SAMPLES as f64)
+        } else if i < SAMPLES
+            && distances[i + 1].abs() > PRECISION
+            && distances[i].signum() != distances[i + 1].signum()
+        {
+            let (mut lo, mut hi) = (i as f64 / SAMPLES as f64, (i + 1) as f64 / SAMPLES as f64);
+            for _ in 0..BISECTION_ITERATIONS {
+                let t = (lo + hi) / 2.0;
+                if signed_distance(surface, at(t)).signum() == distances[i].signum() {
+                    lo = t;
+                } else {
+                    hi = t;
+                }
+            }
+            at((lo + hi) / 2.0)
+        } else {
+            continue;
+        };
+
+        // Bounded surfaces can flip the sign of the distance beyond their boundary without being hit
+        let q = surface.project(p);
+        if (p - q).norm() > 10.0 * PRECISION {
+            continue;
+        }
+        if !points.iter().any(|r| (q - *r).norm() <= 10.0 * PRECISION) {
+            points.push(q);
+        }
+    }
+    Some(points)
+}
+
+// Intersects a helix with a bounded surface, which lies in the convex hull of the given points, by sampling every turn
+// that passes the points on its own, such that the samples are as dense as for a circle.
+pub fn helix_surface_intersection_sampled(
+    helix: &Helix,
+    surface: &dyn SurfaceLike,
+    hull: &[Point],
+) -> Option<Vec<Point>> {
+    let pitch_sq = helix.pitch.norm_sq().to_f64();
+    let turns = hull
+        .iter()
+        .map(|p| (*p - helix.basis).dot(helix.pitch).to_f64() / pitch_sq)
+        .collect::<Vec<f64>>();
+    let t0 = turns.iter().cloned().fold(f64::INFINITY, f64::min).floor();
+    let t1 = turns
+        .iter()
+        .cloned()
+        .fold(f64::NEG_INFINITY, f64::max)
+        .ceil();
+
+    let mut on_surface = true;
+    let mut points = Vec::<Point>::new();
+    for turn in (t0 as i64)..(t1 as i64).max(t0 as i64 + 1) {
+        let turn = turn as f64;
+        match curve_surface_intersection_sampled(&|t| helix.point_at(turn + t), surface) {
+            None => {}
+            Some(turn_points) => {
+                on_surface = false;
+                for p in turn_points {
+                    if !points.iter().any(|q| (p - *q).norm() <= 10.0 * PRECISION) {
+                        points.push(p);
+                    }
+                }
+            }
+        }
+    }
+    match on_surface {
+        true => None,
+        false => Some(points),
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{
+    curves::{nurb::NurbsCurve, CurveLike},
+    point::Point,
+    surfaces::surface::Surface,
+};
+
+use super::curve_surface_intersection_sampled;
+
+pub enum NurbsCurveSurfaceIntersection {
+    Curve(NurbsCurve),
+    Points(Vec<Point>),
+    None,
+}
+
+// Tangential contacts without a sign change of the distance are not found.
+pub fn nurbs_curve_surface_intersection(
+    curve: &NurbsCurve,
+    surface: &Surface,
+) -> NurbsCurveSurfaceIntersection {
+    match curve_surface_intersection_sampled(
+        &|t| curve.interpolate(None, None, t).unwrap(),
+        surface,
+    ) {
+        None => NurbsCurveSurfaceIntersection::Curve(curve.clone()),
+        Some(points) if points.is_empty() => NurbsCurveSurfaceIntersection::None,
+        Some(points) => NurbsCurveSurfaceIntersection::Points(points),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geop_algebra::efloat::EFloat64;
+
+    use crate::surfaces::{plane::Plane, SurfaceLike};
+
+    #[test]
+    fn test_nurbs_plane_intersection() {
+        // A cubic curve in the xy plane, going from x = -1 to x = 1 with an s-shape
+        let curve = NurbsCurve::try_new(
+            3,
+            vec![
+                Point::from_f64(-1.0, 0.0, 0.0),
+                Point::from_f64(-0.3, 1.0, 0.0),
+                Point::from_f64(0.3, -1.0, 0.0),
+                Point::from_f64(1.0, 0.0, 0.0),
+            ],
+            vec![
+                EFloat64::one(),
+                EFloat64::from(2.0),
+                EFloat64::one(),
+                EFloat64::one(),
+            ],
+            vec![EFloat64::zero(); 4]
+                .into_iter()
+                .chain(vec![EFloat64::one(); 4])
+                .collect(),
+        )
+        .unwrap();
+
+        let plane = Surface::Plane(Plane::new(Point::zero(), Point::unit_x(), Point::unit_y()));
+        match nurbs_curve_surface_intersection(&curve, &plane) {
+            NurbsCurveSurfaceIntersection::Curve(_) => {}
+            _ => panic!("Intersection should be the curve"),
+        }
+
+        let plane = Surface::Plane(Plane::new(
+            Point::from_f64(0.5, 0.0, 0.0),
+            Point::unit_y(),
+            Point::unit_z(),
+        ));
+        match nurbs_curve_surface_intersection(&curve, &plane) {
+            NurbsCurveSurfaceIntersection::Points(points) => {
+                assert_eq!(points.len(), 1);
+                assert!(plane.on_surface(points[0]));
+                assert!(curve.on_curve(points[0]));
+            }
+            _ => panic!("Intersection should be one point"),
+        }
+
+        let plane = Surface::Plane(Plane::new(
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_y(),
+            Point::unit_z(),
+        ));
+        match nurbs_curve_surface_intersection(&curve, &plane) {
+            NurbsCurveSurfaceIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curves::sampled_curve::SampledCurve,
+    point::Point,
+    surfaces::{surface::Surface, SurfaceLike},
+};
+
+pub enum SampledCurveSurfaceIntersection {
+    Curve(SampledCurve),
+    Points(Vec<Point>),
+    None,
+}
+
+// Distance of p to the surface, positive on the side the normal points to.
+fn signed_distance(surface: &Surface, p: Point) -> f64 {
+    let q = surface.project(p);
+    (p - q).dot(surface.normal(q)).to_f64()
+}
+
+pub fn sampled_curve_surface_intersection(
+    curve: &SampledCurve,
+    surface: &Surface,
+) -> SampledCurveSurfaceIntersection {
+    let distances = curve
+        .points
+        .iter()
+        .map(|p| signed_distance(surface, *p))
+        .collect::<Vec<f64>>();
+
+    if distances.iter().all(|d| d.abs() <= curve.error_bound) {
+        return SampledCurveSurfaceIntersection::Curve(curve.clone());
+    }
+
+    let n = curve.points.len();
+    let mut points = Vec::<Point>::new();
+    for i in 0..n {
+        let (a, b) = (curve.points[i], curve.points[(i + 1) % n]);
+        let (d_a, d_b) = (distances[i], distances[(i + 1) % n]);
+        if d_a != 0.0 && d_a.signum() == d_b.signum() {
+            continue;
+        }
+
+        // Bisect along the segment for the sign change
+        let (mut lo, mut hi) = (0.0, 1.0);
+        for _ in 0..60 {
+            let t = (lo + hi) / 2.0;
+            let d = signed_distance(surface, a + (b - a) * EFloat64::from(t));
+            if d.signum() == d_a.signum() && d != 0.0 {
+                lo = t;
+            } else {
+                hi = t;
+            }
+        }
+        let p = surface.project(a + (b - a) * EFloat64::from(hi));
+        if !points
+            .iter()
+            .any(|q| (p - *q).norm() <= 2.0 * curve.error_bound)
+        {
+            points.push(p);
+        }
+    }
+
+    if points.is_empty() {
+        return SampledCurveSurfaceIntersection::None;
+    }
+    SampledCurveSurfaceIntersection::Points(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::plane::Plane;
+
+    fn unit_circle() -> SampledCurve {
+        let points = (0..256)
+            .map(|i| {
+                let angle = 2.0 * std::f64::consts::PI * i as f64 / 256.0;
+                Point::from_f64(angle.cos(), angle.sin(), 0.0)
+            })
+            .collect();
+        SampledCurve::try_new(points, 1e-3).unwrap()
+    }
+
+    #[test]
+    fn test_sampled_curve_plane_intersection() {
+        let curve = unit_circle();
+
+        let plane = Surface::Plane(Plane::new(Point::zero(), Point::unit_x(), Point::unit_y()));
+        match sampled_curve_surface_intersection(&curve, &plane) {
+            SampledCurveSurfaceIntersection::Curve(_) => {}
+            _ => panic!("Intersection should be the curve"),
+        }
+
+        let plane = Surface::Plane(Plane::new(
+            Point::from_f64(0.5, 0.0, 0.0),
+            Point::unit_y(),
+            Point::unit_z(),
+        ));
+        match sampled_curve_surface_intersection(&curve, &plane) {
+            SampledCurveSurfaceIntersection::Points(points) => {
+                assert_eq!(points.len(), 2);
+                for p in points {
+                    assert!(plane.on_surface(p));
+                    assert!((p.norm() - EFloat64::one()).abs() < 1e-3);
+                }
+            }
+            _ => panic!("Intersection should be two points"),
+        }
+
+        let plane = Surface::Plane(Plane::new(
+            Point::from_f64(0.0, 0.0, 1.0),
+            Point::unit_x(),
+            Point::unit_y(),
+        ));
+        match sampled_curve_surface_intersection(&curve, &plane) {
+            SampledCurveSurfaceIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+    }
+}
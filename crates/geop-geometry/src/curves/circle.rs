@@ -14,13 +14,47 @@ use crate::{
 use super::{curve::Curve, CurveLike};
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "CircleRaw"))]
 pub struct Circle {
     pub basis: Point,
     pub normal: Point,
     pub radius: Point,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
     dir_cross: Point,
 }
 
+// Deserialized circles are checked like in try_new, but keep their radius vector, as normalizing it again would widen it.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CircleRaw {
+    basis: Point,
+    normal: Point,
+    radius: Point,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CircleRaw> for Circle {
+    type Error = GeometryError;
+
+    fn try_from(raw: CircleRaw) -> GeometryResult<Circle> {
+        if !raw.normal.is_normalized() {
+            return Err(GeometryError::new("Normal must be normalized".to_string()));
+        }
+        if raw.radius.norm() <= 0.0 || raw.normal.dot(raw.radius) != 0.0 {
+            return Err(GeometryError::new(
+                "Radius must be positive and orthogonal to the normal".to_string(),
+            ));
+        }
+        Ok(Circle {
+            basis: raw.basis,
+            normal: raw.normal,
+            radius: raw.radius,
+            dir_cross: raw.normal.cross(raw.radius),
+        })
+    }
+}
+
 pub enum CircleTransform {
     Circle(Circle),
     Ellipse(), // TODO: Implement this
@@ -335,10 +369,28 @@ impl CurveLike for Circle {
 
     fn get_bounding_box(
         &self,
-        _interval_self: Option<Point>,
-        _midpoint_self: Option<Point>,
+        start: Option<Point>,
+        end: Option<Point>,
     ) -> GeometryResult<BoundingBox> {
-        todo!("Implement this")
+        let mid_point = self.get_midpoint(start, end)?;
+        let mut bounding_box = BoundingBox::new(mid_point, mid_point);
+        for point in [start, end].into_iter().flatten() {
+            bounding_box.add_point(point);
+        }
+        // The circle is extremal along an axis where the radius points along the axis projected into the plane of the circle
+        for axis in [Point::unit_x(), Point::unit_y(), Point::unit_z()] {
+            if let Ok(direction) = (axis - self.normal * axis.dot(self.normal)).normalize() {
+                for point in [
+                    self.basis + direction * self.radius.norm(),
+                    self.basis - direction * self.radius.norm(),
+                ] {
+                    if self.between(point, start, end)? {
+                        bounding_box.add_point(point);
+                    }
+                }
+            }
+        }
+        Ok(bounding_box)
     }
 
     fn shrink_bounding_box(
@@ -350,14 +402,56 @@ impl CurveLike for Circle {
         todo!("Implement this")
     }
 
-    fn sort(&self, _points: Vec<Option<Point>>) -> Vec<Option<Point>> {
-        todo!("Implement this")
+    // Points are sorted by their angle from the radius vector, followed by the missing points.
+    fn sort(&self, points: Vec<Option<Point>>) -> Vec<Option<Point>> {
+        let angle = |p: Point| {
+            self.radius
+                .angle2(p - self.basis, self.normal)
+                .map_or(0.0, |angle| angle.to_f64())
+                .rem_euclid(2.0 * std::f64::consts::PI)
+        };
+        let mut points = points;
+        points.sort_unstable_by(|a, b| match (a, b) {
+            (Some(a), Some(b)) => angle(*a).total_cmp(&angle(*b)),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+        points
+    }
+
+    // The left side of a counter-clockwise circle is its inside.
+    fn offset_in_plane(&self, distance: EFloat64, normal: Point) -> GeometryResult<Curve> {
+        let normal = normal.normalize()?;
+        if normal.cross(self.normal).norm() != 0.0 {
+            return Err(GeometryError::new(format!(
+                "Circle {} does not lie in a plane with normal {}",
+                self, normal
+            )));
+        }
+        let radius = match normal.dot(self.normal) > 0.0 {
+            true => self.radius.norm() - distance,
+            false => self.radius.norm() + distance,
+        };
+        if radius <= 0.0 {
+            return Err(GeometryError::new(format!(
+                "Offset of circle {} by {} has no positive radius",
+                self, distance
+            )));
+        }
+        Ok(Curve::Circle(Circle::try_new(
+            self.basis,
+            self.normal,
+            radius,
+        )?))
     }
 }
 
 impl PartialEq for Circle {
     fn eq(&self, other: &Circle) -> bool {
-        self.basis == other.basis && self.normal == other.normal && self.radius == other.radius
+        self.basis == other.basis
+            && self.normal == other.normal
+            && self.radius.norm() == other.radius.norm()
     }
 }
 
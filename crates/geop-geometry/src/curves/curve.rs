@@ -11,15 +11,20 @@ use super::{
     ellipse::Ellipse,
     helix::Helix,
     line::Line,
+    nurb::NurbsCurve,
+    sampled_curve::SampledCurve,
     CurveLike,
 };
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Curve {
     Line(Line),
     Circle(Circle),
     Ellipse(Ellipse),
     Helix(Helix),
+    SampledCurve(SampledCurve),
+    Nurbs(NurbsCurve),
 }
 
 // This represents a curve, which can be a line or a circle.
@@ -34,6 +39,8 @@ impl CurveLike for Curve {
             },
             Curve::Ellipse(ellipse) => Curve::Ellipse(ellipse.transform(transform)),
             Curve::Helix(helix) => Curve::Helix(helix.transform(transform)),
+            Curve::SampledCurve(sampled) => Curve::SampledCurve(sampled.transform(transform)),
+            Curve::Nurbs(nurbs) => Curve::Nurbs(nurbs.transform(transform)),
         }
     }
 
@@ -44,6 +51,8 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => Curve::Circle(circle.neg()),
             Curve::Ellipse(ellipse) => Curve::Ellipse(ellipse.neg()),
             Curve::Helix(helix) => Curve::Helix(helix.neg()),
+            Curve::SampledCurve(sampled) => Curve::SampledCurve(sampled.neg()),
+            Curve::Nurbs(nurbs) => Curve::Nurbs(nurbs.neg()),
         }
     }
 
@@ -54,6 +63,8 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.tangent(p),
             Curve::Ellipse(ellipse) => ellipse.tangent(p),
             Curve::Helix(helix) => helix.tangent(p),
+            Curve::SampledCurve(sampled) => sampled.tangent(p),
+            Curve::Nurbs(nurbs) => nurbs.tangent(p),
         }
     }
 
@@ -64,6 +75,8 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.on_curve(p),
             Curve::Ellipse(ellipse) => ellipse.on_curve(p),
             Curve::Helix(helix) => helix.on_curve(p),
+            Curve::SampledCurve(sampled) => sampled.on_curve(p),
+            Curve::Nurbs(nurbs) => nurbs.on_curve(p),
         }
     }
 
@@ -74,6 +87,8 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.distance(x, y),
             Curve::Ellipse(ellipse) => ellipse.distance(x, y),
             Curve::Helix(helix) => helix.distance(x, y),
+            Curve::SampledCurve(sampled) => sampled.distance(x, y),
+            Curve::Nurbs(nurbs) => nurbs.distance(x, y),
         }
     }
 
@@ -89,6 +104,8 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.interpolate(start, end, t),
             Curve::Ellipse(ellipse) => ellipse.interpolate(start, end, t),
             Curve::Helix(helix) => helix.interpolate(start, end, t),
+            Curve::SampledCurve(sampled) => sampled.interpolate(start, end, t),
+            Curve::Nurbs(nurbs) => nurbs.interpolate(start, end, t),
         }
     }
 
@@ -99,6 +116,8 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.between(m, start, end),
             Curve::Ellipse(ellipse) => ellipse.between(m, start, end),
             Curve::Helix(helix) => helix.between(m, start, end),
+            Curve::SampledCurve(sampled) => sampled.between(m, start, end),
+            Curve::Nurbs(nurbs) => nurbs.between(m, start, end),
         }
     }
 
@@ -112,6 +131,8 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.get_midpoint(start, end),
             Curve::Ellipse(ellipse) => ellipse.get_midpoint(start, end),
             Curve::Helix(helix) => helix.get_midpoint(start, end),
+            Curve::SampledCurve(sampled) => sampled.get_midpoint(start, end),
+            Curve::Nurbs(nurbs) => nurbs.get_midpoint(start, end),
         }
     }
 
@@ -122,6 +143,8 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.project(p),
             Curve::Ellipse(ellipse) => ellipse.project(p),
             Curve::Helix(helix) => helix.project(p),
+            Curve::SampledCurve(sampled) => sampled.project(p),
+            Curve::Nurbs(nurbs) => nurbs.project(p),
         }
     }
 
@@ -136,6 +159,8 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.get_bounding_box(start, end),
             Curve::Ellipse(ellipse) => ellipse.get_bounding_box(start, end),
             Curve::Helix(helix) => helix.get_bounding_box(start, end),
+            Curve::SampledCurve(sampled) => sampled.get_bounding_box(start, end),
+            Curve::Nurbs(nurbs) => nurbs.get_bounding_box(start, end),
         }
     }
 
@@ -151,6 +176,8 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.shrink_bounding_box(start, end, bounding_box),
             Curve::Ellipse(ellipse) => ellipse.shrink_bounding_box(start, end, bounding_box),
             Curve::Helix(helix) => helix.shrink_bounding_box(start, end, bounding_box),
+            Curve::SampledCurve(sampled) => sampled.shrink_bounding_box(start, end, bounding_box),
+            Curve::Nurbs(nurbs) => nurbs.shrink_bounding_box(start, end, bounding_box),
         }
     }
 
@@ -162,6 +189,20 @@ impl CurveLike for Curve {
             Curve::Circle(circle) => circle.sort(points),
             Curve::Ellipse(ellipse) => ellipse.sort(points),
             Curve::Helix(helix) => helix.sort(points),
+            Curve::SampledCurve(sampled) => sampled.sort(points),
+            Curve::Nurbs(nurbs) => nurbs.sort(points),
+        }
+    }
+
+    // Returns the curve that is moved by distance to the left in the plane with the given normal.
+    fn offset_in_plane(&self, distance: EFloat64, normal: Point) -> GeometryResult<Curve> {
+        match self {
+            Curve::Line(line) => line.offset_in_plane(distance, normal),
+            Curve::Circle(circle) => circle.offset_in_plane(distance, normal),
+            Curve::Ellipse(ellipse) => ellipse.offset_in_plane(distance, normal),
+            Curve::Helix(helix) => helix.offset_in_plane(distance, normal),
+            Curve::SampledCurve(sampled) => sampled.offset_in_plane(distance, normal),
+            Curve::Nurbs(nurbs) => nurbs.offset_in_plane(distance, normal),
         }
     }
 }
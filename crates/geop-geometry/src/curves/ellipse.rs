@@ -11,9 +11,14 @@ use crate::{
     transforms::Transform,
 };
 
-use super::{curve::Curve, CurveLike};
+use super::{curve::Curve, sampled_curve::SampledCurve, CurveLike};
+
+// Number of samples that represent the offset of an ellipse.
+const ELLIPSE_OFFSET_SAMPLES: usize = 64;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "EllipseRaw"))]
 pub struct Ellipse {
     pub basis: Point,
     pub normal: Point,
@@ -21,6 +26,24 @@ pub struct Ellipse {
     pub minor_radius: Point,
 }
 
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct EllipseRaw {
+    basis: Point,
+    normal: Point,
+    major_radius: Point,
+    minor_radius: Point,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<EllipseRaw> for Ellipse {
+    type Error = GeometryError;
+
+    fn try_from(raw: EllipseRaw) -> GeometryResult<Ellipse> {
+        Ellipse::try_new(raw.basis, raw.normal, raw.major_radius, raw.minor_radius)
+    }
+}
+
 impl Ellipse {
     pub fn try_new(
         basis: Point,
@@ -488,6 +511,57 @@ impl CurveLike for Ellipse {
     fn sort(&self, _points: Vec<Option<Point>>) -> Vec<Option<Point>> {
         todo!("Implement this")
     }
+
+    // The offset of an ellipse is not a conic, so it is represented by samples of the exact offset curve.
+    fn offset_in_plane(&self, distance: EFloat64, normal: Point) -> GeometryResult<Curve> {
+        let normal = normal.normalize()?;
+        if normal.cross(self.normal).norm() != 0.0 {
+            return Err(GeometryError::new(format!(
+                "Ellipse {} does not lie in a plane with normal {}",
+                self, normal
+            )));
+        }
+        // The ellipse runs from the major towards the negative minor radius.
+        // Towards the inside, the offset curve gets cusps beyond the smallest radius of curvature b^2 / a.
+        let (a, b) = (self.major_radius.norm(), self.minor_radius.norm());
+        let outwards = normal.dot(self.major_radius.cross(self.minor_radius)) > 0.0;
+        let inwards_distance = match outwards {
+            true => -distance,
+            false => distance,
+        };
+        if inwards_distance >= (b * b / a)? {
+            return Err(GeometryError::new(format!(
+                "Offset of ellipse {} by {} is larger than its smallest radius of curvature",
+                self, distance
+            )));
+        }
+        let offset_point = |angle: f64| -> GeometryResult<Point> {
+            let (sin, cos) = (EFloat64::from(angle.sin()), EFloat64::from(angle.cos()));
+            let p = self.basis + self.major_radius * cos + self.minor_radius * sin;
+            let tangent = self.major_radius * sin - self.minor_radius * cos;
+            Ok(p + normal.cross(tangent).normalize()? * distance)
+        };
+        let angles = (0..2 * ELLIPSE_OFFSET_SAMPLES)
+            .map(|i| -std::f64::consts::PI * i as f64 / ELLIPSE_OFFSET_SAMPLES as f64)
+            .collect::<Vec<f64>>();
+        let points = angles
+            .iter()
+            .step_by(2)
+            .map(|angle| offset_point(*angle))
+            .collect::<GeometryResult<Vec<Point>>>()?;
+        // The polygon deviates most from the curve between two samples
+        let mut error_bound = f64::EPSILON;
+        for (i, angle) in angles.iter().enumerate().skip(1).step_by(2) {
+            let chord_midpoint =
+                (points[i / 2] + points[(i / 2 + 1) % points.len()]) * EFloat64::from(0.5);
+            let deviation = (offset_point(*angle)? - chord_midpoint).norm().upper_bound;
+            error_bound = error_bound.max(2.0 * deviation);
+        }
+        Ok(Curve::SampledCurve(SampledCurve::try_new(
+            points,
+            error_bound,
+        )?))
+    }
 }
 
 impl PartialEq for Ellipse {
@@ -508,3 +582,39 @@ impl Display for Ellipse {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ellipse() -> Ellipse {
+        Ellipse::try_new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_ellipse_offset() {
+        let ellipse = ellipse();
+        let outwards = ellipse
+            .offset_in_plane(EFloat64::from(0.2), Point::unit_z())
+            .unwrap();
+        assert!(outwards.on_curve(Point::from_f64(2.2, 0.0, 0.0)));
+        assert!(outwards.on_curve(Point::from_f64(0.0, -1.2, 0.0)));
+        let inwards = ellipse
+            .offset_in_plane(EFloat64::from(0.2), -Point::unit_z())
+            .unwrap();
+        assert!(inwards.on_curve(Point::from_f64(-1.8, 0.0, 0.0)));
+        // The smallest radius of curvature is 1 / 2 at the ends of the major axis
+        assert!(ellipse
+            .offset_in_plane(EFloat64::from(0.6), -Point::unit_z())
+            .is_err());
+        assert!(ellipse
+            .offset_in_plane(EFloat64::from(0.2), Point::unit_x())
+            .is_err());
+    }
+}
@@ -3,21 +3,61 @@ use core::f64;
 use geop_algebra::efloat::EFloat64;
 
 use crate::{
-    bounding_box::BoundingBox, geometry_error::GeometryResult, point::Point, transforms::Transform,
+    bounding_box::BoundingBox,
+    geometry_error::{GeometryError, GeometryResult},
+    point::Point,
+    transforms::Transform,
     HORIZON_DIST,
 };
 
 use super::{curve::Curve, CurveLike};
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "HelixRaw"))]
 pub struct Helix {
     pub basis: Point,
     pub pitch: Point,
     pub radius: Point,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
     dir_cross: Point,
     right_winding: bool,
 }
 
+// Helix::new panics on invalid input, so deserialized helices are checked first.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct HelixRaw {
+    basis: Point,
+    pitch: Point,
+    radius: Point,
+    right_winding: bool,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<HelixRaw> for Helix {
+    type Error = GeometryError;
+
+    fn try_from(raw: HelixRaw) -> GeometryResult<Helix> {
+        if raw.pitch.is_zero() || raw.radius.is_zero() {
+            return Err(GeometryError::new(
+                "Pitch and radius of a helix must not be zero".to_string(),
+            ));
+        }
+        if raw.pitch.dot(raw.radius) != 0.0 {
+            return Err(GeometryError::new(
+                "Radius and pitch must be orthogonal".to_string(),
+            ));
+        }
+        Ok(Helix::new(
+            raw.basis,
+            raw.pitch,
+            raw.radius,
+            raw.right_winding,
+        ))
+    }
+}
+
 impl Helix {
     pub fn new(basis: Point, pitch: Point, radius: Point, right_winding: bool) -> Helix {
         assert!(
@@ -36,6 +76,15 @@ impl Helix {
         }
     }
 
+    // The point after t turns from the basis.
+    pub fn point_at(&self, t: f64) -> Point {
+        let t = EFloat64::from(t);
+        self.basis
+            + self.pitch * t
+            + self.radius * (EFloat64::two_pi() * t).cos()
+            + self.dir_cross * (EFloat64::two_pi() * t).sin()
+    }
+
     pub fn transform(&self, transform: Transform) -> Self {
         let basis_old = self.basis;
         let basis = transform * self.basis;
@@ -68,10 +117,16 @@ impl CurveLike for Helix {
 
     fn tangent(&self, p: Point) -> GeometryResult<Point> {
         assert!(self.on_curve(p));
-        Ok((self.pitch.cross(p - self.basis).normalize().unwrap()
-            + (self.pitch / EFloat64::two_pi()).unwrap())
-        .normalize()
-        .unwrap())
+        // r'(t) = pitch + 2pi * (cos(2pi * t) * dir_cross - sin(2pi * t) * radius)
+        let around = match self.right_winding {
+            true => self.pitch.cross(p - self.basis),
+            false => -self.pitch.cross(p - self.basis),
+        };
+        Ok(
+            (self.pitch + around.normalize().unwrap() * self.radius.norm() * EFloat64::two_pi())
+                .normalize()
+                .unwrap(),
+        )
     }
 
     fn on_curve(&self, p: Point) -> bool {
@@ -219,16 +274,48 @@ impl CurveLike for Helix {
         }
     }
 
-    fn project(&self, _p: Point) -> Point {
-        todo!("Implement this")
+    fn project(&self, p: Point) -> Point {
+        let v = p - self.basis;
+        let height = (v.dot(self.pitch) / self.pitch.norm_sq()).unwrap().to_f64();
+        let angle = v
+            .dot(self.dir_cross)
+            .to_f64()
+            .atan2(v.dot(self.radius).to_f64())
+            / (2.0 * f64::consts::PI);
+        // The point with the same angle is on every turn, so take the turn closest to the height of p and its neighbours
+        let turn = (height - angle).round();
+        (-1..=1)
+            .map(|k| self.point_at_pitch(EFloat64::from(turn + k as f64 + angle)))
+            .min_by(|a, b| {
+                (*a - p)
+                    .norm_sq()
+                    .to_f64()
+                    .total_cmp(&(*b - p).norm_sq().to_f64())
+            })
+            .unwrap()
     }
 
+    // The helix lies between the circles at the heights of its end points.
     fn get_bounding_box(
         &self,
-        _interval_self: Option<Point>,
-        _midpoint_self: Option<Point>,
+        start: Option<Point>,
+        end: Option<Point>,
     ) -> GeometryResult<BoundingBox> {
-        todo!("Implement this")
+        let (Some(start), Some(end)) = (start, end) else {
+            return Err(GeometryError::new(
+                "Helix without start or end has no bounding box".to_string(),
+            ));
+        };
+        let axis = self.pitch.normalize()?;
+        let circle = |p: Point| {
+            let center = self.basis + axis * (p - self.basis).dot(axis);
+            BoundingBox::with_circle(center, axis, self.radius.norm())
+        };
+        let mut bounding_box = circle(start);
+        let end_box = circle(end);
+        bounding_box.add_point(end_box.min);
+        bounding_box.add_point(end_box.max);
+        Ok(bounding_box)
     }
 
     fn shrink_bounding_box(
@@ -243,6 +330,34 @@ impl CurveLike for Helix {
     fn sort(&self, _points: Vec<Option<Point>>) -> Vec<Option<Point>> {
         todo!("Implement this")
     }
+
+    // The offset towards or away from the axis is a helix with a different radius.
+    fn offset_in_plane(&self, distance: EFloat64, normal: Point) -> GeometryResult<Curve> {
+        let normal = normal.normalize()?;
+        if normal.cross(self.pitch).norm() != 0.0 {
+            return Err(GeometryError::new(format!(
+                "Helix can only be offset in the plane normal to its axis, not {}",
+                normal
+            )));
+        }
+        let side = normal
+            .cross(self.tangent(self.basis + self.radius)?)
+            .normalize()?;
+        let radius = self.radius + side * distance;
+        if radius.dot(self.radius) <= 0.0 {
+            return Err(GeometryError::new(format!(
+                "Offset of helix with radius {} by {} has no positive radius",
+                self.radius.norm(),
+                distance
+            )));
+        }
+        Ok(Curve::Helix(Helix::new(
+            self.basis,
+            self.pitch,
+            radius,
+            self.right_winding,
+        )))
+    }
 }
 
 // Implement partial eqality for Circle
@@ -15,11 +15,29 @@ use crate::{
 use super::{curve::Curve, CurveLike};
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "LineRaw"))]
 pub struct Line {
     pub basis: Point,
     pub direction: Point,
 }
 
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct LineRaw {
+    basis: Point,
+    direction: Point,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<LineRaw> for Line {
+    type Error = GeometryError;
+
+    fn try_from(raw: LineRaw) -> GeometryResult<Line> {
+        Line::new(raw.basis, raw.direction)
+    }
+}
+
 impl Line {
     pub fn new(basis: Point, direction: Point) -> GeometryResult<Line> {
         if !direction.is_normalized() {
@@ -241,10 +259,16 @@ impl CurveLike for Line {
 
     fn get_bounding_box(
         &self,
-        _interval_self: Option<Point>,
-        _midpoint_self: Option<Point>,
+        start: Option<Point>,
+        end: Option<Point>,
     ) -> GeometryResult<BoundingBox> {
-        todo!()
+        match (start, end) {
+            (Some(start), Some(end)) => Ok(BoundingBox::with_2_points(start, end)),
+            _ => Err(GeometryError::new(format!(
+                "Line {} without start or end has no bounding box",
+                self.basis
+            ))),
+        }
     }
 
     fn shrink_bounding_box(
@@ -276,6 +300,21 @@ impl CurveLike for Line {
         });
         points
     }
+
+    fn offset_in_plane(&self, distance: EFloat64, normal: Point) -> GeometryResult<Curve> {
+        let normal = normal.normalize()?;
+        if normal.dot(self.direction) != 0.0 {
+            return Err(GeometryError::new(format!(
+                "Line {} does not lie in a plane with normal {}",
+                self, normal
+            )));
+        }
+        let side = normal.cross(self.direction).normalize()?;
+        Ok(Curve::Line(Line::new(
+            self.basis + side * distance,
+            self.direction,
+        )?))
+    }
 }
 
 impl PartialEq for Line {
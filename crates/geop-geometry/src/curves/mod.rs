@@ -12,6 +12,8 @@ pub mod circle;
 pub mod ellipse;
 pub mod helix;
 pub mod line;
+pub mod nurb;
+pub mod sampled_curve;
 
 // CurveLike is a trait that all curves should implement.
 pub trait CurveLike {
@@ -68,4 +70,9 @@ pub trait CurveLike {
     // Sorts a list of point such that for three consecutive points (p1, p2, p3) p2 is between p1 and p3.
     // For the first and last point, it is (p2, p3, ..., p1) and (p2, p1, ..., p3) respectively.
     fn sort(&self, points: Vec<Option<Point>>) -> Vec<Option<Point>>;
+
+    // Returns the curve that is moved by distance along normal x tangent, which is to the left when looked at from the normal direction.
+    // The curve has to lie in a plane with the given normal. Helices are offset towards or away from their axis, if the normal is the axis.
+    // Freeform curves are approximated.
+    fn offset_in_plane(&self, distance: EFloat64, normal: Point) -> GeometryResult<Curve>;
 }
@@ -0,0 +1,944 @@
+use std::{fmt::Display, ops::RangeInclusive};
+
+use geop_algebra::{bspline_basis::BSplineBasis, efloat::EFloat64};
+
+use crate::{
+    bounding_box::BoundingBox,
+    color::Category10Color,
+    geometry_error::{ElevateToGeometry, GeometryError, GeometryResult, WithContext},
+    geometry_scene::GeometryScene,
+    point::Point,
+    transforms::Transform,
+};
+
+use super::{circle::Circle, curve::Curve, CurveLike};
+
+// The closest point on a nurbs curve can only be found numerically, so points within this distance are considered on the curve.
+const PRECISION: f64 = 1e-8;
+const SAMPLES_PER_SPAN: usize = 16;
+const ITERATIONS: usize = 80;
+
+// A control point in homogeneous coordinates, i.e. (weight * point, weight).
+pub(crate) type Homogeneous = (Point, EFloat64);
+
+// Rational B-spline curve of the given degree. The curve is bounded by its parameter domain [u_degree, u_n],
+// so a missing start or end point refers to the start or end of the curve.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "NurbsCurveRaw"))]
+pub struct NurbsCurve {
+    pub degree: usize,
+    pub control_points: Vec<Point>,
+    pub weights: Vec<EFloat64>,
+    pub knot_vector: Vec<EFloat64>,
+}
+
+// Deserialized nurbs curves go through try_new, so inconsistent degrees, weights or knots are rejected.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct NurbsCurveRaw {
+    degree: usize,
+    control_points: Vec<Point>,
+    weights: Vec<EFloat64>,
+    knot_vector: Vec<EFloat64>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<NurbsCurveRaw> for NurbsCurve {
+    type Error = GeometryError;
+
+    fn try_from(raw: NurbsCurveRaw) -> GeometryResult<NurbsCurve> {
+        NurbsCurve::try_new(raw.degree, raw.control_points, raw.weights, raw.knot_vector)
+    }
+}
+
+fn lerp(a: Homogeneous, b: Homogeneous, alpha: f64) -> Homogeneous {
+    let alpha = EFloat64::from(alpha);
+    let beta = EFloat64::one() - alpha;
+    (a.0 * beta + b.0 * alpha, a.1 * beta + b.1 * alpha)
+}
+
+// Index k of the knot span [u_k, u_k+1) containing t, clamped to the parameter domain.
+pub(crate) fn find_span(degree: usize, knots: &[f64], n: usize, t: f64) -> usize {
+    let mut k = degree;
+    while k + 1 < n && knots[k + 1] <= t {
+        k += 1;
+    }
+    k
+}
+
+// De Boor's algorithm in homogeneous coordinates.
+pub(crate) fn de_boor(degree: usize, knots: &[f64], points: &[Homogeneous], t: f64) -> Homogeneous {
+    let k = find_span(degree, knots, points.len(), t);
+    let mut d = points[k - degree..=k].to_vec();
+    for r in 1..=degree {
+        for j in (r..=degree).rev() {
+            let i = j + k - degree;
+            let denominator = knots[i + degree + 1 - r] - knots[i];
+            let alpha = match denominator > 0.0 {
+                true => (t - knots[i]) / denominator,
+                false => 0.0,
+            };
+            d[j] = lerp(d[j - 1], d[j], alpha);
+        }
+    }
+    d[degree]
+}
+
+// Derivative of the homogeneous curve at t. The derivative of a B-spline is a B-spline of one degree lower with differences as control points.
+pub(crate) fn de_boor_derivative(
+    degree: usize,
+    knots: &[f64],
+    points: &[Homogeneous],
+    t: f64,
+) -> Homogeneous {
+    let differences = (0..points.len() - 1)
+        .map(|i| {
+            let span = knots[i + degree + 1] - knots[i + 1];
+            let factor = match span > 0.0 {
+                true => EFloat64::from(degree as f64 / span),
+                false => EFloat64::zero(),
+            };
+            (
+                (points[i + 1].0 - points[i].0) * factor,
+                (points[i + 1].1 - points[i].1) * factor,
+            )
+        })
+        .collect::<Vec<Homogeneous>>();
+    de_boor(degree - 1, &knots[1..knots.len() - 1], &differences, t)
+}
+
+// Homogeneous coordinates in plain floating point, used for the iterative numerics where intervals are not needed.
+pub(crate) type Homogeneous64 = [f64; 4];
+
+pub(crate) fn de_boor64(
+    degree: usize,
+    knots: &[f64],
+    points: &[Homogeneous64],
+    t: f64,
+) -> Homogeneous64 {
+    let k = find_span(degree, knots, points.len(), t);
+    let mut d = points[k - degree..=k].to_vec();
+    for r in 1..=degree {
+        for j in (r..=degree).rev() {
+            let i = j + k - degree;
+            let denominator = knots[i + degree + 1 - r] - knots[i];
+            let alpha = match denominator > 0.0 {
+                true => (t - knots[i]) / denominator,
+                false => 0.0,
+            };
+            let previous = d[j - 1];
+            d[j] = [0, 1, 2, 3].map(|c| (1.0 - alpha) * previous[c] + alpha * d[j][c]);
+        }
+    }
+    d[degree]
+}
+
+pub(crate) fn de_boor_derivative64(
+    degree: usize,
+    knots: &[f64],
+    points: &[Homogeneous64],
+    t: f64,
+) -> Homogeneous64 {
+    let differences = (0..points.len() - 1)
+        .map(|i| {
+            let span = knots[i + degree + 1] - knots[i + 1];
+            let factor = match span > 0.0 {
+                true => degree as f64 / span,
+                false => 0.0,
+            };
+            [0, 1, 2, 3].map(|c| (points[i + 1][c] - points[i][c]) * factor)
+        })
+        .collect::<Vec<Homogeneous64>>();
+    de_boor64(degree - 1, &knots[1..knots.len() - 1], &differences, t)
+}
+
+// Boehm's algorithm to insert the knot t once.
+fn insert_knot(degree: usize, knots: &mut Vec<f64>, points: &mut Vec<Homogeneous>, t: f64) {
+    let k = find_span(degree, knots, points.len(), t);
+    let mut new_points = Vec::with_capacity(points.len() + 1);
+    for i in 0..=points.len() {
+        if i + degree <= k {
+            new_points.push(points[i]);
+        } else if i <= k {
+            let alpha = (t - knots[i]) / (knots[i + degree] - knots[i]);
+            new_points.push(lerp(points[i - 1], points[i], alpha));
+        } else {
+            new_points.push(points[i - 1]);
+        }
+    }
+    knots.insert(k + 1, t);
+    *points = new_points;
+}
+
+// Greville abscissa of the i-th control point, the parameter where it has the most influence.
+pub(crate) fn greville(degree: usize, knots: &[f64], i: usize) -> f64 {
+    knots[i + 1..=i + degree].iter().sum::<f64>() / degree as f64
+}
+
+pub(crate) fn to_cartesian(p: Homogeneous) -> Point {
+    (p.0 / p.1).expect("Weights are positive")
+}
+
+// Angle of the arc from start to end around the normal of the circle, which is the full circle if one of them is missing.
+fn arc_angle(circle: &Circle, start: Option<Point>, end: Option<Point>) -> f64 {
+    match (start, end) {
+        (Some(start), Some(end)) => {
+            let (a, b) = (start - circle.basis, end - circle.basis);
+            let angle = circle
+                .normal
+                .dot(a.cross(b))
+                .to_f64()
+                .atan2(a.dot(b).to_f64());
+            match angle <= 0.0 {
+                true => angle + 2.0 * std::f64::consts::PI,
+                false => angle,
+            }
+        }
+        _ => 2.0 * std::f64::consts::PI,
+    }
+}
+
+impl NurbsCurve {
+    pub fn try_new(
+        degree: usize,
+        control_points: Vec<Point>,
+        weights: Vec<EFloat64>,
+        knot_vector: Vec<EFloat64>,
+    ) -> GeometryResult<NurbsCurve> {
+        let error_context = |err: GeometryError| {
+            err.with_context_scene(
+                format!(
+                    "Create a nurbs curve of degree {} with {} control points and {} knots.",
+                    degree,
+                    control_points.len(),
+                    knot_vector.len()
+                ),
+                GeometryScene::with_points(
+                    control_points
+                        .iter()
+                        .map(|p| (*p, Category10Color::Orange))
+                        .collect(),
+                ),
+            )
+        };
+        if degree == 0 {
+            return Err(GeometryError::new("Degree must be at least 1".to_string()))
+                .with_context(&error_context);
+        }
+        if control_points.len() <= degree {
+            return Err(GeometryError::new(
+                "There must be more control points than the degree".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        if weights.len() != control_points.len() {
+            return Err(GeometryError::new(
+                "There must be one weight per control point".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        if weights.iter().any(|w| *w <= 0.0) {
+            return Err(GeometryError::new("Weights must be positive".to_string()))
+                .with_context(&error_context);
+        }
+        if knot_vector.len() != control_points.len() + degree + 1 {
+            return Err(GeometryError::new(
+                "Knot vector must have control_points.len() + degree + 1 entries".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        BSplineBasis::new(0, degree, knot_vector.clone()).elevate(&error_context)?;
+        if knot_vector[degree].to_f64() >= knot_vector[control_points.len()].to_f64() {
+            return Err(GeometryError::new(
+                "Parameter domain must not be empty".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+
+        Ok(NurbsCurve {
+            degree,
+            control_points,
+            weights,
+            knot_vector,
+        })
+    }
+
+    // Polyline through the given points as a curve of degree 1, parametrized by chord length on [0, 1].
+    // This is used for curves that are traced numerically, e.g. geodesics and intersections on nurbs surfaces.
+    pub fn try_new_polyline(points: Vec<Point>) -> GeometryResult<NurbsCurve> {
+        let mut lengths = vec![0.0];
+        for (a, b) in points.iter().zip(points.iter().skip(1)) {
+            lengths.push(lengths[lengths.len() - 1] + (*b - *a).norm().to_f64());
+        }
+        let total = lengths[lengths.len() - 1];
+        if total.is_nan() || total <= 0.0 {
+            return Err(GeometryError::new(
+                "A polyline needs at least two distinct points".to_string(),
+            ));
+        }
+        let mut knot_vector = vec![EFloat64::zero()];
+        knot_vector.extend(lengths.iter().map(|l| EFloat64::from(l / total)));
+        knot_vector.push(EFloat64::one());
+        let weights = vec![EFloat64::one(); points.len()];
+        NurbsCurve::try_new(1, points, weights, knot_vector)
+    }
+
+    // Exact representation of the arc of a circle from start to end as a rational quadratic curve, split into segments
+    // of at most a quarter circle. A missing start or end gives the full circle starting at the radius vector.
+    pub fn try_new_arc(
+        circle: &Circle,
+        start: Option<Point>,
+        end: Option<Point>,
+    ) -> GeometryResult<NurbsCurve> {
+        let segments = (arc_angle(circle, start, end) / std::f64::consts::FRAC_PI_2 - 1e-9)
+            .ceil()
+            .max(1.0) as usize;
+        NurbsCurve::try_new_arc_with_segments(circle, start, end, segments)
+    }
+
+    // Same as try_new_arc with the given number of segments of equal angle, such that arcs can be made compatible.
+    pub fn try_new_arc_with_segments(
+        circle: &Circle,
+        start: Option<Point>,
+        end: Option<Point>,
+        segments: usize,
+    ) -> GeometryResult<NurbsCurve> {
+        let error_context = |err: GeometryError| {
+            err.with_context(format!(
+                "Create a nurbs arc with {} segments between {:?} and {:?} on {:?}",
+                segments, start, end, circle
+            ))
+        };
+        let angle = arc_angle(circle, start, end);
+        if segments == 0 || angle / segments as f64 >= std::f64::consts::PI {
+            return Err(GeometryError::new(
+                "Segments must be smaller than half a circle".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        let radius = circle.radius.norm().to_f64();
+        let x = match start {
+            Some(start) => (start - circle.basis)
+                .normalize()
+                .with_context(&error_context)?,
+            None => circle.radius.normalize().with_context(&error_context)?,
+        };
+        let y = circle.normal.cross(x);
+        let step = angle / segments as f64;
+        let at = |a: f64, scale: f64| {
+            circle.basis
+                + (x * EFloat64::from(a.cos()) + y * EFloat64::from(a.sin()))
+                    * EFloat64::from(radius * scale)
+        };
+        let mut control_points = vec![start.unwrap_or(at(0.0, 1.0))];
+        let mut weights = vec![EFloat64::one()];
+        let mut knot_vector = vec![EFloat64::zero(); 3];
+        for k in 0..segments {
+            let a = step * k as f64;
+            control_points.push(at(a + step / 2.0, 1.0 / (step / 2.0).cos()));
+            weights.push(EFloat64::from((step / 2.0).cos()));
+            control_points.push(match (k + 1 == segments, end) {
+                (true, Some(end)) => end,
+                (true, None) => control_points[0],
+                (false, _) => at(a + step, 1.0),
+            });
+            weights.push(EFloat64::one());
+            let knot = EFloat64::from((k + 1) as f64 / segments as f64);
+            knot_vector.extend([knot, knot]);
+        }
+        knot_vector.push(EFloat64::one());
+        NurbsCurve::try_new(2, control_points, weights, knot_vector).with_context(&error_context)
+    }
+
+    // Parameter of a point, which fails if the point is not on the curve.
+    fn parameter_on_curve(&self, p: Point, variable_name: &str) -> GeometryResult<f64> {
+        let t = self.parameter(p);
+        if (self.point_at(t) - p).norm() > PRECISION {
+            return Err(GeometryError::new(format!(
+                "Point {} {} is not on nurbs curve {}",
+                variable_name, p, self
+            )));
+        }
+        Ok(t)
+    }
+
+    fn knots(&self) -> Vec<f64> {
+        self.knot_vector.iter().map(|u| u.to_f64()).collect()
+    }
+
+    fn homogeneous(&self, range: RangeInclusive<usize>) -> Vec<Homogeneous> {
+        self.control_points[range.clone()]
+            .iter()
+            .zip(self.weights[range].iter())
+            .map(|(p, w)| (*p * *w, *w))
+            .collect()
+    }
+
+    // The knots and the control points that contribute to the curve at t, which are those of the knot span of t.
+    fn local<'a>(&self, knots: &'a [f64], t: f64) -> (&'a [f64], RangeInclusive<usize>) {
+        let k = find_span(self.degree, knots, self.control_points.len(), t);
+        let first = k - self.degree;
+        (&knots[first..=k + self.degree + 1], first..=k)
+    }
+
+    // Start and end of the parameter domain.
+    pub(crate) fn domain(&self) -> (f64, f64) {
+        (
+            self.knot_vector[self.degree].to_f64(),
+            self.knot_vector[self.control_points.len()].to_f64(),
+        )
+    }
+
+    fn homogeneous64(&self, range: RangeInclusive<usize>) -> Vec<Homogeneous64> {
+        self.control_points[range.clone()]
+            .iter()
+            .zip(self.weights[range].iter())
+            .map(|(p, w)| {
+                let w = w.to_f64();
+                [p.x.to_f64() * w, p.y.to_f64() * w, p.z.to_f64() * w, w]
+            })
+            .collect()
+    }
+
+    // Parameters that subdivide every non-empty knot span of the domain.
+    pub(crate) fn samples(&self) -> Vec<f64> {
+        let knots = self.knots();
+        let mut samples = vec![self.domain().0];
+        for k in self.degree..self.control_points.len() {
+            if knots[k + 1] > knots[k] {
+                for i in 1..=SAMPLES_PER_SPAN {
+                    samples.push(
+                        knots[k] + (knots[k + 1] - knots[k]) * i as f64 / SAMPLES_PER_SPAN as f64,
+                    );
+                }
+            }
+        }
+        samples
+    }
+
+    // Point and derivative at t, evaluated in plain floating point for the iterative numerics.
+    pub(crate) fn evaluate64(&self, t: f64) -> (Point, Point) {
+        self.evaluate64_with(&self.knots(), t)
+    }
+
+    // Same as evaluate64 with the knots in plain floating point, such that loops only have to convert them once.
+    fn evaluate64_with(&self, knots: &[f64], t: f64) -> (Point, Point) {
+        let (t0, t1) = self.domain();
+        let t = t.clamp(t0, t1);
+        let (knots, range) = self.local(knots, t);
+        let points = self.homogeneous64(range);
+        let a = de_boor64(self.degree, knots, &points, t);
+        let da = de_boor_derivative64(self.degree, knots, &points, t);
+        (
+            Point::from_f64(a[0] / a[3], a[1] / a[3], a[2] / a[3]),
+            Point::from_f64(
+                (da[0] * a[3] - a[0] * da[3]) / (a[3] * a[3]),
+                (da[1] * a[3] - a[1] * da[3]) / (a[3] * a[3]),
+                (da[2] * a[3] - a[2] * da[3]) / (a[3] * a[3]),
+            ),
+        )
+    }
+
+    pub fn point_at(&self, t: f64) -> Point {
+        let (t0, t1) = self.domain();
+        let t = t.clamp(t0, t1);
+        let knots = self.knots();
+        let (knots, range) = self.local(&knots, t);
+        to_cartesian(de_boor(self.degree, knots, &self.homogeneous(range), t))
+    }
+
+    // Derivative of the curve at t, up to a positive factor.
+    fn derivative_direction(&self, t: f64) -> Point {
+        let knots = self.knots();
+        let (knots, range) = self.local(&knots, t);
+        let points = self.homogeneous(range);
+        let (a, w) = de_boor(self.degree, knots, &points, t);
+        let (da, dw) = de_boor_derivative(self.degree, knots, &points, t);
+        da * w - a * dw
+    }
+
+    fn speed(&self, t: f64) -> f64 {
+        let knots = self.knots();
+        let (knots, range) = self.local(&knots, t);
+        let w = de_boor(self.degree, knots, &self.homogeneous(range), t).1;
+        (self.derivative_direction(t).norm() / (w * w))
+            .expect("Weights are positive")
+            .to_f64()
+    }
+
+    // Parameter of the closest point on the curve.
+    fn parameter(&self, p: Point) -> f64 {
+        let (t0, t1) = self.domain();
+        let samples = self.samples();
+        let knots = self.knots();
+        let distance = |t: f64| (self.evaluate64_with(&knots, t).0 - p).norm_sq().to_f64();
+        let best = (0..samples.len())
+            .min_by(|a, b| distance(samples[*a]).total_cmp(&distance(samples[*b])))
+            .unwrap();
+
+        let mut lo = samples[best.saturating_sub(1)];
+        let mut hi = samples[(best + 1).min(samples.len() - 1)];
+
+        // At an interior minimum, the tangent is perpendicular to the offset, which is found to full precision with the Illinois method
+        let slope = |t: f64| {
+            let (point, derivative) = self.evaluate64_with(&knots, t);
+            derivative.dot(point - p).to_f64()
+        };
+        let (mut f_lo, mut f_hi) = (slope(lo), slope(hi));
+        if f_lo < 0.0 && f_hi > 0.0 {
+            let mut t = lo;
+            for _ in 0..ITERATIONS {
+                let t_new = (lo * f_hi - hi * f_lo) / (f_hi - f_lo);
+                if (t_new - t).abs() <= f64::EPSILON * t.abs().max(1.0) {
+                    return t_new;
+                }
+                t = t_new;
+                let f = slope(t);
+                if f < 0.0 {
+                    lo = t;
+                    f_lo = f;
+                    f_hi /= 2.0;
+                } else if f > 0.0 {
+                    hi = t;
+                    f_hi = f;
+                    f_lo /= 2.0;
+                } else {
+                    return t;
+                }
+            }
+            return t;
+        }
+
+        let ratio = (5.0_f64.sqrt() - 1.0) / 2.0;
+        for _ in 0..ITERATIONS {
+            let a = hi - ratio * (hi - lo);
+            let b = lo + ratio * (hi - lo);
+            if distance(a) < distance(b) {
+                hi = b;
+            } else {
+                lo = a;
+            }
+        }
+        ((lo + hi) / 2.0).clamp(t0, t1)
+    }
+
+    // Parameters of start and end, where None refers to the ends of the domain.
+    fn parameter_range(
+        &self,
+        start: Option<Point>,
+        end: Option<Point>,
+    ) -> GeometryResult<(f64, f64)> {
+        let (t0, t1) = self.domain();
+        Ok((
+            match start {
+                Some(start) => self.parameter_on_curve(start, "start")?,
+                None => t0,
+            },
+            match end {
+                Some(end) => self.parameter_on_curve(end, "end")?,
+                None => t1,
+            },
+        ))
+    }
+
+    // Arc length between the parameters, integrated with Simpson's rule on every knot span.
+    fn arc_length(&self, t0: f64, t1: f64) -> f64 {
+        let (t0, t1) = (t0.min(t1), t0.max(t1));
+        let mut breaks = vec![t0];
+        breaks.extend(self.knots().into_iter().filter(|u| *u > t0 && *u < t1));
+        breaks.push(t1);
+        breaks.dedup();
+
+        let mut length = 0.0;
+        for (a, b) in breaks.iter().zip(breaks.iter().skip(1)) {
+            let h = (b - a) / (2 * SAMPLES_PER_SPAN) as f64;
+            let mut sum = self.speed(*a) + self.speed(*b);
+            for i in 1..2 * SAMPLES_PER_SPAN {
+                let factor = if i % 2 == 1 { 4.0 } else { 2.0 };
+                sum += factor * self.speed(a + h * i as f64);
+            }
+            length += sum * h / 3.0;
+        }
+        length
+    }
+
+    // Control points of the part of the curve between t0 and t1, found by knot insertion.
+    // The part lies in the convex hull of these, which converges to the curve for small intervals.
+    fn segment_control_points(&self, t0: f64, t1: f64) -> Vec<Point> {
+        let (t0, t1) = (t0.min(t1), t0.max(t1));
+        let mut knots = self.knots();
+        let mut points = self.homogeneous(0..=self.control_points.len() - 1);
+        for t in [t0, t1] {
+            let multiplicity = knots.iter().filter(|u| **u == t).count();
+            for _ in multiplicity..self.degree {
+                insert_knot(self.degree, &mut knots, &mut points, t);
+            }
+        }
+        let first = knots.iter().rposition(|u| *u == t0).unwrap() - self.degree;
+        let last = knots.iter().position(|u| *u == t1).unwrap() - 1;
+        points[first..=last.max(first)]
+            .iter()
+            .map(|p| to_cartesian(*p))
+            .collect()
+    }
+
+    pub fn transform(&self, transform: Transform) -> NurbsCurve {
+        NurbsCurve::try_new(
+            self.degree,
+            self.control_points.iter().map(|p| transform * *p).collect(),
+            self.weights.clone(),
+            self.knot_vector.clone(),
+        )
+        .expect("Transform of nurbs curve will always succeed")
+    }
+
+    pub fn neg(&self) -> NurbsCurve {
+        let knots = self.knots();
+        let (first, last) = (knots[0], knots[knots.len() - 1]);
+        NurbsCurve::try_new(
+            self.degree,
+            self.control_points.iter().rev().cloned().collect(),
+            self.weights.iter().rev().cloned().collect(),
+            knots
+                .iter()
+                .rev()
+                .map(|u| EFloat64::from(first + last - u))
+                .collect(),
+        )
+        .expect("Nurbs curve parameters should be valid")
+    }
+}
+
+impl CurveLike for NurbsCurve {
+    fn transform(&self, transform: Transform) -> Curve {
+        Curve::Nurbs(self.transform(transform))
+    }
+
+    fn neg(&self) -> Curve {
+        Curve::Nurbs(self.neg())
+    }
+
+    fn tangent(&self, p: Point) -> GeometryResult<Point> {
+        let t = self.parameter_on_curve(p, "p")?;
+        self.derivative_direction(t).normalize()
+    }
+
+    fn on_curve(&self, p: Point) -> bool {
+        (self.point_at(self.parameter(p)) - p).norm() <= PRECISION
+    }
+
+    fn distance(&self, x: Point, y: Point) -> GeometryResult<EFloat64> {
+        let error_context = |err: GeometryError| {
+            err.with_context_scene(
+                format!("Calculate the distance between {} and {}.", x, y),
+                GeometryScene {
+                    points: vec![(x, Category10Color::Orange), (y, Category10Color::Orange)],
+                    curves: vec![(Curve::Nurbs(self.clone()), Category10Color::Gray)],
+                    surfaces: vec![],
+                },
+            )
+        };
+
+        let t_x = self
+            .parameter_on_curve(x, "x")
+            .with_context(&error_context)?;
+        let t_y = self
+            .parameter_on_curve(y, "y")
+            .with_context(&error_context)?;
+        Ok(EFloat64::from(self.arc_length(t_x, t_y)))
+    }
+
+    fn interpolate(
+        &self,
+        start: Option<Point>,
+        end: Option<Point>,
+        t: f64,
+    ) -> GeometryResult<Point> {
+        let error_context = |err: GeometryError| {
+            err.with_context(format!(
+                "Interpolate between {:?} and {:?} at {}",
+                start, end, t
+            ))
+        };
+        let (t0, t1) = self
+            .parameter_range(start, end)
+            .with_context(&error_context)?;
+        Ok(self.point_at(t0 + (t1 - t0) * t))
+    }
+
+    fn between(&self, m: Point, start: Option<Point>, end: Option<Point>) -> GeometryResult<bool> {
+        let error_context = |err: GeometryError| {
+            err.with_context(format!(
+                "Check if {} is between {:?} and {:?}",
+                m, start, end
+            ))
+        };
+        let t = self
+            .parameter_on_curve(m, "m")
+            .with_context(&error_context)?;
+        let (t0, t1) = self
+            .parameter_range(start, end)
+            .with_context(&error_context)?;
+        if Some(m) == start || Some(m) == end {
+            return Ok(true);
+        }
+        Ok(t0 <= t && t <= t1)
+    }
+
+    fn get_midpoint(&self, start: Option<Point>, end: Option<Point>) -> GeometryResult<Point> {
+        let error_context = |err: GeometryError| {
+            err.with_context_scene(
+                format!("Midpoint between {:?} and {:?}", start, end),
+                GeometryScene {
+                    points: vec![
+                        (start, Category10Color::Orange),
+                        (end, Category10Color::Blue),
+                    ]
+                    .into_iter()
+                    .filter_map(|(p, c)| p.map(|p| (p, c)))
+                    .collect(),
+                    curves: vec![(Curve::Nurbs(self.clone()), Category10Color::Gray)],
+                    surfaces: vec![],
+                },
+            )
+        };
+
+        if let (Some(start), Some(end)) = (start, end) {
+            if start == end {
+                return Err(error_context(GeometryError::new(
+                    "Start and end are the same".to_string(),
+                )));
+            }
+        }
+        self.interpolate(start, end, 0.5)
+            .with_context(&error_context)
+    }
+
+    fn project(&self, p: Point) -> Point {
+        self.point_at(self.parameter(p))
+    }
+
+    fn get_bounding_box(
+        &self,
+        start: Option<Point>,
+        end: Option<Point>,
+    ) -> GeometryResult<BoundingBox> {
+        let (t0, t1) = self.parameter_range(start, end)?;
+        let points = self.segment_control_points(t0, t1);
+        let mut bounding_box = BoundingBox::new(points[0], points[0]);
+        for point in points.iter().skip(1) {
+            bounding_box.add_point(*point);
+        }
+        Ok(bounding_box)
+    }
+
+    fn shrink_bounding_box(
+        &self,
+        start: Option<Point>,
+        end: Option<Point>,
+        bounding_box: BoundingBox,
+    ) -> GeometryResult<BoundingBox> {
+        let tight = self.get_bounding_box(start, end)?;
+        Ok(BoundingBox::new(
+            Point::new(
+                tight.min.x.max(bounding_box.min.x),
+                tight.min.y.max(bounding_box.min.y),
+                tight.min.z.max(bounding_box.min.z),
+            ),
+            Point::new(
+                tight.max.x.min(bounding_box.max.x),
+                tight.max.y.min(bounding_box.max.y),
+                tight.max.z.min(bounding_box.max.z),
+            ),
+        ))
+    }
+
+    // A missing point stands for the start of the curve the first time, and for the end of the curve afterwards.
+    fn sort(&self, points: Vec<Option<Point>>) -> Vec<Option<Point>> {
+        let nones = points.iter().filter(|p| p.is_none()).count();
+        let mut points = points
+            .into_iter()
+            .flatten()
+            .map(|p| (self.parameter(p), p))
+            .collect::<Vec<(f64, Point)>>();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut result = Vec::<Option<Point>>::new();
+        if nones > 0 {
+            result.push(None);
+        }
+        result.extend(points.into_iter().map(|(_, p)| Some(p)));
+        for _ in 1..nones {
+            result.push(None);
+        }
+        result
+    }
+
+    // Approximated by moving every control point along normal x tangent at its greville abscissa.
+    fn offset_in_plane(&self, distance: EFloat64, normal: Point) -> GeometryResult<Curve> {
+        let normal = normal.normalize()?;
+        let knots = self.knots();
+        let control_points = self
+            .control_points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let tangent = self.derivative_direction(greville(self.degree, &knots, i));
+                Ok(*p + normal.cross(tangent).normalize()? * distance)
+            })
+            .collect::<GeometryResult<Vec<Point>>>()?;
+        Ok(Curve::Nurbs(NurbsCurve::try_new(
+            self.degree,
+            control_points,
+            self.weights.clone(),
+            self.knot_vector.clone(),
+        )?))
+    }
+}
+
+impl PartialEq for NurbsCurve {
+    fn eq(&self, other: &NurbsCurve) -> bool {
+        self.degree == other.degree
+            && self.control_points == other.control_points
+            && self.weights == other.weights
+            && self.knot_vector == other.knot_vector
+    }
+}
+
+impl Display for NurbsCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "NurbsCurve of degree {} with {} control points",
+            self.degree,
+            self.control_points.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A quarter of the unit circle as a rational quadratic curve.
+    fn quarter_circle() -> NurbsCurve {
+        NurbsCurve::try_new(
+            2,
+            vec![
+                Point::from_f64(1.0, 0.0, 0.0),
+                Point::from_f64(1.0, 1.0, 0.0),
+                Point::from_f64(0.0, 1.0, 0.0),
+            ],
+            vec![
+                EFloat64::one(),
+                EFloat64::from(0.5_f64.sqrt()),
+                EFloat64::one(),
+            ],
+            vec![
+                EFloat64::zero(),
+                EFloat64::zero(),
+                EFloat64::zero(),
+                EFloat64::one(),
+                EFloat64::one(),
+                EFloat64::one(),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_nurbs_curve_is_circle() {
+        let curve = quarter_circle();
+        for i in 0..=10 {
+            let p = curve.interpolate(None, None, i as f64 / 10.0).unwrap();
+            assert!((p.norm().to_f64() - 1.0).abs() < 1e-12);
+            assert!(curve.on_curve(p));
+        }
+        assert!(!curve.on_curve(Point::from_f64(0.5, 0.5, 0.0)));
+
+        let p = Point::from_f64(0.5_f64.sqrt(), 0.5_f64.sqrt(), 0.0);
+        assert!((curve.project(Point::from_f64(2.0, 2.0, 0.0)) - p).norm() < 1e-9);
+        let tangent = curve.tangent(p).unwrap();
+        assert!((tangent - Point::from_f64(-1.0, 1.0, 0.0).normalize().unwrap()).norm() < 1e-9);
+
+        let length = curve
+            .distance(Point::unit_x(), Point::unit_y())
+            .unwrap()
+            .to_f64();
+        assert!((length - std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_nurbs_curve_between_and_sort() {
+        let curve = quarter_circle();
+        let a = curve.interpolate(None, None, 0.2).unwrap();
+        let b = curve.interpolate(None, None, 0.5).unwrap();
+        let c = curve.interpolate(None, None, 0.8).unwrap();
+        assert!(curve.between(b, Some(a), Some(c)).unwrap());
+        assert!(!curve.between(a, Some(b), Some(c)).unwrap());
+        assert!(curve.between(a, None, Some(b)).unwrap());
+
+        let sorted = curve.sort(vec![Some(c), None, Some(a), Some(b), None]);
+        assert_eq!(sorted, vec![None, Some(a), Some(b), Some(c), None]);
+
+        let bounding_box = curve.get_bounding_box(Some(a), Some(c)).unwrap();
+        for i in 0..=10 {
+            let p = curve
+                .interpolate(Some(a), Some(c), i as f64 / 10.0)
+                .unwrap();
+            assert!(bounding_box.min.x <= p.x && p.x <= bounding_box.max.x);
+            assert!(bounding_box.min.y <= p.y && p.y <= bounding_box.max.y);
+        }
+        assert!(bounding_box.max_size() < 1.0);
+    }
+
+    #[test]
+    fn test_nurbs_curve_neg() {
+        let curve = quarter_circle();
+        let reversed = curve.neg();
+        let p = curve.interpolate(None, None, 0.3).unwrap();
+        assert!(reversed.on_curve(p));
+        assert!(curve.tangent(p).unwrap().dot(reversed.tangent(p).unwrap()) < 0.0);
+        assert_eq!(
+            reversed.interpolate(None, None, 0.0).unwrap(),
+            Point::unit_y()
+        );
+    }
+
+    #[test]
+    fn test_nurbs_curve_arc() {
+        let circle = Circle::try_new(Point::unit_z(), Point::unit_z(), EFloat64::two()).unwrap();
+        let start = Point::from_f64(2.0, 0.0, 1.0);
+        let end = Point::from_f64(-2.0, 0.0, 1.0);
+        let arc = NurbsCurve::try_new_arc(&circle, Some(start), Some(end)).unwrap();
+        assert_eq!(arc.interpolate(None, None, 0.0).unwrap(), start);
+        assert_eq!(arc.interpolate(None, None, 1.0).unwrap(), end);
+        let p = arc.interpolate(None, None, 0.5).unwrap();
+        assert!((p - Point::from_f64(0.0, 2.0, 1.0)).norm() < 1e-12);
+
+        let full = NurbsCurve::try_new_arc(&circle, None, None).unwrap();
+        for i in 0..=10 {
+            let p = full.interpolate(None, None, i as f64 / 10.0).unwrap();
+            assert!(circle.on_curve(p));
+        }
+    }
+
+    #[test]
+    fn test_nurbs_curve_offset() {
+        let curve = quarter_circle();
+        // The circle runs counter-clockwise around z, so the left side is the inside
+        let offset = match curve
+            .offset_in_plane(EFloat64::from(0.1), Point::unit_z())
+            .unwrap()
+        {
+            Curve::Nurbs(offset) => offset,
+            _ => panic!("Offset of a nurbs curve is a nurbs curve"),
+        };
+        assert!((offset.point_at(0.0) - Point::from_f64(0.9, 0.0, 0.0)).norm() < 1e-12);
+        assert!((offset.point_at(1.0) - Point::from_f64(0.0, 0.9, 0.0)).norm() < 1e-12);
+        for t in [0.25, 0.5, 0.75] {
+            assert!((offset.point_at(t).norm().to_f64() - 0.9).abs() < 0.05);
+        }
+    }
+}
@@ -0,0 +1,563 @@
+use std::fmt::Display;
+
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    bounding_box::BoundingBox,
+    color::Category10Color,
+    geometry_error::{GeometryError, GeometryResult, WithContext},
+    geometry_scene::GeometryScene,
+    point::Point,
+    transforms::Transform,
+};
+
+use super::{curve::Curve, CurveLike};
+
+// A closed curve without a closed form, represented by a loop of samples. This is used e.g. for the quartic intersection curves of two cylinders.
+// The exact curve deviates from the polygon through the samples by at most error_bound.
+// Points that are computed on the polygon are widened by error_bound, such that their interval contains the exact curve.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SampledCurveRaw"))]
+pub struct SampledCurve {
+    pub points: Vec<Point>,
+    pub error_bound: f64,
+    // Arc length of the polygon up to point i. The last entry is the total length of the loop.
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    arc_lengths: Vec<f64>,
+}
+
+// Deserialized sampled curves go through try_new, which also recomputes the arc lengths that are not stored.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SampledCurveRaw {
+    points: Vec<Point>,
+    error_bound: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SampledCurveRaw> for SampledCurve {
+    type Error = GeometryError;
+
+    fn try_from(raw: SampledCurveRaw) -> GeometryResult<SampledCurve> {
+        SampledCurve::try_new(raw.points, raw.error_bound)
+    }
+}
+
+fn center(p: Point) -> Point {
+    Point::from_f64(p.x.to_f64(), p.y.to_f64(), p.z.to_f64())
+}
+
+fn widen(p: Point, error_bound: f64) -> Point {
+    let widen_one = |v: EFloat64| {
+        EFloat64::new(
+            v.upper_bound.max(v.to_f64() + error_bound),
+            v.lower_bound.min(v.to_f64() - error_bound),
+        )
+    };
+    Point::new(widen_one(p.x), widen_one(p.y), widen_one(p.z))
+}
+
+fn radius(p: Point) -> f64 {
+    let r = |v: EFloat64| (v.upper_bound - v.lower_bound) / 2.0;
+    r(p.x).max(r(p.y)).max(r(p.z))
+}
+
+impl SampledCurve {
+    pub fn try_new(points: Vec<Point>, error_bound: f64) -> GeometryResult<SampledCurve> {
+        let error_context = |err: GeometryError| {
+            err.with_context_scene(
+                format!(
+                    "Create a sampled curve with {} points and error bound {}.",
+                    points.len(),
+                    error_bound
+                ),
+                GeometryScene::with_points(
+                    points
+                        .iter()
+                        .map(|p| (*p, Category10Color::Orange))
+                        .collect(),
+                ),
+            )
+        };
+        if points.len() < 3 {
+            return Err(GeometryError::new(
+                "A sampled curve needs at least 3 points".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        if !error_bound.is_finite() || error_bound <= 0.0 {
+            return Err(GeometryError::new(
+                "Error bound must be positive and finite".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+
+        let centers = points.iter().map(|p| center(*p)).collect::<Vec<Point>>();
+        let mut arc_lengths = Vec::with_capacity(points.len() + 1);
+        arc_lengths.push(0.0);
+        for i in 0..centers.len() {
+            let length = (centers[(i + 1) % centers.len()] - centers[i])
+                .norm()
+                .to_f64();
+            if length <= 0.0 {
+                return Err(GeometryError::new(
+                    "Consecutive samples must not coincide".to_string(),
+                ))
+                .with_context(&error_context);
+            }
+            arc_lengths.push(arc_lengths[i] + length);
+        }
+
+        Ok(SampledCurve {
+            points: centers,
+            error_bound,
+            arc_lengths,
+        })
+    }
+
+    fn assert_on_curve(&self, p: Point, variable_name: &str) -> GeometryResult<()> {
+        if !self.on_curve(p) {
+            return Err(GeometryError::new(format!(
+                "Point {} {} is not on sampled curve {}",
+                variable_name, p, self
+            )));
+        }
+        Ok(())
+    }
+
+    fn total_length(&self) -> f64 {
+        self.arc_lengths[self.points.len()]
+    }
+
+    fn segment(&self, i: usize) -> (Point, Point) {
+        (self.points[i], self.points[(i + 1) % self.points.len()])
+    }
+
+    // Returns the segment index, the arc length parameter and the distance of the closest point on the polygon.
+    // The samples are exact, so this is done in plain floating point, which is much faster for many samples.
+    fn locate(&self, p: Point) -> (usize, f64, f64) {
+        let coordinates = |p: Point| [p.x.to_f64(), p.y.to_f64(), p.z.to_f64()];
+        let p = coordinates(p);
+        let mut best = (0, 0.0, f64::INFINITY);
+        for i in 0..self.points.len() {
+            let (a, b) = self.segment(i);
+            let (a, b) = (coordinates(a), coordinates(b));
+            let length = self.arc_lengths[i + 1] - self.arc_lengths[i];
+            let ab = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+            let ap = [p[0] - a[0], p[1] - a[1], p[2] - a[2]];
+            let dot = ap[0] * ab[0] + ap[1] * ab[1] + ap[2] * ab[2];
+            let t = (dot / (length * length)).clamp(0.0, 1.0);
+            let dist = ((ap[0] - t * ab[0]).powi(2)
+                + (ap[1] - t * ab[1]).powi(2)
+                + (ap[2] - t * ab[2]).powi(2))
+            .sqrt();
+            if dist < best.2 {
+                best = (i, self.arc_lengths[i] + t * length, dist);
+            }
+        }
+        best
+    }
+
+    fn parameter(&self, p: Point) -> f64 {
+        self.locate(p).1
+    }
+
+    fn point_at(&self, s: f64) -> Point {
+        let s = s.rem_euclid(self.total_length());
+        let i = match self.arc_lengths.partition_point(|l| *l <= s) {
+            0 => 0,
+            i => (i - 1).min(self.points.len() - 1),
+        };
+        let (a, b) = self.segment(i);
+        let length = self.arc_lengths[i + 1] - self.arc_lengths[i];
+        let t = ((s - self.arc_lengths[i]) / length).clamp(0.0, 1.0);
+        widen(a + (b - a) * EFloat64::from(t), self.error_bound)
+    }
+
+    // Arc length when walking from s0 to s1 in the direction of the curve.
+    fn forward_length(&self, s0: f64, s1: f64) -> f64 {
+        (s1 - s0).rem_euclid(self.total_length())
+    }
+
+    pub fn transform(&self, transform: Transform) -> Self {
+        SampledCurve::try_new(
+            self.points.iter().map(|p| transform * *p).collect(),
+            self.error_bound * transform.max_stretch().upper_bound,
+        )
+        .expect("Sampled curve should still be valid after transform")
+    }
+
+    pub fn neg(&self) -> SampledCurve {
+        SampledCurve::try_new(
+            self.points.iter().rev().cloned().collect(),
+            self.error_bound,
+        )
+        .expect("Sampled curve parameters should be valid")
+    }
+}
+
+impl CurveLike for SampledCurve {
+    fn transform(&self, transform: Transform) -> Curve {
+        Curve::SampledCurve(self.transform(transform))
+    }
+
+    fn neg(&self) -> Curve {
+        Curve::SampledCurve(self.neg())
+    }
+
+    fn tangent(&self, p: Point) -> GeometryResult<Point> {
+        self.assert_on_curve(p, "p")?;
+        let (i, _, _) = self.locate(p);
+        let (a, b) = self.segment(i);
+        (b - a).normalize()
+    }
+
+    fn on_curve(&self, p: Point) -> bool {
+        let (_, _, dist) = self.locate(p);
+        dist <= self.error_bound + radius(p)
+    }
+
+    fn distance(&self, x: Point, y: Point) -> GeometryResult<EFloat64> {
+        let error_context = |err: GeometryError| {
+            err.with_context_scene(
+                format!("Calculate the distance between {} and {}.", x, y),
+                GeometryScene {
+                    points: vec![(x, Category10Color::Orange), (y, Category10Color::Orange)],
+                    curves: vec![(Curve::SampledCurve(self.clone()), Category10Color::Gray)],
+                    surfaces: vec![],
+                },
+            )
+        };
+
+        self.assert_on_curve(x, "x").with_context(&error_context)?;
+        self.assert_on_curve(y, "y").with_context(&error_context)?;
+        let forward = self.forward_length(self.parameter(x), self.parameter(y));
+        Ok(EFloat64::from(forward.min(self.total_length() - forward)))
+    }
+
+    fn interpolate(
+        &self,
+        start: Option<Point>,
+        end: Option<Point>,
+        t: f64,
+    ) -> GeometryResult<Point> {
+        let error_context = |err: GeometryError| {
+            err.with_context_scene(
+                format!(
+                    "Interpolating between {:?} and {:?} with t={}",
+                    start, end, t
+                ),
+                GeometryScene {
+                    points: vec![
+                        (start, Category10Color::Orange),
+                        (end, Category10Color::Blue),
+                    ]
+                    .into_iter()
+                    .filter_map(|(p, c)| p.map(|p| (p, c)))
+                    .collect(),
+                    curves: vec![(Curve::SampledCurve(self.clone()), Category10Color::Gray)],
+                    surfaces: vec![],
+                },
+            )
+        };
+        match (start, end) {
+            (Some(start), Some(end)) => {
+                self.assert_on_curve(start, "start")
+                    .with_context(&error_context)?;
+                self.assert_on_curve(end, "end")
+                    .with_context(&error_context)?;
+                if start == end {
+                    return Err(error_context(GeometryError::new(
+                        "Start and end are the same".to_string(),
+                    )));
+                }
+                let s0 = self.parameter(start);
+                let length = self.forward_length(s0, self.parameter(end));
+                Ok(self.point_at(s0 + t * length))
+            }
+            (Some(start), None) => {
+                self.assert_on_curve(start, "start")
+                    .with_context(&error_context)?;
+                Ok(self.point_at(self.parameter(start) + t * self.total_length()))
+            }
+            (None, Some(end)) => {
+                self.assert_on_curve(end, "end")
+                    .with_context(&error_context)?;
+                Ok(self.point_at(self.parameter(end) + t * self.total_length()))
+            }
+            (None, None) => Ok(self.point_at(t * self.total_length())),
+        }
+    }
+
+    // Checks if m is between x and y. m==x and m==y are true.
+    fn between(&self, m: Point, start: Option<Point>, end: Option<Point>) -> GeometryResult<bool> {
+        let error_context = |err: GeometryError| {
+            err.with_context_scene(
+                format!("Checking if {} is between {:?} and {:?}", m, start, end),
+                GeometryScene {
+                    points: vec![
+                        (start, Category10Color::Orange),
+                        (end, Category10Color::Blue),
+                        (Some(m), Category10Color::Green),
+                    ]
+                    .into_iter()
+                    .filter_map(|(p, c)| p.map(|p| (p, c)))
+                    .collect(),
+                    curves: vec![(Curve::SampledCurve(self.clone()), Category10Color::Gray)],
+                    surfaces: vec![],
+                },
+            )
+        };
+
+        self.assert_on_curve(m, "m").with_context(&error_context)?;
+        match (start, end) {
+            (Some(start), Some(end)) => {
+                self.assert_on_curve(start, "start")
+                    .with_context(&error_context)?;
+                self.assert_on_curve(end, "end")
+                    .with_context(&error_context)?;
+                if m == start || m == end {
+                    return Ok(true);
+                }
+                let s0 = self.parameter(start);
+                Ok(self.forward_length(s0, self.parameter(m))
+                    <= self.forward_length(s0, self.parameter(end)))
+            }
+            (Some(start), None) => {
+                self.assert_on_curve(start, "start")
+                    .with_context(&error_context)?;
+                Ok(true)
+            }
+            (None, Some(end)) => {
+                self.assert_on_curve(end, "end")
+                    .with_context(&error_context)?;
+                Ok(true)
+            }
+            (None, None) => Ok(true),
+        }
+    }
+
+    fn get_midpoint(&self, start: Option<Point>, end: Option<Point>) -> GeometryResult<Point> {
+        let error_context = |err: GeometryError| {
+            err.with_context_scene(
+                format!("Midpoint between {:?} and {:?}", start, end),
+                GeometryScene {
+                    points: vec![
+                        (start, Category10Color::Orange),
+                        (end, Category10Color::Blue),
+                    ]
+                    .into_iter()
+                    .filter_map(|(p, c)| p.map(|p| (p, c)))
+                    .collect(),
+                    curves: vec![(Curve::SampledCurve(self.clone()), Category10Color::Gray)],
+                    surfaces: vec![],
+                },
+            )
+        };
+
+        match (start, end) {
+            (Some(start), Some(end)) => {
+                if start == end {
+                    return Err(error_context(GeometryError::new(
+                        "Start and end are the same".to_string(),
+                    )));
+                }
+                self.interpolate(Some(start), Some(end), 0.5)
+                    .with_context(&error_context)
+            }
+            (Some(_), None) | (None, Some(_)) => self
+                .interpolate(start, end, 0.5)
+                .with_context(&error_context),
+            (None, None) => Ok(widen(self.points[0], self.error_bound)),
+        }
+    }
+
+    fn project(&self, p: Point) -> Point {
+        self.point_at(self.parameter(p))
+    }
+
+    fn get_bounding_box(
+        &self,
+        start: Option<Point>,
+        end: Option<Point>,
+    ) -> GeometryResult<BoundingBox> {
+        let mid_point = self.get_midpoint(start, end)?;
+        let mut bounding_box = BoundingBox::new(mid_point, mid_point);
+        if let Some(start) = start {
+            bounding_box.add_point(start);
+        }
+        if let Some(end) = end {
+            bounding_box.add_point(end);
+        }
+        // The samples are compared by their arc length, instead of locating each of them on the curve again
+        let s0 = start.map(|start| self.parameter(start));
+        let s1 = end.map(|end| self.parameter(end));
+        for (i, point) in self.points.iter().enumerate() {
+            let between = match (s0, s1) {
+                (Some(s0), Some(s1)) => {
+                    self.forward_length(s0, self.arc_lengths[i]) <= self.forward_length(s0, s1)
+                }
+                _ => true,
+            };
+            if between {
+                bounding_box.add_point(widen(*point, self.error_bound));
+            }
+        }
+        Ok(bounding_box)
+    }
+
+    fn shrink_bounding_box(
+        &self,
+        start: Option<Point>,
+        end: Option<Point>,
+        bounding_box: BoundingBox,
+    ) -> GeometryResult<BoundingBox> {
+        let tight = self.get_bounding_box(start, end)?;
+        Ok(BoundingBox::new(
+            Point::new(
+                tight.min.x.max(bounding_box.min.x),
+                tight.min.y.max(bounding_box.min.y),
+                tight.min.z.max(bounding_box.min.z),
+            ),
+            Point::new(
+                tight.max.x.min(bounding_box.max.x),
+                tight.max.y.min(bounding_box.max.y),
+                tight.max.z.min(bounding_box.max.z),
+            ),
+        ))
+    }
+
+    fn sort(&self, points: Vec<Option<Point>>) -> Vec<Option<Point>> {
+        let mut points = points;
+        points.sort_unstable_by(|a, b| match (a, b) {
+            (Some(a), Some(b)) => self.parameter(*a).partial_cmp(&self.parameter(*b)).unwrap(),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+        points
+    }
+
+    // Approximated by moving every sample along normal x tangent of the polygon, keeping the error bound.
+    fn offset_in_plane(&self, distance: EFloat64, normal: Point) -> GeometryResult<Curve> {
+        let normal = normal.normalize()?;
+        let centers = self
+            .points
+            .iter()
+            .map(|p| center(*p))
+            .collect::<Vec<Point>>();
+        let n = centers.len();
+        let points = (0..n)
+            .map(|i| {
+                let tangent = centers[(i + 1) % n] - centers[(i + n - 1) % n];
+                Ok(self.points[i] + normal.cross(tangent).normalize()? * distance)
+            })
+            .collect::<GeometryResult<Vec<Point>>>()?;
+        Ok(Curve::SampledCurve(SampledCurve::try_new(
+            points,
+            self.error_bound,
+        )?))
+    }
+}
+
+impl PartialEq for SampledCurve {
+    fn eq(&self, other: &SampledCurve) -> bool {
+        self.points.iter().all(|p| other.on_curve(*p))
+            && other.points.iter().all(|p| self.on_curve(*p))
+            && self
+                .tangent(self.points[0])
+                .unwrap()
+                .dot(other.tangent(self.points[0]).unwrap())
+                > 0.0
+    }
+}
+
+impl Display for SampledCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Sampled curve with {} points and error bound {}",
+            self.points.len(),
+            self.error_bound
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_circle(n: usize) -> SampledCurve {
+        let points = (0..n)
+            .map(|i| {
+                let angle = 2.0 * std::f64::consts::PI * i as f64 / n as f64;
+                Point::from_f64(angle.cos(), angle.sin(), 0.0)
+            })
+            .collect();
+        SampledCurve::try_new(points, 1e-3).unwrap()
+    }
+
+    #[test]
+    fn test_sampled_curve_on_curve() {
+        let curve = unit_circle(256);
+        assert!(curve.on_curve(Point::from_f64(1.0, 0.0, 0.0)));
+        assert!(curve.on_curve(Point::from_f64(0.0, -1.0, 0.0)));
+        assert!(!curve.on_curve(Point::from_f64(0.9, 0.0, 0.0)));
+        assert!(!curve.on_curve(Point::from_f64(1.0, 0.0, 0.1)));
+    }
+
+    #[test]
+    fn test_sampled_curve_between_and_midpoint() {
+        let curve = unit_circle(256);
+        let start = Point::from_f64(1.0, 0.0, 0.0);
+        let end = Point::from_f64(-1.0, 0.0, 0.0);
+        let top = Point::from_f64(0.0, 1.0, 0.0);
+        let bottom = Point::from_f64(0.0, -1.0, 0.0);
+
+        assert!(curve.between(top, Some(start), Some(end)).unwrap());
+        assert!(!curve.between(bottom, Some(start), Some(end)).unwrap());
+        assert!(curve.between(bottom, Some(end), Some(start)).unwrap());
+
+        let mid = curve.get_midpoint(Some(start), Some(end)).unwrap();
+        assert!((mid - top).norm() < 1e-2);
+        let mid = curve.get_midpoint(Some(end), Some(start)).unwrap();
+        assert!((mid - bottom).norm() < 1e-2);
+
+        let sorted = curve.sort(vec![None, Some(bottom), Some(end), Some(top)]);
+        assert_eq!(sorted, vec![Some(top), Some(end), Some(bottom), None]);
+    }
+
+    #[test]
+    fn test_sampled_curve_neg() {
+        let curve = unit_circle(64);
+        let neg = curve.neg();
+        let p = Point::from_f64(1.0, 0.0, 0.0);
+        assert!(curve.tangent(p).unwrap().dot(neg.tangent(p).unwrap()) < 0.0);
+        assert!(curve != neg);
+        assert!(curve == curve.clone());
+    }
+
+    #[test]
+    fn test_sampled_curve_offset() {
+        let curve = unit_circle(256);
+        let inwards = curve
+            .offset_in_plane(EFloat64::from(0.1), Point::unit_z())
+            .unwrap();
+        assert!(inwards.on_curve(Point::from_f64(0.9, 0.0, 0.0)));
+        assert!(!inwards.on_curve(Point::from_f64(1.0, 0.0, 0.0)));
+        let outwards = curve
+            .offset_in_plane(EFloat64::from(0.1), -Point::unit_z())
+            .unwrap();
+        assert!(outwards.on_curve(Point::from_f64(0.0, -1.1, 0.0)));
+    }
+
+    #[test]
+    fn test_sampled_curve_transform() {
+        // The unit x direction is not stretched, but the y direction is.
+        let curve =
+            unit_circle(256).transform(Transform::from_scale(Point::from_f64(1.0, 4.0, 1.0)));
+        assert!(curve.error_bound >= 4e-3 && curve.error_bound < 4.001e-3);
+        assert!(curve.on_curve(Point::from_f64(0.0, 4.0, 0.0)));
+    }
+}
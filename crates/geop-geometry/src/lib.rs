@@ -1,6 +1,7 @@
 pub const HORIZON_DIST: f64 = 100.0; // A big number to represent the distance to the horizon. Used only for visualization purposes.
 
 pub mod bounding_box;
+pub mod bvh;
 pub mod color;
 pub mod coordinate_system;
 pub mod curve_curve_intersection;
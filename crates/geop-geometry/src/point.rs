@@ -8,6 +8,7 @@ use geop_algebra::{efloat::EFloat64, HasZero};
 use crate::geometry_error::{ElevateToGeometry, GeometryError, GeometryResult};
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub x: EFloat64,
     pub y: EFloat64,
@@ -76,16 +77,11 @@ impl Point {
     }
 
     pub fn angle(&self, other: Point) -> GeometryResult<EFloat64> {
-        let dot = self.dot(other);
+        // Unlike the arc cosine of the dot product, this stays accurate for (anti)parallel vectors
         let norm = self.norm() * other.norm();
-        let dot_norm = (dot / norm)?;
-        if dot_norm >= 1.0 {
-            return Ok(EFloat64::zero());
-        }
-        if dot_norm <= -1.0 {
-            return Ok(EFloat64::pi());
-        }
-        Ok(dot_norm.acos())
+        let sin = (self.cross(other).norm() / norm)?;
+        let cos = (self.dot(other) / norm)?;
+        Ok(sin.atan2(cos))
     }
 
     // Oriented angle between two vectors around a normal vector. Measured from self to other.
@@ -0,0 +1,235 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curves::{line::Line, sampled_curve::SampledCurve},
+    point::Point,
+    surfaces::cylinder::Cylinder,
+};
+
+use crate::geometry_error::GeometryResult;
+
+use super::numerical::{cylinder_quadratic_intersection_numerical, RELATIVE_ERROR_BOUND};
+
+pub enum CylinderCylinderIntersection {
+    Cylinder(Cylinder),
+    TwoLines(Line, Line),
+    Line(Line),
+    Curves(Vec<SampledCurve>),
+    None,
+}
+
+pub fn cylinder_cylinder_intersection(
+    a: &Cylinder,
+    b: &Cylinder,
+) -> GeometryResult<CylinderCylinderIntersection> {
+    let axis = a.extend_dir;
+    let r_a = a.radius.norm();
+    let r_b = b.radius.norm();
+
+    if !axis.is_parallel(b.extend_dir) {
+        // Shortest distance between the two skew axes
+        let common_normal = axis.cross(b.extend_dir).normalize().unwrap();
+        if (b.basis - a.basis).dot(common_normal).abs() > r_a + r_b {
+            return Ok(CylinderCylinderIntersection::None);
+        }
+
+        // Otherwise the intersection is a quartic curve, which is traced numerically along the rulings of a
+        let b_axis = b.extend_dir;
+        let e = axis - b_axis * axis.dot(b_axis);
+        let quadratic = |q: Point| {
+            let q = q - b.basis;
+            let q = q - b_axis * q.dot(b_axis);
+            (
+                e.norm_sq(),
+                EFloat64::two() * q.dot(e),
+                q.norm_sq() - r_b * r_b,
+            )
+        };
+        let error_bound = RELATIVE_ERROR_BOUND * r_a.to_f64().max(r_b.to_f64());
+        let curves = cylinder_quadratic_intersection_numerical(a, &quadratic, error_bound)?;
+        if curves.is_empty() {
+            return Ok(CylinderCylinderIntersection::None);
+        }
+        return Ok(CylinderCylinderIntersection::Curves(curves));
+    }
+
+    // With parallel axes the problem reduces to intersecting two circles in a cross section
+    let offset = b.basis - a.basis;
+    let offset = offset - axis * offset.dot(axis);
+    let d = offset.norm();
+
+    if d == 0.0 {
+        if r_a == r_b {
+            return Ok(CylinderCylinderIntersection::Cylinder(a.clone()));
+        }
+        return Ok(CylinderCylinderIntersection::None);
+    }
+
+    if d > r_a + r_b || d < (r_a - r_b).abs() {
+        return Ok(CylinderCylinderIntersection::None);
+    }
+
+    let u = (offset / d).unwrap();
+    let x = ((r_a * r_a - r_b * r_b + d * d) / (EFloat64::two() * d)).unwrap();
+    let foot = a.basis + u * x;
+
+    let y_sq = r_a * r_a - x * x;
+    if y_sq == 0.0 {
+        return Ok(CylinderCylinderIntersection::Line(
+            Line::new(foot, axis).unwrap(),
+        ));
+    }
+    let y = y_sq.sqrt().unwrap();
+    let w = axis.cross(u);
+    Ok(CylinderCylinderIntersection::TwoLines(
+        Line::new(foot + w * y, axis).unwrap(),
+        Line::new(foot - w * y, axis).unwrap(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curves::CurveLike, surfaces::SurfaceLike};
+
+    #[test]
+    fn test_cylinder_cylinder_intersection_parallel() {
+        let a = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::one(), true);
+
+        let b = Cylinder::new(Point::unit_x(), Point::unit_z(), EFloat64::one(), true);
+        match cylinder_cylinder_intersection(&a, &b).unwrap() {
+            CylinderCylinderIntersection::TwoLines(l1, l2) => {
+                let y = 0.75_f64.sqrt();
+                let p1 = Point::from_f64(0.5, y, 0.0);
+                let p2 = Point::from_f64(0.5, -y, 0.0);
+                assert!(l1.on_curve(p1) || l2.on_curve(p1));
+                assert!(l1.on_curve(p2) || l2.on_curve(p2));
+            }
+            _ => panic!("Intersection should be two lines"),
+        }
+
+        let b = Cylinder::new(
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_z(),
+            EFloat64::one(),
+            true,
+        );
+        match cylinder_cylinder_intersection(&a, &b).unwrap() {
+            CylinderCylinderIntersection::Line(line) => {
+                assert!(line.on_curve(Point::unit_x()));
+            }
+            _ => panic!("Intersection should be a line"),
+        }
+
+        match cylinder_cylinder_intersection(&a, &a).unwrap() {
+            CylinderCylinderIntersection::Cylinder(c) => {
+                assert_eq!(c, a);
+            }
+            _ => panic!("Intersection should be a cylinder"),
+        }
+
+        let b = Cylinder::new(
+            Point::from_f64(3.0, 0.0, 0.0),
+            -Point::unit_z(),
+            EFloat64::one(),
+            true,
+        );
+        match cylinder_cylinder_intersection(&a, &b).unwrap() {
+            CylinderCylinderIntersection::None => (),
+            _ => panic!("Intersection should be no intersection"),
+        }
+    }
+
+    #[test]
+    fn test_cylinder_cylinder_intersection_crossing() {
+        // Pipe tee: a thin cylinder pierces a thick one, giving two loops
+        let a = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::from(0.5), true);
+        let b = Cylinder::new(Point::zero(), Point::unit_x(), EFloat64::one(), true);
+        match cylinder_cylinder_intersection(&a, &b).unwrap() {
+            CylinderCylinderIntersection::Curves(curves) => {
+                assert_eq!(curves.len(), 2);
+                for curve in curves.iter() {
+                    for t in 0..10 {
+                        let p = curve.interpolate(None, None, t as f64 / 10.0).unwrap();
+                        assert!(a.on_surface(p));
+                        assert!(b.on_surface(p));
+                    }
+                }
+            }
+            _ => panic!("Intersection should be two sampled curves"),
+        }
+
+        // Skew cylinders that only partially overlap, giving a single loop
+        let b = Cylinder::new(
+            Point::from_f64(0.0, 1.2, 0.0),
+            Point::unit_x(),
+            EFloat64::one(),
+            true,
+        );
+        match cylinder_cylinder_intersection(&a, &b).unwrap() {
+            CylinderCylinderIntersection::Curves(curves) => {
+                assert_eq!(curves.len(), 1);
+                for p in curves[0].points.iter() {
+                    assert!(a.on_surface(*p));
+                    assert!(b.on_surface(*p));
+                }
+            }
+            _ => panic!("Intersection should be a sampled curve"),
+        }
+
+        // Skew cylinders that are too far apart
+        let b = Cylinder::new(
+            Point::from_f64(5.0, 0.0, 0.0),
+            Point::unit_y(),
+            EFloat64::one(),
+            true,
+        );
+        match cylinder_cylinder_intersection(&a, &b).unwrap() {
+            CylinderCylinderIntersection::None => (),
+            _ => panic!("Intersection should be no intersection"),
+        }
+    }
+
+    #[test]
+    fn test_cylinder_cylinder_intersection_error_bound() {
+        // The exact curve is z = +-sqrt(1 - (y - offset)^2) above the circle of a. It must be within the error bound everywhere,
+        // also close to where the two branches meet at sin(theta) = 0.4 for the offset 1.2.
+        let a = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::from(0.5), true);
+        let branch = 0.4_f64.asin();
+        let mut thetas = (0..100)
+            .map(|i| 2.0 * std::f64::consts::PI * i as f64 / 100.0)
+            .collect::<Vec<f64>>();
+        for k in 1..12 {
+            let delta = 10.0_f64.powi(-k);
+            thetas.push(branch + delta);
+            thetas.push(std::f64::consts::PI - branch - delta);
+        }
+
+        for offset in [0.0, 1.2] {
+            let b = Cylinder::new(
+                Point::from_f64(0.0, offset, 0.0),
+                Point::unit_x(),
+                EFloat64::one(),
+                true,
+            );
+            let curves = match cylinder_cylinder_intersection(&a, &b).unwrap() {
+                CylinderCylinderIntersection::Curves(curves) => curves,
+                _ => panic!("Intersection should be sampled curves"),
+            };
+            for curve in curves.iter() {
+                assert!(curve.error_bound <= 1.001 * RELATIVE_ERROR_BOUND);
+            }
+            for theta in thetas.iter() {
+                let (x, y) = (0.5 * theta.cos(), 0.5 * theta.sin());
+                let z_sq = 1.0 - (y - offset) * (y - offset);
+                if z_sq < 0.0 {
+                    continue;
+                }
+                for z in [z_sq.sqrt(), -z_sq.sqrt()] {
+                    let p = Point::from_f64(x, y, z);
+                    assert!(curves.iter().any(|curve| curve.on_curve(p)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curves::{circle::Circle, sampled_curve::SampledCurve},
+    point::Point,
+    surfaces::{cylinder::Cylinder, sphere::Sphere},
+};
+
+use crate::geometry_error::GeometryResult;
+
+use super::numerical::{cylinder_quadratic_intersection_numerical, RELATIVE_ERROR_BOUND};
+
+pub enum CylinderSphereIntersection {
+    TwoCircles(Circle, Circle),
+    Circle(Circle),
+    Curves(Vec<SampledCurve>),
+    None,
+}
+
+pub fn cylinder_sphere_intersection(
+    a: &Cylinder,
+    b: &Sphere,
+) -> GeometryResult<CylinderSphereIntersection> {
+    let axis = a.extend_dir;
+    let r_c = a.radius.norm();
+    let r_s = b.radius;
+
+    let offset = b.basis - a.basis;
+    let height = offset.dot(axis);
+    let axis_dist = (offset - axis * height).norm();
+
+    if axis_dist > r_c + r_s || axis_dist + r_s < r_c {
+        return Ok(CylinderSphereIntersection::None);
+    }
+
+    // Off the axis the intersection is a quartic curve, which is traced numerically
+    if axis_dist != 0.0 {
+        let quadratic = |q: Point| {
+            let q = q - b.basis;
+            (
+                EFloat64::one(),
+                EFloat64::two() * q.dot(axis),
+                q.norm_sq() - r_s * r_s,
+            )
+        };
+        let error_bound = RELATIVE_ERROR_BOUND * r_c.to_f64().max(r_s.to_f64());
+        let curves = cylinder_quadratic_intersection_numerical(a, &quadratic, error_bound)?;
+        if curves.is_empty() {
+            return Ok(CylinderSphereIntersection::None);
+        }
+        return Ok(CylinderSphereIntersection::Curves(curves));
+    }
+
+    // Coaxial case: the sphere is cut in circles of the cylinder radius
+    let h_sq = r_s * r_s - r_c * r_c;
+    if h_sq < 0.0 {
+        return Ok(CylinderSphereIntersection::None);
+    }
+    if h_sq == 0.0 {
+        return Ok(CylinderSphereIntersection::Circle(
+            Circle::try_new(b.basis, axis, r_c).unwrap(),
+        ));
+    }
+    let h = h_sq.sqrt().unwrap();
+    Ok(CylinderSphereIntersection::TwoCircles(
+        Circle::try_new(b.basis + axis * h, axis, r_c).unwrap(),
+        Circle::try_new(b.basis - axis * h, axis, r_c).unwrap(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curves::CurveLike, surfaces::SurfaceLike};
+
+    #[test]
+    fn test_cylinder_sphere_intersection_coaxial() {
+        let cylinder = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::one(), true);
+
+        let sphere = Sphere::new(Point::unit_z(), EFloat64::from(2.0), true);
+        match cylinder_sphere_intersection(&cylinder, &sphere).unwrap() {
+            CylinderSphereIntersection::TwoCircles(c1, c2) => {
+                let h = 3.0_f64.sqrt();
+                assert_eq!(c1.basis, Point::from_f64(0.0, 0.0, 1.0 + h));
+                assert_eq!(c2.basis, Point::from_f64(0.0, 0.0, 1.0 - h));
+                assert_eq!(c1.radius.norm(), 1.0);
+                assert_eq!(c2.radius.norm(), 1.0);
+            }
+            _ => panic!("Intersection should be two circles"),
+        }
+
+        let sphere = Sphere::new(Point::unit_z(), EFloat64::one(), true);
+        match cylinder_sphere_intersection(&cylinder, &sphere).unwrap() {
+            CylinderSphereIntersection::Circle(circle) => {
+                assert_eq!(circle.basis, Point::unit_z());
+                assert_eq!(circle.radius.norm(), 1.0);
+            }
+            _ => panic!("Intersection should be a circle"),
+        }
+
+        let sphere = Sphere::new(Point::unit_z(), EFloat64::from(0.5), true);
+        match cylinder_sphere_intersection(&cylinder, &sphere).unwrap() {
+            CylinderSphereIntersection::None => (),
+            _ => panic!("Intersection should be no intersection"),
+        }
+    }
+
+    #[test]
+    fn test_cylinder_sphere_intersection_far_away() {
+        let cylinder = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::one(), true);
+        let sphere = Sphere::new(Point::from_f64(5.0, 0.0, 0.0), EFloat64::one(), true);
+        match cylinder_sphere_intersection(&cylinder, &sphere).unwrap() {
+            CylinderSphereIntersection::None => (),
+            _ => panic!("Intersection should be no intersection"),
+        }
+    }
+
+    #[test]
+    fn test_cylinder_sphere_intersection_off_axis() {
+        let cylinder = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::one(), true);
+
+        // The sphere pokes through one side of the cylinder
+        let sphere = Sphere::new(Point::unit_x(), EFloat64::from(0.5), true);
+        match cylinder_sphere_intersection(&cylinder, &sphere).unwrap() {
+            CylinderSphereIntersection::Curves(curves) => {
+                assert_eq!(curves.len(), 1);
+                for curve in curves.iter() {
+                    for t in 0..10 {
+                        let p = curve.interpolate(None, None, t as f64 / 10.0).unwrap();
+                        assert!(cylinder.on_surface(p));
+                        assert!(sphere.on_surface(p));
+                    }
+                }
+            }
+            _ => panic!("Intersection should be a sampled curve"),
+        }
+
+        // The sphere contains the axis but is not centered on it, so there are two loops
+        let sphere = Sphere::new(Point::from_f64(0.2, 0.0, 0.0), EFloat64::from(2.0), true);
+        match cylinder_sphere_intersection(&cylinder, &sphere).unwrap() {
+            CylinderSphereIntersection::Curves(curves) => {
+                assert_eq!(curves.len(), 2);
+                for curve in curves.iter() {
+                    for p in curve.points.iter() {
+                        assert!(cylinder.on_surface(*p));
+                        assert!(sphere.on_surface(*p));
+                    }
+                }
+            }
+            _ => panic!("Intersection should be two sampled curves"),
+        }
+    }
+}
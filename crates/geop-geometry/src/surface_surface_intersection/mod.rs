@@ -1,3 +1,9 @@
+pub mod cylinder_cylinder;
+pub mod cylinder_sphere;
+pub mod numerical;
+pub mod nurbs_surface;
+pub mod plane_cylinder;
 pub mod plane_plane;
 pub mod plane_sphere;
+pub mod sphere_sphere;
 pub mod surface_surface;
@@ -0,0 +1,256 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    color::Category10Color,
+    curves::sampled_curve::SampledCurve,
+    geometry_error::{GeometryError, GeometryResult, WithContext},
+    geometry_scene::GeometryScene,
+    point::Point,
+    surfaces::{cylinder::Cylinder, surface::Surface},
+};
+
+// Error bound of traced curves relative to the size of the surfaces involved.
+pub const RELATIVE_ERROR_BOUND: f64 = 1e-4;
+
+const INITIAL_SAMPLES: usize = 360;
+// Where the two branches meet, the curve has a vertical tangent in the angle and the bound is only met on very short intervals.
+const MAX_DEPTH: u32 = 48;
+
+// Traces the intersection of a cylinder with a surface that cuts every ruling of the cylinder in at most two points.
+// For a ruling basis q, quadratic(q) returns (a, b, c) such that q + t * extend_dir is on the other surface iff a t^2 + b t + c = 0, with a > 0.
+// The coefficients must be polynomials of degree at most two in q, as they are for quadrics, and are evaluated in interval arithmetic to bound the error.
+// The rulings are parametrized by their angle, so that the intersection splits into a + and a - branch.
+// Where both branches exist around the whole cylinder, they form two separate loops. Otherwise each interval of angles with two solutions forms one loop.
+// The exact curve deviates from the returned polygons by at most error_bound, unless the bound could not be reached, in which case the curve carries the larger bound that was proven.
+// If no valid bound could be proven for a loop, an error is returned instead of dropping the loop.
+pub fn cylinder_quadratic_intersection_numerical(
+    cylinder: &Cylinder,
+    quadratic: &dyn Fn(Point) -> (EFloat64, EFloat64, EFloat64),
+    error_bound: f64,
+) -> GeometryResult<Vec<SampledCurve>> {
+    let tracer = Tracer {
+        cylinder,
+        quadratic,
+        error_bound,
+    };
+    let two_pi = 2.0 * std::f64::consts::PI;
+    let thetas = (0..INITIAL_SAMPLES)
+        .map(|i| two_pi * i as f64 / INITIAL_SAMPLES as f64)
+        .collect::<Vec<f64>>();
+    let positive = thetas
+        .iter()
+        .map(|theta| tracer.discriminant(*theta) > 0.0)
+        .collect::<Vec<bool>>();
+
+    if positive.iter().all(|p| !*p) {
+        return Ok(Vec::new());
+    }
+
+    // Each loop with the largest deviation of the exact curve from its chords that was proven.
+    let mut loops = Vec::<(Vec<Point>, f64)>::new();
+    if positive.iter().all(|p| *p) {
+        let mut closed_thetas = thetas.clone();
+        closed_thetas.push(two_pi);
+        for sign in [1.0, -1.0] {
+            let (mut points, deviation) = tracer.trace(&closed_thetas, sign);
+            points.pop();
+            loops.push((points, deviation));
+        }
+    } else {
+        // Rotate the start, such that the first sample has no solution and no run wraps around.
+        let n = INITIAL_SAMPLES;
+        let offset = positive.iter().position(|p| !*p).unwrap();
+        let mut i = 0;
+        while i < n {
+            if !positive[(offset + i) % n] {
+                i += 1;
+                continue;
+            }
+            let run_start = i;
+            while i < n && positive[(offset + i) % n] {
+                i += 1;
+            }
+            let run_end = i - 1;
+
+            let theta_at = |k: usize| two_pi * (offset + k) as f64 / n as f64;
+            let theta_lo = tracer.bisect(theta_at(run_start - 1), theta_at(run_start));
+            let theta_hi = tracer.bisect(theta_at(run_end + 1), theta_at(run_end));
+
+            let mut run_thetas = vec![theta_lo];
+            run_thetas.extend((run_start..=run_end).map(theta_at));
+            run_thetas.push(theta_hi);
+
+            // Go along the - branch and come back on the + branch. Both meet where the discriminant vanishes.
+            let (mut points, deviation_minus) = tracer.trace(&run_thetas, -1.0);
+            let (mut back, deviation_plus) = tracer.trace(&run_thetas, 1.0);
+            back.pop();
+            back.reverse();
+            back.pop();
+            points.extend(back);
+            loops.push((points, deviation_minus.max(deviation_plus)));
+        }
+    }
+
+    loops
+        .into_iter()
+        .map(|(points, deviation)| {
+            let mut unique = Vec::<Point>::with_capacity(points.len());
+            for p in points {
+                match unique.last() {
+                    Some(q) if (p - *q).norm().to_f64() <= error_bound * 1e-3 => {}
+                    _ => unique.push(p),
+                }
+            }
+            while unique.len() > 1
+                && (unique[0] - unique[unique.len() - 1]).norm().to_f64() <= error_bound * 1e-3
+            {
+                unique.pop();
+            }
+            let context = |err: GeometryError| {
+                err.with_context_scene(
+                    format!(
+                        "Trace an intersection loop with a proven deviation of {}",
+                        deviation
+                    ),
+                    GeometryScene {
+                        points: unique
+                            .iter()
+                            .map(|p| (*p, Category10Color::Orange))
+                            .collect(),
+                        curves: vec![],
+                        surfaces: vec![(
+                            Surface::Cylinder(cylinder.clone()),
+                            Category10Color::Gray,
+                        )],
+                    },
+                )
+            };
+            // Half of the bound is left for the rounding of the samples.
+            SampledCurve::try_new(unique.clone(), error_bound.max(2.0 * deviation))
+                .with_context(&context)
+        })
+        .collect()
+}
+
+// The length of the diagonal of the box that encloses the point.
+fn width(p: Point) -> f64 {
+    let width = |v: EFloat64| v.upper_bound - v.lower_bound;
+    (width(p.x).powi(2) + width(p.y).powi(2) + width(p.z).powi(2)).sqrt()
+}
+
+struct Tracer<'a> {
+    cylinder: &'a Cylinder,
+    quadratic: &'a dyn Fn(Point) -> (EFloat64, EFloat64, EFloat64),
+    error_bound: f64,
+}
+
+impl<'a> Tracer<'a> {
+    fn ruling_basis(&self, theta: EFloat64) -> Point {
+        let radius = self.cylinder.radius;
+        let dir_cross = self.cylinder.extend_dir.cross(radius);
+        self.cylinder.basis + radius * theta.cos() + dir_cross * theta.sin()
+    }
+
+    fn ruling_basis_derivative(&self, theta: EFloat64) -> Point {
+        let radius = self.cylinder.radius;
+        let dir_cross = self.cylinder.extend_dir.cross(radius);
+        dir_cross * theta.cos() - radius * theta.sin()
+    }
+
+    fn discriminant(&self, theta: f64) -> f64 {
+        let (a, b, c) = (self.quadratic)(self.ruling_basis(EFloat64::from(theta)));
+        let (a, b, c) = (a.to_f64(), b.to_f64(), c.to_f64());
+        b * b - 4.0 * a * c
+    }
+
+    fn point(&self, theta: f64, sign: f64) -> Point {
+        let q = self.ruling_basis(EFloat64::from(theta));
+        let (a, b, c) = (self.quadratic)(q);
+        let (a, b, c) = (a.to_f64(), b.to_f64(), c.to_f64());
+        let root = (b * b - 4.0 * a * c).max(0.0).sqrt();
+        let t = (-b + sign * root) / (2.0 * a);
+        let p = q + self.cylinder.extend_dir * EFloat64::from(t);
+        Point::from_f64(p.x.to_f64(), p.y.to_f64(), p.z.to_f64())
+    }
+
+    // Finds the angle between theta_out (no solution) and theta_in (two solutions) where the discriminant vanishes.
+    fn bisect(&self, theta_out: f64, theta_in: f64) -> f64 {
+        let mut theta_out = theta_out;
+        let mut theta_in = theta_in;
+        for _ in 0..60 {
+            let theta = (theta_out + theta_in) / 2.0;
+            if self.discriminant(theta) > 0.0 {
+                theta_in = theta;
+            } else {
+                theta_out = theta;
+            }
+        }
+        theta_in
+    }
+
+    // Bounds the distance of the branch between theta_a and theta_b to its chord, by evaluating the branch for all angles in between at once.
+    // The branch and the chord lie in the box that encloses the branch, so the diagonal of the box is a bound.
+    // Where the branch is smooth, the direction of the chord is a mean of its derivative,
+    // such that half the length of the interval times the diagonal of the box that encloses the derivative is a bound as well.
+    fn chord_deviation_bound(&self, theta_a: f64, theta_b: f64, sign: f64) -> f64 {
+        let theta = EFloat64::new(theta_b, theta_a);
+        let q = self.ruling_basis(theta);
+        let (a, b, c) = (self.quadratic)(q);
+        let root = match (b * b - EFloat64::from(4.0) * a * c).sqrt() {
+            Some(root) => root * EFloat64::from(sign),
+            None => return f64::INFINITY,
+        };
+        let t = match (root - b) / (EFloat64::two() * a) {
+            Ok(t) => t,
+            Err(_) => return f64::INFINITY,
+        };
+        let dir = self.cylinder.extend_dir;
+        let enclosure = width(q + dir * t);
+
+        // Central differences are exact for polynomials of degree two, which gives the derivatives of the coefficients along the rulings.
+        let dq = self.ruling_basis_derivative(theta);
+        let (a_plus, b_plus, c_plus) = (self.quadratic)(q + dq);
+        let (a_minus, b_minus, c_minus) = (self.quadratic)(q - dq);
+        let half = EFloat64::from(0.5);
+        let (da, db, dc) = (
+            (a_plus - a_minus) * half,
+            (b_plus - b_minus) * half,
+            (c_plus - c_minus) * half,
+        );
+        // Differentiating a t^2 + b t + c = 0 gives (2 a t + b) t' = -(a' t^2 + b' t + c'), where 2 a t + b is the signed root.
+        match -(da * t * t + db * t + dc) / root {
+            Ok(dt) => enclosure.min((theta_b - theta_a) / 2.0 * width(dq + dir * dt)),
+            Err(_) => enclosure,
+        }
+    }
+
+    // Samples the branch at the given angles and refines until every chord is close to the exact curve.
+    // Returns the samples and the largest proven distance of the branch to a chord.
+    fn trace(&self, thetas: &[f64], sign: f64) -> (Vec<Point>, f64) {
+        let mut points = vec![self.point(thetas[0], sign)];
+        let mut deviation = 0.0_f64;
+        for window in thetas.windows(2) {
+            deviation = deviation.max(self.refine(window[0], window[1], sign, 0, &mut points));
+        }
+        (points, deviation)
+    }
+
+    fn refine(
+        &self,
+        theta_a: f64,
+        theta_b: f64,
+        sign: f64,
+        depth: u32,
+        points: &mut Vec<Point>,
+    ) -> f64 {
+        let deviation = self.chord_deviation_bound(theta_a, theta_b, sign);
+        if deviation > self.error_bound / 2.0 && depth < MAX_DEPTH {
+            let theta_m = (theta_a + theta_b) / 2.0;
+            let left = self.refine(theta_a, theta_m, sign, depth + 1, points);
+            let right = self.refine(theta_m, theta_b, sign, depth + 1, points);
+            return left.max(right);
+        }
+        points.push(self.point(theta_b, sign));
+        deviation
+    }
+}
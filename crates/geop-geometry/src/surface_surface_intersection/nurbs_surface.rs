@@ -0,0 +1,368 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    curves::{curve::Curve, nurb::NurbsCurve, sampled_curve::SampledCurve},
+    point::Point,
+    surfaces::{nurbs::NurbsSurface, surface::Surface, SurfaceLike},
+};
+
+pub enum NurbsSurfaceIntersection {
+    Surface(NurbsSurface),
+    Curves(Vec<Curve>),
+    None,
+}
+
+// Resolution of the parameter grid that is searched for starting points.
+const GRID: usize = 32;
+// The step size of the tracing relative to the size of the nurbs surface.
+const STEPS_PER_DIAGONAL: f64 = 128.0;
+// The largest distance between a chord of the traced polygon and the curve, relative to the size of the nurbs surface.
+const MAX_DEVIATION: f64 = 1e-6;
+const MAX_STEPS: usize = 4096;
+const CORRECTION_ITERATIONS: usize = 16;
+const PRECISION: f64 = 1e-8;
+
+// Distance of p to the surface, positive on the side the normal points to.
+fn signed_distance(surface: &Surface, p: Point) -> f64 {
+    let q = surface.project(p);
+    (p - q).dot(surface.normal(q)).to_f64()
+}
+
+// Moves p onto both surfaces by repeatedly jumping to the closest point on the intersection line of the two tangent planes.
+// Returns the parameters on the nurbs surface and the point, or None if this does not converge.
+fn refine(
+    nurbs: &NurbsSurface,
+    other: &Surface,
+    p: Point,
+    uv: (f64, f64),
+) -> Option<((f64, f64), Point)> {
+    let mut p = p;
+    let mut uv = uv;
+    for _ in 0..CORRECTION_ITERATIONS {
+        uv = nurbs.parameter_near(p, uv);
+        let q_a = nurbs.point_at(uv.0, uv.1);
+        let q_b = other.project(p);
+        if (q_a - p).norm() <= PRECISION && (q_b - p).norm() <= PRECISION {
+            return Some((uv, q_a));
+        }
+        let n_a = nurbs.normal_at(uv);
+        let n_b = other.normal(q_b);
+        let (a, b, c) = (
+            n_a.dot(n_a).to_f64(),
+            n_a.dot(n_b).to_f64(),
+            n_b.dot(n_b).to_f64(),
+        );
+        let det = a * c - b * b;
+        if det <= f64::EPSILON {
+            return None;
+        }
+        let (r_a, r_b) = (n_a.dot(q_a - p).to_f64(), n_b.dot(q_b - p).to_f64());
+        let alpha = (c * r_a - b * r_b) / det;
+        let beta = (a * r_b - b * r_a) / det;
+        p = p + n_a * EFloat64::from(alpha) + n_b * EFloat64::from(beta);
+    }
+    None
+}
+
+fn on_boundary(nurbs: &NurbsSurface, other: &Surface, uv: (f64, f64), p: Point) -> bool {
+    nurbs.on_boundary(uv)
+        || match other {
+            Surface::Nurbs(other) => other.on_boundary(other.parameter(p)),
+            _ => false,
+        }
+}
+
+// Follows the intersection curve from the start point until it leaves one of the surfaces or closes.
+// The step is halved when the curve cannot be followed, such that the trace ends close to the boundary,
+// and where it bends, such that the polygon stays close to the curve.
+fn trace(
+    nurbs: &NurbsSurface,
+    other: &Surface,
+    start: ((f64, f64), Point),
+    step: f64,
+    deviation: f64,
+    forward: bool,
+) -> (Vec<Point>, bool) {
+    let (mut uv, mut p) = start;
+    let mut points = vec![p];
+    let mut left = false;
+    // Across the seam of a closed surface, the parameters are found by a global search instead of from the last ones
+    let project = |q: Point, uv: (f64, f64)| {
+        refine(nurbs, other, q, uv).or_else(|| refine(nurbs, other, q, nurbs.parameter(q)))
+    };
+    let bends = |p: Point, q: Point, uv: (f64, f64)| {
+        let mid = (p + q) * EFloat64::from(0.5);
+        !project(mid, uv).is_some_and(|(_, m)| (m - mid).norm() <= deviation)
+    };
+    let mut h = step;
+    for _ in 0..MAX_STEPS {
+        let tangent = match nurbs
+            .normal_at(uv)
+            .cross(other.normal(other.project(p)))
+            .normalize()
+        {
+            Ok(tangent) if forward => tangent,
+            Ok(tangent) => -tangent,
+            Err(_) => break,
+        };
+        match project(p + tangent * EFloat64::from(h), uv) {
+            // The step is also halved where the curve bends away from the chord
+            Some((_, q)) if bends(p, q, uv) => {
+                h /= 2.0;
+                if h < 1e-6 * step {
+                    break;
+                }
+            }
+            Some((uv_new, q)) => {
+                // The curve is closed when it comes back to the start after it has left it
+                let distance = (q - start.1).norm();
+                if left && distance <= (q - p).norm() {
+                    return (points, true);
+                }
+                left = left || distance > step;
+                points.push(q);
+                if on_boundary(nurbs, other, uv_new, q) {
+                    break;
+                }
+                (uv, p) = (uv_new, q);
+                h = (2.0 * h).min(step);
+            }
+            None => {
+                h /= 2.0;
+                if h < 1e-6 * step {
+                    break;
+                }
+            }
+        }
+    }
+    (points, false)
+}
+
+// Intersects a nurbs surface with any other surface by tracing the intersection curves from starting points,
+// which are found as sign changes of the distance on a grid. Closed curves are sampled curves, open curves are polylines.
+// Tangential intersections without a sign change are not found.
+pub fn nurbs_surface_intersection(
+    nurbs: &NurbsSurface,
+    other: &Surface,
+) -> NurbsSurfaceIntersection {
+    if let Surface::Nurbs(other) = other {
+        if nurbs == other {
+            return NurbsSurfaceIntersection::Surface(nurbs.clone());
+        }
+    }
+
+    let ((u0, u1), (v0, v1)) = nurbs.domain();
+    let param = |i: usize, j: usize| {
+        (
+            u0 + (u1 - u0) * i as f64 / GRID as f64,
+            v0 + (v1 - v0) * j as f64 / GRID as f64,
+        )
+    };
+    let distance = |(u, v): (f64, f64)| signed_distance(other, nurbs.point_at(u, v));
+    let distances = (0..=GRID)
+        .map(|i| (0..=GRID).map(|j| distance(param(i, j))).collect())
+        .collect::<Vec<Vec<f64>>>();
+    if distances.iter().flatten().all(|d| d.abs() <= PRECISION) {
+        return NurbsSurfaceIntersection::Surface(nurbs.clone());
+    }
+
+    // Starting points are bisected on the grid lines where the distance changes its sign
+    let mut seeds = Vec::<((f64, f64), Point)>::new();
+    for i in 0..=GRID {
+        for j in 0..=GRID {
+            for (k, l) in [(i + 1, j), (i, j + 1)] {
+                if k > GRID || l > GRID || distances[i][j].signum() == distances[k][l].signum() {
+                    continue;
+                }
+                let (a, b) = (param(i, j), param(k, l));
+                let (mut lo, mut hi) = (0.0, 1.0);
+                for _ in 0..50 {
+                    let t = (lo + hi) / 2.0;
+                    let d = distance((a.0 + t * (b.0 - a.0), a.1 + t * (b.1 - a.1)));
+                    if d.signum() == distances[i][j].signum() {
+                        lo = t;
+                    } else {
+                        hi = t;
+                    }
+                }
+                let t = (lo + hi) / 2.0;
+                let uv = (a.0 + t * (b.0 - a.0), a.1 + t * (b.1 - a.1));
+                if let Some(seed) = refine(nurbs, other, nurbs.point_at(uv.0, uv.1), uv) {
+                    seeds.push(seed);
+                }
+            }
+        }
+    }
+
+    let diagonal = {
+        let points = nurbs.control_points.iter().flatten();
+        let mut min = *nurbs.control_points[0].first().unwrap();
+        let mut max = min;
+        for p in points {
+            min = Point::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+            max = Point::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+        }
+        (max - min).norm().to_f64()
+    };
+    let step = diagonal / STEPS_PER_DIAGONAL;
+    let deviation = diagonal * MAX_DEVIATION;
+
+    let mut traced = Vec::<(Vec<Point>, bool)>::new();
+    for seed in seeds {
+        if traced
+            .iter()
+            .any(|(points, _)| points.iter().any(|p| (*p - seed.1).norm() <= step))
+        {
+            continue;
+        }
+        let (forward, closed) = trace(nurbs, other, seed, step, deviation, true);
+        if closed {
+            traced.push((forward, true));
+            continue;
+        }
+        let (backward, _) = trace(nurbs, other, seed, step, deviation, false);
+        let mut points = backward.into_iter().skip(1).rev().collect::<Vec<Point>>();
+        points.extend(forward);
+        traced.push((points, false));
+    }
+
+    let mut curves = Vec::<Curve>::new();
+    for (points, closed) in traced {
+        if closed && points.len() >= 3 {
+            // The polygon deviates from the exact curve by at most the distance of its chord midpoints to the curve
+            let mut error_bound = PRECISION;
+            for i in 0..points.len() {
+                let (a, b) = (points[i], points[(i + 1) % points.len()]);
+                let mid = (a + b) * EFloat64::from(0.5);
+                if let Some((_, q)) = refine(nurbs, other, mid, nurbs.parameter(mid)) {
+                    error_bound = error_bound.max(2.0 * (q - mid).norm().to_f64());
+                }
+            }
+            if let Ok(curve) = SampledCurve::try_new(points, error_bound) {
+                curves.push(Curve::SampledCurve(curve));
+            }
+        } else if let Ok(curve) = NurbsCurve::try_new_polyline(points) {
+            curves.push(Curve::Nurbs(curve));
+        }
+    }
+
+    if curves.is_empty() {
+        return NurbsSurfaceIntersection::None;
+    }
+    NurbsSurfaceIntersection::Curves(curves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curves::CurveLike, surfaces::plane::Plane};
+
+    fn patch(u_degree: usize, v_degree: usize, heights: Vec<Vec<f64>>) -> NurbsSurface {
+        let n = heights.len();
+        let m = heights[0].len();
+        let knots = |degree: usize, count: usize| {
+            let mut knots = vec![EFloat64::zero(); degree + 1];
+            knots.extend(vec![EFloat64::one(); count + degree + 1 - (degree + 1)]);
+            knots
+        };
+        NurbsSurface::try_new(
+            u_degree,
+            v_degree,
+            heights
+                .iter()
+                .enumerate()
+                .map(|(i, row)| {
+                    row.iter()
+                        .enumerate()
+                        .map(|(j, z)| {
+                            Point::from_f64(
+                                2.0 * i as f64 / (n - 1) as f64 - 1.0,
+                                2.0 * j as f64 / (m - 1) as f64 - 1.0,
+                                *z,
+                            )
+                        })
+                        .collect()
+                })
+                .collect(),
+            vec![vec![EFloat64::one(); m]; n],
+            knots(u_degree, n),
+            knots(v_degree, m),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_nurbs_plane_intersection_open() {
+        // The saddle z = x * y cut at z = 1/4 gives two branches of a hyperbola, ending on the boundary of the patch
+        let saddle = patch(1, 1, vec![vec![1.0, -1.0], vec![-1.0, 1.0]]);
+        let plane = Plane::new(
+            Point::from_f64(0.0, 0.0, 0.25),
+            Point::unit_x(),
+            Point::unit_y(),
+        );
+        match nurbs_surface_intersection(&saddle, &Surface::Plane(plane.clone())) {
+            NurbsSurfaceIntersection::Curves(curves) => {
+                assert_eq!(curves.len(), 2);
+                for curve in curves {
+                    let start = curve.interpolate(None, None, 0.0).unwrap();
+                    let end = curve.interpolate(None, None, 1.0).unwrap();
+                    for p in [start, end] {
+                        assert!(saddle.on_surface(p));
+                        assert!((plane.project(p) - p).norm() < 1e-8);
+                        assert!((p.x.abs().max(p.y.abs()).to_f64() - 1.0).abs() < 1e-6);
+                    }
+                }
+            }
+            _ => panic!("Intersection should be two curves"),
+        }
+    }
+
+    #[test]
+    fn test_nurbs_plane_intersection_closed() {
+        // A biquadratic bump of height 1/2 cut at half its height gives a closed loop
+        let bump = patch(
+            2,
+            2,
+            vec![
+                vec![0.0, 0.0, 0.0],
+                vec![0.0, 2.0, 0.0],
+                vec![0.0, 0.0, 0.0],
+            ],
+        );
+        let plane = Surface::Plane(Plane::new(
+            Point::from_f64(0.0, 0.0, 0.25),
+            Point::unit_x(),
+            Point::unit_y(),
+        ));
+        match nurbs_surface_intersection(&bump, &plane) {
+            NurbsSurfaceIntersection::Curves(curves) => {
+                assert_eq!(curves.len(), 1);
+                match &curves[0] {
+                    Curve::SampledCurve(curve) => {
+                        for p in curve.points.iter() {
+                            assert!(bump.on_surface(*p));
+                            assert!((plane.project(*p) - *p).norm() < 1e-8);
+                        }
+                    }
+                    _ => panic!("Intersection should be a closed curve"),
+                }
+            }
+            _ => panic!("Intersection should be a curve"),
+        }
+
+        let plane = Surface::Plane(Plane::new(
+            Point::from_f64(0.0, 0.0, 1.0),
+            Point::unit_x(),
+            Point::unit_y(),
+        ));
+        match nurbs_surface_intersection(&bump, &plane) {
+            NurbsSurfaceIntersection::None => {}
+            _ => panic!("Intersection should be empty"),
+        }
+
+        match nurbs_surface_intersection(&bump, &Surface::Nurbs(bump.clone())) {
+            NurbsSurfaceIntersection::Surface(_) => {}
+            _ => panic!("Intersection should be the surface"),
+        }
+    }
+}
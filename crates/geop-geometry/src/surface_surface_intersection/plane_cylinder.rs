@@ -0,0 +1,133 @@
+use crate::{
+    curves::{circle::Circle, ellipse::Ellipse, line::Line},
+    surfaces::{cylinder::Cylinder, plane::Plane, SurfaceLike},
+};
+
+pub enum PlaneCylinderIntersection {
+    Circle(Circle),
+    Ellipse(Ellipse),
+    TwoLines(Line, Line),
+    Line(Line),
+    None,
+}
+
+pub fn plane_cylinder_intersection(a: &Plane, b: &Cylinder) -> PlaneCylinderIntersection {
+    let n = a.normal(a.basis).normalize().unwrap();
+    let axis = b.extend_dir;
+    let r = b.radius.norm();
+
+    // The plane is parallel to the axis, so it cuts the cylinder along its rulings
+    if n.is_perpendicular(axis) {
+        let h = (b.basis - a.basis).dot(n);
+        if h > r || h < -r {
+            return PlaneCylinderIntersection::None;
+        }
+        let foot = b.basis - n * h;
+        let w_sq = r * r - h * h;
+        if w_sq == 0.0 {
+            return PlaneCylinderIntersection::Line(Line::new(foot, axis).unwrap());
+        }
+        let w = w_sq.sqrt().unwrap();
+        let side = axis.cross(n).normalize().unwrap();
+        return PlaneCylinderIntersection::TwoLines(
+            Line::new(foot + side * w, axis).unwrap(),
+            Line::new(foot - side * w, axis).unwrap(),
+        );
+    }
+
+    // Otherwise the axis pierces the plane in the center of the resulting conic
+    let cos_angle = axis.dot(n);
+    let t = ((a.basis - b.basis).dot(n) / cos_angle).unwrap();
+    let center = b.basis + axis * t;
+
+    if n.is_parallel(axis) {
+        return PlaneCylinderIntersection::Circle(Circle::try_new(center, n, r).unwrap());
+    }
+
+    // The minor axis is perpendicular to the cylinder axis, the major axis is stretched by 1 / cos
+    let minor_dir = axis.cross(n).normalize().unwrap();
+    let major_dir = n.cross(minor_dir).normalize().unwrap();
+    let major_length = (r / cos_angle.abs()).unwrap();
+    PlaneCylinderIntersection::Ellipse(
+        Ellipse::try_new(center, n, major_dir * major_length, minor_dir * r).unwrap(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_algebra::efloat::EFloat64;
+
+    use super::*;
+    use crate::{curves::CurveLike, point::Point};
+
+    fn unit_cylinder() -> Cylinder {
+        Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::one(), true)
+    }
+
+    #[test]
+    fn test_plane_cylinder_intersection_circle() {
+        let plane = Plane::new(Point::unit_z(), Point::unit_x(), Point::unit_y());
+        match plane_cylinder_intersection(&plane, &unit_cylinder()) {
+            PlaneCylinderIntersection::Circle(circle) => {
+                assert_eq!(circle.basis, Point::unit_z());
+                assert_eq!(circle.radius.norm(), 1.0);
+            }
+            _ => panic!("Intersection should be a circle"),
+        }
+    }
+
+    #[test]
+    fn test_plane_cylinder_intersection_ellipse() {
+        let plane = Plane::new(
+            Point::zero(),
+            Point::from_f64(1.0, 0.0, 1.0),
+            Point::unit_y(),
+        );
+        match plane_cylinder_intersection(&plane, &unit_cylinder()) {
+            PlaneCylinderIntersection::Ellipse(ellipse) => {
+                assert_eq!(ellipse.basis, Point::zero());
+                assert_eq!(ellipse.major_radius.norm(), 2.0_f64.sqrt());
+                assert_eq!(ellipse.minor_radius.norm(), 1.0);
+                assert!(ellipse.on_curve(Point::from_f64(1.0, 0.0, 1.0)));
+                assert!(ellipse.on_curve(Point::from_f64(0.0, 1.0, 0.0)));
+            }
+            _ => panic!("Intersection should be an ellipse"),
+        }
+    }
+
+    #[test]
+    fn test_plane_cylinder_intersection_lines() {
+        // Plane through the axis
+        let plane = Plane::new(Point::zero(), Point::unit_x(), Point::unit_z());
+        match plane_cylinder_intersection(&plane, &unit_cylinder()) {
+            PlaneCylinderIntersection::TwoLines(l1, l2) => {
+                assert!(l1.direction.is_parallel(Point::unit_z()));
+                assert!(l2.direction.is_parallel(Point::unit_z()));
+                assert!(l1.on_curve(Point::unit_x()) || l2.on_curve(Point::unit_x()));
+                assert!(l1.on_curve(-Point::unit_x()) || l2.on_curve(-Point::unit_x()));
+            }
+            _ => panic!("Intersection should be two lines"),
+        }
+
+        // Tangent plane
+        let plane = Plane::new(Point::unit_y(), Point::unit_x(), Point::unit_z());
+        match plane_cylinder_intersection(&plane, &unit_cylinder()) {
+            PlaneCylinderIntersection::Line(line) => {
+                assert!(line.on_curve(Point::unit_y()));
+                assert!(line.direction.is_parallel(Point::unit_z()));
+            }
+            _ => panic!("Intersection should be a line"),
+        }
+
+        // Plane parallel to the axis but outside of the cylinder
+        let plane = Plane::new(
+            Point::from_f64(0.0, 2.0, 0.0),
+            Point::unit_x(),
+            Point::unit_z(),
+        );
+        match plane_cylinder_intersection(&plane, &unit_cylinder()) {
+            PlaneCylinderIntersection::None => (),
+            _ => panic!("Intersection should be no intersection"),
+        }
+    }
+}
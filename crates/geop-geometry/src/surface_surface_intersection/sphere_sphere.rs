@@ -1,4 +1,6 @@
-use crate::geometry::{curves::circle::Circle, point::Point, surfaces::sphere::Sphere};
+use geop_algebra::efloat::EFloat64;
+
+use crate::{curves::circle::Circle, point::Point, surfaces::sphere::Sphere};
 
 pub enum SphereSphereIntersection {
     Sphere(Sphere),
@@ -8,27 +10,93 @@ pub enum SphereSphereIntersection {
 }
 
 pub fn sphere_sphere_intersection(a: &Sphere, b: &Sphere) -> SphereSphereIntersection {
-    let r_a: f64 = a.radius;
-    let r_b: f64 = b.radius;
-    let a: Point = a.basis;
-    let b: Point = b.basis;
+    let r_a = a.radius;
+    let r_b = b.radius;
+    let diff = b.basis - a.basis;
+    let d = diff.norm();
 
-    let d = (a - b).norm();
-    if d > r_a + r_b {
+    // Concentric spheres either coincide or are nested
+    if d == 0.0 {
+        if r_a == r_b {
+            return SphereSphereIntersection::Sphere(a.clone());
+        }
         return SphereSphereIntersection::None;
-    } else if d < (r_a - r_b).abs() {
+    }
+
+    if d > r_a + r_b || d < (r_a - r_b).abs() {
         return SphereSphereIntersection::None;
-    } else if d <= 0.0 && (r_a - r_b).abs() <= 0.0 {
-        return SphereSphereIntersection::Sphere(Sphere::new(a, r_a.min(r_b)));
-    } else {
-        let x = (r_a.powi(2) - r_b.powi(2) + d.powi(2)) / (2.0 * d);
-        let y = (r_a.powi(2) - x.powi(2)).sqrt();
-        let z = (b - a) / d;
-        let p = a + z * x;
-        let n = z.cross(Point::from_f64(0.0, 0.0, 1.0)).cross(z);
-        if y <= 0.0 {
-            return SphereSphereIntersection::Point(p);
+    }
+
+    // Distance of the intersection plane from the center of a along the center line
+    let x = ((r_a * r_a - r_b * r_b + d * d) / (EFloat64::two() * d)).unwrap();
+    let z = (diff / d).unwrap();
+    let p = a.basis + z * x;
+
+    let y_sq = r_a * r_a - x * x;
+    if y_sq == 0.0 {
+        return SphereSphereIntersection::Point(p);
+    }
+    let y = y_sq.sqrt().unwrap();
+    SphereSphereIntersection::Circle(Circle::try_new(p, z, y).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sphere_sphere_intersection() {
+        let sphere = Sphere::new(Point::zero(), EFloat64::one(), true);
+
+        // Two unit spheres with centers 1 apart intersect in a circle of radius sqrt(3) / 2
+        let other = Sphere::new(Point::unit_z(), EFloat64::one(), true);
+        match sphere_sphere_intersection(&sphere, &other) {
+            SphereSphereIntersection::Circle(circle) => {
+                assert_eq!(circle.basis, Point::from_f64(0.0, 0.0, 0.5));
+                assert!(circle.normal.is_parallel(Point::unit_z()));
+                assert_eq!(circle.radius.norm(), 0.75_f64.sqrt());
+            }
+            _ => panic!("Intersection should be a circle"),
+        }
+
+        // Spheres touching from the outside
+        let other = Sphere::new(Point::from_f64(2.0, 0.0, 0.0), EFloat64::one(), true);
+        match sphere_sphere_intersection(&sphere, &other) {
+            SphereSphereIntersection::Point(point) => {
+                assert_eq!(point, Point::unit_x());
+            }
+            _ => panic!("Intersection should be a single point"),
+        }
+
+        // Sphere touching from the inside
+        let other = Sphere::new(Point::from_f64(0.0, 0.5, 0.0), EFloat64::from(0.5), true);
+        match sphere_sphere_intersection(&sphere, &other) {
+            SphereSphereIntersection::Point(point) => {
+                assert_eq!(point, Point::unit_y());
+            }
+            _ => panic!("Intersection should be a single point"),
+        }
+
+        // Same sphere
+        match sphere_sphere_intersection(&sphere, &sphere) {
+            SphereSphereIntersection::Sphere(s) => {
+                assert_eq!(s, sphere);
+            }
+            _ => panic!("Intersection should be a sphere"),
+        }
+
+        // Too far away
+        let other = Sphere::new(Point::from_f64(3.0, 0.0, 0.0), EFloat64::one(), true);
+        match sphere_sphere_intersection(&sphere, &other) {
+            SphereSphereIntersection::None => (),
+            _ => panic!("Intersection should be no intersection"),
+        }
+
+        // Nested spheres
+        let other = Sphere::new(Point::from_f64(0.1, 0.0, 0.0), EFloat64::from(0.5), true);
+        match sphere_sphere_intersection(&sphere, &other) {
+            SphereSphereIntersection::None => (),
+            _ => panic!("Intersection should be no intersection"),
         }
-        return SphereSphereIntersection::Circle(Circle::new(p, n, y));
     }
 }
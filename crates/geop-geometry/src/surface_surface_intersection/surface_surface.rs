@@ -1,6 +1,27 @@
-use crate::{curves::curve::Curve, point::Point, surfaces::surface::Surface};
+use geop_algebra::efloat::EFloat64;
 
-use super::plane_plane::{plane_plane_intersection, PlanePlaneIntersection};
+use crate::{
+    color::Category10Color,
+    curves::{circle::Circle, curve::Curve},
+    geometry_error::{GeometryError, GeometryResult, WithContext},
+    geometry_scene::GeometryScene,
+    point::Point,
+    surfaces::{
+        cone::Cone, cylinder::Cylinder, nurbs::NurbsSurface, plane::Plane, sphere::Sphere,
+        surface::Surface, torus::Torus, SurfaceLike,
+    },
+    HORIZON_DIST,
+};
+
+use super::{
+    cylinder_cylinder::{cylinder_cylinder_intersection, CylinderCylinderIntersection},
+    cylinder_sphere::{cylinder_sphere_intersection, CylinderSphereIntersection},
+    nurbs_surface::{nurbs_surface_intersection, NurbsSurfaceIntersection},
+    plane_cylinder::{plane_cylinder_intersection, PlaneCylinderIntersection},
+    plane_plane::{plane_plane_intersection, PlanePlaneIntersection},
+    plane_sphere::{plane_sphere_intersection, PlaneSphereIntersection},
+    sphere_sphere::{sphere_sphere_intersection, SphereSphereIntersection},
+};
 
 pub enum FaceSurfaceIntersection {
     None,
@@ -8,11 +29,215 @@ pub enum FaceSurfaceIntersection {
     Surface(Surface),
 }
 
+fn plane_sphere(plane: &Plane, sphere: &Sphere) -> FaceSurfaceIntersection {
+    match plane_sphere_intersection(sphere, plane) {
+        PlaneSphereIntersection::None => FaceSurfaceIntersection::None,
+        PlaneSphereIntersection::Point(p) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![], vec![p])
+        }
+        PlaneSphereIntersection::Circle(c) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Circle(c)], vec![])
+        }
+    }
+}
+
+fn plane_cylinder(plane: &Plane, cylinder: &Cylinder) -> FaceSurfaceIntersection {
+    match plane_cylinder_intersection(plane, cylinder) {
+        PlaneCylinderIntersection::None => FaceSurfaceIntersection::None,
+        PlaneCylinderIntersection::Line(l) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l)], vec![])
+        }
+        PlaneCylinderIntersection::TwoLines(l1, l2) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l1), Curve::Line(l2)], vec![])
+        }
+        PlaneCylinderIntersection::Circle(c) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Circle(c)], vec![])
+        }
+        PlaneCylinderIntersection::Ellipse(e) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Ellipse(e)], vec![])
+        }
+    }
+}
+
+fn cylinder_sphere(
+    cylinder: &Cylinder,
+    sphere: &Sphere,
+) -> GeometryResult<FaceSurfaceIntersection> {
+    Ok(match cylinder_sphere_intersection(cylinder, sphere)? {
+        CylinderSphereIntersection::None => FaceSurfaceIntersection::None,
+        CylinderSphereIntersection::Circle(c) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Circle(c)], vec![])
+        }
+        CylinderSphereIntersection::TwoCircles(c1, c2) => FaceSurfaceIntersection::CurvesAndPoints(
+            vec![Curve::Circle(c1), Curve::Circle(c2)],
+            vec![],
+        ),
+        CylinderSphereIntersection::Curves(curves) => FaceSurfaceIntersection::CurvesAndPoints(
+            curves.into_iter().map(Curve::SampledCurve).collect(),
+            vec![],
+        ),
+    })
+}
+
+fn nurbs_surface(nurbs: &NurbsSurface, other: &Surface) -> FaceSurfaceIntersection {
+    match nurbs_surface_intersection(nurbs, other) {
+        NurbsSurfaceIntersection::None => FaceSurfaceIntersection::None,
+        NurbsSurfaceIntersection::Curves(curves) => {
+            FaceSurfaceIntersection::CurvesAndPoints(curves, vec![])
+        }
+        NurbsSurfaceIntersection::Surface(s) => FaceSurfaceIntersection::Surface(Surface::Nurbs(s)),
+    }
+}
+
+// A torus is bounded, so it is intersected as its exact nurbs representation. Only a plane across the axis,
+// which cuts the torus in circles, is intersected exactly.
+fn torus_surface(torus: &Torus, other: &Surface) -> FaceSurfaceIntersection {
+    match other {
+        Surface::Torus(other) if torus == other => {
+            FaceSurfaceIntersection::Surface(Surface::Torus(torus.clone()))
+        }
+        Surface::Plane(plane) if plane.normal(plane.basis).is_parallel(torus.axis) => {
+            let height = (plane.basis - torus.basis).dot(torus.axis);
+            let center = torus.basis + torus.axis * height;
+            let circle = |radius: EFloat64| {
+                Curve::Circle(
+                    Circle::try_new(center, torus.axis, radius).expect("Radius is positive"),
+                )
+            };
+            let offset = torus.minor_radius * torus.minor_radius - height * height;
+            match offset.sqrt() {
+                Some(offset) if offset > 0.0 => FaceSurfaceIntersection::CurvesAndPoints(
+                    vec![
+                        circle(torus.major_radius - offset),
+                        circle(torus.major_radius + offset),
+                    ],
+                    vec![],
+                ),
+                Some(_) => FaceSurfaceIntersection::CurvesAndPoints(
+                    vec![circle(torus.major_radius)],
+                    vec![],
+                ),
+                None => FaceSurfaceIntersection::None,
+            }
+        }
+        _ => nurbs_surface(&torus.to_nurbs(), other),
+    }
+}
+
+// The range of slant distances from the apex in which the cone can meet the other surface, or None if it cannot.
+// Where the intersection is not bounded, e.g. for a plane that cuts the cone in a hyperbola, the cone is cut off at the horizon.
+fn cone_slant_range(cone: &Cone, other: &Surface) -> Option<(f64, f64)> {
+    let (sin, cos) = (
+        cone.half_angle.sin().to_f64(),
+        cone.half_angle.cos().to_f64(),
+    );
+    // Points within a ball lie at heights along the axis around the height of its center
+    let in_ball = |center: Point, radius: f64| {
+        let height = (center - cone.apex).dot(cone.axis).to_f64();
+        Some(((height - radius) / cos, (height + radius) / cos))
+    };
+    let range = match other {
+        Surface::Plane(plane) => {
+            // The generators meet the plane at the slant distance d / (n * g), where n * g varies around the axis
+            let normal = plane.normal(plane.basis).normalize().ok()?;
+            let d = (plane.basis - cone.apex).dot(normal).to_f64();
+            let along = normal.dot(cone.axis).to_f64();
+            let across = (normal - cone.axis * normal.dot(cone.axis)).norm().to_f64();
+            let (min, max) = (cos * along - sin * across, cos * along + sin * across);
+            match (d > 0.0, d < 0.0) {
+                (true, _) if min > 0.0 => Some((d / max, d / min)),
+                (_, true) if max < 0.0 => Some((d / min, d / max)),
+                (true, _) if max <= 0.0 => None,
+                (_, true) if min >= 0.0 => None,
+                _ => Some((0.0, HORIZON_DIST)),
+            }
+        }
+        Surface::Sphere(sphere) => in_ball(sphere.basis, sphere.radius.to_f64()),
+        Surface::Cylinder(cylinder) => {
+            // A coaxial cylinder is met where the radius of the cone is within the offset of the axes of its radius
+            let radius = cylinder.radius.norm().to_f64();
+            match cylinder.extend_dir.is_parallel(cone.axis) {
+                true => {
+                    let d = cylinder.basis - cone.apex;
+                    let offset = (d - cone.axis * d.dot(cone.axis)).norm().to_f64();
+                    Some(((radius - offset) / sin, (radius + offset) / sin))
+                }
+                false => Some((0.0, HORIZON_DIST)),
+            }
+        }
+        Surface::Torus(torus) => in_ball(
+            torus.basis,
+            (torus.major_radius + torus.minor_radius).to_f64(),
+        ),
+        Surface::Cone(_) | Surface::Nurbs(_) => Some((0.0, HORIZON_DIST)),
+    };
+    let (start, end) = range?;
+    if end <= 0.0 {
+        return None;
+    }
+    // The range is padded, such that closed intersection curves do not touch the boundary of the cone
+    let padding = 0.25 * (end - start) + 0.05 * end;
+    Some(((start - padding).max(0.0), end + padding))
+}
+
+// A cone is unbounded, so it is intersected as the exact nurbs representation of the part that can meet the other surface.
+// Only a plane across the axis cuts the cone exactly in a circle.
+fn cone_surface(cone: &Cone, other: &Surface) -> FaceSurfaceIntersection {
+    match other {
+        Surface::Cone(other) if cone == other => {
+            return FaceSurfaceIntersection::Surface(Surface::Cone(cone.clone()));
+        }
+        Surface::Plane(plane) if plane.normal(plane.basis).is_parallel(cone.axis) => {
+            let height = (plane.basis - cone.apex).dot(cone.axis);
+            let tan = (cone.half_angle.sin() / cone.half_angle.cos())
+                .expect("Half angle is below pi / 2");
+            if height > 0.0 {
+                let circle =
+                    Circle::try_new(cone.apex + cone.axis * height, cone.axis, height * tan)
+                        .expect("Radius is positive");
+                return FaceSurfaceIntersection::CurvesAndPoints(
+                    vec![Curve::Circle(circle)],
+                    vec![],
+                );
+            } else if height == 0.0 {
+                return FaceSurfaceIntersection::CurvesAndPoints(vec![], vec![cone.apex]);
+            }
+            return FaceSurfaceIntersection::None;
+        }
+        _ => {}
+    }
+    let nurbs = cone_slant_range(cone, other).and_then(|(start, end)| {
+        cone.to_nurbs(EFloat64::from(start), EFloat64::from(end))
+            .ok()
+    });
+    match nurbs {
+        Some(nurbs) => nurbs_surface(&nurbs, other),
+        None => FaceSurfaceIntersection::None,
+    }
+}
+
+// Intersection curves that are traced numerically fail with an error if no valid error bound could be proven.
 pub fn surface_surface_intersection(
     face_self: &Surface,
     face_other: &Surface,
-) -> FaceSurfaceIntersection {
-    match face_self {
+) -> GeometryResult<FaceSurfaceIntersection> {
+    let context = |err: GeometryError| {
+        err.with_context_scene(
+            "Intersect surfaces".to_string(),
+            GeometryScene::with_surfaces(vec![
+                (face_self.clone(), Category10Color::Red),
+                (face_other.clone(), Category10Color::Blue),
+            ]),
+        )
+    };
+    intersect_surfaces(face_self, face_other).with_context(&context)
+}
+
+fn intersect_surfaces(
+    face_self: &Surface,
+    face_other: &Surface,
+) -> GeometryResult<FaceSurfaceIntersection> {
+    Ok(match face_self {
         Surface::Plane(plane_self) => match face_other {
             Surface::Plane(plane_other) => {
                 match plane_plane_intersection(plane_self, plane_other) {
@@ -25,34 +250,182 @@ pub fn surface_surface_intersection(
                     }
                 }
             }
-            Surface::Sphere(_sphere_other) => {
-                todo!("Plane-Sphere intersection")
-            }
-            Surface::Cylinder(_cylinder_other) => {
-                todo!("Plane-Cylinder intersection")
-            }
+            Surface::Sphere(sphere_other) => plane_sphere(plane_self, sphere_other),
+            Surface::Cylinder(cylinder_other) => plane_cylinder(plane_self, cylinder_other),
+            Surface::Cone(cone_other) => cone_surface(cone_other, face_self),
+            Surface::Torus(torus_other) => torus_surface(torus_other, face_self),
+            Surface::Nurbs(nurbs_other) => nurbs_surface(nurbs_other, face_self),
         },
-        Surface::Sphere(_sphere_self) => match face_other {
-            Surface::Plane(_plane_other) => {
-                todo!("Sphere-Plane intersection")
-            }
-            Surface::Sphere(_sphere_other) => {
-                todo!("Sphere-Sphere intersection")
-            }
-            Surface::Cylinder(_cylinder_other) => {
-                todo!("Sphere-Cylinder intersection")
+        Surface::Sphere(sphere_self) => match face_other {
+            Surface::Plane(plane_other) => plane_sphere(plane_other, sphere_self),
+            Surface::Sphere(sphere_other) => {
+                match sphere_sphere_intersection(sphere_self, sphere_other) {
+                    SphereSphereIntersection::None => FaceSurfaceIntersection::None,
+                    SphereSphereIntersection::Point(p) => {
+                        FaceSurfaceIntersection::CurvesAndPoints(vec![], vec![p])
+                    }
+                    SphereSphereIntersection::Circle(c) => {
+                        FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Circle(c)], vec![])
+                    }
+                    SphereSphereIntersection::Sphere(s) => {
+                        FaceSurfaceIntersection::Surface(Surface::Sphere(s))
+                    }
+                }
             }
+            Surface::Cylinder(cylinder_other) => cylinder_sphere(cylinder_other, sphere_self)?,
+            Surface::Cone(cone_other) => cone_surface(cone_other, face_self),
+            Surface::Torus(torus_other) => torus_surface(torus_other, face_self),
+            Surface::Nurbs(nurbs_other) => nurbs_surface(nurbs_other, face_self),
         },
-        Surface::Cylinder(_cylinder_self) => match face_other {
-            Surface::Plane(_plane_other) => {
-                todo!("Cylinder-Plane intersection")
-            }
-            Surface::Sphere(_sphere_other) => {
-                todo!("Cylinder-Sphere intersection")
-            }
-            Surface::Cylinder(_cylinder_other) => {
-                todo!("Cylinder-Cylinder intersection")
+        Surface::Cylinder(cylinder_self) => match face_other {
+            Surface::Plane(plane_other) => plane_cylinder(plane_other, cylinder_self),
+            Surface::Sphere(sphere_other) => cylinder_sphere(cylinder_self, sphere_other)?,
+            Surface::Cylinder(cylinder_other) => {
+                match cylinder_cylinder_intersection(cylinder_self, cylinder_other)? {
+                    CylinderCylinderIntersection::None => FaceSurfaceIntersection::None,
+                    CylinderCylinderIntersection::Line(l) => {
+                        FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l)], vec![])
+                    }
+                    CylinderCylinderIntersection::TwoLines(l1, l2) => {
+                        FaceSurfaceIntersection::CurvesAndPoints(
+                            vec![Curve::Line(l1), Curve::Line(l2)],
+                            vec![],
+                        )
+                    }
+                    CylinderCylinderIntersection::Curves(curves) => {
+                        FaceSurfaceIntersection::CurvesAndPoints(
+                            curves.into_iter().map(Curve::SampledCurve).collect(),
+                            vec![],
+                        )
+                    }
+                    CylinderCylinderIntersection::Cylinder(c) => {
+                        FaceSurfaceIntersection::Surface(Surface::Cylinder(c))
+                    }
+                }
             }
+            Surface::Cone(cone_other) => cone_surface(cone_other, face_self),
+            Surface::Torus(torus_other) => torus_surface(torus_other, face_self),
+            Surface::Nurbs(nurbs_other) => nurbs_surface(nurbs_other, face_self),
+        },
+        Surface::Cone(cone_self) => match face_other {
+            Surface::Torus(torus_other) => torus_surface(torus_other, face_self),
+            Surface::Nurbs(nurbs_other) => nurbs_surface(nurbs_other, face_self),
+            _ => cone_surface(cone_self, face_other),
         },
+        Surface::Torus(torus_self) => match face_other {
+            Surface::Nurbs(nurbs_other) => nurbs_surface(nurbs_other, face_self),
+            _ => torus_surface(torus_self, face_other),
+        },
+        Surface::Nurbs(nurbs_self) => nurbs_surface(nurbs_self, face_other),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::CurveLike;
+
+    fn curves(intersection: FaceSurfaceIntersection) -> Vec<Curve> {
+        match intersection {
+            FaceSurfaceIntersection::CurvesAndPoints(curves, points) if points.is_empty() => curves,
+            _ => panic!("Intersection should be curves"),
+        }
+    }
+
+    // Checks that the curve runs on both surfaces by sampling it. Sampled curves only pass through the exact curve at their samples.
+    fn assert_on_surfaces(curve: &Curve, a: &Surface, b: &Surface) {
+        for i in 0..16 {
+            let p = curve.interpolate(None, None, i as f64 / 16.0).unwrap();
+            let p = Point::from_f64(p.x.to_f64(), p.y.to_f64(), p.z.to_f64());
+            assert!((a.project(p) - p).norm() < 1e-3);
+            assert!((b.project(p) - p).norm() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_cone_surface_intersection() {
+        let cone = Surface::Cone(
+            Cone::try_new(
+                Point::zero(),
+                Point::unit_z(),
+                EFloat64::from(std::f64::consts::FRAC_PI_4),
+                true,
+            )
+            .unwrap(),
+        );
+
+        // A plane across the axis cuts the cone in a circle
+        let plane = Surface::Plane(Plane::new(
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::unit_x(),
+            Point::unit_y(),
+        ));
+        let result = curves(surface_surface_intersection(&cone, &plane).unwrap());
+        assert_eq!(result.len(), 1);
+        assert_on_surfaces(&result[0], &cone, &plane);
+
+        // A coaxial cylinder meets the cone in a circle, a plane below the apex not at all
+        let cylinder = Surface::Cylinder(Cylinder::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::one(),
+            true,
+        ));
+        let result = curves(surface_surface_intersection(&cylinder, &cone).unwrap());
+        assert_eq!(result.len(), 1);
+        assert_on_surfaces(&result[0], &cone, &cylinder);
+        let plane = Surface::Plane(Plane::new(
+            Point::from_f64(0.0, 0.0, -1.0),
+            Point::unit_x(),
+            Point::unit_y(),
+        ));
+        assert!(matches!(
+            surface_surface_intersection(&plane, &cone).unwrap(),
+            FaceSurfaceIntersection::None
+        ));
+    }
+
+    #[test]
+    fn test_torus_surface_intersection() {
+        let torus = Surface::Torus(
+            Torus::try_new(
+                Point::zero(),
+                Point::unit_z(),
+                EFloat64::from(2.0),
+                EFloat64::from(0.5),
+                true,
+            )
+            .unwrap(),
+        );
+
+        // A plane through the axis cuts the tube twice, a plane across the axis meets the inner and outer side
+        for (u, v) in [
+            (Point::unit_y(), Point::unit_z()),
+            (Point::unit_x(), Point::unit_y()),
+        ] {
+            let plane = Surface::Plane(Plane::new(Point::from_f64(0.0, 0.0, 0.25), u, v));
+            let result = curves(surface_surface_intersection(&torus, &plane).unwrap());
+            assert_eq!(result.len(), 2);
+            for curve in result.iter() {
+                assert_on_surfaces(curve, &torus, &plane);
+            }
+        }
+
+        let sphere = Surface::Sphere(Sphere::new(
+            Point::from_f64(2.0, 0.0, 0.0),
+            EFloat64::one(),
+            true,
+        ));
+        // The sphere around a point of the center circle cuts the tube on either side
+        let result = curves(surface_surface_intersection(&sphere, &torus).unwrap());
+        assert_eq!(result.len(), 2);
+        for curve in result.iter() {
+            assert_on_surfaces(curve, &torus, &sphere);
+        }
+
+        assert!(matches!(
+            surface_surface_intersection(&torus, &torus).unwrap(),
+            FaceSurfaceIntersection::Surface(_)
+        ));
     }
 }
@@ -0,0 +1,456 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    color::Category10Color,
+    curves::{curve::Curve, line::Line, nurb::NurbsCurve},
+    geometry_error::{GeometryError, GeometryResult, WithContext},
+    geometry_scene::GeometryScene,
+    point::Point,
+    transforms::Transform,
+    HORIZON_DIST,
+};
+
+use super::{
+    nurbs::{pin_ends, NurbsSurface},
+    surface::{Surface, TangentPoint},
+    SurfaceLike,
+};
+
+// Number of points of a geodesic, which are joined by pieces on the surface.
+const GEODESIC_POINTS: usize = 32;
+
+// A single nappe of a circular cone. The cone opens from the apex into the direction of the axis.
+// The cone is developable, so geodesics are straight lines when the cone is rolled out into the plane.
+// A point at slant distance s from the apex and angle theta around the axis is rolled out to polar coordinates (s, theta * sin(half_angle)).
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "ConeRaw"))]
+pub struct Cone {
+    pub apex: Point,
+    pub axis: Point,
+    pub half_angle: EFloat64,
+    pub normal_outwards: bool,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    ref_dir: Point,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    dir_cross: Point,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ConeRaw {
+    apex: Point,
+    axis: Point,
+    half_angle: EFloat64,
+    normal_outwards: bool,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ConeRaw> for Cone {
+    type Error = GeometryError;
+
+    fn try_from(raw: ConeRaw) -> GeometryResult<Cone> {
+        Cone::try_new(raw.apex, raw.axis, raw.half_angle, raw.normal_outwards)
+    }
+}
+
+impl Cone {
+    pub fn try_new(
+        apex: Point,
+        axis: Point,
+        half_angle: EFloat64,
+        normal_outwards: bool,
+    ) -> GeometryResult<Cone> {
+        let error_context = |err: GeometryError| {
+            err.with_context_scene(
+                format!(
+                    "Create a cone at {} with axis {} and half angle {}.",
+                    apex, axis, half_angle
+                ),
+                GeometryScene::with_points(vec![(apex, Category10Color::Orange)]),
+            )
+        };
+        let axis = axis.normalize().with_context(&error_context)?;
+        if half_angle <= 0.0 || half_angle >= std::f64::consts::FRAC_PI_2 {
+            return Err(GeometryError::new(
+                "Half angle must be between 0 and pi / 2".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        let ref_dir = match Point::unit_x().cross(axis).norm_sq().lower_bound
+            > Point::unit_y().cross(axis).norm_sq().lower_bound
+        {
+            true => Point::unit_x().cross(axis).normalize().unwrap(),
+            false => Point::unit_y().cross(axis).normalize().unwrap(),
+        };
+        Ok(Cone {
+            apex,
+            axis,
+            half_angle,
+            normal_outwards,
+            ref_dir,
+            dir_cross: axis.cross(ref_dir),
+        })
+    }
+
+    pub fn transform(&self, transform: Transform) -> Self {
+        let apex = transform * self.apex;
+        let axis = transform * (self.axis + self.apex) - apex;
+        Cone::try_new(apex, axis, self.half_angle, self.normal_outwards)
+            .expect("Transform of a cone is a cone")
+    }
+
+    pub fn neg(&self) -> Self {
+        Cone::try_new(self.apex, self.axis, self.half_angle, !self.normal_outwards)
+            .expect("Cone is valid")
+    }
+
+    // Height along the axis, distance to the axis and the unit direction from the axis to p.
+    // On the axis, the reference direction is used.
+    fn radial(&self, p: Point) -> (EFloat64, EFloat64, Point) {
+        let d = p - self.apex;
+        let height = d.dot(self.axis);
+        let radial = d - height * self.axis;
+        let distance = radial.norm();
+        match radial.normalize() {
+            Ok(direction) if distance > 0.0 => (height, distance, direction),
+            _ => (height, distance, self.ref_dir),
+        }
+    }
+
+    // Unit direction of the generator line in the given radial direction, pointing away from the apex.
+    fn generator(&self, radial_dir: Point) -> Point {
+        self.axis * self.half_angle.cos() + radial_dir * self.half_angle.sin()
+    }
+
+    // Signed angle around the axis from the radial direction a to the radial direction b, in [-pi, pi].
+    fn angle_around(&self, a: Point, b: Point) -> EFloat64 {
+        a.cross(b).dot(self.axis).atan2(a.dot(b))
+    }
+
+    // The vector from x to y in the rolled out plane, where y lies at the given angle around the axis from x.
+    fn rolled_out(&self, x: Point, y: Point, angle: EFloat64) -> TangentPoint {
+        let (s_x, s_y) = ((x - self.apex).norm(), (y - self.apex).norm());
+        let (_, _, radial_dir) = self.radial(x);
+        let angle = angle * self.half_angle.sin();
+        let along = self.generator(radial_dir);
+        let around = self.axis.cross(radial_dir);
+        along * (s_y * angle.cos() - s_x) + around * (s_y * angle.sin())
+    }
+
+    pub fn point_at(&self, angle: EFloat64, slant: EFloat64) -> Point {
+        let radial_dir = self.ref_dir * angle.cos() + self.dir_cross * angle.sin();
+        self.apex + self.generator(radial_dir) * slant
+    }
+
+    // Exact representation of the part of the cone between two slant distances from the apex as a surface of revolution,
+    // with the same orientation.
+    pub fn to_nurbs(
+        &self,
+        slant_start: EFloat64,
+        slant_end: EFloat64,
+    ) -> GeometryResult<NurbsSurface> {
+        let profile = NurbsCurve::try_new_polyline(vec![
+            self.point_at(EFloat64::zero(), slant_start),
+            self.point_at(EFloat64::zero(), slant_end),
+        ])?;
+        let nurbs = NurbsSurface::try_new_revolution(&profile, self.apex, self.axis)?;
+        let ((u0, u1), (v0, v1)) = nurbs.domain();
+        let uv = ((u0 + u1) / 3.0, (v0 + v1) / 2.0);
+        Ok(
+            match nurbs
+                .normal_at(uv)
+                .dot(self.normal(nurbs.point_at(uv.0, uv.1)))
+                < 0.0
+            {
+                true => nurbs.neg(),
+                false => nurbs,
+            },
+        )
+    }
+}
+
+impl SurfaceLike for Cone {
+    fn transform(&self, transform: Transform) -> Surface {
+        Surface::Cone(self.transform(transform))
+    }
+
+    fn neg(&self) -> Surface {
+        Surface::Cone(self.neg())
+    }
+
+    fn normal(&self, p: Point) -> Point {
+        let (_, _, radial_dir) = self.radial(p);
+        let normal = radial_dir * self.half_angle.cos() - self.axis * self.half_angle.sin();
+        if self.normal_outwards {
+            normal
+        } else {
+            -normal
+        }
+    }
+
+    fn on_surface(&self, p: Point) -> bool {
+        let (height, distance, _) = self.radial(p);
+        height >= 0.0 && (distance * self.half_angle.cos() - height * self.half_angle.sin()) == 0.0
+    }
+
+    fn metric(&self, _x: Point, u: TangentPoint, v: TangentPoint) -> EFloat64 {
+        u.dot(v)
+    }
+
+    fn distance(&self, x: Point, y: Point) -> EFloat64 {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        let s_x = (x - self.apex).norm();
+        let s_y = (y - self.apex).norm();
+        let angle = self.angle_around(self.radial(x).2, self.radial(y).2) * self.half_angle.sin();
+        let distance_sq = s_x * s_x + s_y * s_y - EFloat64::two() * s_x * s_y * angle.cos();
+        distance_sq.max(EFloat64::zero()).sqrt().unwrap()
+    }
+
+    fn exp(&self, x: Point, u: TangentPoint) -> Point {
+        assert!(self.on_surface(x));
+        let s_x = (x - self.apex).norm();
+        let (_, _, radial_dir) = self.radial(x);
+        let along = self.generator(radial_dir);
+        let around = self.axis.cross(radial_dir);
+        // The coordinates of the target in the rolled out plane, where x lies at (s_x, 0)
+        let a = s_x + u.dot(along);
+        let b = u.dot(around);
+        let s_y = (a * a + b * b).sqrt().unwrap();
+        if s_y <= 0.0 {
+            return self.apex;
+        }
+        let angle = (b.atan2(a) / self.half_angle.sin()).unwrap();
+        let radial_dir = radial_dir * angle.cos() + around * angle.sin();
+        self.apex + self.generator(radial_dir) * s_y
+    }
+
+    fn log(&self, x: Point, y: Point) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        if x == y {
+            return Some(Point::zero());
+        }
+        let s_x = (x - self.apex).norm();
+        // The cone is not smooth at the apex
+        if s_x <= 0.0 {
+            return None;
+        }
+        let angle = self.angle_around(self.radial(x).2, self.radial(y).2);
+        // On the opposite side of the cone, there are two geodesics
+        if (angle.abs() - EFloat64::pi()) == 0.0 && (y - self.apex).norm() > 0.0 {
+            return None;
+        }
+        Some(self.rolled_out(x, y, angle))
+    }
+
+    // Parallel transport is trivial in the rolled out plane. The local frame at y is rotated by the rolled out angle between x and y.
+    fn parallel_transport(
+        &self,
+        v: Option<TangentPoint>,
+        x: Point,
+        y: Point,
+    ) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        let v = v?;
+        let (_, _, radial_x) = self.radial(x);
+        let (_, _, radial_y) = self.radial(y);
+        let angle = self.angle_around(radial_x, radial_y) * self.half_angle.sin();
+        let a = v.dot(self.generator(radial_x));
+        let b = v.dot(self.axis.cross(radial_x));
+        Some(
+            self.generator(radial_y) * (a * angle.cos() + b * angle.sin())
+                + self.axis.cross(radial_y) * (b * angle.cos() - a * angle.sin()),
+        )
+    }
+
+    // Geodesics on the same generator are lines. All others are sampled from the straight line in the rolled out plane.
+    fn geodesic(&self, p: Point, q: Point) -> Curve {
+        assert!(self.on_surface(p));
+        assert!(self.on_surface(q));
+        assert!(p != q);
+        let (_, distance_p, radial_p) = self.radial(p);
+        let (_, distance_q, radial_q) = self.radial(q);
+        if distance_p <= 0.0 || distance_q <= 0.0 || self.angle_around(radial_p, radial_q) == 0.0 {
+            return Curve::Line(Line::new(p, (q - p).normalize().unwrap()).unwrap());
+        }
+        // Between opposite points, the geodesic that runs around the axis in positive direction is taken
+        let u = self
+            .log(p, q)
+            .unwrap_or_else(|| self.rolled_out(p, q, EFloat64::pi()));
+        let mut points = (0..GEODESIC_POINTS)
+            .map(|i| {
+                let t = EFloat64::from(i as f64 / (GEODESIC_POINTS - 1) as f64);
+                self.exp(p, u * t)
+            })
+            .collect::<Vec<Point>>();
+        points[0] = p;
+        points[GEODESIC_POINTS - 1] = q;
+
+        // The points are lifted onto the exact surface, as a polyline through them would leave the cone
+        let slants = points
+            .iter()
+            .map(|point| (*point - self.apex).norm().to_f64())
+            .collect::<Vec<f64>>();
+        let nurbs = self
+            .to_nurbs(
+                EFloat64::from(slants.iter().cloned().fold(f64::INFINITY, f64::min)),
+                EFloat64::from(slants.iter().cloned().fold(f64::NEG_INFINITY, f64::max)),
+            )
+            .expect("The geodesic does not run through the apex");
+        let curve = nurbs
+            .curve_on_surface(&nurbs.parameters_along(&points))
+            .expect("Points of a geodesic are distinct");
+        Curve::Nurbs(pin_ends(curve, p, q))
+    }
+
+    fn point_grid(&self, density: f64) -> Vec<Point> {
+        let n = (16.0 * density) as usize;
+        let m = (16.0 * density) as usize;
+        let mut points = Vec::with_capacity(n * m);
+        for i in 0..n {
+            for j in 1..=m {
+                let angle = EFloat64::from(2.0 * std::f64::consts::PI * i as f64 / n as f64);
+                let slant = EFloat64::from(j as f64 / m as f64 * HORIZON_DIST);
+                points.push(self.point_at(angle, slant));
+            }
+        }
+        points
+    }
+
+    fn project(&self, point: Point) -> Point {
+        let (height, distance, radial_dir) = self.radial(point);
+        let slant = height * self.half_angle.cos() + distance * self.half_angle.sin();
+        if slant <= 0.0 {
+            return self.apex;
+        }
+        self.apex + self.generator(radial_dir) * slant
+    }
+
+    fn unsigned_l2_squared_distance_gradient(&self, point: Point) -> Option<Point> {
+        Some(self.project(point) - point)
+    }
+
+    // The offset of a cone is a cone with the same axis and half angle, whose apex is moved along the axis.
+    fn offset(&self, distance: EFloat64) -> GeometryResult<Surface> {
+        let shift = (distance / self.half_angle.sin())?;
+        let apex = match self.normal_outwards {
+            true => self.apex - self.axis * shift,
+            false => self.apex + self.axis * shift,
+        };
+        Ok(Surface::Cone(Cone::try_new(
+            apex,
+            self.axis,
+            self.half_angle,
+            self.normal_outwards,
+        )?))
+    }
+}
+
+impl PartialEq for Cone {
+    fn eq(&self, other: &Cone) -> bool {
+        self.apex == other.apex
+            && self.axis == other.axis
+            && (self.half_angle - other.half_angle) == 0.0
+            && self.normal_outwards == other.normal_outwards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::CurveLike;
+
+    fn cone() -> Cone {
+        Cone::try_new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(std::f64::consts::FRAC_PI_4),
+            true,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_cone_on_surface_and_normal() {
+        let cone = cone();
+        let p = Point::from_f64(1.0, 0.0, 1.0);
+        assert!(cone.on_surface(p));
+        assert!(!cone.on_surface(Point::from_f64(1.0, 0.0, -1.0)));
+        assert!(!cone.on_surface(Point::from_f64(1.0, 0.0, 2.0)));
+        let normal = Point::from_f64(1.0, 0.0, -1.0).normalize().unwrap();
+        assert!((cone.normal(p) - normal).norm() < 1e-12);
+        assert_eq!(cone.project(Point::from_f64(2.0, 0.0, 0.0)), p);
+        assert_eq!(cone.project(Point::from_f64(0.0, 0.0, -1.0)), Point::zero());
+    }
+
+    #[test]
+    fn test_cone_exp_log() {
+        let cone = cone();
+        let x = Point::from_f64(1.0, 0.0, 1.0);
+        for y in [
+            Point::from_f64(0.0, 2.0, 2.0),
+            Point::from_f64(-0.5, -0.5, 0.5_f64.sqrt()),
+            Point::from_f64(3.0, 0.0, 3.0),
+        ] {
+            assert!(cone.on_surface(y));
+            let u = cone.log(x, y).unwrap();
+            assert!(u.dot(cone.normal(x)).abs() < 1e-12);
+            assert!((cone.exp(x, u) - y).norm() < 1e-12);
+            assert!((u.norm() - cone.distance(x, y)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_cone_geodesic() {
+        let cone = cone();
+        let x = Point::from_f64(1.0, 0.0, 1.0);
+        let y = Point::from_f64(-1.0, 1.0, 2.0_f64.sqrt());
+        let geodesic = cone.geodesic(x, y);
+        let mid = geodesic.get_midpoint(Some(x), Some(y)).unwrap();
+        assert!(cone.on_surface(cone.project(mid)));
+        assert!((cone.project(mid) - mid).norm() < 1e-2);
+        // The geodesic is shorter than the path along the circle through x and then along the generator
+        let length = cone.distance(x, y).to_f64();
+        assert!(length < 3.0 * std::f64::consts::PI / 4.0 + 2.0 - 2.0_f64.sqrt());
+
+        // Parallel transport keeps lengths and maps the geodesic direction to the geodesic direction
+        let u = cone.log(x, y).unwrap();
+        let transported = cone.parallel_transport(Some(u), x, y).unwrap();
+        assert!((transported.norm() - u.norm()).abs() < 1e-12);
+        assert!((transported + cone.log(y, x).unwrap()).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_cone_offset() {
+        let cone = cone();
+        let p = Point::from_f64(1.0, 0.0, 1.0);
+        let distance = EFloat64::from(2.0_f64.sqrt());
+        let offset = cone.offset(distance).unwrap();
+        assert!(offset.on_surface(p + cone.normal(p) * distance));
+        assert!(offset.on_surface(Point::from_f64(0.0, 2.0, 0.0)));
+        let inwards = cone.offset(-distance).unwrap();
+        assert!(inwards.on_surface(p - cone.normal(p) * distance));
+    }
+
+    #[test]
+    fn test_cone_to_nurbs() {
+        for cone in [cone(), cone().neg()] {
+            let nurbs = cone
+                .to_nurbs(EFloat64::from(1.0), EFloat64::from(3.0))
+                .unwrap();
+            for i in 0..6 {
+                for j in 0..=4 {
+                    let angle = EFloat64::from(1.1 * i as f64);
+                    let p = cone.point_at(angle, EFloat64::from(1.0 + 0.5 * j as f64));
+                    assert!(nurbs.on_surface(p));
+                    assert!((nurbs.normal(p) - cone.normal(p)).norm() < 1e-6);
+                }
+            }
+            // Only the part between the slant distances is represented
+            assert!(!nurbs.on_surface(cone.point_at(EFloat64::zero(), EFloat64::from(0.5))));
+        }
+    }
+}
@@ -2,6 +2,7 @@ use geop_algebra::efloat::EFloat64;
 
 use crate::{
     curves::{circle::Circle, curve::Curve, helix::Helix, line::Line, CurveLike},
+    geometry_error::{GeometryError, GeometryResult},
     point::Point,
     transforms::Transform,
     HORIZON_DIST,
@@ -13,14 +14,52 @@ use super::{
 };
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "CylinderRaw"))]
 pub struct Cylinder {
     pub basis: Point,
     pub extend_dir: Point,
     pub radius: Point,
     pub normal_outwards: bool,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
     dir_cross: Point,
 }
 
+// Deserialized cylinders are checked like in Cylinder::new, but keep their radius vector, as normalizing it again would widen it.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CylinderRaw {
+    basis: Point,
+    extend_dir: Point,
+    radius: Point,
+    normal_outwards: bool,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CylinderRaw> for Cylinder {
+    type Error = GeometryError;
+
+    fn try_from(raw: CylinderRaw) -> GeometryResult<Cylinder> {
+        if !raw.extend_dir.is_normalized() {
+            return Err(GeometryError::new(
+                "Direction must be normalized".to_string(),
+            ));
+        }
+        if raw.radius.norm() <= 0.0 || raw.extend_dir.dot(raw.radius) != 0.0 {
+            return Err(GeometryError::new(
+                "Radius must be positive and orthogonal to the direction".to_string(),
+            ));
+        }
+        Ok(Cylinder {
+            basis: raw.basis,
+            extend_dir: raw.extend_dir,
+            radius: raw.radius,
+            normal_outwards: raw.normal_outwards,
+            dir_cross: raw.extend_dir.normalize()?.cross(raw.radius),
+        })
+    }
+}
+
 impl Cylinder {
     pub fn new(
         basis: Point,
@@ -100,9 +139,8 @@ impl SurfaceLike for Cylinder {
     fn distance(&self, x: Point, y: Point) -> EFloat64 {
         assert!(self.on_surface(x), "{:?} has to be on {:?}", x, self);
         assert!(self.on_surface(y), "{:?} has to be on {:?}", y, self);
-        println!("x: {:?} y: {:?}", x, y);
-        let x = x - self.basis;
         let height_diff = (y - x).dot(self.extend_dir);
+        let x = x - self.basis;
         let x = x - x.dot(self.extend_dir) * self.extend_dir;
         let y = y - self.basis;
         let y = y - y.dot(self.extend_dir) * self.extend_dir;
@@ -168,6 +206,17 @@ impl SurfaceLike for Cylinder {
         assert!(self.on_surface(p));
         assert!(self.on_surface(q));
         assert!(p != q);
+        // Points on sampled curves carry wide intervals, which would make the angle between them and with it the pitch
+        // of the helix very uncertain. The helix is built through their centers instead, which the intervals contain.
+        // If the heights of the points cannot be told apart, the circle through both of them is used, as a helix with a
+        // tiny pitch would come close to every point of the cylinder.
+        let heights = (
+            (p - self.basis).dot(self.extend_dir),
+            (q - self.basis).dot(self.extend_dir),
+        );
+        let level = heights.0 == heights.1;
+        let p = self.project(center(p));
+        let q = self.project(center(q));
         let p_loc = p - self.basis;
         let q_loc = q - self.basis;
         let p_height = p_loc.dot(self.extend_dir);
@@ -182,11 +231,16 @@ impl SurfaceLike for Cylinder {
         let helix_radius = p_proj;
         let helix_pitch =
             (self.extend_dir * (q_height - p_height) * EFloat64::two_pi() / angle).unwrap();
-        if helix_pitch.norm() == 0.0 {
+        if level || helix_pitch.norm() == 0.0 {
+            // The circle has to run the short way from p to q
+            let normal = match p_proj.cross(q_proj).dot(self.extend_dir) > 0.0 {
+                true => self.extend_dir,
+                false => -self.extend_dir,
+            };
             return Curve::Circle(
                 Circle::try_new(
-                    self.basis + p_height * self.extend_dir,
-                    self.extend_dir.normalize().unwrap(),
+                    self.basis + heights.0.union(heights.1) * self.extend_dir,
+                    normal.normalize().unwrap(),
                     helix_radius.norm(),
                 )
                 .unwrap(),
@@ -240,6 +294,30 @@ impl SurfaceLike for Cylinder {
         let grad = -normal.unwrap() * dist;
         Some(grad)
     }
+
+    fn offset(&self, distance: EFloat64) -> GeometryResult<Surface> {
+        let radius = match self.normal_outwards {
+            true => self.radius.norm() + distance,
+            false => self.radius.norm() - distance,
+        };
+        if radius <= 0.0 {
+            return Err(GeometryError::new(format!(
+                "Offset of cylinder with radius {} by {} has no positive radius",
+                self.radius.norm(),
+                distance
+            )));
+        }
+        Ok(Surface::Cylinder(Cylinder::new(
+            self.basis,
+            self.extend_dir,
+            radius,
+            self.normal_outwards,
+        )))
+    }
+}
+
+fn center(p: Point) -> Point {
+    Point::from_f64(p.x.to_f64(), p.y.to_f64(), p.z.to_f64())
 }
 
 impl PartialEq for Cylinder {
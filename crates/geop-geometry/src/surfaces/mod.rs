@@ -1,12 +1,17 @@
 use geop_algebra::efloat::EFloat64;
 use surface::{Surface, TangentPoint};
 
-use crate::{curves::curve::Curve, point::Point, transforms::Transform};
+use crate::{
+    curves::curve::Curve, geometry_error::GeometryResult, point::Point, transforms::Transform,
+};
 
+pub mod cone;
 pub mod cylinder;
+pub mod nurbs;
 pub mod plane;
 pub mod sphere;
 pub mod surface;
+pub mod torus;
 
 pub trait SurfaceLike {
     // Transforms the surface by the given transform.
@@ -52,4 +57,8 @@ pub trait SurfaceLike {
 
     // Returns a gradient that leads to the surface.
     fn unsigned_l2_squared_distance_gradient(&self, point: Point) -> Option<Point>;
+
+    // Returns the surface that is moved by distance along the normal. Negative distances move against the normal.
+    // Freeform surfaces are approximated.
+    fn offset(&self, distance: EFloat64) -> GeometryResult<Surface>;
 }
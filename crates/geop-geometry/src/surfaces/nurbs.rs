@@ -0,0 +1,1382 @@
+use std::fmt::Display;
+
+use geop_algebra::{bspline_basis::BSplineBasis, efloat::EFloat64};
+
+use crate::{
+    color::Category10Color,
+    curves::{
+        circle::Circle,
+        curve::Curve,
+        nurb::{
+            de_boor, de_boor64, de_boor_derivative64, find_span, greville, to_cartesian,
+            Homogeneous, Homogeneous64, NurbsCurve,
+        },
+    },
+    geometry_error::{ElevateToGeometry, GeometryError, GeometryResult, WithContext},
+    geometry_scene::GeometryScene,
+    point::Point,
+    transforms::Transform,
+};
+
+use super::{
+    surface::{Surface, TangentPoint},
+    SurfaceLike,
+};
+
+// The closest point on a nurbs surface can only be found numerically, so points within this distance are considered on the surface.
+const PRECISION: f64 = 1e-8;
+const SAMPLES_PER_SPAN: usize = 4;
+const ITERATIONS: usize = 50;
+// Number of steps used to integrate path lengths and the exponential map.
+const INTEGRATION_STEPS: usize = 32;
+// A discrete geodesic is a polyline where every point is the projected midpoint of its neighbours.
+const GEODESIC_POINTS: usize = 16;
+const GEODESIC_ITERATIONS: usize = 16;
+
+// Tensor product rational B-spline surface. control_points[i][j] belongs to the i-th basis function in u and the j-th basis function in v.
+// The surface is bounded by its parameter domain and its normal points along the cross product of the u and v derivatives.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "NurbsSurfaceRaw"))]
+pub struct NurbsSurface {
+    pub u_degree: usize,
+    pub v_degree: usize,
+    pub control_points: Vec<Vec<Point>>,
+    pub weights: Vec<Vec<EFloat64>>,
+    pub u_knots: Vec<EFloat64>,
+    pub v_knots: Vec<EFloat64>,
+}
+
+// The stored fields of a nurbs surface, checked by try_new when it is deserialized.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct NurbsSurfaceRaw {
+    u_degree: usize,
+    v_degree: usize,
+    control_points: Vec<Vec<Point>>,
+    weights: Vec<Vec<EFloat64>>,
+    u_knots: Vec<EFloat64>,
+    v_knots: Vec<EFloat64>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<NurbsSurfaceRaw> for NurbsSurface {
+    type Error = GeometryError;
+
+    fn try_from(raw: NurbsSurfaceRaw) -> GeometryResult<NurbsSurface> {
+        NurbsSurface::try_new(
+            raw.u_degree,
+            raw.v_degree,
+            raw.control_points,
+            raw.weights,
+            raw.u_knots,
+            raw.v_knots,
+        )
+    }
+}
+
+// The end points of a curve that was found numerically are replaced by the exact points it runs between.
+pub(crate) fn pin_ends(mut curve: NurbsCurve, start: Point, end: Point) -> NurbsCurve {
+    let n = curve.control_points.len();
+    curve.control_points[0] = start;
+    curve.control_points[n - 1] = end;
+    curve
+}
+
+fn to_f64(knots: &[EFloat64]) -> Vec<f64> {
+    knots.iter().map(|u| u.to_f64()).collect()
+}
+
+// Parameters that subdivide every non-empty knot span of the domain.
+fn samples(degree: usize, knots: &[f64], n: usize) -> Vec<f64> {
+    let mut samples = vec![knots[degree]];
+    for k in degree..n {
+        if knots[k + 1] > knots[k] {
+            for i in 1..=SAMPLES_PER_SPAN {
+                samples.push(
+                    knots[k] + (knots[k + 1] - knots[k]) * i as f64 / SAMPLES_PER_SPAN as f64,
+                );
+            }
+        }
+    }
+    samples
+}
+
+impl NurbsSurface {
+    pub fn try_new(
+        u_degree: usize,
+        v_degree: usize,
+        control_points: Vec<Vec<Point>>,
+        weights: Vec<Vec<EFloat64>>,
+        u_knots: Vec<EFloat64>,
+        v_knots: Vec<EFloat64>,
+    ) -> GeometryResult<NurbsSurface> {
+        let error_context = |err: GeometryError| {
+            err.with_context_scene(
+                format!(
+                    "Create a nurbs surface of degree ({}, {}) with {} rows of control points, {} u knots and {} v knots.",
+                    u_degree,
+                    v_degree,
+                    control_points.len(),
+                    u_knots.len(),
+                    v_knots.len()
+                ),
+                GeometryScene::with_points(
+                    control_points
+                        .iter()
+                        .flatten()
+                        .map(|p| (*p, Category10Color::Orange))
+                        .collect(),
+                ),
+            )
+        };
+        if u_degree == 0 || v_degree == 0 {
+            return Err(GeometryError::new("Degrees must be at least 1".to_string()))
+                .with_context(&error_context);
+        }
+        let n = control_points.len();
+        let m = control_points.first().map_or(0, |row| row.len());
+        if n <= u_degree || m <= v_degree {
+            return Err(GeometryError::new(
+                "There must be more control points than the degree in both directions".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        if control_points.iter().any(|row| row.len() != m) {
+            return Err(GeometryError::new(
+                "All rows of control points must have the same length".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        if weights.len() != n || weights.iter().any(|row| row.len() != m) {
+            return Err(GeometryError::new(
+                "There must be one weight per control point".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        if weights.iter().flatten().any(|w| *w <= 0.0) {
+            return Err(GeometryError::new("Weights must be positive".to_string()))
+                .with_context(&error_context);
+        }
+        if u_knots.len() != n + u_degree + 1 || v_knots.len() != m + v_degree + 1 {
+            return Err(GeometryError::new(
+                "Knot vectors must have control_points.len() + degree + 1 entries".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        BSplineBasis::new(0, u_degree, u_knots.clone()).elevate(&error_context)?;
+        BSplineBasis::new(0, v_degree, v_knots.clone()).elevate(&error_context)?;
+        if u_knots[u_degree].to_f64() >= u_knots[n].to_f64()
+            || v_knots[v_degree].to_f64() >= v_knots[m].to_f64()
+        {
+            return Err(GeometryError::new(
+                "Parameter domain must not be empty".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+
+        Ok(NurbsSurface {
+            u_degree,
+            v_degree,
+            control_points,
+            weights,
+            u_knots,
+            v_knots,
+        })
+    }
+
+    // Skinned surface interpolating the given compatible curves, i.e. curves with the same degree and knot vector.
+    // The sections become the iso curves in v direction at chord length parameters in u, so the u direction runs
+    // from the first to the last section. The degree in u is at most the given degree.
+    pub fn try_new_skin(sections: &[NurbsCurve], degree: usize) -> GeometryResult<NurbsSurface> {
+        let error_context = |err: GeometryError| {
+            err.with_context_scene(
+                format!(
+                    "Create a skinned nurbs surface of degree {} through {} sections.",
+                    degree,
+                    sections.len()
+                ),
+                GeometryScene::with_points(
+                    sections
+                        .iter()
+                        .flat_map(|section| section.control_points.iter())
+                        .map(|p| (*p, Category10Color::Orange))
+                        .collect(),
+                ),
+            )
+        };
+        if sections.len() < 2 || degree == 0 {
+            return Err(GeometryError::new(
+                "A skin needs at least two sections and a degree of at least 1".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        let first = &sections[0];
+        let knots = to_f64(&first.knot_vector);
+        if sections.iter().any(|section| {
+            section.degree != first.degree
+                || section.control_points.len() != first.control_points.len()
+                || to_f64(&section.knot_vector) != knots
+        }) {
+            return Err(GeometryError::new(
+                "All sections must have the same degree and knot vector".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        let k = sections.len();
+        let u_degree = degree.min(k - 1);
+
+        // Chord length parameters, averaged over the control points.
+        let mut parameters = vec![0.0];
+        for i in 1..k {
+            let distance = sections[i]
+                .control_points
+                .iter()
+                .zip(sections[i - 1].control_points.iter())
+                .map(|(a, b)| (*a - *b).norm().to_f64())
+                .sum::<f64>();
+            parameters.push(parameters[i - 1] + distance);
+        }
+        let total = parameters[k - 1];
+        if total <= PRECISION {
+            return Err(GeometryError::new("Sections must not coincide".to_string()))
+                .with_context(&error_context);
+        }
+        let parameters = parameters.iter().map(|t| t / total).collect::<Vec<f64>>();
+        let mut u_knots = vec![0.0; u_degree + 1];
+        for j in 1..k - u_degree {
+            u_knots.push(parameters[j..j + u_degree].iter().sum::<f64>() / u_degree as f64);
+        }
+        u_knots.extend(vec![1.0; u_degree + 1]);
+
+        // Interpolate the homogeneous control points column by column by solving the system of basis functions.
+        let unit = |i: usize| {
+            (0..k)
+                .map(|j| match i == j {
+                    true => [1.0, 0.0, 0.0, 0.0],
+                    false => [0.0; 4],
+                })
+                .collect::<Vec<Homogeneous64>>()
+        };
+        let basis = (0..k).map(unit).collect::<Vec<_>>();
+        let mut matrix = parameters
+            .iter()
+            .map(|t| {
+                basis
+                    .iter()
+                    .map(|points| de_boor64(u_degree, &u_knots, points, *t)[0])
+                    .collect::<Vec<f64>>()
+            })
+            .collect::<Vec<Vec<f64>>>();
+        let mut rhs = sections
+            .iter()
+            .map(|section| {
+                section
+                    .control_points
+                    .iter()
+                    .zip(section.weights.iter())
+                    .flat_map(|(p, w)| {
+                        let w = w.to_f64();
+                        [p.x.to_f64() * w, p.y.to_f64() * w, p.z.to_f64() * w, w]
+                    })
+                    .collect::<Vec<f64>>()
+            })
+            .collect::<Vec<Vec<f64>>>();
+        for c in 0..k {
+            let pivot = (c..k)
+                .max_by(|a, b| matrix[*a][c].abs().total_cmp(&matrix[*b][c].abs()))
+                .expect("Range is not empty");
+            if matrix[pivot][c].abs() <= PRECISION {
+                return Err(GeometryError::new(
+                    "Interpolation system is singular".to_string(),
+                ))
+                .with_context(&error_context);
+            }
+            matrix.swap(c, pivot);
+            rhs.swap(c, pivot);
+            for r in 0..k {
+                if r != c {
+                    let factor = matrix[r][c] / matrix[c][c];
+                    let (pivot_row, pivot_rhs) = (matrix[c].clone(), rhs[c].clone());
+                    for (x, y) in matrix[r].iter_mut().zip(pivot_row) {
+                        *x -= factor * y;
+                    }
+                    for (x, y) in rhs[r].iter_mut().zip(pivot_rhs) {
+                        *x -= factor * y;
+                    }
+                }
+            }
+        }
+        let (control_points, weights) = rhs
+            .iter()
+            .enumerate()
+            .map(|(r, row)| {
+                row.chunks(4)
+                    .map(|h| {
+                        let w = h[3] / matrix[r][r];
+                        let point = Point::from_f64(h[0], h[1], h[2]) / EFloat64::from(h[3]);
+                        (point, EFloat64::from(w))
+                    })
+                    .unzip::<_, _, Vec<_>, Vec<_>>()
+            })
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+        let control_points = control_points
+            .into_iter()
+            .map(|row| row.into_iter().collect::<GeometryResult<Vec<Point>>>())
+            .collect::<GeometryResult<Vec<Vec<Point>>>>()
+            .with_context(&error_context)?;
+        NurbsSurface::try_new(
+            u_degree,
+            first.degree,
+            control_points,
+            weights,
+            u_knots.into_iter().map(EFloat64::from).collect(),
+            first.knot_vector.clone(),
+        )
+        .with_context(&error_context)
+    }
+
+    // Exact surface of revolution of a profile curve around the axis through basis. The profile has to lie in a plane that
+    // contains the axis, on one side of it. The u direction runs around the axis and the v direction along the profile.
+    pub fn try_new_revolution(
+        profile: &NurbsCurve,
+        basis: Point,
+        axis: Point,
+    ) -> GeometryResult<NurbsSurface> {
+        let error_context = |err: GeometryError| {
+            err.with_context_scene(
+                format!(
+                    "Create a surface of revolution around the axis at {} with direction {}.",
+                    basis, axis
+                ),
+                GeometryScene::with_points(
+                    profile
+                        .control_points
+                        .iter()
+                        .map(|p| (*p, Category10Color::Orange))
+                        .collect(),
+                ),
+            )
+        };
+        let axis = axis.normalize().with_context(&error_context)?;
+        let height = |p: Point| (p - basis).dot(axis);
+        let radial = |p: Point| p - basis - axis * height(p);
+        let direction = profile
+            .control_points
+            .iter()
+            .map(|p| radial(*p))
+            .max_by(|a, b| a.norm().to_f64().total_cmp(&b.norm().to_f64()))
+            .expect("A nurbs curve has control points")
+            .normalize()
+            .with_context(&error_context)?;
+
+        // Every control point of the profile runs on a circle, which is the unit circle scaled by its distance to the axis
+        let circle = NurbsCurve::try_new_arc(
+            &Circle::try_new(Point::zero(), axis, EFloat64::one()).with_context(&error_context)?,
+            None,
+            None,
+        )
+        .with_context(&error_context)?;
+        let control_points = circle
+            .control_points
+            .iter()
+            .map(|c| {
+                profile
+                    .control_points
+                    .iter()
+                    .map(|p| basis + axis * height(*p) + *c * radial(*p).dot(direction))
+                    .collect()
+            })
+            .collect();
+        let weights = circle
+            .weights
+            .iter()
+            .map(|w| profile.weights.iter().map(|v| *w * *v).collect())
+            .collect();
+        NurbsSurface::try_new(
+            circle.degree,
+            profile.degree,
+            control_points,
+            weights,
+            circle.knot_vector.clone(),
+            profile.knot_vector.clone(),
+        )
+        .with_context(&error_context)
+    }
+
+    fn homogeneous(&self) -> Vec<Vec<Homogeneous>> {
+        self.control_points
+            .iter()
+            .zip(self.weights.iter())
+            .map(|(row, weights)| {
+                row.iter()
+                    .zip(weights.iter())
+                    .map(|(p, w)| (*p * *w, *w))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Parameter domain in u and v.
+    pub fn domain(&self) -> ((f64, f64), (f64, f64)) {
+        let n = self.control_points.len();
+        let m = self.control_points[0].len();
+        (
+            (
+                self.u_knots[self.u_degree].to_f64(),
+                self.u_knots[n].to_f64(),
+            ),
+            (
+                self.v_knots[self.v_degree].to_f64(),
+                self.v_knots[m].to_f64(),
+            ),
+        )
+    }
+
+    fn clamp(&self, (u, v): (f64, f64)) -> (f64, f64) {
+        let ((u0, u1), (v0, v1)) = self.domain();
+        (u.clamp(u0, u1), v.clamp(v0, v1))
+    }
+
+    // Checks if the surface is closed in u and in v, i.e. if its first and last control points coincide in that direction.
+    pub(crate) fn is_closed(&self) -> (bool, bool) {
+        let rows = &self.control_points;
+        let closed_u = rows[0] == rows[rows.len() - 1];
+        let closed_v = rows.iter().all(|row| row[0] == row[row.len() - 1]);
+        (closed_u, closed_v)
+    }
+
+    // Checks if the parameters lie on the boundary of the domain. The seam of a closed surface is not a boundary.
+    pub(crate) fn on_boundary(&self, (u, v): (f64, f64)) -> bool {
+        let ((u0, u1), (v0, v1)) = self.domain();
+        let (closed_u, closed_v) = self.is_closed();
+        (!closed_u && (u <= u0 || u >= u1)) || (!closed_v && (v <= v0 || v >= v1))
+    }
+
+    pub fn point_at(&self, u: f64, v: f64) -> Point {
+        let (u, v) = self.clamp((u, v));
+        let v_knots = to_f64(&self.v_knots);
+        let rows = self
+            .homogeneous()
+            .iter()
+            .map(|row| de_boor(self.v_degree, &v_knots, row, v))
+            .collect::<Vec<Homogeneous>>();
+        to_cartesian(de_boor(self.u_degree, &to_f64(&self.u_knots), &rows, u))
+    }
+
+    fn homogeneous64(&self, rows: std::ops::RangeInclusive<usize>) -> Vec<Vec<Homogeneous64>> {
+        self.control_points[rows.clone()]
+            .iter()
+            .zip(self.weights[rows].iter())
+            .map(|(row, weights)| {
+                row.iter()
+                    .zip(weights.iter())
+                    .map(|(p, w)| {
+                        let w = w.to_f64();
+                        [p.x.to_f64() * w, p.y.to_f64() * w, p.z.to_f64() * w, w]
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Point and derivatives in u and v direction at the given parameters, evaluated in plain floating point.
+    fn derivatives(&self, u: f64, v: f64) -> (Point, Point, Point) {
+        let (u, v) = self.clamp((u, v));
+        let v_knots = to_f64(&self.v_knots);
+        // Only the rows of the knot span that contains u contribute to the point
+        let u_knots = to_f64(&self.u_knots);
+        let k = find_span(self.u_degree, &u_knots, self.control_points.len(), u);
+        let first = k - self.u_degree;
+        let u_knots = &u_knots[first..=k + self.u_degree + 1];
+        let homogeneous = self.homogeneous64(first..=k);
+        let rows = homogeneous
+            .iter()
+            .map(|row| de_boor64(self.v_degree, &v_knots, row, v))
+            .collect::<Vec<Homogeneous64>>();
+        let rows_v = homogeneous
+            .iter()
+            .map(|row| de_boor_derivative64(self.v_degree, &v_knots, row, v))
+            .collect::<Vec<Homogeneous64>>();
+        let s = de_boor64(self.u_degree, u_knots, &rows, u);
+        let s_u = de_boor_derivative64(self.u_degree, u_knots, &rows, u);
+        let s_v = de_boor64(self.u_degree, u_knots, &rows_v, u);
+        let w = s[3];
+        let point = |c: [f64; 3]| Point::from_f64(c[0], c[1], c[2]);
+        (
+            point([0, 1, 2].map(|c| s[c] / w)),
+            point([0, 1, 2].map(|c| (s_u[c] * w - s[c] * s_u[3]) / (w * w))),
+            point([0, 1, 2].map(|c| (s_v[c] * w - s[c] * s_v[3]) / (w * w))),
+        )
+    }
+
+    // Unit normal at the given parameters. At degenerate points like poles, the normal of a nearby point is used.
+    pub fn normal_at(&self, (u, v): (f64, f64)) -> Point {
+        let ((u0, u1), (v0, v1)) = self.domain();
+        let (_, s_u, s_v) = self.derivatives(u, v);
+        if let Ok(normal) = s_u.cross(s_v).normalize() {
+            return normal;
+        }
+        let (u, v) = (
+            u + 1e-6 * ((u0 + u1) / 2.0 - u),
+            v + 1e-6 * ((v0 + v1) / 2.0 - v),
+        );
+        let (_, s_u, s_v) = self.derivatives(u, v);
+        s_u.cross(s_v)
+            .normalize()
+            .expect("Nurbs surface must not be degenerate")
+    }
+
+    // Projects v into the tangent plane at the given parameters.
+    fn tangent_projection(&self, uv: (f64, f64), v: Point) -> Point {
+        let normal = self.normal_at(uv);
+        v - normal * v.dot(normal)
+    }
+
+    // Parameters of the closest point to p, found by Gauss-Newton iterations starting at the seed.
+    pub(crate) fn parameter_near(&self, p: Point, seed: (f64, f64)) -> (f64, f64) {
+        let ((u0, u1), (v0, v1)) = self.domain();
+        let (mut u, mut v) = self.clamp(seed);
+        let distance = |u: f64, v: f64| (self.derivatives(u, v).0 - p).norm_sq().to_f64();
+        for _ in 0..ITERATIONS {
+            let (s, s_u, s_v) = self.derivatives(u, v);
+            let r = p - s;
+            let (a, b, c) = (
+                s_u.dot(s_u).to_f64(),
+                s_u.dot(s_v).to_f64(),
+                s_v.dot(s_v).to_f64(),
+            );
+            let (r_u, r_v) = (s_u.dot(r).to_f64(), s_v.dot(r).to_f64());
+            let one_dimensional = |r: f64, a: f64| if a > 0.0 { r / a } else { 0.0 };
+            let det = a * c - b * b;
+            let (mut du, mut dv) = match det > f64::EPSILON * a * c {
+                true => ((c * r_u - b * r_v) / det, (a * r_v - b * r_u) / det),
+                false => (one_dimensional(r_u, a), one_dimensional(r_v, c)),
+            };
+            // On the boundary of the domain, the closest point is searched along the boundary
+            if (u <= u0 && du < 0.0) || (u >= u1 && du > 0.0) {
+                du = 0.0;
+                dv = one_dimensional(r_v, c);
+            }
+            if (v <= v0 && dv < 0.0) || (v >= v1 && dv > 0.0) {
+                dv = 0.0;
+                if du != 0.0 {
+                    du = one_dimensional(r_u, a);
+                }
+            }
+
+            let current = r.norm_sq().to_f64();
+            let mut step = 1.0;
+            let (mut u_new, mut v_new) = self.clamp((u + du, v + dv));
+            while distance(u_new, v_new) > current && step > 1e-3 {
+                step /= 2.0;
+                (u_new, v_new) = self.clamp((u + step * du, v + step * dv));
+            }
+            let converged =
+                (u_new - u).abs() <= 1e-14 * (u1 - u0) && (v_new - v).abs() <= 1e-14 * (v1 - v0);
+            (u, v) = (u_new, v_new);
+            if converged {
+                break;
+            }
+        }
+        (u, v)
+    }
+
+    // Parameters of the closest point to p. The closest sample of a grid over all knot spans is used as the seed.
+    pub fn parameter(&self, p: Point) -> (f64, f64) {
+        let n = self.control_points.len();
+        let u_knots = to_f64(&self.u_knots);
+        let v_knots = to_f64(&self.v_knots);
+        let u_samples = samples(self.u_degree, &u_knots, n);
+        let v_samples = samples(self.v_degree, &v_knots, self.control_points[0].len());
+
+        // The grid is evaluated column by column in plain floating point, as this is the hot path of all numerics
+        let target = [p.x.to_f64(), p.y.to_f64(), p.z.to_f64()];
+        let homogeneous = self.homogeneous64(0..=n - 1);
+        let mut seeds = Vec::<((f64, f64), f64)>::new();
+        for v in v_samples.iter() {
+            let column = homogeneous
+                .iter()
+                .map(|row| de_boor64(self.v_degree, &v_knots, row, *v))
+                .collect::<Vec<Homogeneous64>>();
+            for u in u_samples.iter() {
+                let s = de_boor64(self.u_degree, &u_knots, &column, *u);
+                let distance = (0..3)
+                    .map(|c| (s[c] / s[3] - target[c]).powi(2))
+                    .sum::<f64>();
+                seeds.push(((*u, *v), distance));
+            }
+        }
+
+        // On the seam of a closed surface the closest sample is found twice, but the search can only go on from one of them
+        let closest = seeds
+            .iter()
+            .map(|seed| seed.1)
+            .fold(f64::INFINITY, f64::min);
+        seeds
+            .iter()
+            .filter(|seed| seed.1 <= closest * (1.0 + 1e-9))
+            .map(|seed| self.parameter_near(p, seed.0))
+            .min_by(|a, b| {
+                let distance =
+                    |(u, v): (f64, f64)| (self.derivatives(u, v).0 - p).norm_sq().to_f64();
+                distance(*a).total_cmp(&distance(*b))
+            })
+            .expect("The grid has samples")
+    }
+
+    // Points where a coordinate takes a local extreme, i.e. where the normal is parallel to an axis. They are found as
+    // extremes of a grid over all knot spans and refined by repeatedly subdividing the cell around them.
+    pub fn extreme_points(&self) -> Vec<Point> {
+        let u_samples = samples(
+            self.u_degree,
+            &to_f64(&self.u_knots),
+            self.control_points.len(),
+        );
+        let v_samples = samples(
+            self.v_degree,
+            &to_f64(&self.v_knots),
+            self.control_points[0].len(),
+        );
+        let grid = u_samples
+            .iter()
+            .map(|u| {
+                v_samples
+                    .iter()
+                    .map(|v| self.derivatives(*u, *v).0)
+                    .collect::<Vec<Point>>()
+            })
+            .collect::<Vec<Vec<Point>>>();
+        let coordinate = |p: Point, axis: usize, sign: f64| sign * [p.x, p.y, p.z][axis].to_f64();
+
+        let mut extremes = Vec::<Point>::new();
+        for axis in 0..3 {
+            for sign in [1.0, -1.0] {
+                for i in 0..u_samples.len() {
+                    for j in 0..v_samples.len() {
+                        // Extremes on the boundary of the domain are found on the boundary of the face
+                        if self.on_boundary((u_samples[i], v_samples[j])) {
+                            continue;
+                        }
+                        let value = coordinate(grid[i][j], axis, sign);
+                        let neighbours = (i.saturating_sub(1)..=(i + 1).min(u_samples.len() - 1))
+                            .flat_map(|k| {
+                                (j.saturating_sub(1)..=(j + 1).min(v_samples.len() - 1))
+                                    .map(move |l| (k, l))
+                            })
+                            .filter(|(k, l)| (*k, *l) != (i, j))
+                            .map(|(k, l)| coordinate(grid[k][l], axis, sign))
+                            .collect::<Vec<f64>>();
+                        // Points on a plateau are not extremes, their values are reached elsewhere as well
+                        if neighbours.iter().any(|n| *n > value)
+                            || neighbours.iter().all(|n| *n == value)
+                        {
+                            continue;
+                        }
+
+                        let step = |samples: &[f64], k: usize| {
+                            let previous = samples[k.saturating_sub(1)];
+                            let next = samples[(k + 1).min(samples.len() - 1)];
+                            (next - previous) / 2.0
+                        };
+                        let (mut u, mut v) = (u_samples[i], v_samples[j]);
+                        let (mut du, mut dv) = (step(&u_samples, i), step(&v_samples, j));
+                        for _ in 0..ITERATIONS {
+                            let mut best = (coordinate(self.derivatives(u, v).0, axis, sign), u, v);
+                            for (su, sv) in [(-1.0, 0.0), (1.0, 0.0), (0.0, -1.0), (0.0, 1.0)] {
+                                let candidate = self.clamp((u + su * du, v + sv * dv));
+                                let value = coordinate(
+                                    self.derivatives(candidate.0, candidate.1).0,
+                                    axis,
+                                    sign,
+                                );
+                                if value > best.0 {
+                                    best = (value, candidate.0, candidate.1);
+                                }
+                            }
+                            if (best.1, best.2) == (u, v) {
+                                du /= 2.0;
+                                dv /= 2.0;
+                            }
+                            (u, v) = (best.1, best.2);
+                        }
+                        extremes.push(self.point_at(u, v));
+                    }
+                }
+            }
+        }
+        extremes
+    }
+
+    // Homogeneous point at the given parameters, evaluated in plain floating point.
+    fn homogeneous_at(&self, u: f64, v: f64) -> Homogeneous64 {
+        let (u, v) = self.clamp((u, v));
+        let v_knots = to_f64(&self.v_knots);
+        let rows = self
+            .homogeneous64(0..=self.control_points.len() - 1)
+            .iter()
+            .map(|row| de_boor64(self.v_degree, &v_knots, row, v))
+            .collect::<Vec<Homogeneous64>>();
+        de_boor64(self.u_degree, &to_f64(&self.u_knots), &rows, u)
+    }
+
+    // Parameters of consecutive points on the surface, where each search starts at the parameters of the previous point.
+    // Across the seam of a closed surface, the search of the previous point gets stuck, so the whole surface is searched.
+    pub(crate) fn parameters_along(&self, points: &[Point]) -> Vec<(f64, f64)> {
+        let mut params = Vec::<(f64, f64)>::with_capacity(points.len());
+        for p in points.iter() {
+            let uv = match params.last() {
+                Some(previous) => {
+                    let uv = self.parameter_near(*p, *previous);
+                    match (self.derivatives(uv.0, uv.1).0 - *p).norm() <= PRECISION {
+                        true => uv,
+                        false => self.parameter(*p),
+                    }
+                }
+                None => self.parameter(*p),
+            };
+            params.push(uv);
+        }
+        params
+    }
+
+    // The curve through the points at the given parameters, which runs straight between them in the parameter domain.
+    // Within a knot span, the surface along a straight line is rational of degree u_degree + v_degree, so unlike a polyline
+    // through the points, the curve lies exactly on the surface. Across the seam of a closed surface, the shorter way is taken.
+    pub fn curve_on_surface(&self, params: &[(f64, f64)]) -> GeometryResult<NurbsCurve> {
+        let error_context = |err: GeometryError| {
+            err.with_context(format!(
+                "Create a curve on a nurbs surface through {} parameters",
+                params.len()
+            ))
+        };
+        let ((u0, u1), (v0, v1)) = self.domain();
+        let (closed_u, closed_v) = self.is_closed();
+        let u_knots = to_f64(&self.u_knots);
+        let v_knots = to_f64(&self.v_knots);
+
+        // Splits every straight line at the knots, such that each piece lies within one knot span
+        let mut pieces = Vec::<((f64, f64), (f64, f64))>::new();
+        for pair in params.windows(2) {
+            let ((u_a, v_a), (mut u_b, mut v_b)) = (self.clamp(pair[0]), self.clamp(pair[1]));
+            if closed_u && (u_b - u_a).abs() > (u1 - u0) / 2.0 {
+                u_b -= (u1 - u0) * (u_b - u_a).signum();
+            }
+            if closed_v && (v_b - v_a).abs() > (v1 - v0) / 2.0 {
+                v_b -= (v1 - v0) * (v_b - v_a).signum();
+            }
+            let crossings = |a: f64, b: f64, knots: &[f64], width: f64| {
+                knots
+                    .iter()
+                    .flat_map(|k| [k - width, *k, k + width])
+                    .map(|k| (k - a) / (b - a))
+                    .filter(|t| 1e-12 < *t && *t < 1.0 - 1e-12)
+                    .collect::<Vec<f64>>()
+            };
+            let mut breaks = vec![0.0, 1.0];
+            breaks.extend(crossings(u_a, u_b, &u_knots, u1 - u0));
+            breaks.extend(crossings(v_a, v_b, &v_knots, v1 - v0));
+            breaks.sort_by(|a, b| a.total_cmp(b));
+            breaks.dedup_by(|a, b| *a - *b <= 1e-12);
+            for t in breaks.windows(2) {
+                let at = |t: f64| (u_a + t * (u_b - u_a), v_a + t * (v_b - v_a));
+                let (a, b) = (at(t[0]), at(t[1]));
+
+                // Pieces beyond the seam are moved back into the domain
+                let shift = |a: f64, b: f64, lo: f64, hi: f64| match (a + b) / 2.0 {
+                    m if m > hi => lo - hi,
+                    m if m < lo => hi - lo,
+                    _ => 0.0,
+                };
+                let (du, dv) = (shift(a.0, b.0, u0, u1), shift(a.1, b.1, v0, v1));
+                pieces.push((
+                    self.clamp((a.0 + du, a.1 + dv)),
+                    self.clamp((b.0 + du, b.1 + dv)),
+                ));
+            }
+        }
+
+        // Every piece is a rational Bezier curve, whose control points are found by interpolating the homogeneous points
+        let degree = self.u_degree + self.v_degree;
+        let binomial =
+            |n: usize, k: usize| (0..k).fold(1.0, |c, i| c * (n - i) as f64 / (i + 1) as f64);
+        let mut homogeneous = Vec::<Homogeneous64>::new();
+        let mut lengths = vec![0.0];
+        for (a, b) in pieces {
+            let length = (self.derivatives(b.0, b.1).0 - self.derivatives(a.0, a.1).0)
+                .norm()
+                .to_f64();
+            // Pieces between knots that almost coincide with a point would have a vanishing derivative
+            if length <= PRECISION {
+                continue;
+            }
+            let mut matrix = (0..=degree)
+                .map(|k| {
+                    let t = k as f64 / degree as f64;
+                    let h = self.homogeneous_at(a.0 + t * (b.0 - a.0), a.1 + t * (b.1 - a.1));
+                    let mut row = (0..=degree)
+                        .map(|i| {
+                            binomial(degree, i)
+                                * t.powi(i as i32)
+                                * (1.0 - t).powi((degree - i) as i32)
+                        })
+                        .collect::<Vec<f64>>();
+                    row.extend(h);
+                    row
+                })
+                .collect::<Vec<Vec<f64>>>();
+            for c in 0..=degree {
+                let pivot = (c..=degree)
+                    .max_by(|i, j| matrix[*i][c].abs().total_cmp(&matrix[*j][c].abs()))
+                    .unwrap();
+                matrix.swap(c, pivot);
+                let pivot_row = matrix[c].clone();
+                for (r, row) in matrix.iter_mut().enumerate() {
+                    if r != c {
+                        let factor = row[c] / pivot_row[c];
+                        for (x, p) in row.iter_mut().zip(&pivot_row).skip(c) {
+                            *x -= factor * p;
+                        }
+                    }
+                }
+            }
+            let first = match homogeneous.is_empty() {
+                true => 0,
+                false => 1,
+            };
+            for (i, row) in matrix.iter().enumerate().skip(first) {
+                homogeneous.push([0, 1, 2, 3].map(|c| row[degree + 1 + c] / row[i]));
+            }
+            lengths.push(lengths[lengths.len() - 1] + length);
+        }
+
+        let total = lengths[lengths.len() - 1];
+        if total <= 0.0 {
+            return Err(GeometryError::new(
+                "A curve on a surface needs at least two distinct points".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        if homogeneous.iter().any(|h| h[3] <= 0.0) {
+            return Err(GeometryError::new(
+                "The curve on the surface has non-positive weights".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        let mut knot_vector = vec![EFloat64::zero(); degree + 1];
+        for length in lengths[1..lengths.len() - 1].iter() {
+            knot_vector.extend(vec![EFloat64::from(length / total); degree]);
+        }
+        knot_vector.extend(vec![EFloat64::one(); degree + 1]);
+        NurbsCurve::try_new(
+            degree,
+            homogeneous
+                .iter()
+                .map(|h| Point::from_f64(h[0] / h[3], h[1] / h[3], h[2] / h[3]))
+                .collect(),
+            homogeneous.iter().map(|h| EFloat64::from(h[3])).collect(),
+            knot_vector,
+        )
+        .with_context(&error_context)
+    }
+
+    // The curve on the surface where u is constant, running in v direction.
+    pub fn iso_curve_u(&self, u: f64) -> NurbsCurve {
+        let (u, _) = self.clamp((u, self.domain().1 .0));
+        let u_knots = to_f64(&self.u_knots);
+        let homogeneous = self.homogeneous();
+        let points = (0..self.control_points[0].len())
+            .map(|j| {
+                let column = homogeneous
+                    .iter()
+                    .map(|row| row[j])
+                    .collect::<Vec<Homogeneous>>();
+                de_boor(self.u_degree, &u_knots, &column, u)
+            })
+            .collect::<Vec<Homogeneous>>();
+        NurbsCurve::try_new(
+            self.v_degree,
+            points.iter().map(|p| to_cartesian(*p)).collect(),
+            points.iter().map(|p| p.1).collect(),
+            self.v_knots.clone(),
+        )
+        .expect("Iso curves of a valid nurbs surface are valid")
+    }
+
+    // The curve on the surface where v is constant, running in u direction.
+    pub fn iso_curve_v(&self, v: f64) -> NurbsCurve {
+        let (_, v) = self.clamp((self.domain().0 .0, v));
+        let v_knots = to_f64(&self.v_knots);
+        let points = self
+            .homogeneous()
+            .iter()
+            .map(|row| de_boor(self.v_degree, &v_knots, row, v))
+            .collect::<Vec<Homogeneous>>();
+        NurbsCurve::try_new(
+            self.u_degree,
+            points.iter().map(|p| to_cartesian(*p)).collect(),
+            points.iter().map(|p| p.1).collect(),
+            self.u_knots.clone(),
+        )
+        .expect("Iso curves of a valid nurbs surface are valid")
+    }
+
+    pub fn transform(&self, transform: Transform) -> NurbsSurface {
+        NurbsSurface::try_new(
+            self.u_degree,
+            self.v_degree,
+            self.control_points
+                .iter()
+                .map(|row| row.iter().map(|p| transform * *p).collect())
+                .collect(),
+            self.weights.clone(),
+            self.u_knots.clone(),
+            self.v_knots.clone(),
+        )
+        .expect("Transform of nurbs surface will always succeed")
+    }
+
+    // Reverses the u direction, which flips the normal.
+    pub fn neg(&self) -> NurbsSurface {
+        let knots = to_f64(&self.u_knots);
+        let (first, last) = (knots[0], knots[knots.len() - 1]);
+        NurbsSurface::try_new(
+            self.u_degree,
+            self.v_degree,
+            self.control_points.iter().rev().cloned().collect(),
+            self.weights.iter().rev().cloned().collect(),
+            knots
+                .iter()
+                .rev()
+                .map(|u| EFloat64::from(first + last - u))
+                .collect(),
+            self.v_knots.clone(),
+        )
+        .expect("Nurbs surface parameters should be valid")
+    }
+}
+
+impl SurfaceLike for NurbsSurface {
+    fn transform(&self, transform: Transform) -> Surface {
+        Surface::Nurbs(self.transform(transform))
+    }
+
+    fn neg(&self) -> Surface {
+        Surface::Nurbs(self.neg())
+    }
+
+    fn normal(&self, p: Point) -> Point {
+        self.normal_at(self.parameter(p))
+    }
+
+    fn on_surface(&self, p: Point) -> bool {
+        (self.project(p) - p).norm() <= PRECISION
+    }
+
+    fn metric(&self, _x: Point, u: TangentPoint, v: TangentPoint) -> EFloat64 {
+        u.dot(v)
+    }
+
+    fn distance(&self, x: Point, y: Point) -> EFloat64 {
+        match self.log(x, y) {
+            Some(v) => v.norm(),
+            None => (y - x).norm(),
+        }
+    }
+
+    // Walks along the surface in small steps, where each step continues straight ahead in the tangent plane.
+    fn exp(&self, x: Point, u: TangentPoint) -> Point {
+        let mut uv = self.parameter(x);
+        let length = u.norm().to_f64();
+        let mut direction = match self.tangent_projection(uv, u).normalize() {
+            Ok(direction) => direction,
+            Err(_) => return x,
+        };
+        let step = EFloat64::from(length / INTEGRATION_STEPS as f64);
+        let mut p = x;
+        for _ in 0..INTEGRATION_STEPS {
+            uv = self.parameter_near(p + direction * step, uv);
+            let q = self.derivatives(uv.0, uv.1).0;
+            direction = match self.tangent_projection(uv, q - p).normalize() {
+                Ok(direction) => direction,
+                Err(_) => return q,
+            };
+            p = q;
+        }
+        p
+    }
+
+    // The length is integrated along the path that is straight in the parameter domain, the direction is its tangent at x.
+    fn log(&self, x: Point, y: Point) -> Option<TangentPoint> {
+        let (u_x, v_x) = self.parameter(x);
+        let (u_y, v_y) = self.parameter(y);
+        let mut length = 0.0;
+        let mut previous = self.derivatives(u_x, v_x).0;
+        for i in 1..=INTEGRATION_STEPS {
+            let t = i as f64 / INTEGRATION_STEPS as f64;
+            let q = self
+                .derivatives(u_x + t * (u_y - u_x), v_x + t * (v_y - v_x))
+                .0;
+            length += (q - previous).norm().to_f64();
+            previous = q;
+        }
+        if length <= 0.0 {
+            return Some(Point::zero());
+        }
+        let (_, s_u, s_v) = self.derivatives(u_x, v_x);
+        let direction = s_u * EFloat64::from(u_y - u_x) + s_v * EFloat64::from(v_y - v_x);
+        direction
+            .normalize()
+            .ok()
+            .map(|direction| direction * EFloat64::from(length))
+    }
+
+    // Projects v into the tangent plane at y and keeps its length.
+    fn parallel_transport(
+        &self,
+        v: Option<TangentPoint>,
+        _x: Point,
+        y: Point,
+    ) -> Option<TangentPoint> {
+        let v = v?;
+        let projected = self.tangent_projection(self.parameter(y), v);
+        projected.normalize().ok().map(|p| p * v.norm())
+    }
+
+    fn geodesic(&self, x: Point, y: Point) -> Curve {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        assert!(x != y);
+        let (u_x, v_x) = self.parameter(x);
+        let (u_y, v_y) = self.parameter(y);
+        let mut params = (0..GEODESIC_POINTS)
+            .map(|i| {
+                let t = i as f64 / (GEODESIC_POINTS - 1) as f64;
+                (u_x + t * (u_y - u_x), v_x + t * (v_y - v_x))
+            })
+            .collect::<Vec<(f64, f64)>>();
+        let mut points = params
+            .iter()
+            .map(|(u, v)| self.derivatives(*u, *v).0)
+            .collect::<Vec<Point>>();
+        points[0] = x;
+        points[GEODESIC_POINTS - 1] = y;
+        for _ in 0..GEODESIC_ITERATIONS {
+            for i in 1..GEODESIC_POINTS - 1 {
+                let mid = (points[i - 1] + points[i + 1]) * EFloat64::from(0.5);
+                params[i] = self.parameter_near(mid, params[i]);
+                points[i] = self.derivatives(params[i].0, params[i].1).0;
+            }
+        }
+        Curve::Nurbs(pin_ends(
+            self.curve_on_surface(&params)
+                .expect("Points of a geodesic are distinct"),
+            x,
+            y,
+        ))
+    }
+
+    fn point_grid(&self, density: f64) -> Vec<Point> {
+        let n = ((16.0 * density) as usize).max(2);
+        let ((u0, u1), (v0, v1)) = self.domain();
+        let mut points = Vec::with_capacity(n * n);
+        for i in 0..n {
+            for j in 0..n {
+                let u = u0 + (u1 - u0) * i as f64 / (n - 1) as f64;
+                let v = v0 + (v1 - v0) * j as f64 / (n - 1) as f64;
+                points.push(self.point_at(u, v));
+            }
+        }
+        points
+    }
+
+    fn project(&self, point: Point) -> Point {
+        let (u, v) = self.parameter(point);
+        self.derivatives(u, v).0
+    }
+
+    fn unsigned_l2_squared_distance_gradient(&self, point: Point) -> Option<Point> {
+        Some(self.project(point) - point)
+    }
+
+    // Approximated by moving every control point along the normal at its greville abscissae.
+    fn offset(&self, distance: EFloat64) -> GeometryResult<Surface> {
+        let u_knots = to_f64(&self.u_knots);
+        let v_knots = to_f64(&self.v_knots);
+        let control_points = self
+            .control_points
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, p)| {
+                        let uv = (
+                            greville(self.u_degree, &u_knots, i),
+                            greville(self.v_degree, &v_knots, j),
+                        );
+                        *p + self.normal_at(uv) * distance
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(Surface::Nurbs(NurbsSurface::try_new(
+            self.u_degree,
+            self.v_degree,
+            control_points,
+            self.weights.clone(),
+            self.u_knots.clone(),
+            self.v_knots.clone(),
+        )?))
+    }
+}
+
+impl PartialEq for NurbsSurface {
+    fn eq(&self, other: &NurbsSurface) -> bool {
+        self.u_degree == other.u_degree
+            && self.v_degree == other.v_degree
+            && self.control_points == other.control_points
+            && self.weights == other.weights
+            && self.u_knots == other.u_knots
+            && self.v_knots == other.v_knots
+    }
+}
+
+impl Display for NurbsSurface {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "NurbsSurface of degree ({}, {}) with {}x{} control points",
+            self.u_degree,
+            self.v_degree,
+            self.control_points.len(),
+            self.control_points[0].len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::CurveLike;
+    use crate::surfaces::torus::Torus;
+
+    // A quarter of the unit cylinder around the y axis, from x = 1 to z = 1 and from y = 0 to y = 1.
+    fn quarter_cylinder() -> NurbsSurface {
+        let arc = [
+            Point::from_f64(1.0, 0.0, 0.0),
+            Point::from_f64(1.0, 0.0, 1.0),
+            Point::from_f64(0.0, 0.0, 1.0),
+        ];
+        let arc_weights = [
+            EFloat64::one(),
+            EFloat64::from(0.5_f64.sqrt()),
+            EFloat64::one(),
+        ];
+        NurbsSurface::try_new(
+            2,
+            1,
+            arc.iter().map(|p| vec![*p, *p + Point::unit_y()]).collect(),
+            arc_weights.iter().map(|w| vec![*w, *w]).collect(),
+            vec![EFloat64::zero(); 3]
+                .into_iter()
+                .chain(vec![EFloat64::one(); 3])
+                .collect(),
+            vec![
+                EFloat64::zero(),
+                EFloat64::zero(),
+                EFloat64::one(),
+                EFloat64::one(),
+            ],
+        )
+        .unwrap()
+    }
+
+    // A biquadratic patch over the unit square, whose middle control point is raised to z = 2.
+    fn bump() -> NurbsSurface {
+        let knots = vec![EFloat64::zero(); 3]
+            .into_iter()
+            .chain(vec![EFloat64::one(); 3])
+            .collect::<Vec<EFloat64>>();
+        NurbsSurface::try_new(
+            2,
+            2,
+            (0..3)
+                .map(|i| {
+                    (0..3)
+                        .map(|j| {
+                            let z = if (i, j) == (1, 1) { 2.0 } else { 0.0 };
+                            Point::from_f64(i as f64 / 2.0, j as f64 / 2.0, z)
+                        })
+                        .collect()
+                })
+                .collect(),
+            vec![vec![EFloat64::one(); 3]; 3],
+            knots.clone(),
+            knots,
+        )
+        .unwrap()
+    }
+
+    fn on_cylinder(angle: f64, y: f64) -> Point {
+        Point::from_f64(angle.cos(), y, angle.sin())
+    }
+
+    #[test]
+    fn test_nurbs_surface_on_surface_and_project() {
+        let surface = quarter_cylinder();
+        for i in 0..=4 {
+            let p = on_cylinder(std::f64::consts::FRAC_PI_2 * i as f64 / 4.0, 0.3);
+            assert!(surface.on_surface(p));
+            // The cross product of the derivatives points towards the axis
+            let normal = surface.normal(p);
+            assert!((normal + Point::from_f64(p.x.to_f64(), 0.0, p.z.to_f64())).norm() < 1e-6);
+
+            let far = Point::from_f64(2.0 * p.x.to_f64(), 0.3, 2.0 * p.z.to_f64());
+            assert!((surface.project(far) - p).norm() < 1e-8);
+        }
+        assert!(!surface.on_surface(Point::from_f64(0.5, 0.5, 0.5)));
+        assert!(!surface.on_surface(on_cylinder(-0.5, 0.5)));
+
+        let neg = surface.neg();
+        let p = on_cylinder(0.4, 0.5);
+        assert!(neg.on_surface(p));
+        assert!((neg.normal(p) + surface.normal(p)).norm() < 1e-6);
+    }
+
+    #[test]
+    fn test_nurbs_surface_exp_log() {
+        let surface = quarter_cylinder();
+        let x = on_cylinder(0.0, 0.5);
+        let y = on_cylinder(std::f64::consts::FRAC_PI_2, 0.5);
+        let log = surface.log(x, y).unwrap();
+        assert!((log.norm().to_f64() - std::f64::consts::FRAC_PI_2).abs() < 1e-3);
+        assert!((log.normalize().unwrap() - Point::unit_z()).norm() < 1e-6);
+
+        let u = Point::unit_z() * EFloat64::from(std::f64::consts::FRAC_PI_4);
+        let p = surface.exp(x, u);
+        assert!(surface.on_surface(p));
+        assert!((p - on_cylinder(std::f64::consts::FRAC_PI_4, 0.5)).norm() < 1e-3);
+    }
+
+    #[test]
+    fn test_nurbs_surface_geodesic_and_iso_curves() {
+        let surface = quarter_cylinder();
+        let x = on_cylinder(0.0, 0.0);
+        let y = on_cylinder(std::f64::consts::FRAC_PI_2, 1.0);
+        let geodesic = surface.geodesic(x, y);
+        assert!(geodesic.on_curve(x));
+        assert!(geodesic.on_curve(y));
+        for i in 1..10 {
+            let p = geodesic
+                .interpolate(Some(x), Some(y), i as f64 / 10.0)
+                .unwrap();
+            assert!(surface.on_surface(p));
+        }
+
+        let iso = surface.iso_curve_u(0.0);
+        assert!(iso.on_curve(x));
+        assert!(iso.on_curve(on_cylinder(0.0, 1.0)));
+        let iso = surface.iso_curve_v(1.0);
+        assert!(iso.on_curve(y));
+        for i in 0..=4 {
+            let p = iso.interpolate(None, None, i as f64 / 4.0).unwrap();
+            assert!(surface.on_surface(p));
+        }
+    }
+
+    #[test]
+    fn test_nurbs_surface_curve_on_surface() {
+        // A torus is closed in both directions, so the short way between these parameters runs across both seams
+        let torus = Torus::try_new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::two(),
+            EFloat64::one(),
+            true,
+        )
+        .unwrap()
+        .to_nurbs();
+        let ((u0, u1), (v0, v1)) = torus.domain();
+        let at = |s: f64, t: f64| (u0 + s * (u1 - u0), v0 + t * (v1 - v0));
+        let params = [at(0.9, 0.1), at(0.95, 0.95), at(0.1, 0.9)];
+        let curve = torus.curve_on_surface(&params).unwrap();
+        assert!(curve.point_at(0.0) == torus.point_at(params[0].0, params[0].1));
+        assert!(curve.point_at(1.0) == torus.point_at(params[2].0, params[2].1));
+        assert!(curve.on_curve(torus.point_at(params[1].0, params[1].1)));
+        for i in 0..=32 {
+            let p = curve.point_at(i as f64 / 32.0);
+            assert!(torus.on_surface(p));
+            let (u, v) = torus.parameter(p);
+            let near_seam = |s: f64| s <= 0.15 || s >= 0.85;
+            assert!(near_seam((u - u0) / (u1 - u0)));
+            assert!(near_seam((v - v0) / (v1 - v0)));
+        }
+    }
+
+    #[test]
+    fn test_nurbs_surface_skin() {
+        // Circles of radius 1, 2 and 1 at heights 0, 1 and 2 around the z axis.
+        let sections = [(0.0, 1.0), (1.0, 2.0), (2.0, 1.0)]
+            .iter()
+            .map(|(z, r)| {
+                let circle = crate::curves::circle::Circle::try_new(
+                    Point::from_f64(0.0, 0.0, *z),
+                    Point::unit_z(),
+                    EFloat64::from(*r),
+                )
+                .unwrap();
+                NurbsCurve::try_new_arc(&circle, None, None).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let surface = NurbsSurface::try_new_skin(&sections, 3).unwrap();
+        assert_eq!(surface.u_degree, 2);
+        for (z, r) in [(0.0, 1.0), (1.0, 2.0), (2.0, 1.0)] {
+            for i in 0..8 {
+                let angle = std::f64::consts::PI * i as f64 / 4.0;
+                let p = Point::from_f64(r * angle.cos(), r * angle.sin(), z);
+                assert!(surface.on_surface(p));
+            }
+        }
+        // The surface bulges outwards between the sections.
+        assert!(!surface.on_surface(Point::from_f64(1.0, 0.0, 0.5)));
+
+        let line = NurbsCurve::try_new_polyline(vec![Point::zero(), Point::unit_x()]).unwrap();
+        assert!(NurbsSurface::try_new_skin(std::slice::from_ref(&line), 3).is_err());
+        assert!(NurbsSurface::try_new_skin(&[line, sections[0].clone()], 3).is_err());
+    }
+
+    #[test]
+    fn test_nurbs_surface_offset() {
+        let surface = quarter_cylinder();
+        let normal = surface.normal(on_cylinder(0.3, 0.5));
+        let outwards = normal.dot(on_cylinder(0.3, 0.5) - Point::from_f64(0.0, 0.5, 0.0)) > 0.0;
+        let distance = match outwards {
+            true => 0.1,
+            false => -0.1,
+        };
+        let offset = match surface.offset(EFloat64::from(distance)).unwrap() {
+            Surface::Nurbs(offset) => offset,
+            _ => panic!("Offset of a nurbs surface is a nurbs surface"),
+        };
+        // The corners are moved exactly, the rest is approximated
+        let corner = offset.point_at(0.0, 0.0);
+        assert!((corner - Point::from_f64(1.1, 0.0, 0.0)).norm() < 1e-12);
+        for (u, v) in [(0.25, 0.5), (0.5, 0.5), (0.75, 1.0)] {
+            let p = offset.point_at(u, v);
+            let radius = (p - Point::from_f64(0.0, p.y.to_f64(), 0.0)).norm();
+            assert!((radius.to_f64() - 1.1).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    fn test_nurbs_surface_extreme_points() {
+        // The top of the bump is a quarter of the height of its middle control point
+        let extremes = bump().extreme_points();
+        assert_eq!(extremes.len(), 1);
+        assert!((extremes[0] - Point::from_f64(0.5, 0.5, 0.5)).norm() < 1e-6);
+
+        // The quarter cylinder takes its extremes on the boundary of its domain
+        assert!(quarter_cylinder().extreme_points().is_empty());
+    }
+}
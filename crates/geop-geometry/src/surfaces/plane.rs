@@ -2,6 +2,7 @@ use geop_algebra::efloat::EFloat64;
 
 use crate::{
     curves::{curve::Curve, line::Line},
+    geometry_error::GeometryResult,
     point::Point,
     transforms::Transform,
     HORIZON_DIST,
@@ -13,12 +14,44 @@ use super::{
 };
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "PlaneRaw"))]
 pub struct Plane {
     pub basis: Point,
     pub u_slope: Point,
     pub v_slope: Point,
 }
 
+// Deserialized planes are checked like in Plane::new, but keep their slopes, which are normalized already.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct PlaneRaw {
+    basis: Point,
+    u_slope: Point,
+    v_slope: Point,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<PlaneRaw> for Plane {
+    type Error = crate::geometry_error::GeometryError;
+
+    fn try_from(raw: PlaneRaw) -> GeometryResult<Plane> {
+        if !raw.u_slope.is_normalized()
+            || !raw.v_slope.is_normalized()
+            || raw.u_slope.cross(raw.v_slope).is_zero()
+        {
+            return Err(crate::geometry_error::GeometryError::new(
+                "Slopes of a plane must be normalized and not parallel".to_string(),
+            ));
+        }
+        Ok(Plane {
+            basis: raw.basis,
+            u_slope: raw.u_slope,
+            v_slope: raw.v_slope,
+        })
+    }
+}
+
 impl Plane {
     pub fn new(basis: Point, u_slope: Point, v_slope: Point) -> Plane {
         assert!(!u_slope.cross(v_slope).is_zero());
@@ -140,6 +173,15 @@ impl SurfaceLike for Plane {
         let distance = (point - self.basis).dot(normal);
         Some(-normal * distance)
     }
+
+    fn offset(&self, distance: EFloat64) -> GeometryResult<Surface> {
+        let normal = self.u_slope.cross(self.v_slope).normalize()?;
+        Ok(Surface::Plane(Plane::new(
+            self.basis + normal * distance,
+            self.u_slope,
+            self.v_slope,
+        )))
+    }
 }
 
 impl PartialEq for Plane {
@@ -2,6 +2,7 @@ use geop_algebra::efloat::EFloat64;
 
 use crate::{
     curves::{circle::Circle, curve::Curve},
+    geometry_error::{GeometryError, GeometryResult},
     point::Point,
     transforms::Transform,
 };
@@ -12,12 +13,34 @@ use super::{
 };
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SphereRaw"))]
 pub struct Sphere {
     pub basis: Point,
     pub radius: EFloat64,
     pub normal_outwards: bool,
 }
 
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SphereRaw {
+    basis: Point,
+    radius: EFloat64,
+    normal_outwards: bool,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SphereRaw> for Sphere {
+    type Error = GeometryError;
+
+    fn try_from(raw: SphereRaw) -> GeometryResult<Sphere> {
+        if raw.radius <= 0.0 {
+            return Err(GeometryError::new("Radius must be positive".to_string()));
+        }
+        Ok(Sphere::new(raw.basis, raw.radius, raw.normal_outwards))
+    }
+}
+
 pub enum SphereTransform {
     Sphere(Sphere),
     Ellipsoid(), // TODO: Implement this
@@ -196,6 +219,24 @@ impl SurfaceLike for Sphere {
         let dist = diff.norm() - self.radius;
         Some(-(diff / diff_norm).unwrap() * dist)
     }
+
+    fn offset(&self, distance: EFloat64) -> GeometryResult<Surface> {
+        let radius = match self.normal_outwards {
+            true => self.radius + distance,
+            false => self.radius - distance,
+        };
+        if radius <= 0.0 {
+            return Err(GeometryError::new(format!(
+                "Offset of sphere {} by {} has no positive radius",
+                self.radius, distance
+            )));
+        }
+        Ok(Surface::Sphere(Sphere::new(
+            self.basis,
+            radius,
+            self.normal_outwards,
+        )))
+    }
 }
 
 impl PartialEq for Sphere {
@@ -1,16 +1,25 @@
 use geop_algebra::efloat::EFloat64;
 
-use crate::{curves::curve::Curve, point::Point, transforms::Transform};
+use crate::{
+    curves::curve::Curve, geometry_error::GeometryResult, point::Point, transforms::Transform,
+};
 
-use super::{cylinder::Cylinder, plane::Plane, sphere::Sphere, SurfaceLike};
+use super::{
+    cone::Cone, cylinder::Cylinder, nurbs::NurbsSurface, plane::Plane, sphere::Sphere,
+    torus::Torus, SurfaceLike,
+};
 
 pub type TangentPoint = Point;
 
 #[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Surface {
     Plane(Plane),
     Sphere(Sphere),
     Cylinder(Cylinder),
+    Cone(Cone),
+    Torus(Torus),
+    Nurbs(NurbsSurface),
 }
 
 impl SurfaceLike for Surface {
@@ -20,6 +29,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.transform(transform),
             Surface::Sphere(sphere) => sphere.transform(transform),
             Surface::Cylinder(cylinder) => cylinder.transform(transform),
+            Surface::Cone(cone) => Surface::Cone(cone.transform(transform)),
+            Surface::Torus(torus) => Surface::Torus(torus.transform(transform)),
+            Surface::Nurbs(nurbs) => Surface::Nurbs(nurbs.transform(transform)),
         }
     }
 
@@ -29,6 +41,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.neg(),
             Surface::Sphere(sphere) => sphere.neg(),
             Surface::Cylinder(cylinder) => cylinder.neg(),
+            Surface::Cone(cone) => Surface::Cone(cone.neg()),
+            Surface::Torus(torus) => Surface::Torus(torus.neg()),
+            Surface::Nurbs(nurbs) => Surface::Nurbs(nurbs.neg()),
         }
     }
 
@@ -38,6 +53,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.normal(p),
             Surface::Sphere(sphere) => sphere.normal(p),
             Surface::Cylinder(cylinder) => cylinder.normal(p),
+            Surface::Cone(cone) => cone.normal(p),
+            Surface::Torus(torus) => torus.normal(p),
+            Surface::Nurbs(nurbs) => nurbs.normal(p),
         }
     }
     // Checks if the point p is on the surface.
@@ -46,6 +64,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.on_surface(p),
             Surface::Sphere(sphere) => sphere.on_surface(p),
             Surface::Cylinder(cylinder) => cylinder.on_surface(p),
+            Surface::Cone(cone) => cone.on_surface(p),
+            Surface::Torus(torus) => torus.on_surface(p),
+            Surface::Nurbs(nurbs) => nurbs.on_surface(p),
         }
     }
 
@@ -55,6 +76,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.metric(x, u, v),
             Surface::Sphere(sphere) => sphere.metric(x, u, v),
             Surface::Cylinder(cylinder) => cylinder.metric(x, u, v),
+            Surface::Cone(cone) => cone.metric(x, u, v),
+            Surface::Torus(torus) => torus.metric(x, u, v),
+            Surface::Nurbs(nurbs) => nurbs.metric(x, u, v),
         }
     }
     // Returns the Riemannian distance between x and y.
@@ -63,6 +87,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.distance(x, y),
             Surface::Sphere(sphere) => sphere.distance(x, y),
             Surface::Cylinder(cylinder) => cylinder.distance(x, y),
+            Surface::Cone(cone) => cone.distance(x, y),
+            Surface::Torus(torus) => torus.distance(x, y),
+            Surface::Nurbs(nurbs) => nurbs.distance(x, y),
         }
     }
     // Exponential of u at base x. u_z is ignored.
@@ -71,6 +98,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.exp(x, u),
             Surface::Sphere(sphere) => sphere.exp(x, u),
             Surface::Cylinder(cylinder) => cylinder.exp(x, u),
+            Surface::Cone(cone) => cone.exp(x, u),
+            Surface::Torus(torus) => torus.exp(x, u),
+            Surface::Nurbs(nurbs) => nurbs.exp(x, u),
         }
     }
     // Log of y at base x. Z coordinate is set to 0.
@@ -79,6 +109,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.log(x, y),
             Surface::Sphere(sphere) => sphere.log(x, y),
             Surface::Cylinder(cylinder) => cylinder.log(x, y),
+            Surface::Cone(cone) => cone.log(x, y),
+            Surface::Torus(torus) => torus.log(x, y),
+            Surface::Nurbs(nurbs) => nurbs.log(x, y),
         }
     }
     // Parallel transport of v from x to y.
@@ -92,6 +125,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.parallel_transport(v, x, y),
             Surface::Sphere(sphere) => sphere.parallel_transport(v, x, y),
             Surface::Cylinder(cylinder) => cylinder.parallel_transport(v, x, y),
+            Surface::Cone(cone) => cone.parallel_transport(v, x, y),
+            Surface::Torus(torus) => torus.parallel_transport(v, x, y),
+            Surface::Nurbs(nurbs) => nurbs.parallel_transport(v, x, y),
         }
     }
     // Returns the geodesic between p and q.
@@ -100,6 +136,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.geodesic(x, y),
             Surface::Sphere(sphere) => sphere.geodesic(x, y),
             Surface::Cylinder(cylinder) => cylinder.geodesic(x, y),
+            Surface::Cone(cone) => cone.geodesic(x, y),
+            Surface::Torus(torus) => torus.geodesic(x, y),
+            Surface::Nurbs(nurbs) => nurbs.geodesic(x, y),
         }
     }
     // Returns a point grid on the surface, which can be used for visualization.
@@ -108,6 +147,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.point_grid(density),
             Surface::Sphere(sphere) => sphere.point_grid(density),
             Surface::Cylinder(cylinder) => cylinder.point_grid(density),
+            Surface::Cone(cone) => cone.point_grid(density),
+            Surface::Torus(torus) => torus.point_grid(density),
+            Surface::Nurbs(nurbs) => nurbs.point_grid(density),
         }
     }
     // Finds the closest point on the surface to the given point.
@@ -116,6 +158,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.project(point),
             Surface::Sphere(sphere) => sphere.project(point),
             Surface::Cylinder(cylinder) => cylinder.project(point),
+            Surface::Cone(cone) => cone.project(point),
+            Surface::Torus(torus) => torus.project(point),
+            Surface::Nurbs(nurbs) => nurbs.project(point),
         }
     }
 
@@ -125,6 +170,21 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.unsigned_l2_squared_distance_gradient(point),
             Surface::Sphere(sphere) => sphere.unsigned_l2_squared_distance_gradient(point),
             Surface::Cylinder(cylinder) => cylinder.unsigned_l2_squared_distance_gradient(point),
+            Surface::Cone(cone) => cone.unsigned_l2_squared_distance_gradient(point),
+            Surface::Torus(torus) => torus.unsigned_l2_squared_distance_gradient(point),
+            Surface::Nurbs(nurbs) => nurbs.unsigned_l2_squared_distance_gradient(point),
+        }
+    }
+
+    // Returns the surface that is moved by distance along the normal.
+    fn offset(&self, distance: EFloat64) -> GeometryResult<Surface> {
+        match self {
+            Surface::Plane(plane) => plane.offset(distance),
+            Surface::Sphere(sphere) => sphere.offset(distance),
+            Surface::Cylinder(cylinder) => cylinder.offset(distance),
+            Surface::Cone(cone) => cone.offset(distance),
+            Surface::Torus(torus) => torus.offset(distance),
+            Surface::Nurbs(nurbs) => nurbs.offset(distance),
         }
     }
 }
@@ -0,0 +1,600 @@
+use geop_algebra::efloat::EFloat64;
+
+use crate::{
+    color::Category10Color,
+    curves::{circle::Circle, curve::Curve, nurb::NurbsCurve},
+    geometry_error::{GeometryError, GeometryResult, WithContext},
+    geometry_scene::GeometryScene,
+    point::Point,
+    transforms::Transform,
+};
+
+use super::{
+    nurbs::{pin_ends, NurbsSurface},
+    surface::{Surface, TangentPoint},
+    SurfaceLike,
+};
+
+// Number of Runge-Kutta steps used to integrate a geodesic.
+const INTEGRATION_STEPS: usize = 64;
+// Number of pieces of the curve on the surface that approximates a geodesic.
+const GEODESIC_PIECES: usize = 16;
+const SHOOTING_ITERATIONS: usize = 32;
+const PRECISION: f64 = 1e-12;
+
+// The state of a geodesic in torus coordinates: the angle around the axis, the angle around the tube and their derivatives.
+type GeodesicState = [f64; 4];
+
+// Wraps an angle to [-pi, pi].
+fn wrap(angle: f64) -> f64 {
+    angle.sin().atan2(angle.cos())
+}
+
+// A ring torus. The center of the tube runs on a circle with the major radius around the axis through basis.
+// Points are parametrized by the angle theta around the axis and the angle phi around the tube, where phi = 0 is the outer equator.
+// Geodesics have no closed form. They are integrated in these coordinates and found by shooting.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "TorusRaw"))]
+pub struct Torus {
+    pub basis: Point,
+    pub axis: Point,
+    pub major_radius: EFloat64,
+    pub minor_radius: EFloat64,
+    pub normal_outwards: bool,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    ref_dir: Point,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    dir_cross: Point,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct TorusRaw {
+    basis: Point,
+    axis: Point,
+    major_radius: EFloat64,
+    minor_radius: EFloat64,
+    normal_outwards: bool,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<TorusRaw> for Torus {
+    type Error = GeometryError;
+
+    fn try_from(raw: TorusRaw) -> GeometryResult<Torus> {
+        Torus::try_new(
+            raw.basis,
+            raw.axis,
+            raw.major_radius,
+            raw.minor_radius,
+            raw.normal_outwards,
+        )
+    }
+}
+
+impl Torus {
+    pub fn try_new(
+        basis: Point,
+        axis: Point,
+        major_radius: EFloat64,
+        minor_radius: EFloat64,
+        normal_outwards: bool,
+    ) -> GeometryResult<Torus> {
+        let error_context = |err: GeometryError| {
+            err.with_context_scene(
+                format!(
+                    "Create a torus at {} with axis {}, major radius {} and minor radius {}.",
+                    basis, axis, major_radius, minor_radius
+                ),
+                GeometryScene::with_points(vec![(basis, Category10Color::Orange)]),
+            )
+        };
+        let axis = axis.normalize().with_context(&error_context)?;
+        if minor_radius <= 0.0 {
+            return Err(GeometryError::new(
+                "Minor radius must be positive".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        if major_radius <= minor_radius {
+            return Err(GeometryError::new(
+                "Major radius must be larger than the minor radius".to_string(),
+            ))
+            .with_context(&error_context);
+        }
+        let ref_dir = match Point::unit_x().cross(axis).norm_sq().lower_bound
+            > Point::unit_y().cross(axis).norm_sq().lower_bound
+        {
+            true => Point::unit_x().cross(axis).normalize().unwrap(),
+            false => Point::unit_y().cross(axis).normalize().unwrap(),
+        };
+        Ok(Torus {
+            basis,
+            axis,
+            major_radius,
+            minor_radius,
+            normal_outwards,
+            ref_dir,
+            dir_cross: axis.cross(ref_dir),
+        })
+    }
+
+    pub fn transform(&self, transform: Transform) -> Self {
+        let basis = transform * self.basis;
+        let axis = transform * (self.axis + self.basis) - basis;
+        let scale = transform.uniform_scale_factor();
+        Torus::try_new(
+            basis,
+            axis,
+            self.major_radius * scale,
+            self.minor_radius * scale,
+            self.normal_outwards,
+        )
+        .expect("Transform of a torus is a torus")
+    }
+
+    pub fn neg(&self) -> Self {
+        Torus::try_new(
+            self.basis,
+            self.axis,
+            self.major_radius,
+            self.minor_radius,
+            !self.normal_outwards,
+        )
+        .expect("Torus is valid")
+    }
+
+    // Unit direction from the axis to p. On the axis, the reference direction is used.
+    fn radial_dir(&self, p: Point) -> Point {
+        let d = p - self.basis;
+        let radial = d - d.dot(self.axis) * self.axis;
+        match radial.normalize() {
+            Ok(direction) if radial.norm() > 0.0 => direction,
+            _ => self.ref_dir,
+        }
+    }
+
+    // The closest point to p on the center circle of the tube.
+    fn tube_center(&self, p: Point) -> Point {
+        self.basis + self.radial_dir(p) * self.major_radius
+    }
+
+    pub fn point_at(&self, theta: EFloat64, phi: EFloat64) -> Point {
+        let radial_dir = self.ref_dir * theta.cos() + self.dir_cross * theta.sin();
+        self.basis
+            + radial_dir * (self.major_radius + self.minor_radius * phi.cos())
+            + self.axis * (self.minor_radius * phi.sin())
+    }
+
+    // Exact representation as a surface of revolution of the circle of the tube, with the same orientation.
+    pub fn to_nurbs(&self) -> NurbsSurface {
+        let tube = Circle::try_new(
+            self.basis + self.ref_dir * self.major_radius,
+            self.dir_cross,
+            self.minor_radius,
+        )
+        .expect("The tube of a torus is a circle");
+        let profile = NurbsCurve::try_new_arc(&tube, None, None).expect("A full circle is an arc");
+        let nurbs = NurbsSurface::try_new_revolution(&profile, self.basis, self.axis)
+            .expect("The tube does not cross the axis");
+        let ((u0, u1), (v0, v1)) = nurbs.domain();
+        let uv = ((u0 + u1) / 3.0, (v0 + v1) / 3.0);
+        match nurbs
+            .normal_at(uv)
+            .dot(self.normal(nurbs.point_at(uv.0, uv.1)))
+            < 0.0
+        {
+            true => nurbs.neg(),
+            false => nurbs,
+        }
+    }
+
+    // The angles theta and phi of the point on the torus closest to p.
+    fn angles(&self, p: Point) -> (f64, f64) {
+        let d = p - self.basis;
+        let theta = d.dot(self.dir_cross).atan2(d.dot(self.ref_dir)).to_f64();
+        let height = d.dot(self.axis);
+        let radial = (d - height * self.axis).norm();
+        let phi = height.atan2(radial - self.major_radius).to_f64();
+        (theta, phi)
+    }
+
+    // Unit tangents in direction of theta and phi.
+    fn frame(&self, theta: f64, phi: f64) -> (Point, Point) {
+        let (theta, phi) = (EFloat64::from(theta), EFloat64::from(phi));
+        let radial_dir = self.ref_dir * theta.cos() + self.dir_cross * theta.sin();
+        (
+            self.dir_cross * theta.cos() - self.ref_dir * theta.sin(),
+            self.axis * phi.cos() - radial_dir * phi.sin(),
+        )
+    }
+
+    // The tangent vector that belongs to the given state.
+    fn tangent(&self, state: GeodesicState) -> Point {
+        let (e_theta, e_phi) = self.frame(state[0], state[1]);
+        let rho = self.major_radius.to_f64() + self.minor_radius.to_f64() * state[1].cos();
+        e_theta * EFloat64::from(state[2] * rho)
+            + e_phi * EFloat64::from(state[3] * self.minor_radius.to_f64())
+    }
+
+    // The state at x that starts into the direction of the tangent vector u.
+    fn start_state(&self, x: Point, u: TangentPoint) -> GeodesicState {
+        let (theta, phi) = self.angles(x);
+        let (e_theta, e_phi) = self.frame(theta, phi);
+        let rho = self.major_radius.to_f64() + self.minor_radius.to_f64() * phi.cos();
+        [
+            theta,
+            phi,
+            u.dot(e_theta).to_f64() / rho,
+            u.dot(e_phi).to_f64() / self.minor_radius.to_f64(),
+        ]
+    }
+
+    // The geodesic equations for the metric (R + r cos(phi))^2 dtheta^2 + r^2 dphi^2.
+    fn derivative(&self, state: GeodesicState) -> GeodesicState {
+        let (major, minor) = (self.major_radius.to_f64(), self.minor_radius.to_f64());
+        let [_, phi, d_theta, d_phi] = state;
+        let rho = major + minor * phi.cos();
+        [
+            d_theta,
+            d_phi,
+            2.0 * minor * phi.sin() / rho * d_theta * d_phi,
+            -rho * phi.sin() / minor * d_theta * d_theta,
+        ]
+    }
+
+    // Integrates the geodesic over the unit interval with the classical Runge-Kutta method and returns all intermediate states.
+    fn integrate(&self, start: GeodesicState) -> Vec<GeodesicState> {
+        let h = 1.0 / INTEGRATION_STEPS as f64;
+        let step = |s: GeodesicState, k: GeodesicState, f: f64| -> GeodesicState {
+            [0, 1, 2, 3].map(|i| s[i] + f * h * k[i])
+        };
+        let mut states = Vec::with_capacity(INTEGRATION_STEPS + 1);
+        let mut state = start;
+        states.push(state);
+        for _ in 0..INTEGRATION_STEPS {
+            let k1 = self.derivative(state);
+            let k2 = self.derivative(step(state, k1, 0.5));
+            let k3 = self.derivative(step(state, k2, 0.5));
+            let k4 = self.derivative(step(state, k3, 1.0));
+            state = [0, 1, 2, 3]
+                .map(|i| state[i] + h / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]));
+            states.push(state);
+        }
+        states
+    }
+
+    // Finds the start state of the geodesic from x to y with Newton iterations on the initial velocity.
+    // The iteration starts with the path that is straight in the angles, which is exact for meridians and equators.
+    fn shoot(&self, x: Point, y: Point) -> Option<GeodesicState> {
+        let (theta_x, phi_x) = self.angles(x);
+        let (theta_y, phi_y) = self.angles(y);
+        let residual = |velocity: (f64, f64)| {
+            let end = *self
+                .integrate([theta_x, phi_x, velocity.0, velocity.1])
+                .last()
+                .unwrap();
+            (wrap(end[0] - theta_y), wrap(end[1] - phi_y))
+        };
+        let norm = |r: (f64, f64)| r.0.abs().max(r.1.abs());
+
+        let mut velocity = (wrap(theta_y - theta_x), wrap(phi_y - phi_x));
+        let mut r = residual(velocity);
+        for _ in 0..SHOOTING_ITERATIONS {
+            if norm(r) <= PRECISION {
+                break;
+            }
+            let h = 1e-7;
+            let r_theta = residual((velocity.0 + h, velocity.1));
+            let r_phi = residual((velocity.0, velocity.1 + h));
+            let (a, b) = ((r_theta.0 - r.0) / h, (r_phi.0 - r.0) / h);
+            let (c, d) = ((r_theta.1 - r.1) / h, (r_phi.1 - r.1) / h);
+            let det = a * d - b * c;
+            if det.abs() <= f64::EPSILON {
+                return None;
+            }
+            let step = ((d * r.0 - b * r.1) / det, (a * r.1 - c * r.0) / det);
+            let mut factor = 1.0;
+            loop {
+                let candidate = (velocity.0 - factor * step.0, velocity.1 - factor * step.1);
+                let r_candidate = residual(candidate);
+                if norm(r_candidate) < norm(r) {
+                    (velocity, r) = (candidate, r_candidate);
+                    break;
+                }
+                factor /= 2.0;
+                if factor < 1e-4 {
+                    return None;
+                }
+            }
+        }
+        if norm(r) > PRECISION {
+            return None;
+        }
+        Some([theta_x, phi_x, velocity.0, velocity.1])
+    }
+}
+
+impl SurfaceLike for Torus {
+    fn transform(&self, transform: Transform) -> Surface {
+        Surface::Torus(self.transform(transform))
+    }
+
+    fn neg(&self) -> Surface {
+        Surface::Torus(self.neg())
+    }
+
+    fn normal(&self, p: Point) -> Point {
+        let normal = (p - self.tube_center(p)).normalize().unwrap();
+        if self.normal_outwards {
+            normal
+        } else {
+            -normal
+        }
+    }
+
+    fn on_surface(&self, p: Point) -> bool {
+        ((p - self.tube_center(p)).norm() - self.minor_radius) == 0.0
+    }
+
+    fn metric(&self, _x: Point, u: TangentPoint, v: TangentPoint) -> EFloat64 {
+        u.dot(v)
+    }
+
+    fn distance(&self, x: Point, y: Point) -> EFloat64 {
+        match self.log(x, y) {
+            Some(u) => u.norm(),
+            None => (y - x).norm(),
+        }
+    }
+
+    fn exp(&self, x: Point, u: TangentPoint) -> Point {
+        assert!(self.on_surface(x));
+        if u.norm() == 0.0 {
+            return x;
+        }
+        let end = *self.integrate(self.start_state(x, u)).last().unwrap();
+        self.point_at(EFloat64::from(end[0]), EFloat64::from(end[1]))
+    }
+
+    fn log(&self, x: Point, y: Point) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        if x == y {
+            return Some(Point::zero());
+        }
+        self.shoot(x, y).map(|start| self.tangent(start))
+    }
+
+    // A parallel vector field along a geodesic keeps its angle to the geodesic.
+    fn parallel_transport(
+        &self,
+        v: Option<TangentPoint>,
+        x: Point,
+        y: Point,
+    ) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        let v = v?;
+        if x == y {
+            return Some(v);
+        }
+        let start = self.shoot(x, y)?;
+        let end = *self.integrate(start).last().unwrap();
+        let t_x = self.tangent(start).normalize().ok()?;
+        let t_y = self.tangent(end).normalize().ok()?;
+        let b_x = self.normal(x).cross(t_x);
+        let b_y = self.normal(y).cross(t_y);
+        Some(t_y * v.dot(t_x) + b_y * v.dot(b_x))
+    }
+
+    // Meridians and equators are circles. All other geodesics are sampled at the integration steps.
+    fn geodesic(&self, p: Point, q: Point) -> Curve {
+        assert!(self.on_surface(p));
+        assert!(self.on_surface(q));
+        assert!(p != q);
+        let (theta_p, phi_p) = self.angles(p);
+        let (theta_q, phi_q) = self.angles(q);
+        let circle = |center: Point, fallback_normal: Point| {
+            let normal = (p - center)
+                .cross(q - center)
+                .normalize()
+                .unwrap_or(fallback_normal);
+            Curve::Circle(Circle::try_new(center, normal, (p - center).norm()).unwrap())
+        };
+        if wrap(theta_q - theta_p).abs() <= PRECISION {
+            let (e_theta, _) = self.frame(theta_p, phi_p);
+            return circle(self.tube_center(p), e_theta);
+        }
+        if wrap(phi_q - phi_p).abs() <= PRECISION && phi_p.sin().abs() <= PRECISION {
+            let center = self.basis + self.axis * (p - self.basis).dot(self.axis);
+            return circle(center, self.axis);
+        }
+
+        let start = self
+            .shoot(p, q)
+            .expect("Geodesic on the torus could not be found");
+        // The states are lifted onto the exact surface, as a polyline through them would leave the torus
+        let points = self
+            .integrate(start)
+            .iter()
+            .step_by(INTEGRATION_STEPS / GEODESIC_PIECES)
+            .map(|state| self.point_at(EFloat64::from(state[0]), EFloat64::from(state[1])))
+            .collect::<Vec<Point>>();
+        let nurbs = self.to_nurbs();
+        let curve = nurbs
+            .curve_on_surface(&nurbs.parameters_along(&points))
+            .expect("Points of a geodesic are distinct");
+        Curve::Nurbs(pin_ends(curve, p, q))
+    }
+
+    fn point_grid(&self, density: f64) -> Vec<Point> {
+        let n = (16.0 * density) as usize;
+        let m = (16.0 * density) as usize;
+        let mut points = Vec::with_capacity(n * m);
+        for i in 0..n {
+            for j in 0..m {
+                let theta = EFloat64::from(2.0 * std::f64::consts::PI * i as f64 / n as f64);
+                let phi = EFloat64::from(2.0 * std::f64::consts::PI * j as f64 / m as f64);
+                points.push(self.point_at(theta, phi));
+            }
+        }
+        points
+    }
+
+    fn project(&self, point: Point) -> Point {
+        let center = self.tube_center(point);
+        let direction = match (point - center).normalize() {
+            Ok(direction) => direction,
+            Err(_) => self.radial_dir(point),
+        };
+        center + direction * self.minor_radius
+    }
+
+    fn unsigned_l2_squared_distance_gradient(&self, point: Point) -> Option<Point> {
+        Some(self.project(point) - point)
+    }
+
+    fn offset(&self, distance: EFloat64) -> GeometryResult<Surface> {
+        let minor_radius = match self.normal_outwards {
+            true => self.minor_radius + distance,
+            false => self.minor_radius - distance,
+        };
+        if minor_radius <= 0.0 {
+            return Err(GeometryError::new(format!(
+                "Offset of torus with minor radius {} by {} has no positive minor radius",
+                self.minor_radius, distance
+            )));
+        }
+        Ok(Surface::Torus(Torus::try_new(
+            self.basis,
+            self.axis,
+            self.major_radius,
+            minor_radius,
+            self.normal_outwards,
+        )?))
+    }
+}
+
+impl PartialEq for Torus {
+    fn eq(&self, other: &Torus) -> bool {
+        self.basis == other.basis
+            && self.axis.is_parallel(other.axis)
+            && (self.major_radius - other.major_radius) == 0.0
+            && (self.minor_radius - other.minor_radius) == 0.0
+            && self.normal_outwards == other.normal_outwards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::CurveLike;
+
+    fn torus() -> Torus {
+        Torus::try_new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(2.0),
+            EFloat64::from(0.5),
+            true,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_torus_on_surface_and_normal() {
+        let torus = torus();
+        let p = Point::from_f64(2.5, 0.0, 0.0);
+        assert!(torus.on_surface(p));
+        assert!(torus.on_surface(Point::from_f64(0.0, -2.0, 0.5)));
+        assert!(!torus.on_surface(Point::from_f64(2.0, 0.0, 0.0)));
+        assert_eq!(torus.normal(p), Point::unit_x());
+        assert_eq!(
+            torus.normal(Point::from_f64(0.0, 1.5, 0.0)),
+            -Point::unit_y()
+        );
+        assert_eq!(
+            torus.project(Point::from_f64(0.0, 3.0, 0.0)),
+            Point::from_f64(0.0, 2.5, 0.0)
+        );
+        assert!(Torus::try_new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::one(),
+            EFloat64::two(),
+            true
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_torus_exp_log() {
+        let torus = torus();
+        let x = torus.point_at(EFloat64::from(0.3), EFloat64::from(0.2));
+        for (theta, phi) in [(1.2, 0.9), (0.3, 2.0), (-0.8, -1.0), (2.0, 0.2)] {
+            let y = torus.point_at(EFloat64::from(theta), EFloat64::from(phi));
+            let u = torus.log(x, y).unwrap();
+            assert!(u.dot(torus.normal(x)).abs() < 1e-12);
+            assert!((torus.exp(x, u) - y).norm() < 1e-9);
+            // Both directions are integrated independently, so they agree up to the integration error
+            assert!((torus.distance(x, y) - torus.distance(y, x)).abs() < 1e-6);
+            assert!(torus.distance(x, y) <= (y - x).norm() * EFloat64::from(2.0));
+            assert!(torus.distance(x, y) >= (y - x).norm());
+        }
+    }
+
+    #[test]
+    fn test_torus_geodesic() {
+        let torus = torus();
+        // The outer equator and the meridians are geodesics
+        let x = Point::from_f64(2.5, 0.0, 0.0);
+        match torus.geodesic(x, Point::from_f64(0.0, 2.5, 0.0)) {
+            Curve::Circle(circle) => assert_eq!(circle.basis, Point::zero()),
+            _ => panic!("Geodesic should be a circle"),
+        }
+        match torus.geodesic(x, Point::from_f64(2.0, 0.0, 0.5)) {
+            Curve::Circle(circle) => assert_eq!(circle.basis, Point::from_f64(2.0, 0.0, 0.0)),
+            _ => panic!("Geodesic should be a circle"),
+        }
+
+        let y = torus.point_at(EFloat64::from(1.0), EFloat64::from(1.0));
+        let geodesic = torus.geodesic(x, y);
+        let mid = geodesic.get_midpoint(Some(x), Some(y)).unwrap();
+        assert!((torus.project(mid) - mid).norm() < 1e-3);
+
+        // Parallel transport keeps lengths and maps the geodesic direction to the geodesic direction
+        let u = torus.log(x, y).unwrap();
+        let transported = torus.parallel_transport(Some(u), x, y).unwrap();
+        assert!((transported.norm() - u.norm()).abs() < 1e-9);
+        assert!((transported + torus.log(y, x).unwrap()).norm() < 1e-6);
+    }
+
+    #[test]
+    fn test_torus_offset() {
+        let torus = torus();
+        let offset = torus.offset(EFloat64::from(0.25)).unwrap();
+        assert!(offset.on_surface(Point::from_f64(2.75, 0.0, 0.0)));
+        assert!(offset.on_surface(Point::from_f64(0.0, 2.0, 0.75)));
+        assert!(torus.offset(EFloat64::from(-0.5)).is_err());
+        assert!(torus.offset(EFloat64::from(2.0)).is_err());
+    }
+
+    #[test]
+    fn test_torus_to_nurbs() {
+        for torus in [torus(), torus().neg()] {
+            let nurbs = torus.to_nurbs();
+            for i in 0..6 {
+                for j in 0..6 {
+                    let theta = EFloat64::from(1.1 * i as f64);
+                    let phi = EFloat64::from(0.9 * j as f64 + 0.2);
+                    let p = torus.point_at(theta, phi);
+                    assert!(nurbs.on_surface(p));
+                    assert!((nurbs.normal(p) - torus.normal(p)).norm() < 1e-6);
+                }
+            }
+            assert!(!nurbs.on_surface(Point::from_f64(2.0, 0.0, 0.0)));
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::{fmt::Display, ops::Mul};
 
 use geop_algebra::efloat::EFloat64;
 
-use crate::point::Point;
+use crate::{geometry_error::GeometryResult, point::Point};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Transform {
@@ -107,20 +107,53 @@ impl Transform {
         Transform { matrix }
     }
 
+    // Rotation around an axis through the origin, counter-clockwise when looked at from the axis direction.
+    pub fn from_axis_angle(axis: Point, angle: EFloat64) -> GeometryResult<Transform> {
+        let axis = axis.normalize()?;
+        let (sin, cos) = (angle.sin(), angle.cos());
+        let one_minus_cos = EFloat64::one() - cos;
+        let (x, y, z) = (axis.x, axis.y, axis.z);
+        let mut matrix = [[EFloat64::zero(); 4]; 4];
+        matrix[0][0] = cos + x * x * one_minus_cos;
+        matrix[0][1] = x * y * one_minus_cos - z * sin;
+        matrix[0][2] = x * z * one_minus_cos + y * sin;
+        matrix[1][0] = y * x * one_minus_cos + z * sin;
+        matrix[1][1] = cos + y * y * one_minus_cos;
+        matrix[1][2] = y * z * one_minus_cos - x * sin;
+        matrix[2][0] = z * x * one_minus_cos - y * sin;
+        matrix[2][1] = z * y * one_minus_cos + x * sin;
+        matrix[2][2] = cos + z * z * one_minus_cos;
+        matrix[3][3] = EFloat64::one();
+        Ok(Transform { matrix })
+    }
+
+    // The scale factor is the length of the columns of the linear part, which is the same for all columns if the scaling is uniform.
     pub fn uniform_scale_factor(&self) -> EFloat64 {
-        let scale_x = self.matrix[0][0] + self.matrix[0][1] + self.matrix[0][2];
-        let scale_y = self.matrix[1][0] + self.matrix[1][1] + self.matrix[1][2];
-        let scale_z = self.matrix[2][0] + self.matrix[2][1] + self.matrix[2][2];
-        assert!(
-            (scale_x.abs() - scale_y.abs()) == 0.0,
-            "Scale must be uniform"
-        );
-        assert!(
-            (scale_x.abs() - scale_z.abs()) == 0.0,
-            "Scale must be uniform"
-        );
+        let column_norm =
+            |j: usize| Point::new(self.matrix[0][j], self.matrix[1][j], self.matrix[2][j]).norm();
+        let scale_x = column_norm(0);
+        let scale_y = column_norm(1);
+        let scale_z = column_norm(2);
+        assert!((scale_x - scale_y) == 0.0, "Scale must be uniform");
+        assert!((scale_x - scale_z) == 0.0, "Scale must be uniform");
         return scale_x;
     }
+
+    // Upper bound of the factor by which the transform stretches any distance, also for non-uniform scaling and shear.
+    // The largest eigenvalue of A^T A, with A the linear part, is bounded by its largest absolute row sum.
+    pub fn max_stretch(&self) -> EFloat64 {
+        let ata = |i: usize, j: usize| {
+            (0..3).fold(EFloat64::zero(), |sum, k| {
+                sum + self.matrix[k][i] * self.matrix[k][j]
+            })
+        };
+        let row_sum = |i: usize| (0..3).fold(EFloat64::zero(), |sum, j| sum + ata(i, j).abs());
+        row_sum(0)
+            .max(row_sum(1))
+            .max(row_sum(2))
+            .sqrt()
+            .expect("Sums of absolute values are not negative")
+    }
 }
 
 #[cfg(test)]
@@ -155,4 +188,26 @@ mod tests {
             Point::from_f64(0.0, 0.0, 1.0)
         );
     }
+
+    #[test]
+    fn test_axis_angle() {
+        let t = Transform::from_axis_angle(Point::unit_z(), EFloat64::half_pi()).unwrap();
+        assert_eq!(t * Point::unit_x(), Point::unit_y());
+        assert_eq!(t * Point::unit_z(), Point::unit_z());
+        assert_eq!(t.uniform_scale_factor(), 1.0);
+
+        let t = Transform::from_axis_angle(Point::ones(), EFloat64::from(0.7)).unwrap()
+            * Transform::from_scale(Point::from_f64(2.0, 2.0, 2.0));
+        assert_eq!(t.uniform_scale_factor(), 2.0);
+        assert_eq!(t * Point::ones(), Point::from_f64(2.0, 2.0, 2.0));
+        assert_eq!(t.max_stretch(), 2.0);
+    }
+
+    #[test]
+    fn test_max_stretch() {
+        let t = Transform::from_scale(Point::from_f64(1.0, 3.0, 0.5));
+        assert_eq!(t.max_stretch(), 3.0);
+        let t = Transform::from_translation(Point::from_f64(4.0, 5.0, 6.0));
+        assert_eq!(t.max_stretch(), 1.0);
+    }
 }
@@ -3,17 +3,33 @@ use geop_topology::topology::{edge::Edge, scene::Color};
 
 use crate::{
     edge_buffer::{EdgeBuffer, RenderEdge},
+    tolerance::RasterizeTolerance,
     vertex_buffer::{RenderVertex, VertexBuffer},
 };
 
 // Rasterizes an edge loop into triangle list.
 pub fn rasterize_edge_into_line_list(edge: &Edge, color: Color) -> EdgeBuffer {
-    let n = match edge.curve {
+    let n = match &edge.curve {
         Curve::Line(_) => 10,
         Curve::Circle(_) => 32,
         Curve::Ellipse(_) => 32,
         Curve::Helix(_) => 32 * HORIZON_DIST as usize,
+        Curve::SampledCurve(sampled) => sampled.points.len(),
+        Curve::Nurbs(nurbs) => 8 * nurbs.control_points.len(),
     };
+    rasterize_edge_into_segments(edge, n, color)
+}
+
+// Rasterizes an edge into as many segments as the tolerance requires.
+pub fn rasterize_edge_into_line_list_with_tolerance(
+    edge: &Edge,
+    color: Color,
+    tolerance: &RasterizeTolerance,
+) -> EdgeBuffer {
+    rasterize_edge_into_segments(edge, tolerance.edge_segments(edge), color)
+}
+
+fn rasterize_edge_into_segments(edge: &Edge, n: usize, color: Color) -> EdgeBuffer {
     let mut edges = Vec::<RenderEdge>::with_capacity(n);
     for j in 0..n {
         let v1 = (j as f64) / n as f64;
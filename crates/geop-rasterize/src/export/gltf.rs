@@ -0,0 +1,245 @@
+use std::io::{Result, Write};
+
+use super::{center, corners_and_normals, group_by_color, MeshBuffers};
+
+const GLB_MAGIC: u32 = 0x46546C67;
+const GLB_VERSION: u32 = 2;
+const CHUNK_JSON: u32 = 0x4E4F534A;
+const CHUNK_BIN: u32 = 0x004E4942;
+
+// Primitive modes of glTF
+const MODE_POINTS: u32 = 0;
+const MODE_LINES: u32 = 1;
+const MODE_TRIANGLES: u32 = 4;
+
+// Collects the vertex data in one binary buffer, with one buffer view and accessor per attribute.
+struct Attributes {
+    bin: Vec<u8>,
+    buffer_views: Vec<String>,
+    accessors: Vec<String>,
+}
+
+impl Attributes {
+    fn new() -> Self {
+        Attributes {
+            bin: Vec::new(),
+            buffer_views: Vec::new(),
+            accessors: Vec::new(),
+        }
+    }
+
+    // Adds a list of vectors and returns the index of its accessor. Positions need their bounds.
+    fn push(&mut self, vectors: &[[f32; 3]], with_bounds: bool) -> usize {
+        let offset = self.bin.len();
+        for value in vectors.iter().flatten() {
+            self.bin.extend_from_slice(&value.to_le_bytes());
+        }
+        self.buffer_views.push(format!(
+            "{{\"buffer\":0,\"byteOffset\":{},\"byteLength\":{}}}",
+            offset,
+            self.bin.len() - offset
+        ));
+
+        let bounds = match with_bounds {
+            true => {
+                let mut min = [f32::INFINITY; 3];
+                let mut max = [f32::NEG_INFINITY; 3];
+                for v in vectors.iter() {
+                    for k in 0..3 {
+                        min[k] = min[k].min(v[k]);
+                        max[k] = max[k].max(v[k]);
+                    }
+                }
+                format!(
+                    ",\"min\":[{},{},{}],\"max\":[{},{},{}]",
+                    min[0], min[1], min[2], max[0], max[1], max[2]
+                )
+            }
+            false => String::new(),
+        };
+        // Component type 5126 is a 32 bit float
+        self.accessors.push(format!(
+            "{{\"bufferView\":{},\"componentType\":5126,\"count\":{},\"type\":\"VEC3\"{}}}",
+            self.buffer_views.len() - 1,
+            vectors.len(),
+            bounds
+        ));
+        self.accessors.len() - 1
+    }
+}
+
+fn material(color: &[f32; 4]) -> String {
+    let alpha_mode = match color[3] < 1.0 {
+        true => ",\"alphaMode\":\"BLEND\"",
+        false => "",
+    };
+    format!(
+        "{{\"pbrMetallicRoughness\":{{\"baseColorFactor\":[{},{},{},{}],\"metallicFactor\":0,\"roughnessFactor\":1}}{}}}",
+        color[0], color[1], color[2], color[3], alpha_mode
+    )
+}
+
+// Writes the buffers as binary glTF 2.0 with one material per color.
+// Triangles, edges and points become triangle, line and point primitives of a single mesh. Triangles without area are left out.
+pub fn write_glb(buffers: &MeshBuffers, writer: &mut impl Write) -> Result<()> {
+    let colors = buffers.colors();
+    let mut attributes = Attributes::new();
+    let mut primitives = Vec::<String>::new();
+
+    let triangles = buffers
+        .triangles
+        .triangles
+        .iter()
+        .filter_map(|triangle| Some((triangle.a.color, corners_and_normals(triangle)?)));
+    for (material, group) in group_by_color(&colors, triangles) {
+        let positions = group.iter().flat_map(|(c, _)| *c).collect::<Vec<_>>();
+        let normals = group.iter().flat_map(|(_, n)| *n).collect::<Vec<_>>();
+        let position = attributes.push(&positions, true);
+        let normal = attributes.push(&normals, false);
+        primitives.push(format!(
+            "{{\"attributes\":{{\"POSITION\":{},\"NORMAL\":{}}},\"mode\":{},\"material\":{}}}",
+            position, normal, MODE_TRIANGLES, material
+        ));
+    }
+
+    let edges = buffers.edges.edges.iter().map(|edge| {
+        (
+            edge.start.color,
+            [edge.start, edge.end].map(|v| center(v.min_position, v.max_position)),
+        )
+    });
+    for (material, group) in group_by_color(&colors, edges) {
+        let positions = group.into_iter().flatten().collect::<Vec<_>>();
+        let position = attributes.push(&positions, true);
+        primitives.push(format!(
+            "{{\"attributes\":{{\"POSITION\":{}}},\"mode\":{},\"material\":{}}}",
+            position, MODE_LINES, material
+        ));
+    }
+
+    let points = buffers.points.vertices.iter().map(|vertex| {
+        (
+            vertex.color,
+            center(vertex.min_position, vertex.max_position),
+        )
+    });
+    for (material, group) in group_by_color(&colors, points) {
+        let position = attributes.push(&group, true);
+        primitives.push(format!(
+            "{{\"attributes\":{{\"POSITION\":{}}},\"mode\":{},\"material\":{}}}",
+            position, MODE_POINTS, material
+        ));
+    }
+
+    // A mesh needs at least one primitive, so an empty export has a scene without nodes
+    let mut json =
+        "{\"asset\":{\"version\":\"2.0\",\"generator\":\"geop\"},\"scene\":0".to_string();
+    if primitives.is_empty() {
+        json += ",\"scenes\":[{}]}";
+    } else {
+        json += &format!(
+            ",\"scenes\":[{{\"nodes\":[0]}}],\"nodes\":[{{\"mesh\":0}}],\"meshes\":[{{\"primitives\":[{}]}}],\"materials\":[{}],\"buffers\":[{{\"byteLength\":{}}}],\"bufferViews\":[{}],\"accessors\":[{}]}}",
+            primitives.join(","),
+            colors.iter().map(material).collect::<Vec<_>>().join(","),
+            attributes.bin.len(),
+            attributes.buffer_views.join(","),
+            attributes.accessors.join(",")
+        );
+    }
+
+    // Chunks are aligned to 4 bytes, the JSON with spaces and the binary data with zeros
+    let mut json = json.into_bytes();
+    json.resize(json.len().next_multiple_of(4), b' ');
+    let mut bin = attributes.bin;
+    bin.resize(bin.len().next_multiple_of(4), 0);
+
+    let mut length = 12 + 8 + json.len();
+    if !bin.is_empty() {
+        length += 8 + bin.len();
+    }
+    for value in [GLB_MAGIC, GLB_VERSION, length as u32] {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    writer.write_all(&(json.len() as u32).to_le_bytes())?;
+    writer.write_all(&CHUNK_JSON.to_le_bytes())?;
+    writer.write_all(&json)?;
+    if !bin.is_empty() {
+        writer.write_all(&(bin.len() as u32).to_le_bytes())?;
+        writer.write_all(&CHUNK_BIN.to_le_bytes())?;
+        writer.write_all(&bin)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::{efloat::EFloat64, point::Point};
+    use geop_topology::{
+        primitive_objects::volumes::cube::primitive_cube,
+        topology::scene::{Color, Scene},
+    };
+
+    use crate::tolerance::RasterizeTolerance;
+
+    use super::*;
+
+    fn chunks(bytes: &[u8]) -> (String, Vec<u8>) {
+        let word = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+        assert_eq!(word(0), GLB_MAGIC);
+        assert_eq!(word(4), GLB_VERSION);
+        assert_eq!(word(8) as usize, bytes.len());
+        assert_eq!(word(16), CHUNK_JSON);
+        let json_end = 20 + word(12) as usize;
+        let json = String::from_utf8(bytes[20..json_end].to_vec()).unwrap();
+        let bin = match json_end < bytes.len() {
+            true => {
+                assert_eq!(word(json_end + 4), CHUNK_BIN);
+                bytes[json_end + 8..json_end + 8 + word(json_end) as usize].to_vec()
+            }
+            false => Vec::new(),
+        };
+        (json, bin)
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut bytes = Vec::<u8>::new();
+        write_glb(
+            &MeshBuffers::from_scene(&Scene::empty(), &RasterizeTolerance::default()),
+            &mut bytes,
+        )
+        .unwrap();
+        let (json, bin) = chunks(&bytes);
+        assert!(json.contains("\"scenes\":[{}]"));
+        assert!(bin.is_empty());
+    }
+
+    #[test]
+    fn test_scene() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let mut scene = Scene::empty();
+        scene.volumes.push((cube, Color::red()));
+        scene
+            .points
+            .push((Point::unit_z() * EFloat64::two(), Color::blue()));
+        let buffers = MeshBuffers::from_scene(&scene, &RasterizeTolerance::default());
+
+        let mut bytes = Vec::<u8>::new();
+        write_glb(&buffers, &mut bytes).unwrap();
+        let (json, bin) = chunks(&bytes);
+
+        // The boundaries share the color of the faces, so there are two materials
+        assert_eq!(json.matches("baseColorFactor").count(), 2);
+        assert!(json.contains("\"baseColorFactor\":[1,0,0,1]"));
+        assert!(json.contains("\"baseColorFactor\":[0,0,1,1]"));
+        assert_eq!(json.matches("\"mode\":4").count(), 1);
+        assert_eq!(json.matches("\"mode\":1").count(), 1);
+        assert_eq!(json.matches("\"mode\":0").count(), 1);
+
+        // Triangles have positions and normals, lines have two positions and the point has one
+        let triangles = buffers.triangles.triangles.len();
+        let edges = buffers.edges.edges.len();
+        assert!(triangles >= 12);
+        assert_eq!(bin.len(), 12 * (6 * triangles + 2 * edges + 1));
+    }
+}
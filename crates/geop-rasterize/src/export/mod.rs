@@ -0,0 +1,155 @@
+pub mod gltf;
+pub mod obj;
+pub mod stl;
+
+use geop_geometry::point::Point;
+use geop_topology::topology::scene::Scene;
+
+use crate::{
+    contour::rasterize_contour_into_line_list_with_tolerance,
+    edge::rasterize_edge_into_line_list_with_tolerance,
+    edge_buffer::EdgeBuffer,
+    face::rasterize_face_into_triangle_list_with_tolerance,
+    tolerance::RasterizeTolerance,
+    triangle_buffer::{RenderTriangle, TriangleBuffer},
+    vertex_normal_buffer::{RenderNormalVertex, VertexNormalBuffer},
+};
+
+// The rasterized triangles, lines and points that are written to a mesh file together.
+pub struct MeshBuffers {
+    pub triangles: TriangleBuffer,
+    pub edges: EdgeBuffer,
+    pub points: VertexNormalBuffer,
+}
+
+impl MeshBuffers {
+    pub fn new(triangles: TriangleBuffer, edges: EdgeBuffer, points: VertexNormalBuffer) -> Self {
+        MeshBuffers {
+            triangles,
+            edges,
+            points,
+        }
+    }
+
+    // Rasterizes the faces of the scene in their colors, together with their boundaries and the edges and points of the scene.
+    pub fn from_scene(scene: &Scene, tolerance: &RasterizeTolerance) -> Self {
+        let mut triangles = TriangleBuffer::empty();
+        let mut edges = EdgeBuffer::empty();
+
+        let faces = scene
+            .volumes
+            .iter()
+            .flat_map(|(volume, color)| {
+                volume
+                    .all_faces()
+                    .into_iter()
+                    .map(move |face| (face, *color))
+            })
+            .chain(scene.faces.iter().cloned());
+        for (face, color) in faces {
+            triangles.join(&rasterize_face_into_triangle_list_with_tolerance(
+                &face, color, tolerance,
+            ));
+            for contour in face.boundaries().iter() {
+                edges.join(&rasterize_contour_into_line_list_with_tolerance(
+                    contour, color, tolerance,
+                ));
+            }
+        }
+        for (edge, color) in scene.edges.iter() {
+            edges.join(&rasterize_edge_into_line_list_with_tolerance(
+                edge, *color, tolerance,
+            ));
+        }
+        let points = scene
+            .points
+            .iter()
+            .map(|(point, color)| RenderNormalVertex::new(*point, *color, Point::zero()))
+            .collect();
+
+        MeshBuffers::new(triangles, edges, VertexNormalBuffer::new(points))
+    }
+
+    // The distinct colors of the triangles, lines and points, in the order in which they appear.
+    pub fn colors(&self) -> Vec<[f32; 4]> {
+        let mut colors = Vec::<[f32; 4]>::new();
+        let all = self
+            .triangles
+            .triangles
+            .iter()
+            .map(|triangle| triangle.a.color)
+            .chain(self.edges.edges.iter().map(|edge| edge.start.color))
+            .chain(self.points.vertices.iter().map(|vertex| vertex.color));
+        for color in all {
+            if !colors.contains(&color) {
+                colors.push(color);
+            }
+        }
+        colors
+    }
+}
+
+// Groups the items by color, keeping the order of the colors in the given list.
+pub(crate) fn group_by_color<T>(
+    colors: &[[f32; 4]],
+    items: impl Iterator<Item = ([f32; 4], T)>,
+) -> Vec<(usize, Vec<T>)> {
+    let mut groups = colors
+        .iter()
+        .enumerate()
+        .map(|(i, _)| (i, Vec::<T>::new()))
+        .collect::<Vec<(usize, Vec<T>)>>();
+    for (color, item) in items {
+        let i = colors.iter().position(|c| *c == color).unwrap();
+        groups[i].1.push(item);
+    }
+    groups.retain(|(_, items)| !items.is_empty());
+    groups
+}
+
+// One vector for each corner of a triangle.
+pub(crate) type Corners = [[f32; 3]; 3];
+
+// The corners of a triangle with a unit normal at each corner, or None if the triangle has no area.
+// Corners without a usable normal get the normal of the triangle.
+pub(crate) fn corners_and_normals(triangle: &RenderTriangle) -> Option<(Corners, Corners)> {
+    let vertices = [triangle.a, triangle.b, triangle.c];
+    let corners = vertices.map(|v| center(v.min_position, v.max_position));
+    let facet_normal = normalize(cross(
+        sub(corners[1], corners[0]),
+        sub(corners[2], corners[0]),
+    ))?;
+    let normals =
+        vertices.map(|v| normalize(center(v.min_normal, v.max_normal)).unwrap_or(facet_normal));
+    Some((corners, normals))
+}
+
+// The center of the bounds of a rasterized position or normal.
+pub(crate) fn center(min: [f32; 3], max: [f32; 3]) -> [f32; 3] {
+    [
+        (min[0] + max[0]) / 2.0,
+        (min[1] + max[1]) / 2.0,
+        (min[2] + max[2]) / 2.0,
+    ]
+}
+
+pub(crate) fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+pub(crate) fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+// Scales the vector to unit length, or returns None if it is too short to have a direction.
+pub(crate) fn normalize(v: [f32; 3]) -> Option<[f32; 3]> {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm <= f32::EPSILON * f32::EPSILON {
+        return None;
+    }
+    Some(v.map(|x| x / norm))
+}
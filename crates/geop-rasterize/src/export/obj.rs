@@ -0,0 +1,168 @@
+use std::{
+    collections::HashMap,
+    io::{Result, Write},
+};
+
+use super::{center, corners_and_normals, group_by_color, MeshBuffers};
+
+// Numbers the distinct vectors in the order in which they are first used, starting at 1 as OBJ does.
+struct Indices {
+    indices: HashMap<[u32; 3], usize>,
+    vectors: Vec<[f32; 3]>,
+}
+
+impl Indices {
+    fn new() -> Self {
+        Indices {
+            indices: HashMap::new(),
+            vectors: Vec::new(),
+        }
+    }
+
+    fn index(&mut self, v: [f32; 3]) -> usize {
+        let vectors = &mut self.vectors;
+        *self.indices.entry(v.map(f32::to_bits)).or_insert_with(|| {
+            vectors.push(v);
+            vectors.len()
+        })
+    }
+}
+
+// Writes the buffers as Wavefront OBJ, with one material per color in a separate MTL file.
+// Triangles become faces, edges become lines and points become points. Triangles without area are left out.
+pub fn write_obj(
+    buffers: &MeshBuffers,
+    mtl_file_name: &str,
+    obj: &mut impl Write,
+    mtl: &mut impl Write,
+) -> Result<()> {
+    let colors = buffers.colors();
+    for (i, color) in colors.iter().enumerate() {
+        writeln!(mtl, "newmtl color_{}", i)?;
+        writeln!(mtl, "Kd {} {} {}", color[0], color[1], color[2])?;
+        writeln!(mtl, "d {}", color[3])?;
+    }
+
+    let mut positions = Indices::new();
+    let mut normals = Indices::new();
+    let mut elements = Vec::<(usize, String)>::new();
+
+    let triangles = buffers
+        .triangles
+        .triangles
+        .iter()
+        .filter_map(|triangle| Some((triangle.a.color, corners_and_normals(triangle)?)));
+    for (material, group) in group_by_color(&colors, triangles) {
+        for (corners, corner_normals) in group {
+            let mut line = "f".to_string();
+            for k in 0..3 {
+                line += &format!(
+                    " {}//{}",
+                    positions.index(corners[k]),
+                    normals.index(corner_normals[k])
+                );
+            }
+            elements.push((material, line));
+        }
+    }
+
+    let edges = buffers.edges.edges.iter().map(|edge| {
+        (
+            edge.start.color,
+            [edge.start, edge.end].map(|v| center(v.min_position, v.max_position)),
+        )
+    });
+    for (material, group) in group_by_color(&colors, edges) {
+        for [start, end] in group {
+            let line = format!("l {} {}", positions.index(start), positions.index(end));
+            elements.push((material, line));
+        }
+    }
+
+    let points = buffers.points.vertices.iter().map(|vertex| {
+        (
+            vertex.color,
+            center(vertex.min_position, vertex.max_position),
+        )
+    });
+    for (material, group) in group_by_color(&colors, points) {
+        for point in group {
+            elements.push((material, format!("p {}", positions.index(point))));
+        }
+    }
+
+    writeln!(obj, "mtllib {}", mtl_file_name)?;
+    for v in positions.vectors.iter() {
+        writeln!(obj, "v {} {} {}", v[0], v[1], v[2])?;
+    }
+    for n in normals.vectors.iter() {
+        writeln!(obj, "vn {} {} {}", n[0], n[1], n[2])?;
+    }
+    let mut current = None;
+    for (material, line) in elements.iter() {
+        if current != Some(*material) {
+            writeln!(obj, "usemtl color_{}", material)?;
+            current = Some(*material);
+        }
+        writeln!(obj, "{}", line)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::point::Point;
+    use geop_topology::topology::scene::Color;
+
+    use crate::{
+        edge_buffer::{EdgeBuffer, RenderEdge},
+        triangle_buffer::{RenderTriangle, TriangleBuffer},
+        vertex_normal_buffer::{RenderNormalVertex, VertexNormalBuffer},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_write_obj() {
+        let triangle = RenderTriangle::new(
+            Point::zero(),
+            Point::unit_x(),
+            Point::unit_y(),
+            Color::red(),
+            Point::unit_z(),
+            Point::unit_z(),
+            Point::unit_z(),
+        );
+        let buffers = MeshBuffers::new(
+            TriangleBuffer::new(vec![triangle]),
+            EdgeBuffer::new(vec![RenderEdge::new(
+                Point::unit_x(),
+                Point::unit_y(),
+                Color::black(),
+            )]),
+            VertexNormalBuffer::new(vec![RenderNormalVertex::new(
+                Point::unit_z(),
+                Color::red(),
+                Point::zero(),
+            )]),
+        );
+        let (mut obj, mut mtl) = (Vec::<u8>::new(), Vec::<u8>::new());
+        write_obj(&buffers, "part.mtl", &mut obj, &mut mtl).unwrap();
+        let obj = String::from_utf8(obj).unwrap();
+        let mtl = String::from_utf8(mtl).unwrap();
+
+        assert_eq!(
+            mtl,
+            "newmtl color_0\nKd 1 0 0\nd 1\nnewmtl color_1\nKd 0 0 0\nd 1\n"
+        );
+        assert_eq!(
+            obj,
+            "mtllib part.mtl\n\
+             v 0 0 0\nv 1 0 0\nv 0 1 0\nv 0 0 1\n\
+             vn 0 0 1\n\
+             usemtl color_0\nf 1//1 2//1 3//1\n\
+             usemtl color_1\nl 2 3\n\
+             usemtl color_0\np 4\n"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use std::io::{Result, Write};
+
+use geop_topology::topology::{scene::Color, volume::Volume};
+
+use crate::{
+    tolerance::RasterizeTolerance,
+    triangle_buffer::{RenderTriangle, TriangleBuffer},
+    volume::rasterize_volume_into_triangle_list_with_tolerance,
+};
+
+use super::{center, cross, normalize, sub};
+
+// The corners and the unit normal of a triangle, or None if the triangle has no area.
+// The corners are counter clockwise around the normal, as STL requires.
+fn facet(triangle: &RenderTriangle) -> Option<([f32; 3], [[f32; 3]; 3])> {
+    let corners = [triangle.a, triangle.b, triangle.c]
+        .map(|vertex| center(vertex.min_position, vertex.max_position));
+    let normal = normalize(cross(
+        sub(corners[1], corners[0]),
+        sub(corners[2], corners[0]),
+    ))?;
+    Some((normal, corners))
+}
+
+// Writes the triangles as binary STL. Triangles without area are left out.
+pub fn write_stl_binary(buffer: &TriangleBuffer, writer: &mut impl Write) -> Result<()> {
+    let facets = buffer
+        .triangles
+        .iter()
+        .filter_map(facet)
+        .collect::<Vec<_>>();
+
+    let mut header = [0u8; 80];
+    let title = b"geop binary stl";
+    header[..title.len()].copy_from_slice(title);
+    writer.write_all(&header)?;
+    writer.write_all(&(facets.len() as u32).to_le_bytes())?;
+    for (normal, corners) in facets.iter() {
+        for value in normal.iter().chain(corners.iter().flatten()) {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        // Attribute byte count, which is unused
+        writer.write_all(&0u16.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+// Writes the triangles as ASCII STL with the given solid name. Triangles without area are left out.
+pub fn write_stl_ascii(buffer: &TriangleBuffer, name: &str, writer: &mut impl Write) -> Result<()> {
+    writeln!(writer, "solid {}", name)?;
+    for (normal, corners) in buffer.triangles.iter().filter_map(facet) {
+        writeln!(
+            writer,
+            "  facet normal {:e} {:e} {:e}",
+            normal[0], normal[1], normal[2]
+        )?;
+        writeln!(writer, "    outer loop")?;
+        for corner in corners.iter() {
+            writeln!(
+                writer,
+                "      vertex {:e} {:e} {:e}",
+                corner[0], corner[1], corner[2]
+            )?;
+        }
+        writeln!(writer, "    endloop")?;
+        writeln!(writer, "  endfacet")?;
+    }
+    writeln!(writer, "endsolid {}", name)?;
+    Ok(())
+}
+
+// Rasterizes the volume as fine as the tolerance requires and writes it as binary STL.
+pub fn write_volume_stl_binary(
+    volume: &Volume,
+    tolerance: &RasterizeTolerance,
+    writer: &mut impl Write,
+) -> Result<()> {
+    let buffer =
+        rasterize_volume_into_triangle_list_with_tolerance(volume, Color::white(), tolerance);
+    write_stl_binary(&buffer, writer)
+}
+
+// Rasterizes the volume as fine as the tolerance requires and writes it as ASCII STL.
+pub fn write_volume_stl_ascii(
+    volume: &Volume,
+    name: &str,
+    tolerance: &RasterizeTolerance,
+    writer: &mut impl Write,
+) -> Result<()> {
+    let buffer =
+        rasterize_volume_into_triangle_list_with_tolerance(volume, Color::white(), tolerance);
+    write_stl_ascii(&buffer, name, writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::{efloat::EFloat64, point::Point};
+    use geop_topology::primitive_objects::volumes::cube::primitive_cube;
+
+    use super::*;
+
+    fn buffer() -> TriangleBuffer {
+        let triangle = |a: Point, b: Point, c: Point| {
+            RenderTriangle::new(
+                a,
+                b,
+                c,
+                Color::white(),
+                Point::zero(),
+                Point::zero(),
+                Point::zero(),
+            )
+        };
+        TriangleBuffer::new(vec![
+            triangle(Point::zero(), Point::unit_x(), Point::unit_y()),
+            // Degenerate triangles are left out
+            triangle(
+                Point::zero(),
+                Point::unit_x(),
+                Point::unit_x() * EFloat64::two(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_binary() {
+        let mut bytes = Vec::<u8>::new();
+        write_stl_binary(&buffer(), &mut bytes).unwrap();
+        assert_eq!(bytes.len(), 84 + 50);
+        assert_eq!(u32::from_le_bytes(bytes[80..84].try_into().unwrap()), 1);
+        let value =
+            |i: usize| f32::from_le_bytes(bytes[84 + 4 * i..88 + 4 * i].try_into().unwrap());
+        assert_eq!([value(0), value(1), value(2)], [0.0, 0.0, 1.0]);
+        assert_eq!([value(6), value(7), value(8)], [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_ascii() {
+        let mut bytes = Vec::<u8>::new();
+        write_stl_ascii(&buffer(), "part", &mut bytes).unwrap();
+        let text = String::from_utf8(bytes).unwrap();
+        assert!(text.starts_with("solid part\n"));
+        assert!(text.ends_with("endsolid part\n"));
+        assert_eq!(text.matches("facet normal 0e0 0e0 1e0").count(), 1);
+        assert_eq!(text.matches("vertex").count(), 3);
+    }
+
+    #[test]
+    fn test_volume() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let mut binary = Vec::<u8>::new();
+        write_volume_stl_binary(&cube, &RasterizeTolerance::default(), &mut binary).unwrap();
+        let count = u32::from_le_bytes(binary[80..84].try_into().unwrap()) as usize;
+        assert!(count >= 12);
+        assert_eq!(binary.len(), 84 + 50 * count);
+
+        let mut ascii = Vec::<u8>::new();
+        write_volume_stl_ascii(&cube, "cube", &RasterizeTolerance::default(), &mut ascii).unwrap();
+        let text = String::from_utf8(ascii).unwrap();
+        assert_eq!(text.matches("endfacet").count(), count);
+    }
+}
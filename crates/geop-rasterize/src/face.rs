@@ -11,8 +11,9 @@ use geop_topology::{
 };
 
 use crate::{
-    contour::rasterize_contour_into_line_list,
+    contour::{rasterize_contour_into_line_list, rasterize_contour_into_line_list_with_tolerance},
     edge_buffer::{EdgeBuffer, RenderEdge},
+    tolerance::RasterizeTolerance,
     triangle_buffer::{RenderTriangle, TriangleBuffer},
     vertex_buffer::{RenderVertex, VertexBuffer},
 };
@@ -314,14 +315,39 @@ pub fn rasterize_face_into_triangle_list(face: &Face, color: Color) -> TriangleB
     println!("/////////////////////////////////////////////////////////");
     println!("Rasterizing face {}", face);
     // Now we have to divide the face into triangles. First rasterize the boundaries. This will give us a set of open edges to work with
-    let mut contours = Vec::<EdgeBuffer>::new();
-    for contour in face.boundaries.iter() {
-        let edge_buffer = rasterize_contour_into_line_list(contour, color);
+    let contours = face
+        .boundaries()
+        .iter()
+        .map(|contour| rasterize_contour_into_line_list(contour, color))
+        .collect();
+    triangulate_face(face, contours, 1.0, color)
+}
+
+// Rasterizes a face such that the boundary segments and the surface points are as dense as the tolerance requires.
+pub fn rasterize_face_into_triangle_list_with_tolerance(
+    face: &Face,
+    color: Color,
+    tolerance: &RasterizeTolerance,
+) -> TriangleBuffer {
+    let contours = face
+        .boundaries()
+        .iter()
+        .map(|contour| rasterize_contour_into_line_list_with_tolerance(contour, color, tolerance))
+        .collect();
+    triangulate_face(face, contours, tolerance.face_density(face), color)
+}
+
+fn triangulate_face(
+    face: &Face,
+    contours: Vec<EdgeBuffer>,
+    density: f64,
+    color: Color,
+) -> TriangleBuffer {
+    for edge_buffer in contours.iter() {
         for edge in edge_buffer.edges.iter() {
-            assert!(face.surface.on_surface(edge.start.point()));
-            assert!(face.surface.on_surface(edge.end.point()));
+            assert!(face.surface().on_surface(edge.start.point()));
+            assert!(face.surface().on_surface(edge.end.point()));
         }
-        contours.push(edge_buffer);
     }
 
     // Rasterize the edges first
@@ -338,8 +364,8 @@ pub fn rasterize_face_into_triangle_list(face: &Face, color: Color) -> TriangleB
 
     // Then generate additional points on the surface
     connection_points.extend(
-        face.surface
-            .point_grid(1.0)
+        face.surface()
+            .point_grid(density)
             .drain(..)
             .filter(|p| face_point_contains(face, *p) == FacePointContains::Inside)
             .map(|point| RenderVertex::new(point.clone(), color)),
@@ -353,7 +379,7 @@ pub fn rasterize_face_into_triangle_list(face: &Face, color: Color) -> TriangleB
         for i in 0..connection_points.len() {
             for j in i + 1..connection_points.len() {
                 let distance = face
-                    .surface
+                    .surface()
                     .distance(connection_points[i].point(), connection_points[j].point());
                 if distance <= 0.0 {
                     continue;
@@ -378,6 +404,8 @@ pub fn rasterize_face_into_triangle_list(face: &Face, color: Color) -> TriangleB
 
     // Now iterate until all open_edges are processed.
     let mut processed_edges = Vec::<RenderEdge>::new();
+    // Finer grids need more steps, so the limit grows with the number of grid points
+    let max_steps = (1500.0 * density * density) as usize;
     let mut counter = 0;
     while let Some(edge) = open_edges.pop_front() {
         // println!(
@@ -397,7 +425,7 @@ pub fn rasterize_face_into_triangle_list(face: &Face, color: Color) -> TriangleB
         processed_edges.push(edge);
 
         counter += 1;
-        if counter > 1500 {
+        if counter > max_steps {
             break;
         }
         let mut best_triangle_point: Option<RenderVertex> = None;
@@ -405,7 +433,7 @@ pub fn rasterize_face_into_triangle_list(face: &Face, color: Color) -> TriangleB
         // Now find the best valid triangle
         for point in connection_points.iter() {
             if let Some(_) = check_triangle(
-                &face.surface,
+                face.surface(),
                 edge,
                 *point,
                 color,
@@ -422,9 +450,9 @@ pub fn rasterize_face_into_triangle_list(face: &Face, color: Color) -> TriangleB
                 edge.end.into(),
                 point.into(),
                 color,
-                face.surface.normal(edge.start.point()),
-                face.surface.normal(edge.end.point()),
-                face.surface.normal(point.point()),
+                face.surface().normal(edge.start.point()),
+                face.surface().normal(edge.end.point()),
+                face.surface().normal(point.point()),
             ));
             processed_edges.push(RenderEdge::new(point.into(), edge.start.into(), color));
             processed_edges.push(RenderEdge::new(edge.end.into(), point.into(), color));
@@ -450,7 +478,7 @@ pub fn rasterize_face_into_triangle_list(face: &Face, color: Color) -> TriangleB
 
 pub fn rasterize_face_into_line_list(face: &Face, color: Color) -> EdgeBuffer {
     let mut buffer = EdgeBuffer::empty();
-    for contour in face.boundaries.iter() {
+    for contour in face.boundaries().iter() {
         buffer.join(&rasterize_contour_into_line_list(contour, color));
     }
     buffer
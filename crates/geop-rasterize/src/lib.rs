@@ -2,8 +2,10 @@ pub mod boundingbox;
 pub mod contour;
 pub mod edge;
 pub mod edge_buffer;
+pub mod export;
 pub mod face;
 pub mod functions;
+pub mod tolerance;
 pub mod triangle_buffer;
 pub mod vertex_buffer;
 pub mod vertex_normal_buffer;
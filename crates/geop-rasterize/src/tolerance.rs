@@ -0,0 +1,284 @@
+use std::f64::consts::PI;
+
+use geop_geometry::{
+    curves::curve::Curve,
+    efloat::EFloat64,
+    geometry_error::{GeometryError, GeometryResult},
+    point::Point,
+    surfaces::{nurbs::NurbsSurface, surface::Surface},
+    HORIZON_DIST,
+};
+use geop_topology::topology::{edge::Edge, face::Face};
+
+// Edges are never split into more segments than this, even if the tolerance is not met.
+const MAX_EDGE_SEGMENTS: usize = 4096;
+// Number of samples along each parameter line when estimating the curvature of a nurbs surface.
+const NURBS_SAMPLES: usize = 17;
+
+// Limits how far a rasterization may deviate from the exact geometry.
+#[derive(Debug, Clone, Copy)]
+pub struct RasterizeTolerance {
+    // Largest distance between a segment and the curve or surface it approximates.
+    pub chord: f64,
+    // Largest angle in radians by which the tangent may turn along one segment.
+    pub angle: f64,
+}
+
+impl RasterizeTolerance {
+    pub fn new(chord: f64, angle: f64) -> GeometryResult<Self> {
+        if chord.is_nan() || chord <= 0.0 {
+            return Err(GeometryError::new(format!(
+                "Chord tolerance must be positive, got {}",
+                chord
+            )));
+        }
+        if angle.is_nan() || angle <= 0.0 {
+            return Err(GeometryError::new(format!(
+                "Angle tolerance must be positive, got {}",
+                angle
+            )));
+        }
+        Ok(RasterizeTolerance { chord, angle })
+    }
+
+    // The largest angle that one segment may span on an arc with the given radius.
+    pub fn max_arc_angle(&self, radius: f64) -> f64 {
+        if self.chord >= radius {
+            return self.angle.min(PI);
+        }
+        self.angle.min(2.0 * (1.0 - self.chord / radius).acos())
+    }
+
+    // The number of segments needed for an arc that turns by the given angle.
+    // A small slack keeps rounding errors of the angle from adding a segment.
+    pub fn arc_segments(&self, angle: f64, radius: f64) -> usize {
+        ((angle.abs() / self.max_arc_angle(radius) - 1e-9).ceil() as usize).max(1)
+    }
+
+    // The number of segments needed for an edge. The tangent of an edge turns by at most its length times its largest curvature.
+    pub fn edge_segments(&self, edge: &Edge) -> usize {
+        let length = edge.length().map(|length| length.to_f64());
+        match &edge.curve {
+            Curve::Line(_) => 1,
+            Curve::Circle(circle) => {
+                let radius = circle.radius.norm().to_f64();
+                self.arc_segments(length.unwrap_or(2.0 * PI * radius) / radius, radius)
+            }
+            Curve::Ellipse(ellipse) => {
+                let major = ellipse.major_radius.norm().to_f64();
+                let minor = ellipse.minor_radius.norm().to_f64();
+                let radius = minor * minor / major;
+                self.arc_segments(length.unwrap_or(2.0 * PI * major) / radius, radius)
+            }
+            Curve::Helix(helix) => match length {
+                Some(length) => {
+                    let radius = helix.radius.norm().to_f64();
+                    self.arc_segments(length / radius, radius)
+                }
+                None => 32 * HORIZON_DIST as usize,
+            },
+            // Sampled curves have corners at their samples, so only the chord tolerance applies to them
+            Curve::SampledCurve(sampled) => {
+                self.refine_segments(edge, sampled.points.len().max(2) - 1, false)
+            }
+            Curve::Nurbs(nurbs) => self.refine_segments(edge, nurbs.control_points.len(), true),
+        }
+    }
+
+    // Doubles the number of uniform segments of an edge until the midpoint of every segment is within the chord
+    // tolerance of the edge, and, for smooth edges, the two halves of every segment turn by at most half the angle.
+    fn refine_segments(&self, edge: &Edge, segments: usize, smooth: bool) -> usize {
+        let mut segments = segments.max(1);
+        while segments < MAX_EDGE_SEGMENTS {
+            let fits = (0..segments).all(|j| {
+                let start = edge.interpolate(j as f64 / segments as f64);
+                let middle = edge.interpolate((j as f64 + 0.5) / segments as f64);
+                let end = edge.interpolate((j + 1) as f64 / segments as f64);
+                if (middle - EFloat64::from(0.5) * (start + end)).norm() > self.chord {
+                    return false;
+                }
+                if !smooth {
+                    return true;
+                }
+                match (middle - start).angle(end - middle) {
+                    Ok(angle) => 2.0 * angle.to_f64() <= self.angle,
+                    Err(_) => true,
+                }
+            });
+            if fits {
+                return segments;
+            }
+            segments *= 2;
+        }
+        MAX_EDGE_SEGMENTS
+    }
+
+    // The density of the point grid of the surface of a face, such that neighbouring grid points are close enough.
+    pub fn face_density(&self, face: &Face) -> f64 {
+        // The point grids place 16 times the density points around a full turn
+        let density = |radius: f64| 2.0 * PI / (16.0 * self.max_arc_angle(radius));
+        let density = match &**face.surface() {
+            Surface::Sphere(sphere) => density(sphere.radius.to_f64()),
+            Surface::Cylinder(cylinder) => density(cylinder.radius.norm().to_f64()),
+            Surface::Torus(torus) => density(torus.minor_radius.to_f64()),
+            Surface::Cone(cone) => match face.bounding_box() {
+                // The widest circle of the face is at most as far from the axis as the box corners are from the apex
+                Ok(bounding_box) => {
+                    let corners = [bounding_box.min, bounding_box.max];
+                    let mut radius = 0.0_f64;
+                    for x in corners.iter() {
+                        for y in corners.iter() {
+                            for z in corners.iter() {
+                                let corner = Point::new(x.x, y.y, z.z);
+                                radius = radius.max((corner - cone.apex).norm().to_f64());
+                            }
+                        }
+                    }
+                    density(radius * cone.half_angle.sin().to_f64())
+                }
+                Err(_) => 1.0,
+            },
+            Surface::Nurbs(nurbs) => self.nurbs_density(nurbs),
+            // Planes are flat, so every grid approximates them exactly
+            Surface::Plane(_) => 1.0,
+        };
+        density.max(1.0)
+    }
+
+    // The point grid of a nurbs surface has 16 times the density points along each parameter line. Along the
+    // parameter lines of a sample grid, the normals turn by some total angle and the radius of curvature is
+    // estimated from the turn between neighbouring samples. The line that needs the most segments decides.
+    fn nurbs_density(&self, nurbs: &NurbsSurface) -> f64 {
+        let ((u0, u1), (v0, v1)) = nurbs.domain();
+        let parameter = |i: usize, j: usize| {
+            (
+                u0 + (u1 - u0) * i as f64 / (NURBS_SAMPLES - 1) as f64,
+                v0 + (v1 - v0) * j as f64 / (NURBS_SAMPLES - 1) as f64,
+            )
+        };
+        let samples: Vec<Vec<(Point, Point)>> = (0..NURBS_SAMPLES)
+            .map(|i| {
+                (0..NURBS_SAMPLES)
+                    .map(|j| {
+                        let (u, v) = parameter(i, j);
+                        (nurbs.point_at(u, v), nurbs.normal_at((u, v)))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let u_lines =
+            (0..NURBS_SAMPLES).map(|j| samples.iter().map(|row| row[j]).collect::<Vec<_>>());
+        let mut segments = 1;
+        for line in u_lines.chain(samples.iter().cloned()) {
+            let mut turn = 0.0;
+            let mut radius = f64::INFINITY;
+            for pair in line.windows(2) {
+                let (p1, n1) = pair[0];
+                let (p2, n2) = pair[1];
+                let angle = n1.angle(n2).map(|a| a.to_f64()).unwrap_or(0.0);
+                turn += angle;
+                if angle > 1e-9 {
+                    radius = radius.min((p2 - p1).norm().to_f64() / angle);
+                }
+            }
+            if radius.is_finite() {
+                segments = segments.max(self.arc_segments(turn, radius));
+            }
+        }
+        segments as f64 / 16.0
+    }
+}
+
+impl Default for RasterizeTolerance {
+    fn default() -> Self {
+        RasterizeTolerance {
+            chord: 0.01,
+            angle: PI / 12.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::{
+        curves::{circle::Circle, nurb::NurbsCurve},
+        surfaces::torus::Torus,
+    };
+    use geop_topology::primitive_objects::edges::{circle::primitive_circle, line::primitive_line};
+
+    use super::*;
+
+    #[test]
+    fn test_edge_segments() {
+        let tolerance = RasterizeTolerance::new(0.01, PI / 12.0).unwrap();
+        let line = primitive_line(Point::zero(), Point::unit_x()).unwrap();
+        assert_eq!(tolerance.edge_segments(&line), 1);
+
+        // A unit circle needs 24 segments for the angle, and 23 for the chord
+        let circle = primitive_circle(Point::zero(), Point::unit_z(), EFloat64::one());
+        assert_eq!(tolerance.edge_segments(&circle), 24);
+
+        // For a large circle the chord tolerance dominates
+        let circle = primitive_circle(Point::zero(), Point::unit_z(), EFloat64::from(100.0));
+        let segments = tolerance.edge_segments(&circle);
+        let step = 2.0 * PI / segments as f64;
+        assert!(100.0 * (1.0 - (step / 2.0).cos()) <= 0.01);
+        assert!(segments > 24);
+    }
+
+    #[test]
+    fn test_new_rejects_invalid_tolerances() {
+        assert!(RasterizeTolerance::new(0.0, PI / 12.0).is_err());
+        assert!(RasterizeTolerance::new(0.01, -1.0).is_err());
+        assert!(RasterizeTolerance::new(f64::NAN, PI / 12.0).is_err());
+    }
+
+    #[test]
+    fn test_nurbs_edge_segments() {
+        let circle = Circle::try_new(Point::zero(), Point::unit_z(), EFloat64::from(10.0)).unwrap();
+        let start = Point::from_f64(10.0, 0.0, 0.0);
+        let end = Point::from_f64(-10.0, 0.0, 0.0);
+        let arc = NurbsCurve::try_new_arc(&circle, Some(start), Some(end)).unwrap();
+        let edge = Edge::new(Some(start), Some(end), Curve::Nurbs(arc));
+
+        let coarse = RasterizeTolerance::new(0.1, PI / 4.0).unwrap();
+        let fine = RasterizeTolerance::new(0.001, PI / 4.0).unwrap();
+        assert!(fine.edge_segments(&edge) > coarse.edge_segments(&edge));
+
+        // Every segment stays within the chord tolerance of the arc
+        let segments = fine.edge_segments(&edge);
+        for j in 0..segments {
+            let t = (j as f64 + 0.5) / segments as f64;
+            let middle = EFloat64::from(0.5)
+                * (edge.interpolate(j as f64 / segments as f64)
+                    + edge.interpolate((j + 1) as f64 / segments as f64));
+            let distance = (edge.interpolate(t) - middle).norm().to_f64();
+            assert!(distance <= 0.001);
+        }
+    }
+
+    #[test]
+    fn test_nurbs_face_density() {
+        let tolerance = RasterizeTolerance::default();
+        let torus = Torus::try_new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(3.0),
+            EFloat64::one(),
+            true,
+        )
+        .unwrap();
+        let exact =
+            tolerance.face_density(&Face::new(vec![], Rc::new(Surface::Torus(torus.clone()))));
+        let nurbs = tolerance.face_density(&Face::new(
+            vec![],
+            Rc::new(Surface::Nurbs(torus.to_nurbs())),
+        ));
+        assert!(nurbs > 1.0);
+        // The nurbs version needs about as many points around the tube as the exact torus
+        assert!(nurbs >= 0.75 * exact && nurbs <= 2.0 * exact);
+    }
+}
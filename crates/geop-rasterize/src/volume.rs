@@ -2,7 +2,11 @@ use geop_topology::topology::{scene::Color, volume::Volume};
 
 use crate::{
     edge_buffer::EdgeBuffer,
-    face::{rasterize_face_into_line_list, rasterize_face_into_triangle_list},
+    face::{
+        rasterize_face_into_line_list, rasterize_face_into_triangle_list,
+        rasterize_face_into_triangle_list_with_tolerance,
+    },
+    tolerance::RasterizeTolerance,
     triangle_buffer::TriangleBuffer,
     vertex_buffer::{RenderVertex, VertexBuffer},
 };
@@ -10,14 +14,14 @@ use crate::{
 pub fn rasterize_volume_into_triangle_list(volume: &Volume, color: Color) -> TriangleBuffer {
     let mut buffer = TriangleBuffer::empty();
 
-    for face in volume.boundary.faces.iter() {
+    for face in volume.boundary().faces().iter() {
         println!("Rasterizing face: {}", face);
         let face_buffer = rasterize_face_into_triangle_list(face, color);
         buffer.join(&face_buffer);
     }
 
-    for hole in volume.cavities.iter() {
-        for face in hole.faces.iter() {
+    for hole in volume.cavities().iter() {
+        for face in hole.faces().iter() {
             println!("Rasterizing face: {}", face);
             let face_buffer = rasterize_face_into_triangle_list(face, color);
             buffer.join(&face_buffer);
@@ -27,16 +31,33 @@ pub fn rasterize_volume_into_triangle_list(volume: &Volume, color: Color) -> Tri
     buffer
 }
 
+// Rasterizes all faces of the volume, including the cavities, as fine as the tolerance requires.
+pub fn rasterize_volume_into_triangle_list_with_tolerance(
+    volume: &Volume,
+    color: Color,
+    tolerance: &RasterizeTolerance,
+) -> TriangleBuffer {
+    let mut buffer = TriangleBuffer::empty();
+
+    for face in volume.all_faces().iter() {
+        buffer.join(&rasterize_face_into_triangle_list_with_tolerance(
+            face, color, tolerance,
+        ));
+    }
+
+    buffer
+}
+
 pub fn rasterize_volume_into_line_list(volume: &Volume, color: Color) -> EdgeBuffer {
     let mut buffer = EdgeBuffer::empty();
 
-    for face in volume.boundary.faces.iter() {
+    for face in volume.boundary().faces().iter() {
         let face_buffer = rasterize_face_into_line_list(face, color);
         buffer.join(&face_buffer);
     }
 
-    for hole in volume.cavities.iter() {
-        for face in hole.faces.iter() {
+    for hole in volume.cavities().iter() {
+        for face in hole.faces().iter() {
             let face_buffer = rasterize_face_into_line_list(face, color);
             buffer.join(&face_buffer);
         }
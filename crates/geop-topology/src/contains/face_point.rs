@@ -1,6 +1,10 @@
 use geop_geometry::{
-    curve_curve_intersection::curve_curve::{curve_curve_intersection, CurveCurveIntersection},
-    curves::curve::Curve,
+    bounding_box::BoundingBox,
+    curve_curve_intersection::{
+        curve_curve::{curve_curve_intersection, CurveCurveIntersection},
+        curve_curve_intersection_sampled,
+    },
+    curves::{curve::Curve, CurveLike},
     efloat::EFloat64,
     point::Point,
     surfaces::SurfaceLike,
@@ -19,13 +23,30 @@ pub enum FacePointContains {
     NotOnSurface,
 }
 
+// Faces with more edges than this use a bounding volume hierarchy to skip the edges that are far away.
+const BVH_EDGE_COUNT: usize = 16;
+
 pub fn face_point_contains(face: &Face, point: Point) -> FacePointContains {
-    if !face.surface.on_surface(point) {
+    if !face.surface().on_surface(point) {
         return FacePointContains::NotOnSurface;
     }
 
+    let edges = face.all_edges();
+    let bvh = match edges.len() > BVH_EDGE_COUNT {
+        true => Some(face.edge_bvh()),
+        false => None,
+    };
+    let near = |bounding_box: Option<BoundingBox>| match (&bvh, bounding_box) {
+        (Some(bvh), Some(bounding_box)) => bvh
+            .query(&bounding_box)
+            .into_iter()
+            .map(|i| edges[i].clone())
+            .collect(),
+        _ => edges.clone(),
+    };
+
     // If the point is on the border, it is part of the set
-    for edge in face.all_edges() {
+    for edge in near(Some(BoundingBox::new(point, point))) {
         match edge_point_contains(&edge, point) {
             EdgePointContains::Inside => return FacePointContains::OnEdge(edge.clone()),
             EdgePointContains::OnPoint(point) => return FacePointContains::OnPoint(point),
@@ -42,15 +63,35 @@ pub fn face_point_contains(face: &Face, point: Point) -> FacePointContains {
     let geodesic = face.edge_from_to(point, q);
 
     // Find the closest intersection point and check by using the face normal and the curve tangent if the intersection is from inside or outside.
-    let mut closest_distance = face.surface.distance(point, q);
+    let mut closest_distance = face.surface().distance(point, q);
     let curve_dir = geodesic.tangent(q);
-    let normal = face.surface.normal(q);
+    let normal = face.surface().normal(q);
     let contour_dir = face.boundary_tangent(q);
     let mut closest_intersect_from_inside = contour_dir.is_inside(normal, curve_dir);
 
     let mut intersection_points = Vec::<Point>::new();
-    for edge in face.all_edges() {
-        match curve_curve_intersection(&edge.curve, &geodesic.curve) {
+    let geodesic_box = geodesic
+        .curve
+        .get_bounding_box(geodesic.start, geodesic.end)
+        .ok();
+    for edge in near(geodesic_box) {
+        let intersection =
+            curve_curve_intersection(&edge.curve, &geodesic.curve).unwrap_or_else(|_| {
+                // Curves that cannot be intersected as a whole, e.g. two helices, are sampled along the bounded geodesic
+                match curve_curve_intersection_sampled(
+                    &|t| {
+                        geodesic
+                            .curve
+                            .interpolate(geodesic.start, geodesic.end, t)
+                            .unwrap()
+                    },
+                    &edge.curve,
+                ) {
+                    None => CurveCurveIntersection::Curve(geodesic.curve.clone()),
+                    Some(points) => CurveCurveIntersection::FinitePoints(points),
+                }
+            });
+        match intersection {
             CurveCurveIntersection::FinitePoints(points) => {
                 for p in points {
                     if edge_point_contains(&geodesic, p) != EdgePointContains::Outside {
@@ -68,12 +109,21 @@ pub fn face_point_contains(face: &Face, point: Point) -> FacePointContains {
                     }
                 }
 
-                let start_i = (point - point_array.basis).dot(point_array.extend_dir);
-                intersection_points.push(point_array.basis + start_i * point_array.extend_dir);
-                intersection_points
-                    .push(point_array.basis + (start_i + EFloat64::one()) * point_array.extend_dir);
-                intersection_points
-                    .push(point_array.basis + (start_i - EFloat64::one()) * point_array.extend_dir);
+                // Only the points of the turns next to the point can be on the geodesic
+                let start_i = ((point - point_array.basis).dot(point_array.extend_dir)
+                    / point_array.extend_dir.norm_sq())
+                .unwrap()
+                .to_f64()
+                .round();
+                for i in -1..=1 {
+                    let p = point_array.basis
+                        + EFloat64::from(start_i + i as f64) * point_array.extend_dir;
+                    if edge_point_contains(&geodesic, p) != EdgePointContains::Outside
+                        && edge_point_contains(&edge, p) != EdgePointContains::Outside
+                    {
+                        intersection_points.push(p);
+                    }
+                }
             }
             CurveCurveIntersection::Curve(_curve) => {
                 if let Some(start) = edge.start {
@@ -96,10 +146,10 @@ pub fn face_point_contains(face: &Face, point: Point) -> FacePointContains {
     }
 
     for int in intersection_points {
-        let distance = face.surface.distance(point, int);
+        let distance = face.surface().distance(point, int);
         if distance < closest_distance.lower_bound {
             let curve_dir = geodesic.tangent(int);
-            let normal = face.surface.normal(int);
+            let normal = face.surface().normal(int);
             let contour_dir = face.boundary_tangent(int);
             closest_distance = distance;
             closest_intersect_from_inside = contour_dir.is_inside(normal, curve_dir);
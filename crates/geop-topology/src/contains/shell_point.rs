@@ -5,7 +5,7 @@ use crate::topology::shell::Shell;
 use super::face_point::{face_point_contains, FacePointContains};
 
 pub fn shell_point_contains(shell: &Shell, point: Point) -> FacePointContains {
-    for face in shell.faces.iter() {
+    for face in shell.faces().iter() {
         let contains: FacePointContains = face_point_contains(face, point);
         match contains {
             FacePointContains::OnEdge(edge) => {
@@ -1,15 +1,24 @@
 use geop_geometry::{
+    color::Category10Color,
     curve_curve_intersection::curve_curve::{curve_curve_intersection, CurveCurveIntersection},
     curve_surface_intersection::curve_surface::{
         curve_surface_intersection, CurveSurfaceIntersection,
     },
-    curves::{curve::Curve, line::Line, CurveLike},
+    curves::{curve::Curve, line::Line},
     point::Point,
 };
 
-use crate::topology::{edge::Edge, face::Face, volume::Volume};
+use crate::{
+    ray_cast::ray_cast,
+    topology::{edge::Edge, face::Face, volume::Volume},
+    topology_error::{TopologyError, TopologyResult, WithContext},
+    topology_scene::TopologyScene,
+};
 
-use super::face_point::{face_point_contains, FacePointContains};
+use super::{
+    edge_point::{edge_point_contains, EdgePointContains},
+    face_point::{face_point_contains, FacePointContains},
+};
 
 pub enum VolumePointContains {
     Inside,
@@ -19,90 +28,83 @@ pub enum VolumePointContains {
     Outside,
 }
 
-pub fn volume_point_contains(volume: &Volume, other: Point) -> VolumePointContains {
+// Casts a ray from the point to an inner point of one of the faces and uses the normal of the closest face it crosses.
+// A ray that touches an edge, a corner or a face tangentially is ambiguous, so the rays to the other faces are tried as well.
+pub fn volume_point_contains(volume: &Volume, other: Point) -> TopologyResult<VolumePointContains> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            format!("Check if volume contains point {}", other),
+            TopologyScene::with_points(vec![(other, Category10Color::Red)]),
+        )
+    };
+
     // first check if point is on any other face
-    for face in volume.all_faces().iter() {
+    let faces = volume.all_faces();
+    for i in volume.face_bvh().query_point(other) {
+        let face = &faces[i];
         match face_point_contains(face, other) {
-            FacePointContains::Inside => return VolumePointContains::OnFace(face.clone()),
-            FacePointContains::OnEdge(edge) => return VolumePointContains::OnEdge(edge),
-            FacePointContains::OnPoint(point) => return VolumePointContains::OnPoint(point),
+            FacePointContains::Inside => return Ok(VolumePointContains::OnFace(face.clone())),
+            FacePointContains::OnEdge(edge) => return Ok(VolumePointContains::OnEdge(edge)),
+            FacePointContains::OnPoint(point) => return Ok(VolumePointContains::OnPoint(point)),
             FacePointContains::Outside => {}
             FacePointContains::NotOnSurface => {}
         }
     }
 
-    // choose a random point on a face
-    let q = volume.all_faces()[0].inner_point();
-    let geodesic = Edge::new(
-        Some(other.clone()),
-        Some(q.clone()),
-        Curve::Line(Line::new(other, (q - other).normalize().unwrap()).unwrap()),
+    for face in faces.iter() {
+        if let Some(from_inside) = ray_crossing(volume, other, face.inner_point()) {
+            return Ok(match from_inside {
+                true => VolumePointContains::Inside,
+                false => VolumePointContains::Outside,
+            });
+        }
+    }
+    Err(TopologyError::new(
+        "Every ray from the point touches an edge, a corner or a face tangentially".to_string(),
+    ))
+    .with_context(&context)
+}
+
+// Whether the closest crossing of the ray from the point towards q comes from the inside of the volume.
+// Returns None if the ray touches an edge, a corner or a face tangentially before or at q.
+fn ray_crossing(volume: &Volume, other: Point, q: Point) -> Option<bool> {
+    let direction = (q - other).normalize().ok()?;
+    let ray = Edge::new(
+        Some(other),
+        None,
+        Curve::Line(Line::new(other, direction).ok()?),
     );
 
-    let mut intersection_points = Vec::<Point>::new();
+    // The ray may run within the surface of a face, which is fine as long as it does not enter the face
     for face in volume.all_faces().iter() {
-        let intersections = curve_surface_intersection(&geodesic.curve, &*face.surface);
-        match intersections {
-            CurveSurfaceIntersection::Curve(_) => {
-                for edge in face.all_edges() {
-                    match curve_curve_intersection(&geodesic.curve, &edge.curve) {
-                        CurveCurveIntersection::FinitePoints(points) => {
-                            for point in points {
-                                if face_point_contains(&face, point) != FacePointContains::Outside {
-                                    intersection_points.push(point)
-                                }
+        if let CurveSurfaceIntersection::Curve(_) =
+            curve_surface_intersection(&ray.curve, face.surface()).ok()?
+        {
+            for edge in face.all_edges() {
+                match curve_curve_intersection(&ray.curve, &edge.curve).ok()? {
+                    CurveCurveIntersection::FinitePoints(points) => {
+                        for point in points {
+                            if edge_point_contains(&ray, point) != EdgePointContains::Outside
+                                && edge_point_contains(&edge, point) != EdgePointContains::Outside
+                            {
+                                return None;
                             }
                         }
-                        CurveCurveIntersection::InfiniteDiscretePoints(_) => todo!(),
-                        CurveCurveIntersection::Curve(_) => {
-                            if let Some(start) = edge.start {
-                                if face_point_contains(&face, start) != FacePointContains::Outside {
-                                    intersection_points.push(start)
-                                }
-                            }
-                            if let Some(end) = edge.end {
-                                if face_point_contains(&face, end) != FacePointContains::Outside {
-                                    intersection_points.push(end)
-                                }
-                            }
-                        }
-                        CurveCurveIntersection::None => {}
-                    }
-                }
-            }
-            CurveSurfaceIntersection::Points(points) => {
-                for point in points {
-                    match face_point_contains(&face, point) {
-                        FacePointContains::Inside => intersection_points.push(point),
-                        FacePointContains::OnEdge(_) => {
-                            intersection_points.push(point);
-                        }
-                        FacePointContains::OnPoint(point) => intersection_points.push(point),
-                        FacePointContains::Outside => {}
-                        FacePointContains::NotOnSurface => {}
                     }
+                    CurveCurveIntersection::None => {}
+                    _ => return None,
                 }
             }
-            CurveSurfaceIntersection::None => {}
         }
     }
 
-    // Find the closest intersection point with any other face and use the normal to determine if the point is inside or outside
-    let mut closest_distance = (other - q).norm();
-    let curve_dir = q - other;
-    let normal = volume.boundary_normal(q);
-    let mut closest_intersect_from_inside = normal.is_from_inside(curve_dir);
-    for point in intersection_points.iter() {
-        let distance = (other - *point).norm();
-        if distance < closest_distance.lower_bound {
-            let curve_dir = geodesic.curve.tangent(*point).unwrap();
-            let normal = volume.boundary_normal(*point);
-            closest_distance = distance;
-            closest_intersect_from_inside = normal.is_from_inside(curve_dir);
+    let hits = ray_cast(volume, other, direction).ok()?;
+    for hit in hits.iter() {
+        if face_point_contains(&hit.face, hit.point) != FacePointContains::Inside
+            || hit.normal.dot(direction) == 0.0
+        {
+            return None;
         }
     }
-    match closest_intersect_from_inside {
-        true => VolumePointContains::Inside,
-        false => VolumePointContains::Outside,
-    }
+    hits.first().map(|hit| hit.normal.dot(direction) > 0.0)
 }
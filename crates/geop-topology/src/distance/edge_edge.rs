@@ -0,0 +1,83 @@
+use geop_geometry::curve_curve_intersection::curve_curve::{
+    curve_curve_intersection, CurveCurveIntersection,
+};
+
+use crate::{
+    contains::edge_point::{edge_point_contains, EdgePointContains},
+    topology::edge::Edge,
+};
+
+use super::{alternate, closest, edge_point::edge_point_distance, edge_samples, ClosestPoints};
+
+// Checks the intersections and the end points, then searches for pairs of points in the interior of both edges.
+pub fn edge_edge_distance(edge: &Edge, other: &Edge) -> ClosestPoints {
+    let mut candidates = Vec::<ClosestPoints>::new();
+    // Curves that cannot be intersected are left to the search below
+    if let Ok(CurveCurveIntersection::FinitePoints(points)) =
+        curve_curve_intersection(&edge.curve, &other.curve)
+    {
+        for point in points {
+            if edge_point_contains(edge, point) != EdgePointContains::Outside
+                && edge_point_contains(other, point) != EdgePointContains::Outside
+            {
+                return ClosestPoints::new(point, point);
+            }
+        }
+    }
+
+    for end in [edge.start, edge.end].into_iter().flatten() {
+        candidates.push(edge_point_distance(other, end).flip());
+    }
+    for end in [other.start, other.end].into_iter().flatten() {
+        candidates.push(edge_point_distance(edge, end));
+    }
+    for start in edge_samples(edge) {
+        candidates.push(alternate(
+            start,
+            |p| edge_point_distance(edge, p).from,
+            |p| edge_point_distance(other, p).from,
+        ));
+    }
+    closest(candidates).expect("Edges have closest points")
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::{efloat::EFloat64, point::Point};
+
+    use crate::primitive_objects::edges::{circle::primitive_circle, line::primitive_line};
+
+    use super::*;
+
+    #[test]
+    fn test_skew_lines() {
+        let line1 = primitive_line(Point::from_f64(-1.0, 0.0, 0.0), Point::unit_x()).unwrap();
+        let line2 = primitive_line(
+            Point::from_f64(0.5, -1.0, 1.0),
+            Point::from_f64(0.5, 1.0, 1.0),
+        )
+        .unwrap();
+        let result = edge_edge_distance(&line1, &line2);
+        assert!((result.from - Point::from_f64(0.5, 0.0, 0.0)).norm() < 1e-9);
+        assert!((result.to - Point::from_f64(0.5, 0.0, 1.0)).norm() < 1e-9);
+        assert!(result.distance == 1.0);
+    }
+
+    #[test]
+    fn test_line_circle() {
+        let circle = primitive_circle(Point::zero(), Point::unit_z(), EFloat64::one());
+        let line = primitive_line(
+            Point::from_f64(3.0, -1.0, 0.0),
+            Point::from_f64(3.0, 1.0, 0.0),
+        )
+        .unwrap();
+        let result = edge_edge_distance(&circle, &line);
+        assert!((result.from - Point::unit_x()).norm() < 1e-9);
+        assert!(result.distance == 2.0);
+
+        // Crossing edges touch
+        let line = primitive_line(Point::zero(), Point::from_f64(3.0, 0.0, 0.0)).unwrap();
+        let result = edge_edge_distance(&circle, &line);
+        assert!(result.distance == 0.0);
+    }
+}
@@ -0,0 +1,52 @@
+use geop_geometry::{curves::CurveLike, point::Point};
+
+use crate::{
+    contains::edge_point::{edge_point_contains, EdgePointContains},
+    topology::edge::Edge,
+};
+
+use super::{closest, ClosestPoints};
+
+// The projection onto the curve is the closest point if it lies on the edge, otherwise one of the end points is.
+pub fn edge_point_distance(edge: &Edge, point: Point) -> ClosestPoints {
+    let mut candidates = Vec::<ClosestPoints>::new();
+    let projection = edge.curve.project(point);
+    if edge_point_contains(edge, projection) != EdgePointContains::Outside {
+        candidates.push(ClosestPoints::new(projection, point));
+    }
+    for end in [edge.start, edge.end].into_iter().flatten() {
+        candidates.push(ClosestPoints::new(end, point));
+    }
+    closest(candidates).expect("Edge has a closest point")
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::efloat::EFloat64;
+
+    use crate::primitive_objects::edges::{arc::primitive_arc, line::primitive_line};
+
+    use super::*;
+
+    #[test]
+    fn test_edge_point_distance() {
+        let line = primitive_line(Point::zero(), Point::unit_x()).unwrap();
+        let result = edge_point_distance(&line, Point::from_f64(0.5, 2.0, 0.0));
+        assert_eq!(result.from, Point::from_f64(0.5, 0.0, 0.0));
+        assert!(result.distance == 2.0);
+
+        let result = edge_point_distance(&line, Point::from_f64(4.0, 4.0, 0.0));
+        assert_eq!(result.from, Point::unit_x());
+        assert!(result.distance == 5.0);
+
+        // The projection onto the circle is not on the arc
+        let arc = primitive_arc(
+            Point::unit_x(),
+            Point::unit_y(),
+            EFloat64::one(),
+            Point::unit_z(),
+        );
+        let result = edge_point_distance(&arc, Point::from_f64(-2.0, -2.0, 0.0));
+        assert!(result.from == Point::unit_x() || result.from == Point::unit_y());
+    }
+}
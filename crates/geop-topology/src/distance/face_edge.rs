@@ -0,0 +1,85 @@
+use geop_geometry::curve_surface_intersection::curve_surface::{
+    curve_surface_intersection, CurveSurfaceIntersection,
+};
+
+use crate::{
+    contains::{
+        edge_point::{edge_point_contains, EdgePointContains},
+        face_point::{face_point_contains, FacePointContains},
+    },
+    topology::{edge::Edge, face::Face},
+};
+
+use super::{
+    alternate, closest, edge_edge::edge_edge_distance, edge_point::edge_point_distance,
+    edge_samples, face_point::face_point_distance, ClosestPoints,
+};
+
+// Checks where the edge crosses the face, the end points of the edge and the boundary of the face, then searches the interiors.
+pub fn face_edge_distance(face: &Face, edge: &Edge) -> ClosestPoints {
+    let mut candidates = Vec::<ClosestPoints>::new();
+    // Curves that cannot be intersected with the surface are left to the search below
+    if let Ok(CurveSurfaceIntersection::Points(points)) =
+        curve_surface_intersection(&edge.curve, face.surface())
+    {
+        for point in points {
+            if edge_point_contains(edge, point) != EdgePointContains::Outside
+                && face_point_contains(face, point) != FacePointContains::Outside
+            {
+                return ClosestPoints::new(point, point);
+            }
+        }
+    }
+
+    for end in [edge.start, edge.end].into_iter().flatten() {
+        candidates.push(face_point_distance(face, end));
+    }
+    for boundary in face.all_edges() {
+        candidates.push(edge_edge_distance(&boundary, edge));
+    }
+    for start in edge_samples(edge) {
+        candidates.push(
+            alternate(
+                start,
+                |p| edge_point_distance(edge, p).from,
+                |p| face_point_distance(face, p).from,
+            )
+            .flip(),
+        );
+    }
+    closest(candidates).expect("Face and edge have closest points")
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::point::Point;
+
+    use crate::primitive_objects::{
+        edges::line::primitive_line, faces::rectangle::primitive_rectangle,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_face_edge_distance() {
+        let face = primitive_rectangle(Point::zero(), Point::unit_x(), Point::unit_y());
+        let edge = primitive_line(
+            Point::from_f64(-3.0, 0.0, 2.0),
+            Point::from_f64(3.0, 0.0, 2.0),
+        )
+        .unwrap();
+        let result = face_edge_distance(&face, &edge);
+        assert!(result.distance == 2.0);
+        assert!(result.from.z.to_f64().abs() < 1e-9);
+
+        // The edge passes through the face
+        let edge = primitive_line(
+            Point::from_f64(0.5, 0.5, -1.0),
+            Point::from_f64(0.5, 0.5, 1.0),
+        )
+        .unwrap();
+        let result = face_edge_distance(&face, &edge);
+        assert!((result.from - Point::from_f64(0.5, 0.5, 0.0)).norm() < 1e-9);
+        assert!(result.distance == 0.0);
+    }
+}
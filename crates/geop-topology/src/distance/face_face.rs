@@ -0,0 +1,59 @@
+use crate::topology::face::Face;
+
+use super::{
+    alternate, closest, face_edge::face_edge_distance, face_point::face_point_distance,
+    face_samples, ClosestPoints,
+};
+
+// The closest points are either on the boundary of one of the faces or in the interior of both.
+pub fn face_face_distance(face: &Face, other: &Face) -> ClosestPoints {
+    let mut candidates = Vec::<ClosestPoints>::new();
+    for edge in face.all_edges() {
+        candidates.push(face_edge_distance(other, &edge).flip());
+    }
+    for edge in other.all_edges() {
+        candidates.push(face_edge_distance(face, &edge));
+    }
+    for start in face_samples(face) {
+        candidates.push(alternate(
+            start,
+            |p| face_point_distance(face, p).from,
+            |p| face_point_distance(other, p).from,
+        ));
+    }
+    closest(candidates).expect("Faces have closest points")
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::{efloat::EFloat64, point::Point};
+
+    use crate::primitive_objects::faces::{
+        rectangle::primitive_rectangle, sphere::primitive_sphere,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_spheres() {
+        let sphere1 = primitive_sphere(Point::zero(), EFloat64::one());
+        let sphere2 = primitive_sphere(Point::from_f64(0.0, 4.0, 0.0), EFloat64::one());
+        let result = face_face_distance(&sphere1, &sphere2);
+        assert!((result.from - Point::unit_y()).norm() < 1e-9);
+        assert!((result.to - Point::from_f64(0.0, 3.0, 0.0)).norm() < 1e-9);
+        assert!(result.distance == 2.0);
+    }
+
+    #[test]
+    fn test_sphere_rectangle() {
+        let sphere = primitive_sphere(Point::zero(), EFloat64::one());
+        let face = primitive_rectangle(
+            Point::from_f64(0.0, 0.0, 3.0),
+            Point::unit_x(),
+            Point::unit_y(),
+        );
+        let result = face_face_distance(&face, &sphere);
+        assert!(result.distance == 2.0);
+        assert!((result.to - Point::unit_z()).norm() < 1e-9);
+    }
+}
@@ -0,0 +1,44 @@
+use geop_geometry::{point::Point, surfaces::SurfaceLike};
+
+use crate::{
+    contains::face_point::{face_point_contains, FacePointContains},
+    topology::face::Face,
+};
+
+use super::{closest, edge_point::edge_point_distance, ClosestPoints};
+
+// The projection onto the surface is the closest point if it lies on the face, otherwise the closest point is on the boundary.
+pub fn face_point_distance(face: &Face, point: Point) -> ClosestPoints {
+    let mut candidates = Vec::<ClosestPoints>::new();
+    let projection = face.surface().project(point);
+    match face_point_contains(face, projection) {
+        FacePointContains::Inside
+        | FacePointContains::OnEdge(_)
+        | FacePointContains::OnPoint(_) => candidates.push(ClosestPoints::new(projection, point)),
+        FacePointContains::Outside | FacePointContains::NotOnSurface => {}
+    }
+    for edge in face.all_edges() {
+        candidates.push(edge_point_distance(&edge, point));
+    }
+    closest(candidates).expect("Face has a closest point")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primitive_objects::faces::rectangle::primitive_rectangle;
+
+    use super::*;
+
+    #[test]
+    fn test_face_point_distance() {
+        let face = primitive_rectangle(Point::zero(), Point::unit_x(), Point::unit_y());
+        let result = face_point_distance(&face, Point::from_f64(0.5, 0.5, 3.0));
+        assert_eq!(result.from, Point::from_f64(0.5, 0.5, 0.0));
+        assert!(result.distance == 3.0);
+
+        // Outside of the face the closest point is on the boundary
+        let result = face_point_distance(&face, Point::from_f64(4.0, 0.5, 4.0));
+        assert_eq!(result.from, Point::from_f64(1.0, 0.5, 0.0));
+        assert!(result.distance == 5.0);
+    }
+}
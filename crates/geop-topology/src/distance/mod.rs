@@ -0,0 +1,204 @@
+use geop_geometry::{efloat::EFloat64, point::Point};
+
+use crate::{
+    topology::{edge::Edge, face::Face, volume::Volume},
+    topology_error::TopologyResult,
+};
+
+pub mod edge_edge;
+pub mod edge_point;
+pub mod face_edge;
+pub mod face_face;
+pub mod face_point;
+pub mod volume;
+
+// Iterations of the alternating search for the closest points of two entities.
+const MAX_ITERATIONS: usize = 100;
+// The alternating search stops once the points move less than this.
+const TOLERANCE: f64 = 1e-12;
+
+// The closest points of two entities. `from` lies on the first entity and `to` on the second one.
+#[derive(Clone, Debug)]
+pub struct ClosestPoints {
+    pub from: Point,
+    pub to: Point,
+    pub distance: EFloat64,
+}
+
+impl ClosestPoints {
+    pub fn new(from: Point, to: Point) -> ClosestPoints {
+        ClosestPoints {
+            from,
+            to,
+            distance: (to - from).norm(),
+        }
+    }
+
+    pub fn flip(&self) -> ClosestPoints {
+        ClosestPoints {
+            from: self.to,
+            to: self.from,
+            distance: self.distance,
+        }
+    }
+}
+
+// Picks the pair with the smallest distance.
+pub(crate) fn closest(
+    candidates: impl IntoIterator<Item = ClosestPoints>,
+) -> Option<ClosestPoints> {
+    candidates
+        .into_iter()
+        .min_by(|a, b| a.distance.to_f64().total_cmp(&b.distance.to_f64()))
+}
+
+// Drops the error bounds, which would otherwise grow with every iteration.
+fn collapse(p: Point) -> Point {
+    Point::from_f64(p.x.to_f64(), p.y.to_f64(), p.z.to_f64())
+}
+
+// Alternates between the closest point on the first entity and the closest point on the second one, starting at a point of the first entity.
+// This converges to a pair of points which is locally closest, so it has to be started from several points.
+pub(crate) fn alternate(
+    start: Point,
+    first: impl Fn(Point) -> Point,
+    second: impl Fn(Point) -> Point,
+) -> ClosestPoints {
+    let mut from = start;
+    let mut to = collapse(second(from));
+    for _ in 0..MAX_ITERATIONS {
+        let next = collapse(first(to));
+        let moved = (next - from).norm().to_f64();
+        from = next;
+        to = collapse(second(from));
+        if moved < TOLERANCE {
+            break;
+        }
+    }
+    ClosestPoints::new(from, to)
+}
+
+// Points that are spread over the edge, used as starting points of the alternating search.
+pub(crate) fn edge_samples(edge: &Edge) -> Vec<Point> {
+    match edge.length() {
+        Some(_) => (0..8)
+            .map(|i| edge.interpolate((i as f64 + 0.5) / 8.0))
+            .collect(),
+        None => vec![edge.get_midpoint()],
+    }
+}
+
+// Points that are spread over the face, used as starting points of the alternating search.
+pub(crate) fn face_samples(face: &Face) -> Vec<Point> {
+    let mut samples = vec![face.inner_point()];
+    for edge in face.all_edges() {
+        samples.push(edge.get_midpoint());
+    }
+    samples.extend(face.all_points());
+    samples
+}
+
+// The minimum distance between two topological entities.
+pub trait Distance<T> {
+    fn distance(&self, other: &T) -> TopologyResult<ClosestPoints>;
+}
+
+pub fn distance<A: Distance<B>, B>(a: &A, b: &B) -> TopologyResult<ClosestPoints> {
+    a.distance(b)
+}
+
+impl Distance<Point> for Point {
+    fn distance(&self, other: &Point) -> TopologyResult<ClosestPoints> {
+        Ok(ClosestPoints::new(*self, *other))
+    }
+}
+
+impl Distance<Edge> for Point {
+    fn distance(&self, other: &Edge) -> TopologyResult<ClosestPoints> {
+        Ok(edge_point::edge_point_distance(other, *self).flip())
+    }
+}
+
+impl Distance<Face> for Point {
+    fn distance(&self, other: &Face) -> TopologyResult<ClosestPoints> {
+        Ok(face_point::face_point_distance(other, *self).flip())
+    }
+}
+
+impl Distance<Volume> for Point {
+    fn distance(&self, other: &Volume) -> TopologyResult<ClosestPoints> {
+        Ok(volume::volume_point_distance(other, *self)?.flip())
+    }
+}
+
+impl Distance<Point> for Edge {
+    fn distance(&self, other: &Point) -> TopologyResult<ClosestPoints> {
+        Ok(edge_point::edge_point_distance(self, *other))
+    }
+}
+
+impl Distance<Edge> for Edge {
+    fn distance(&self, other: &Edge) -> TopologyResult<ClosestPoints> {
+        Ok(edge_edge::edge_edge_distance(self, other))
+    }
+}
+
+impl Distance<Face> for Edge {
+    fn distance(&self, other: &Face) -> TopologyResult<ClosestPoints> {
+        Ok(face_edge::face_edge_distance(other, self).flip())
+    }
+}
+
+impl Distance<Volume> for Edge {
+    fn distance(&self, other: &Volume) -> TopologyResult<ClosestPoints> {
+        Ok(volume::volume_edge_distance(other, self)?.flip())
+    }
+}
+
+impl Distance<Point> for Face {
+    fn distance(&self, other: &Point) -> TopologyResult<ClosestPoints> {
+        Ok(face_point::face_point_distance(self, *other))
+    }
+}
+
+impl Distance<Edge> for Face {
+    fn distance(&self, other: &Edge) -> TopologyResult<ClosestPoints> {
+        Ok(face_edge::face_edge_distance(self, other))
+    }
+}
+
+impl Distance<Face> for Face {
+    fn distance(&self, other: &Face) -> TopologyResult<ClosestPoints> {
+        Ok(face_face::face_face_distance(self, other))
+    }
+}
+
+impl Distance<Volume> for Face {
+    fn distance(&self, other: &Volume) -> TopologyResult<ClosestPoints> {
+        Ok(volume::volume_face_distance(other, self)?.flip())
+    }
+}
+
+impl Distance<Point> for Volume {
+    fn distance(&self, other: &Point) -> TopologyResult<ClosestPoints> {
+        volume::volume_point_distance(self, *other)
+    }
+}
+
+impl Distance<Edge> for Volume {
+    fn distance(&self, other: &Edge) -> TopologyResult<ClosestPoints> {
+        volume::volume_edge_distance(self, other)
+    }
+}
+
+impl Distance<Face> for Volume {
+    fn distance(&self, other: &Face) -> TopologyResult<ClosestPoints> {
+        volume::volume_face_distance(self, other)
+    }
+}
+
+impl Distance<Volume> for Volume {
+    fn distance(&self, other: &Volume) -> TopologyResult<ClosestPoints> {
+        volume::volume_volume_distance(self, other)
+    }
+}
@@ -0,0 +1,142 @@
+use geop_geometry::{color::Category10Color, point::Point};
+
+use crate::{
+    contains::volume_point::{volume_point_contains, VolumePointContains},
+    topology::{edge::Edge, face::Face, volume::Volume},
+    topology_error::{TopologyError, TopologyResult, WithContext},
+    topology_scene::TopologyScene,
+};
+
+use super::{
+    closest, face_edge::face_edge_distance, face_face::face_face_distance,
+    face_point::face_point_distance, ClosestPoints,
+};
+
+// A point of the other entity that lies inside the volume gives a distance of zero.
+// Otherwise the other entity is outside or crosses a face, so the closest points are on the faces.
+fn volume_contains(volume: &Volume, point: Point) -> TopologyResult<bool> {
+    Ok(!matches!(
+        volume_point_contains(volume, point)?,
+        VolumePointContains::Outside
+    ))
+}
+
+pub fn volume_point_distance(volume: &Volume, point: Point) -> TopologyResult<ClosestPoints> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            format!("Distance between volume and point {}", point),
+            TopologyScene::with_points(vec![(point, Category10Color::Red)]),
+        )
+    };
+    if volume_contains(volume, point).with_context(&context)? {
+        return Ok(ClosestPoints::new(point, point));
+    }
+    let candidates = volume
+        .all_faces()
+        .iter()
+        .map(|face| face_point_distance(face, point))
+        .collect::<Vec<ClosestPoints>>();
+    closest(candidates)
+        .ok_or(TopologyError::new("Volume has no faces".to_string()))
+        .with_context(&context)
+}
+
+pub fn volume_edge_distance(volume: &Volume, edge: &Edge) -> TopologyResult<ClosestPoints> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            "Distance between volume and edge".to_string(),
+            TopologyScene::with_edges(vec![(edge.clone(), Category10Color::Red)]),
+        )
+    };
+    let midpoint = edge.get_midpoint();
+    if volume_contains(volume, midpoint).with_context(&context)? {
+        return Ok(ClosestPoints::new(midpoint, midpoint));
+    }
+    let candidates = volume
+        .all_faces()
+        .iter()
+        .map(|face| face_edge_distance(face, edge))
+        .collect::<Vec<ClosestPoints>>();
+    closest(candidates)
+        .ok_or(TopologyError::new("Volume has no faces".to_string()))
+        .with_context(&context)
+}
+
+pub fn volume_face_distance(volume: &Volume, face: &Face) -> TopologyResult<ClosestPoints> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            "Distance between volume and face".to_string(),
+            TopologyScene::with_faces(vec![(face.clone(), Category10Color::Red)]),
+        )
+    };
+    let inner_point = face.inner_point();
+    if volume_contains(volume, inner_point).with_context(&context)? {
+        return Ok(ClosestPoints::new(inner_point, inner_point));
+    }
+    let candidates = volume
+        .all_faces()
+        .iter()
+        .map(|other| face_face_distance(other, face))
+        .collect::<Vec<ClosestPoints>>();
+    closest(candidates)
+        .ok_or(TopologyError::new("Volume has no faces".to_string()))
+        .with_context(&context)
+}
+
+pub fn volume_volume_distance(volume: &Volume, other: &Volume) -> TopologyResult<ClosestPoints> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            "Distance between volumes".to_string(),
+            TopologyScene::with_volumes(vec![
+                (volume.clone(), Category10Color::Red),
+                (other.clone(), Category10Color::Blue),
+            ]),
+        )
+    };
+    let mut candidates = Vec::<ClosestPoints>::new();
+    for face in other.all_faces() {
+        candidates.push(volume_face_distance(volume, &face).with_context(&context)?);
+    }
+    for face in volume.all_faces() {
+        let inner_point = face.inner_point();
+        if volume_contains(other, inner_point).with_context(&context)? {
+            return Ok(ClosestPoints::new(inner_point, inner_point));
+        }
+    }
+    closest(candidates)
+        .ok_or(TopologyError::new("Volume has no faces".to_string()))
+        .with_context(&context)
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::transforms::Transform;
+
+    use crate::test_fixtures::cube;
+
+    use super::*;
+
+    #[test]
+    fn test_volume_point_distance() {
+        let result = volume_point_distance(&cube(), Point::from_f64(3.0, 0.5, 0.5)).unwrap();
+        assert!((result.from - Point::from_f64(1.0, 0.5, 0.5)).norm() < 1e-9);
+        assert!(result.distance == 2.0);
+
+        let result = volume_point_distance(&cube(), Point::from_f64(0.2, 0.5, 0.5)).unwrap();
+        assert!(result.distance == 0.0);
+    }
+
+    #[test]
+    fn test_volume_volume_distance() {
+        let other = cube().transform(Transform::from_translation(Point::from_f64(3.5, 0.5, 0.0)));
+        let result = volume_volume_distance(&cube(), &other).unwrap();
+        assert!(result.distance == 1.5);
+        assert!((result.from.x.to_f64() - 1.0).abs() < 1e-9);
+        assert!((result.to.x.to_f64() - 2.5).abs() < 1e-9);
+
+        // Overlapping volumes
+        let other = cube().transform(Transform::from_translation(Point::from_f64(1.5, 0.5, 0.0)));
+        let result = volume_volume_distance(&cube(), &other).unwrap();
+        assert!(result.distance == 0.0);
+    }
+}
@@ -0,0 +1,513 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    rc::Rc,
+    sync::Mutex,
+};
+
+use geop_geometry::{
+    geometry_error::{GeometryError, GeometryErrorRoot},
+    geometry_scene::GeometryScene,
+};
+
+use crate::{
+    topology::{
+        edge::Edge,
+        face::Face,
+        scene::{Color, Scene},
+    },
+    topology_error::{TopologyError, TopologyErrorRoot, TopologyResult},
+    topology_scene::TopologyScene,
+};
+
+// Set by `set_failure_dump_directory`. Without it, $GEOP_FAILURE_DUMP_DIR is used.
+static DIRECTORY: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+thread_local! {
+    // Set once the panic that is propagating has been dumped, so that the operations around it do not dump it again.
+    static DUMPED: Cell<bool> = const { Cell::new(false) };
+}
+
+// The context that is added to errors that were dumped.
+const DUMP_NOTE: &str = "Failure dump of";
+
+// Dumps are only written if geop is built with the serde feature and a directory is set.
+pub fn set_failure_dump_directory(directory: Option<PathBuf>) {
+    *DIRECTORY.lock().unwrap() = directory;
+}
+
+pub fn failure_dump_directory() -> Option<PathBuf> {
+    DIRECTORY
+        .lock()
+        .unwrap()
+        .clone()
+        .or_else(|| std::env::var_os("GEOP_FAILURE_DUMP_DIR").map(PathBuf::from))
+}
+
+// One step of the error chain, with the scene that was attached to it.
+#[derive(Clone, Debug)]
+pub struct DumpedError {
+    pub message: String,
+    pub scene: Scene,
+}
+
+// The inputs of a failed operation, so that it can be replayed, and the error chain from the outermost context to the root.
+#[derive(Clone, Debug)]
+pub struct FailureDump {
+    pub operation: String,
+    pub inputs: Scene,
+    pub errors: Vec<DumpedError>,
+}
+
+impl FailureDump {
+    pub fn new(operation: &str, inputs: Scene, error: &TopologyError) -> FailureDump {
+        let mut errors = Vec::<DumpedError>::new();
+        topology_error_chain(error, &mut errors);
+        FailureDump {
+            operation: operation.to_string(),
+            inputs,
+            errors,
+        }
+    }
+}
+
+fn topology_scene_to_scene(scene: &TopologyScene) -> Scene {
+    Scene::new(
+        scene
+            .volumes
+            .iter()
+            .map(|(volume, color)| (volume.clone(), Color::from_category_color(*color)))
+            .collect(),
+        scene
+            .face
+            .iter()
+            .map(|(face, color)| (face.clone(), Color::from_category_color(*color)))
+            .collect(),
+        scene
+            .edges
+            .iter()
+            .map(|(edge, color)| (edge.clone(), Color::from_category_color(*color)))
+            .collect(),
+        scene
+            .points
+            .iter()
+            .map(|(point, color)| (*point, Color::from_category_color(*color)))
+            .collect(),
+    )
+}
+
+// Curves become edges without end points and surfaces become faces without boundaries.
+fn geometry_scene_to_scene(scene: &GeometryScene) -> Scene {
+    Scene::new(
+        Vec::new(),
+        scene
+            .surfaces
+            .iter()
+            .map(|(surface, color)| {
+                (
+                    Face::new_unchecked(Vec::new(), Rc::new(surface.clone())),
+                    Color::from_category_color(*color),
+                )
+            })
+            .collect(),
+        scene
+            .curves
+            .iter()
+            .map(|(curve, color)| {
+                (
+                    Edge::new(None, None, curve.clone()),
+                    Color::from_category_color(*color),
+                )
+            })
+            .collect(),
+        scene
+            .points
+            .iter()
+            .map(|(point, color)| (*point, Color::from_category_color(*color)))
+            .collect(),
+    )
+}
+
+fn topology_error_chain(error: &TopologyError, errors: &mut Vec<DumpedError>) {
+    let (message, scene) = match error {
+        TopologyError::Context {
+            message,
+            error_scene,
+            inner_error,
+        } => {
+            errors.push(DumpedError {
+                message: message.clone(),
+                scene: match error_scene {
+                    Some(scene) => topology_scene_to_scene(scene),
+                    None => Scene::empty(),
+                },
+            });
+            return topology_error_chain(inner_error, errors);
+        }
+        TopologyError::Root(TopologyErrorRoot::FromGeometryError { geometry_error }) => {
+            return geometry_error_chain(geometry_error, errors);
+        }
+        TopologyError::Root(TopologyErrorRoot::InTopologyCrate { message, .. }) => {
+            (message.clone(), Scene::empty())
+        }
+        TopologyError::Root(TopologyErrorRoot::FromStepError { step_error }) => {
+            (step_error.to_string(), Scene::empty())
+        }
+    };
+    errors.push(DumpedError { message, scene });
+}
+
+fn geometry_error_chain(error: &GeometryError, errors: &mut Vec<DumpedError>) {
+    let message = match error {
+        GeometryError::Context {
+            message,
+            error_scene,
+            inner_error,
+        } => {
+            errors.push(DumpedError {
+                message: message.clone(),
+                scene: match error_scene {
+                    Some(scene) => geometry_scene_to_scene(scene),
+                    None => Scene::empty(),
+                },
+            });
+            return geometry_error_chain(inner_error, errors);
+        }
+        GeometryError::Root(GeometryErrorRoot::InGeometryCrate { message, .. }) => message.clone(),
+        GeometryError::Root(GeometryErrorRoot::FromAlgebraError { algebra_error }) => {
+            algebra_error.to_string()
+        }
+    };
+    errors.push(DumpedError {
+        message,
+        scene: Scene::empty(),
+    });
+}
+
+// Whether an operation inside already dumped the error, which it then passed on.
+fn is_dumped(error: &TopologyError) -> bool {
+    match error {
+        TopologyError::Context {
+            message,
+            inner_error,
+            ..
+        } => message.starts_with(DUMP_NOTE) || is_dumped(inner_error),
+        TopologyError::Root(_) => false,
+    }
+}
+
+// Runs the operation and writes a dump if it returns an error or panics and dumps are enabled. The path of the dump
+// is added to the error, or printed before the panic continues. Of operations that run inside each other, only the
+// innermost one that fails is dumped, as its inputs are the smallest. The inputs are only collected on failure.
+pub fn dump_on_failure<T, I: FnOnce() -> Scene>(
+    operation: &str,
+    inputs: I,
+    run: impl FnOnce() -> TopologyResult<T>,
+) -> TopologyResult<T> {
+    let (result, inputs) = run_nested(operation, inputs, run);
+    let error = match result {
+        Ok(value) => return Ok(value),
+        Err(error) if is_dumped(&error) => return Err(error),
+        Err(error) => error,
+    };
+    let directory = match failure_dump_directory() {
+        Some(directory) => directory,
+        None => return Err(error),
+    };
+    let dump = FailureDump::new(operation, inputs(), &error);
+    match write_failure_dump(&dump, directory) {
+        Ok(Some(path)) => Err(error.with_context(format!(
+            "{} {} written to {}",
+            DUMP_NOTE,
+            operation,
+            path.display()
+        ))),
+        Ok(None) => Err(error),
+        Err(dump_error) => Err(error.with_context(format!(
+            "{} {} could not be written: {}",
+            DUMP_NOTE, operation, dump_error
+        ))),
+    }
+}
+
+// Like `dump_on_failure` for operations that do not return errors, but may panic.
+pub fn dump_on_panic<T>(
+    operation: &str,
+    inputs: impl FnOnce() -> Scene,
+    run: impl FnOnce() -> T,
+) -> T {
+    run_nested(operation, inputs, run).0
+}
+
+// Runs the operation and hands the inputs back. A panic is dumped by the innermost operation and then continues.
+fn run_nested<T, I: FnOnce() -> Scene>(
+    operation: &str,
+    inputs: I,
+    run: impl FnOnce() -> T,
+) -> (T, I) {
+    // Nothing is propagating when an operation starts, so a panic that was dumped before has been caught
+    DUMPED.with(|dumped| dumped.set(false));
+    match panic::catch_unwind(AssertUnwindSafe(run)) {
+        Ok(value) => (value, inputs),
+        Err(payload) => {
+            // The flag is kept while the panic propagates through the operations around this one
+            if !DUMPED.with(|dumped| dumped.replace(true)) {
+                dump_panic(operation, inputs, payload.as_ref());
+            }
+            panic::resume_unwind(payload)
+        }
+    }
+}
+
+// The panic message becomes the root of the error chain of the dump.
+fn dump_panic(operation: &str, inputs: impl FnOnce() -> Scene, payload: &(dyn Any + Send)) {
+    let directory = match failure_dump_directory() {
+        Some(directory) => directory,
+        None => return,
+    };
+    let message = match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(message), _) => message.to_string(),
+        (None, Some(message)) => message.clone(),
+        (None, None) => "Unknown panic".to_string(),
+    };
+    let error = TopologyError::new(format!("Panicked: {}", message));
+    match write_failure_dump(&FailureDump::new(operation, inputs(), &error), directory) {
+        Ok(Some(path)) => eprintln!("{} {} written to {}", DUMP_NOTE, operation, path.display()),
+        Ok(None) => {}
+        Err(dump_error) => eprintln!(
+            "{} {} could not be written: {}",
+            DUMP_NOTE, operation, dump_error
+        ),
+    }
+}
+
+// Writes the dump into the directory and returns its path. Without the serde feature nothing is written.
+#[cfg(not(feature = "serde"))]
+pub fn write_failure_dump(
+    _dump: &FailureDump,
+    _directory: PathBuf,
+) -> TopologyResult<Option<PathBuf>> {
+    Ok(None)
+}
+
+#[cfg(feature = "serde")]
+pub fn write_failure_dump(
+    dump: &FailureDump,
+    directory: PathBuf,
+) -> TopologyResult<Option<PathBuf>> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // Numbers the dumps of this process, so that several failures do not overwrite each other.
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let path = directory.join(format!(
+        "{}-{}-{}.json",
+        dump.operation,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let json = crate::serialize::to_json(dump)?;
+    std::fs::create_dir_all(&directory)
+        .and_then(|_| std::fs::write(&path, json))
+        .map_err(|e| TopologyError::new(e.to_string()))?;
+    Ok(Some(path))
+}
+
+// Loads a dump, e.g. to replay the failure in a unit test.
+#[cfg(feature = "serde")]
+pub fn load_failure_dump(path: impl AsRef<std::path::Path>) -> TopologyResult<FailureDump> {
+    let json = std::fs::read_to_string(path.as_ref()).map_err(|e| {
+        TopologyError::new(format!("Cannot read {}: {}", path.as_ref().display(), e))
+    })?;
+    crate::serialize::from_json(&json)
+}
+
+#[cfg(feature = "serde")]
+mod storable {
+    use std::rc::Rc;
+
+    use geop_geometry::surfaces::surface::Surface;
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        serialize::{Storable, StoredScene, SurfaceTable},
+        topology::scene::Scene,
+        topology_error::TopologyResult,
+    };
+
+    use super::{DumpedError, FailureDump};
+
+    #[derive(Serialize, Deserialize)]
+    pub struct StoredFailureDump {
+        operation: String,
+        inputs: StoredScene,
+        errors: Vec<(String, StoredScene)>,
+    }
+
+    impl Storable for FailureDump {
+        type Stored = StoredFailureDump;
+
+        fn store(&self, surfaces: &mut SurfaceTable) -> StoredFailureDump {
+            StoredFailureDump {
+                operation: self.operation.clone(),
+                inputs: self.inputs.store(surfaces),
+                errors: self
+                    .errors
+                    .iter()
+                    .map(|error| (error.message.clone(), error.scene.store(surfaces)))
+                    .collect(),
+            }
+        }
+
+        fn restore(
+            stored: StoredFailureDump,
+            surfaces: &[Rc<Surface>],
+        ) -> TopologyResult<FailureDump> {
+            let mut errors = Vec::<DumpedError>::new();
+            for (message, scene) in stored.errors {
+                errors.push(DumpedError {
+                    message,
+                    scene: Scene::restore(scene, surfaces)?,
+                });
+            }
+            Ok(FailureDump {
+                operation: stored.operation,
+                inputs: Scene::restore(stored.inputs, surfaces)?,
+                errors,
+            })
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use geop_geometry::{color::Category10Color, efloat::EFloat64, point::Point};
+
+    use crate::primitive_objects::volumes::cube::primitive_cube;
+
+    use super::*;
+
+    // The tests share the dump directory
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_dump_and_load() {
+        let _lock = LOCK.lock().unwrap();
+        let directory = std::env::temp_dir().join(format!("geop-dumps-{}", std::process::id()));
+        set_failure_dump_directory(Some(directory.clone()));
+
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let error = TopologyError::from(GeometryError::new("Root cause".to_string()))
+            .with_context_scene(
+                "Split the cube".to_string(),
+                TopologyScene::with_points(vec![(Point::unit_x(), Category10Color::Red)]),
+            );
+        let result = dump_on_failure(
+            "split",
+            || Scene::new(vec![(cube.clone(), Color::red())], vec![], vec![], vec![]),
+            || Err::<(), TopologyError>(error),
+        );
+        set_failure_dump_directory(None);
+
+        // The error tells where the dump is
+        let message = match result.unwrap_err() {
+            TopologyError::Context { message, .. } => message,
+            _ => panic!("Expected the path of the dump"),
+        };
+        let path = message.split(" written to ").nth(1).unwrap();
+
+        let dump = load_failure_dump(path).unwrap();
+        assert_eq!(dump.operation, "split");
+        assert_eq!(dump.inputs.volumes[0].0.boundary().faces().len(), 6);
+        assert_eq!(dump.errors.len(), 2);
+        assert_eq!(dump.errors[0].message, "Split the cube");
+        assert!(dump.errors[0].scene.points[0].0 == Point::unit_x());
+        assert_eq!(dump.errors[1].message, "Root cause");
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_success() {
+        let result = dump_on_failure("nothing", || panic!("Inputs are not needed"), || Ok(1));
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_dump_on_panic() {
+        let _lock = LOCK.lock().unwrap();
+        let directory = std::env::temp_dir().join(format!("geop-panics-{}", std::process::id()));
+        set_failure_dump_directory(Some(directory.clone()));
+
+        // Only the innermost operation is dumped, and the panic continues
+        let result = panic::catch_unwind(|| {
+            dump_on_panic("outer", Scene::empty, || {
+                dump_on_panic(
+                    "inner",
+                    || {
+                        Scene::new(
+                            vec![],
+                            vec![],
+                            vec![],
+                            vec![(Point::unit_x(), Color::red())],
+                        )
+                    },
+                    || panic!("Degenerate input"),
+                )
+            })
+        });
+        assert!(result.is_err());
+        let paths = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        assert_eq!(paths.len(), 1);
+        let dump = load_failure_dump(&paths[0]).unwrap();
+        assert_eq!(dump.operation, "inner");
+        assert!(dump.inputs.points[0].0 == Point::unit_x());
+        assert_eq!(dump.errors[0].message, "Panicked: Degenerate input");
+
+        // Once the panic was caught, later panics are dumped again
+        let result =
+            panic::catch_unwind(|| dump_on_panic("later", Scene::empty, || panic!("Again")));
+        set_failure_dump_directory(None);
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 2);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_handled_failure() {
+        let _lock = LOCK.lock().unwrap();
+        let directory = std::env::temp_dir().join(format!("geop-handled-{}", std::process::id()));
+        set_failure_dump_directory(Some(directory.clone()));
+        let fail =
+            |message: &str| Err::<(), TopologyError>(TopologyError::new(message.to_string()));
+
+        // The failure of the first operation is handled, the failure of its sibling is passed on
+        let result = dump_on_failure("outer", Scene::empty, || {
+            let _ = dump_on_failure("first", Scene::empty, || fail("Handled"));
+            dump_on_failure("second", Scene::empty, || fail("Passed on"))
+        });
+        assert!(result.is_err());
+        // A failure of its own after a handled failure inside is dumped as well
+        let result = dump_on_failure("other", Scene::empty, || {
+            let _ = dump_on_failure("first", Scene::empty, || fail("Handled"));
+            fail("Own failure")
+        });
+        assert!(result.is_err());
+        set_failure_dump_directory(None);
+
+        let mut operations = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| load_failure_dump(entry.unwrap().path()).unwrap().operation)
+            .collect::<Vec<_>>();
+        operations.sort();
+        assert_eq!(operations, vec!["first", "first", "other", "second"]);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
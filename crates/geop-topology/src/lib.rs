@@ -2,8 +2,16 @@ pub mod debug_data;
 pub mod topology;
 
 pub mod contains;
+pub mod distance;
+pub mod failure_dump;
 pub mod operations;
 pub mod primitive_objects;
+pub mod ray_cast;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod step;
+#[cfg(test)]
+mod test_fixtures;
 pub mod topology_error;
 pub mod topology_scene;
 
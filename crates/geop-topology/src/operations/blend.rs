@@ -0,0 +1,688 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    color::Category10Color,
+    curves::{circle::Circle, curve::Curve, line::Line},
+    efloat::EFloat64,
+    geometry_error::GeometryError,
+    point::Point,
+    surfaces::{cylinder::Cylinder, plane::Plane, surface::Surface, torus::Torus, SurfaceLike},
+    transforms::Transform,
+};
+
+use super::revolve::revolve_edge;
+use crate::{
+    contains::{
+        edge_point::{edge_point_contains, EdgePointContains},
+        face_point::{face_point_contains, FacePointContains},
+    },
+    primitive_objects::edges::line::primitive_line,
+    topology::{contour::Contour, edge::Edge, face::Face, shell::Shell, volume::Volume},
+    topology_error::{ElevateToTopology, TopologyError, TopologyResult, WithContext},
+    topology_scene::TopologyScene,
+};
+
+// The cross section of a blend. A round blend is an arc with the given radius that touches both faces.
+// A chamfer is a straight cut that starts at the given distances from the edge on the first and the second face.
+pub(crate) enum Profile {
+    Round(EFloat64),
+    Chamfer(EFloat64, EFloat64),
+}
+
+// Replaces the edges of a volume by blend faces and trims the neighbouring faces.
+// Line edges have to lie between two planes or between a plane and a cylinder along the edge. They have to end in planes perpendicular
+// to the edge, which get the profile instead of the corner. Closed circles have to lie between a plane and a coaxial cylinder.
+// The first face of an edge is the one whose boundary runs along the edge in the same direction.
+pub(crate) fn blend(volume: &Volume, edges: &[Edge], profile: Profile) -> TopologyResult<Volume> {
+    let vertices = edges
+        .iter()
+        .flat_map(|edge| edge.start.into_iter().chain(edge.end))
+        .collect::<Vec<Point>>();
+    for (i, vertex) in vertices.iter().enumerate() {
+        if vertices[i + 1..].contains(vertex) {
+            return Err(TopologyError::new(
+                "Blended edges must not share a vertex".to_string(),
+            ));
+        }
+    }
+
+    let mut shells = std::iter::once(volume.boundary())
+        .chain(volume.cavities().iter())
+        .map(|shell| shell.faces().to_vec())
+        .collect::<Vec<Vec<Face>>>();
+    for edge in edges {
+        blend_edge(&mut shells, edge, &profile)?;
+    }
+
+    let mut shells = shells.into_iter().map(Shell::new);
+    let boundary = shells
+        .next()
+        .ok_or_else(|| TopologyError::new("Volume has no boundary".to_string()))?;
+    Ok(Volume::new(boundary, shells.collect()))
+}
+
+// Cross section of the blend at a point of the edge. The profile touches the first face at t1 and the second face at t2.
+// For round blends, center is the center of the arc.
+struct Section {
+    center: Point,
+    t1: Point,
+    t2: Point,
+}
+
+// The center of a round blend has the distance radius to both faces. For convex edges it is behind both faces, for concave edges in front of them.
+fn section(
+    face1: &Face,
+    face2: &Face,
+    edge1: &Edge,
+    p: Point,
+    profile: &Profile,
+) -> TopologyResult<(Section, bool)> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            format!("Cross section of the blend at {}", p),
+            TopologyScene::with_points(vec![(p, Category10Color::Red)]),
+        )
+    };
+    let n1 = face1.normal(p);
+    let n2 = face2.normal(p);
+    if n1.cross(n2).norm() == 0.0 {
+        return Err(TopologyError::new(
+            "Faces meet tangentially at the edge".to_string(),
+        ))
+        .with_context(&context);
+    }
+    // The first face lies on the side of the second face where the material is
+    let tangent = edge1.tangent(p);
+    let convex = n1.cross(tangent).dot(n2) < 0.0;
+    let section = match profile {
+        Profile::Round(radius) => {
+            let signed_radius = match convex {
+                true => *radius,
+                false => -*radius,
+            };
+            let offset =
+                ((n1 + n2) * -signed_radius / (EFloat64::one() + n1.dot(n2))).elevate(&context)?;
+            let center = p + offset;
+            Section {
+                center,
+                t1: center + n1 * signed_radius,
+                t2: center + n2 * signed_radius,
+            }
+        }
+        // The directions into the faces are perpendicular to the edge
+        Profile::Chamfer(distance1, distance2) => Section {
+            center: p,
+            t1: p + n1.cross(tangent) * *distance1,
+            t2: p + n2.cross(-tangent) * *distance2,
+        },
+    };
+    Ok((section, convex))
+}
+
+// Cross section of the blend at a point of a line edge. Where a face is a cylinder along the edge, the cross section
+// of the cylinder is a circle, so the profile touches it on that circle instead of on the tangent plane.
+fn line_section(
+    face1: &Face,
+    face2: &Face,
+    edge1: &Edge,
+    p: Point,
+    profile: &Profile,
+) -> TopologyResult<(Section, bool)> {
+    let (section, convex) = section(face1, face2, edge1, p, profile)?;
+    let (cylinder, plane_face, first) = match (&**face1.surface(), &**face2.surface()) {
+        (Surface::Cylinder(cylinder), _) => (cylinder, face2, true),
+        (_, Surface::Cylinder(cylinder)) => (cylinder, face1, false),
+        _ => return Ok((section, convex)),
+    };
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            format!("Cross section of the blend on a cylinder at {}", p),
+            TopologyScene::with_points(vec![(p, Category10Color::Red)]),
+        )
+    };
+    let too_large =
+        || TopologyError::new("Blend is too large for the faces next to the edge".to_string());
+    let direction = cylinder.extend_dir.normalize().elevate(&context)?;
+    let axis = cylinder.basis + direction * (p - cylinder.basis).dot(direction);
+    let radius = cylinder.radius.norm();
+    let section = match profile {
+        // The cut ends on the circle at the given distance from the edge, on the side where the tangent plane is cut
+        Profile::Chamfer(distance1, distance2) => {
+            let (t, distance) = match first {
+                true => (section.t1, *distance1),
+                false => (section.t2, *distance2),
+            };
+            let half = (distance / (radius * EFloat64::two()))
+                .map_err(GeometryError::from)
+                .elevate(&context)?;
+            let cos_half = (EFloat64::one() - half * half)
+                .sqrt()
+                .ok_or_else(too_large)
+                .with_context(&context)?;
+            let radial = p - axis;
+            let around = match direction.cross(radial).dot(t - p) > 0.0 {
+                true => direction.cross(radial),
+                false => radial.cross(direction),
+            };
+            let (cos, sin) = (
+                EFloat64::one() - EFloat64::two() * half * half,
+                EFloat64::two() * half * cos_half,
+            );
+            let t = axis + radial * cos + around * sin;
+            match first {
+                true => Section { t1: t, ..section },
+                false => Section { t2: t, ..section },
+            }
+        }
+        // The center has the distance radius to the plane and to the circle, so it lies on a line parallel to the plane
+        // and on a circle around the axis
+        Profile::Round(round) => {
+            let signed_radius = match convex {
+                true => *round,
+                false => -*round,
+            };
+            let n_plane = plane_face.normal(p);
+            let around = match (first, face1.normal(p), face2.normal(p)) {
+                (true, n, _) | (false, _, n) if n.dot(p - axis) > 0.0 => radius - signed_radius,
+                _ => radius + signed_radius,
+            };
+            let height = (p - axis).dot(n_plane) - signed_radius;
+            if around <= 0.0 {
+                return Err(too_large()).with_context(&context);
+            }
+            let width = (around * around - height * height)
+                .sqrt()
+                .ok_or_else(too_large)
+                .with_context(&context)?;
+            let across = direction.cross(n_plane);
+            let (center1, center2) = (
+                axis + n_plane * height + across * width,
+                axis + n_plane * height - across * width,
+            );
+            let center = match (center1 - section.center).norm().to_f64()
+                <= (center2 - section.center).norm().to_f64()
+            {
+                true => center1,
+                false => center2,
+            };
+            let on_plane = center + n_plane * signed_radius;
+            let on_cylinder = axis + ((center - axis) / around).elevate(&context)? * radius;
+            match first {
+                true => Section {
+                    center,
+                    t1: on_cylinder,
+                    t2: on_plane,
+                },
+                false => Section {
+                    center,
+                    t1: on_plane,
+                    t2: on_cylinder,
+                },
+            }
+        }
+    };
+    Ok((section, convex))
+}
+
+// The edge of the profile from start to end.
+fn profile_edge(
+    section: &Section,
+    start: Point,
+    end: Point,
+    profile: &Profile,
+) -> TopologyResult<Edge> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            "Profile of the blend".to_string(),
+            TopologyScene::with_points(vec![
+                (start, Category10Color::Red),
+                (end, Category10Color::Blue),
+            ]),
+        )
+    };
+    let curve = match profile {
+        Profile::Round(radius) => {
+            let normal = (start - section.center)
+                .cross(end - section.center)
+                .normalize()
+                .elevate(&context)?;
+            Curve::Circle(Circle::try_new(section.center, normal, *radius).elevate(&context)?)
+        }
+        Profile::Chamfer(_, _) => Curve::Line(
+            Line::new(start, (end - start).normalize().elevate(&context)?).elevate(&context)?,
+        ),
+    };
+    Ok(Edge::new(Some(start), Some(end), curve))
+}
+
+// Replaces every edge of the face by the edges returned for it.
+fn rebuild_face(
+    face: &Face,
+    mut replace: impl FnMut(&Edge) -> TopologyResult<Vec<Edge>>,
+) -> TopologyResult<Face> {
+    let mut boundaries = Vec::<Contour>::new();
+    for contour in face.boundaries().iter() {
+        let mut edges = Vec::<Edge>::new();
+        for edge in contour.edges.iter() {
+            edges.extend(replace(edge)?);
+        }
+        boundaries.push(Contour::new(edges));
+    }
+    Ok(Face::new(boundaries, face.surface().clone()))
+}
+
+// The edge as it is oriented in the boundary of the face.
+fn oriented_edge(face: &Face, edge: &Edge) -> TopologyResult<Edge> {
+    face.all_edges()
+        .into_iter()
+        .find(|e| e == edge)
+        .ok_or_else(|| TopologyError::new("Edge is not part of the face".to_string()))
+}
+
+fn blend_edge(shells: &mut [Vec<Face>], edge: &Edge, profile: &Profile) -> TopologyResult<()> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            "Blend edge".to_string(),
+            TopologyScene::with_edges(vec![(edge.clone(), Category10Color::Red)]),
+        )
+    };
+
+    let (faces, adjacent) = shells
+        .iter_mut()
+        .find_map(|faces| {
+            let adjacent = (0..faces.len())
+                .filter(|i| faces[*i].all_edges().contains(edge))
+                .collect::<Vec<usize>>();
+            (!adjacent.is_empty()).then_some((faces, adjacent))
+        })
+        .ok_or_else(|| TopologyError::new("Edge is not part of the volume".to_string()))
+        .with_context(&context)?;
+    if adjacent.len() != 2 {
+        return Err(TopologyError::new(
+            "Edge has to be shared by exactly two faces".to_string(),
+        ))
+        .with_context(&context);
+    }
+    let (i1, i2) = match oriented_edge(&faces[adjacent[0]], edge)
+        .with_context(&context)?
+        .curve
+        == edge.curve
+    {
+        true => (adjacent[0], adjacent[1]),
+        false => (adjacent[1], adjacent[0]),
+    };
+    let (face1, face2) = (faces[i1].clone(), faces[i2].clone());
+    let edge1 = oriented_edge(&face1, edge).with_context(&context)?;
+    let edge2 = oriented_edge(&face2, edge).with_context(&context)?;
+
+    match (&edge1.curve, edge1.start, edge1.end) {
+        (Curve::Line(line), Some(a), Some(b)) => {
+            let supported = match (&**face1.surface(), &**face2.surface()) {
+                (Surface::Plane(_), Surface::Plane(_)) => true,
+                (Surface::Plane(_), Surface::Cylinder(cylinder))
+                | (Surface::Cylinder(cylinder), Surface::Plane(_)) => {
+                    cylinder.extend_dir.is_parallel(line.direction)
+                }
+                _ => false,
+            };
+            if !supported {
+                return Err(TopologyError::new(
+                    "Line edges can only be blended between two planes or a plane and a cylinder along the edge"
+                        .to_string(),
+                ))
+                .with_context(&context);
+            }
+            let (section_a, convex) = line_section(&face1, &face2, &edge1, a, profile)?;
+            let (section_b, _) = line_section(&face1, &face2, &edge1, b, profile)?;
+            let tangent_point = |p: Point, first: bool| {
+                let section = match p == a {
+                    true => &section_a,
+                    false => &section_b,
+                };
+                match first {
+                    true => section.t1,
+                    false => section.t2,
+                }
+            };
+
+            // The faces that end the edge at a and b
+            let mut ends = Vec::<usize>::new();
+            for vertex in [a, b] {
+                let end = (0..faces.len())
+                    .filter(|i| *i != i1 && *i != i2 && faces[*i].all_points().contains(&vertex))
+                    .collect::<Vec<usize>>();
+                if end.len() != 1
+                    || !matches!(**faces[end[0]].surface(), Surface::Plane(_))
+                    || faces[end[0]].normal(vertex).cross(line.direction).norm() != 0.0
+                {
+                    return Err(TopologyError::new(
+                        "Edge has to end in a plane perpendicular to the edge".to_string(),
+                    ))
+                    .with_context(&context);
+                }
+                ends.push(end[0]);
+            }
+
+            // The end faces get the profile instead of the corner. This also checks that the profile fits.
+            let mut profiles = Vec::<Edge>::new();
+            for (vertex, i) in [a, b].into_iter().zip(ends) {
+                let section = match vertex == a {
+                    true => &section_a,
+                    false => &section_b,
+                };
+                let trim_point = |e: &Edge| {
+                    [section.t1, section.t2]
+                        .into_iter()
+                        .find(|t| edge_point_contains(e, *t) == EdgePointContains::Inside)
+                        .ok_or_else(|| {
+                            TopologyError::new(
+                                "Blend is too large for the faces next to the edge".to_string(),
+                            )
+                        })
+                };
+                let face = faces[i].clone();
+                faces[i] = rebuild_face(&face, |e| {
+                    if e.end == Some(vertex) {
+                        let start = trim_point(e)?;
+                        let next = face
+                            .all_edges()
+                            .into_iter()
+                            .find(|next| next.start == Some(vertex))
+                            .ok_or_else(|| {
+                                TopologyError::new(
+                                    "Boundary of the end face is not closed".to_string(),
+                                )
+                            })?;
+                        let end = trim_point(&next)?;
+                        let profile_edge = profile_edge(section, start, end, profile)?;
+                        profiles.push(profile_edge.clone());
+                        return Ok(vec![
+                            Edge::new(e.start, Some(start), e.curve.clone()),
+                            profile_edge,
+                        ]);
+                    }
+                    if e.start == Some(vertex) {
+                        return Ok(vec![Edge::new(
+                            Some(trim_point(e)?),
+                            e.end,
+                            e.curve.clone(),
+                        )]);
+                    }
+                    Ok(vec![e.clone()])
+                })?;
+            }
+
+            // The faces next to the edge are trimmed at the tangent lines
+            let mut trimmed = Vec::<Edge>::new();
+            for (i, first) in [(i1, true), (i2, false)] {
+                let face = faces[i].clone();
+                let oriented = oriented_edge(&face, edge).with_context(&context)?;
+                faces[i] = rebuild_face(&face, |e| {
+                    if let (Some(start), Some(end)) = (e.start, e.end) {
+                        if e.start == oriented.start && e.end == oriented.end {
+                            let (start, end) =
+                                (tangent_point(start, first), tangent_point(end, first));
+                            let edge = primitive_line(start, end).with_context(&context)?;
+                            trimmed.push(edge.clone());
+                            return Ok(vec![edge]);
+                        }
+                    }
+                    let replace = |p: Option<Point>| match p {
+                        Some(p) if p == a || p == b => Some(tangent_point(p, first)),
+                        p => p,
+                    };
+                    Ok(vec![Edge::new(
+                        replace(e.start),
+                        replace(e.end),
+                        e.curve.clone(),
+                    )])
+                })?;
+            }
+
+            // The blend face runs along all new edges in the opposite direction
+            let mut remaining = trimmed
+                .iter()
+                .chain(profiles.iter())
+                .map(|e| e.flip())
+                .collect::<Vec<Edge>>();
+            let mut boundary = vec![remaining.remove(0)];
+            while !remaining.is_empty() {
+                let next = boundary
+                    .last()
+                    .and_then(|last| remaining.iter().position(|e| e.start == last.end))
+                    .ok_or_else(|| TopologyError::new("New edges do not form a loop".to_string()))
+                    .with_context(&context)?;
+                boundary.push(remaining.remove(next));
+            }
+            let surface = match profile {
+                Profile::Round(radius) => Surface::Cylinder(Cylinder::new(
+                    section_a.center,
+                    line.direction,
+                    *radius,
+                    convex,
+                )),
+                // The normal points away from the material, like the normals of both faces
+                Profile::Chamfer(_, _) => {
+                    let across = section_a.t2 - section_a.t1;
+                    let outwards = face1.normal(a) + face2.normal(a);
+                    match line.direction.cross(across).dot(outwards) > 0.0 {
+                        true => Surface::Plane(Plane::new(section_a.t1, line.direction, across)),
+                        false => Surface::Plane(Plane::new(section_a.t1, across, line.direction)),
+                    }
+                }
+            };
+            faces.push(Face::new(vec![Contour::new(boundary)], Rc::new(surface)));
+        }
+        (Curve::Circle(circle), None, None) => {
+            let coaxial = |face: &Face| match &**face.surface() {
+                Surface::Plane(plane) => plane.normal(plane.basis).is_parallel(circle.normal),
+                Surface::Cylinder(cylinder) => cylinder.extend_dir.is_parallel(circle.normal),
+                _ => false,
+            };
+            let plane_and_cylinder = matches!(
+                (&**face1.surface(), &**face2.surface()),
+                (Surface::Plane(_), Surface::Cylinder(_))
+                    | (Surface::Cylinder(_), Surface::Plane(_))
+            );
+            if !plane_and_cylinder || !coaxial(&face1) || !coaxial(&face2) {
+                return Err(TopologyError::new(
+                    "Circle edges can only be blended between a plane and a coaxial cylinder"
+                        .to_string(),
+                ))
+                .with_context(&context);
+            }
+            let p = circle.basis + circle.radius;
+            let (section, convex) = section(&face1, &face2, &edge1, p, profile)?;
+            if face_point_contains(&face1, section.t1) != FacePointContains::Inside
+                || face_point_contains(&face2, section.t2) != FacePointContains::Inside
+            {
+                return Err(TopologyError::new(
+                    "Blend is too large for the faces next to the edge".to_string(),
+                ))
+                .with_context(&context);
+            }
+            let foot =
+                |q: Point| circle.basis + circle.normal * (q - circle.basis).dot(circle.normal);
+
+            // The faces next to the edge are trimmed at the tangent circles
+            let mut trimmed = Vec::<Edge>::new();
+            for (i, oriented, t) in [(i1, &edge1, section.t1), (i2, &edge2, section.t2)] {
+                let normal = match &oriented.curve {
+                    Curve::Circle(circle) => circle.normal,
+                    _ => {
+                        return Err(TopologyError::new(
+                            "Edge is not a circle in both faces".to_string(),
+                        ))
+                        .with_context(&context)
+                    }
+                };
+                let circle =
+                    Circle::try_new(foot(t), normal, (t - foot(t)).norm()).elevate(&context)?;
+                let new_edge = Edge::new(None, None, Curve::Circle(circle));
+                trimmed.push(new_edge.clone());
+                faces[i] = rebuild_face(&faces[i], |e| match e == oriented {
+                    true => Ok(vec![new_edge.clone()]),
+                    false => Ok(vec![e.clone()]),
+                })?;
+            }
+
+            let blend_face = match profile {
+                Profile::Round(radius) => {
+                    let basis = foot(section.center);
+                    let torus = Torus::try_new(
+                        basis,
+                        circle.normal,
+                        (section.center - basis).norm(),
+                        *radius,
+                        convex,
+                    )
+                    .elevate(&context)?;
+                    Face::new(
+                        trimmed
+                            .iter()
+                            .map(|e| Contour::new(vec![e.flip()]))
+                            .collect(),
+                        Rc::new(Surface::Torus(torus)),
+                    )
+                }
+                // The chamfer is the surface that is swept by the straight profile around the axis
+                Profile::Chamfer(_, _) => {
+                    let line = primitive_line(section.t1, section.t2).with_context(&context)?;
+                    let face = revolve_edge(
+                        &line,
+                        circle.basis,
+                        circle.normal,
+                        Transform::from_translation(Point::zero()),
+                        true,
+                    )?
+                    .ok_or_else(|| {
+                        TopologyError::new("Profile of the chamfer lies on the axis".to_string())
+                    })
+                    .with_context(&context)?;
+                    let outwards = face1.normal(p) + face2.normal(p);
+                    let midpoint = line.get_midpoint();
+                    match face.normal(midpoint).dot(outwards) > 0.0 {
+                        true => face,
+                        false => face.flip(),
+                    }
+                }
+            };
+            faces.push(blend_face);
+        }
+        _ => {
+            return Err(TopologyError::new(
+                "Only bounded lines and closed circles can be blended".to_string(),
+            ))
+            .with_context(&context)
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{operations::extrude::extrude, test_fixtures::is_inside};
+
+    use super::*;
+
+    // A half cylinder, whose flat side at x = 0 meets the cylinder in lines along the axis.
+    fn half_cylinder() -> Volume {
+        let (top, bottom) = (Point::unit_y(), -Point::unit_y());
+        let circle = Circle::try_new(Point::zero(), -Point::unit_z(), EFloat64::one()).unwrap();
+        let face = Face::new(
+            vec![Contour::new(vec![
+                Edge::new(Some(top), Some(bottom), Curve::Circle(circle)),
+                primitive_line(bottom, top).unwrap(),
+            ])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        extrude(face, Point::from_f64(0.0, 0.0, 2.0))
+    }
+
+    fn side_edge(volume: &Volume) -> Edge {
+        volume
+            .all_faces()
+            .iter()
+            .flat_map(|face| face.all_edges())
+            .find(|edge| edge.get_midpoint() == Point::from_f64(0.0, 1.0, 1.0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_blend_plane_cylinder_line_edge() {
+        // The chamfer ends 1/4 from the edge on the flat side and on the cylinder
+        let volume = half_cylinder();
+        let quarter = EFloat64::from(0.25);
+        let chamfered = blend(
+            &volume,
+            &[side_edge(&volume)],
+            Profile::Chamfer(quarter, quarter),
+        )
+        .unwrap();
+        assert_eq!(chamfered.boundary().faces().len(), 5);
+        let angle = 2.0 * (0.125f64).asin();
+        let on_cylinder = Point::from_f64(angle.sin(), angle.cos(), 1.0);
+        let cut = chamfered.boundary().faces().last().unwrap();
+        assert!(cut.surface().on_surface(Point::from_f64(0.0, 0.75, 1.0)));
+        assert!(cut.surface().on_surface(on_cylinder));
+        assert!(!is_inside(&chamfered, Point::from_f64(0.05, 0.97, 1.0)));
+        assert!(is_inside(&chamfered, Point::from_f64(0.1, 0.8, 1.0)));
+
+        // The round has its center 1/4 away from the flat side and 3/4 away from the axis
+        let filleted = blend(&volume, &[side_edge(&volume)], Profile::Round(quarter)).unwrap();
+        assert_eq!(filleted.boundary().faces().len(), 5);
+        let round = filleted.boundary().faces().last().unwrap();
+        let center = Point::from_f64(0.25, 0.5f64.sqrt(), 1.0);
+        assert!(round
+            .surface()
+            .on_surface(center - Point::unit_x() * quarter));
+        let on_cylinder = Point::from_f64(1.0 / 3.0, 4.0 / 3.0 * 0.5f64.sqrt(), 1.0);
+        assert!(round.surface().on_surface(on_cylinder));
+        assert!(!is_inside(&filleted, Point::from_f64(0.03, 0.9, 1.0)));
+        assert!(is_inside(&filleted, Point::from_f64(0.2, 0.8, 1.0)));
+
+        // The radius of the cylinder bounds the blend
+        assert!(blend(
+            &volume,
+            &[side_edge(&volume)],
+            Profile::Chamfer(quarter, EFloat64::from(2.5))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_blend_unsupported_edges() {
+        let volume = half_cylinder();
+
+        // The round does not fit between the flat side and the cylinder
+        assert!(blend(
+            &volume,
+            &[side_edge(&volume)],
+            Profile::Round(EFloat64::from(1.5))
+        )
+        .is_err());
+
+        // The rim of the half cylinder is a circle that is not closed
+        let rim = volume
+            .all_faces()
+            .iter()
+            .flat_map(|face| face.all_edges())
+            .find(|edge| edge.get_midpoint() == Point::from_f64(1.0, 0.0, 2.0))
+            .unwrap();
+        assert!(blend(&volume, &[rim], Profile::Round(EFloat64::from(0.25))).is_err());
+
+        // The line edge across the bottom ends in the cylinder instead of a plane
+        let bottom = volume
+            .all_faces()
+            .iter()
+            .flat_map(|face| face.all_edges())
+            .find(|edge| edge.get_midpoint() == Point::zero())
+            .unwrap();
+        let quarter = EFloat64::from(0.25);
+        assert!(blend(&volume, &[bottom], Profile::Chamfer(quarter, quarter)).is_err());
+    }
+}
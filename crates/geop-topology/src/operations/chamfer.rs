@@ -0,0 +1,157 @@
+use geop_geometry::{color::Category10Color, efloat::EFloat64};
+
+use crate::{
+    topology::{edge::Edge, volume::Volume},
+    topology_error::{TopologyError, TopologyResult, WithContext},
+    topology_scene::TopologyScene,
+};
+
+use super::blend::{blend, Profile};
+
+// Cuts the edges of a volume by a ruled face.
+// The cut starts at distance1 from the edge on the face whose boundary runs along the edge in the same direction,
+// and at distance2 on the other face. Line edges between two planes, or between a plane and a cylinder along the edge,
+// are cut by a plane and have to end in planes perpendicular to the edge. On a cylinder, the distance is measured along
+// the chord. Closed circles between a plane and a coaxial cylinder are cut by a cone.
+pub fn chamfer(
+    volume: &Volume,
+    edges: &[Edge],
+    distance1: EFloat64,
+    distance2: EFloat64,
+) -> TopologyResult<Volume> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            format!(
+                "Chamfer {} edges with distances {} and {}",
+                edges.len(),
+                distance1,
+                distance2
+            ),
+            TopologyScene::with_edges(
+                edges
+                    .iter()
+                    .map(|edge| (edge.clone(), Category10Color::Red))
+                    .collect(),
+            ),
+        )
+    };
+
+    if distance1 <= 0.0 || distance2 <= 0.0 {
+        return Err(TopologyError::new("Distances must be positive".to_string()))
+            .with_context(&context);
+    }
+    blend(volume, edges, Profile::Chamfer(distance1, distance2)).with_context(&context)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::{
+        curves::curve::Curve,
+        point::Point,
+        surfaces::{plane::Plane, surface::Surface},
+    };
+
+    use crate::{
+        operations::extrude::extrude,
+        primitive_objects::edges::circle::primitive_circle,
+        test_fixtures::{cube, find_edge, is_inside},
+        topology::{contour::Contour, face::Face},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_chamfer_cube_edge() {
+        let volume = cube();
+        let edge = find_edge(&volume, Point::from_f64(1.0, 0.0, 1.0));
+        let chamfered =
+            chamfer(&volume, &[edge], EFloat64::from(0.5), EFloat64::from(0.5)).unwrap();
+        assert_eq!(chamfered.boundary().faces().len(), 7);
+        // The cut goes through (0.5, y, 1) and (1, y, 0.5)
+        assert!(is_inside(&chamfered, Point::from_f64(0.7, 0.0, 0.7)));
+        assert!(!is_inside(&chamfered, Point::from_f64(0.8, 0.0, 0.8)));
+        assert!(is_inside(&chamfered, Point::from_f64(0.9, 0.0, -0.9)));
+    }
+
+    #[test]
+    fn test_chamfer_asymmetric() {
+        let volume = cube();
+        let edge = find_edge(&volume, Point::from_f64(1.0, 0.0, 1.0));
+        let chamfered = chamfer(
+            &volume,
+            std::slice::from_ref(&edge),
+            EFloat64::from(0.2),
+            EFloat64::from(0.8),
+        )
+        .unwrap();
+        let flipped = chamfer(
+            &volume,
+            &[edge.flip()],
+            EFloat64::from(0.8),
+            EFloat64::from(0.2),
+        )
+        .unwrap();
+        // Both cut the same corner, no matter which direction the edge has
+        for volume in [chamfered, flipped] {
+            assert_eq!(volume.boundary().faces().len(), 7);
+            let a = Point::from_f64(0.7, 0.0, 0.95);
+            let b = Point::from_f64(0.95, 0.0, 0.7);
+            assert!(is_inside(&volume, a) != is_inside(&volume, b));
+        }
+    }
+
+    #[test]
+    fn test_chamfer_cylinder_rim() {
+        let disc = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::zero(),
+                -Point::unit_z(),
+                EFloat64::one(),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let volume = extrude(disc, Point::from_f64(0.0, 0.0, 2.0));
+        let edge = volume
+            .all_faces()
+            .iter()
+            .flat_map(|face| face.all_edges())
+            .find(|edge| match &edge.curve {
+                Curve::Circle(circle) => circle.basis == Point::from_f64(0.0, 0.0, 2.0),
+                _ => false,
+            })
+            .unwrap();
+        let chamfered =
+            chamfer(&volume, &[edge], EFloat64::from(0.25), EFloat64::from(0.25)).unwrap();
+        assert_eq!(chamfered.boundary().faces().len(), 4);
+        assert!(chamfered
+            .boundary()
+            .faces()
+            .iter()
+            .any(|f| matches!(**f.surface(), Surface::Cone(_))));
+        assert!(!is_inside(&chamfered, Point::from_f64(0.9, 0.0, 1.9)));
+        assert!(is_inside(&chamfered, Point::from_f64(0.8, 0.0, 1.85)));
+    }
+
+    #[test]
+    fn test_chamfer_errors() {
+        let volume = cube();
+        let edge1 = find_edge(&volume, Point::from_f64(1.0, 0.0, 1.0));
+        let edge2 = find_edge(&volume, Point::from_f64(1.0, 1.0, 0.0));
+        let d = EFloat64::from(0.5);
+        assert!(chamfer(
+            &volume,
+            std::slice::from_ref(&edge1),
+            EFloat64::from(2.5),
+            d
+        )
+        .is_err());
+        assert!(chamfer(&volume, std::slice::from_ref(&edge1), d, EFloat64::zero()).is_err());
+        assert!(chamfer(&volume, &[edge1, edge2], d, d).is_err());
+    }
+}
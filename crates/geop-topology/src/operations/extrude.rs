@@ -1,17 +1,71 @@
 use std::rc::Rc;
 
 use geop_geometry::{
-    curves::{curve::Curve, CurveLike},
+    curves::{curve::Curve, nurb::NurbsCurve, CurveLike},
     point::Point,
-    surfaces::{cylinder::Cylinder, plane::Plane, surface::Surface},
+    surfaces::{
+        cylinder::Cylinder, nurbs::NurbsSurface, plane::Plane, surface::Surface, SurfaceLike,
+    },
     transforms::Transform,
 };
 
 use crate::{
     primitive_objects::edges::line::primitive_line,
-    topology::{contour::Contour, face::Face, shell::Shell, volume::Volume},
+    topology::{contour::Contour, edge::Edge, face::Face, shell::Shell, volume::Volume},
 };
 
+// The edges of the side face of an edge of the start face: the edge itself, its translation on the end face and,
+// if the edge has end points, the two lines between them.
+fn side_edges(
+    edge: &Edge,
+    end_edges: &[Edge],
+    direction: Point,
+) -> (Edge, Edge, Option<Edge>, Option<Edge>) {
+    let top = edge.flip();
+    let bottom = end_edges
+        .iter()
+        .find(|e| {
+            **e == edge
+                .transform(Transform::from_translation(direction))
+                .flip()
+        })
+        .unwrap()
+        .flip();
+
+    let right = match (bottom.end, top.start) {
+        (Some(start), Some(end)) => Some(primitive_line(start, end).unwrap()),
+        _ => None,
+    };
+    let left = match (top.end, bottom.start) {
+        (Some(start), Some(end)) => Some(primitive_line(start, end).unwrap()),
+        _ => None,
+    };
+    (top, bottom, right, left)
+}
+
+// The side face of an edge on a curve without a closed form is the ruled surface between the curve and its translation,
+// which contains the lines between the end points of the edge and their translations.
+fn ruled_face(edge: &Edge, curve: NurbsCurve, end_edges: &[Edge], direction: Point) -> Face {
+    let (top, bottom, right, left) = side_edges(edge, end_edges, direction);
+    let translated = curve.transform(Transform::from_translation(direction));
+    let surface = NurbsSurface::try_new_skin(&[curve, translated], 1)
+        .expect("A curve and its translation are compatible sections");
+
+    // The outwards normal is the direction of the extrusion crossed with the direction of the edge
+    let midpoint = edge.get_midpoint();
+    let outwards = direction.cross(edge.tangent(midpoint));
+    let surface = match surface.normal(midpoint).dot(outwards) < 0.0 {
+        true => surface.neg(),
+        false => surface,
+    };
+
+    let boundaries = match (right, left) {
+        (Some(right), Some(left)) => vec![Contour::new(vec![right, top, left, bottom])],
+        _ => vec![Contour::new(vec![top]), Contour::new(vec![bottom])],
+    };
+    Face::new(boundaries, Rc::new(Surface::Nurbs(surface)))
+}
+
 pub fn extrude(start_face: Face, direction: Point) -> Volume {
     let end_face = start_face
         .transform(Transform::from_translation(direction))
@@ -24,25 +78,7 @@ pub fn extrude(start_face: Face, direction: Point) -> Volume {
     for i in 0..n {
         match &all_edges[i].curve {
             Curve::Line(line) => {
-                let top = all_edges[i].flip();
-                let bottom = end_edges
-                    .iter()
-                    .find(|e| {
-                        **e == all_edges[i]
-                            .transform(Transform::from_translation(direction))
-                            .flip()
-                    })
-                    .unwrap()
-                    .flip();
-
-                let right = match (bottom.end, top.start) {
-                    (Some(start), Some(end)) => Some(primitive_line(start, end).unwrap()),
-                    _ => None,
-                };
-                let left = match (top.end, bottom.start) {
-                    (Some(start), Some(end)) => Some(primitive_line(start, end).unwrap()),
-                    _ => None,
-                };
+                let (top, bottom, right, left) = side_edges(&all_edges[i], end_edges, direction);
 
                 let plane = Surface::Plane(Plane::new(line.basis, direction, line.direction));
                 let contour = Contour::new(
@@ -56,25 +92,7 @@ pub fn extrude(start_face: Face, direction: Point) -> Volume {
                 faces.push(face);
             }
             Curve::Circle(circle) => {
-                let top = all_edges[i].flip();
-                let bottom = end_edges
-                    .iter()
-                    .find(|e| {
-                        **e == all_edges[i]
-                            .transform(Transform::from_translation(direction))
-                            .flip()
-                    })
-                    .unwrap()
-                    .flip();
-
-                let right = match (bottom.end, top.start) {
-                    (Some(start), Some(end)) => Some(primitive_line(start, end).unwrap()),
-                    _ => None,
-                };
-                let left = match (top.end, bottom.start) {
-                    (Some(start), Some(end)) => Some(primitive_line(start, end).unwrap()),
-                    _ => None,
-                };
+                let (top, bottom, right, left) = side_edges(&all_edges[i], end_edges, direction);
 
                 let midpoint = circle.get_midpoint(top.start, top.end).unwrap();
                 let inwards_direction = direction.cross(circle.tangent(midpoint).unwrap());
@@ -106,6 +124,22 @@ pub fn extrude(start_face: Face, direction: Point) -> Volume {
             }
             Curve::Ellipse(_) => todo!("Implement this"),
             Curve::Helix(_) => panic!("Cannot extrude helix"),
+            Curve::SampledCurve(sampled) => {
+                // The polygon through the samples is a polyline, which contains the sampled curve
+                let mut points = sampled.points.clone();
+                points.push(points[0]);
+                let polyline = NurbsCurve::try_new_polyline(points)
+                    .expect("Samples of a sampled curve are distinct");
+                faces.push(ruled_face(&all_edges[i], polyline, end_edges, direction));
+            }
+            Curve::Nurbs(nurbs) => {
+                faces.push(ruled_face(
+                    &all_edges[i],
+                    nurbs.clone(),
+                    end_edges,
+                    direction,
+                ));
+            }
         }
     }
     faces.push(start_face);
@@ -113,3 +147,112 @@ pub fn extrude(start_face: Face, direction: Point) -> Volume {
 
     Volume::new(Shell::new(faces), vec![])
 }
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::{
+        curves::{circle::Circle, sampled_curve::SampledCurve},
+        efloat::EFloat64,
+    };
+
+    use crate::{
+        contains::volume_point::{volume_point_contains, VolumePointContains},
+        test_fixtures::is_inside,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_extrude_sampled_curve() {
+        // A polygon inscribed into the unit circle, running clockwise such that it bounds the plane with normal -z
+        let n = 32;
+        let points = (0..n)
+            .map(|i| {
+                let angle = -2.0 * std::f64::consts::PI * i as f64 / n as f64;
+                Point::from_f64(angle.cos(), angle.sin(), 0.0)
+            })
+            .collect::<Vec<Point>>();
+        let curve = SampledCurve::try_new(points, 1e-9).unwrap();
+        let face = Face::new(
+            vec![Contour::new(vec![Edge::new(
+                None,
+                None,
+                Curve::SampledCurve(curve),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let volume = extrude(face, Point::unit_z() * EFloat64::two());
+        assert_eq!(volume.all_faces().len(), 3);
+
+        let side = &volume.all_faces()[0];
+        let p = Point::from_f64(1.0, 0.0, 1.0);
+        assert!(side.surface().on_surface(p));
+        assert!(side.surface().normal(p).dot(Point::unit_x()) > 0.9);
+
+        assert!(matches!(
+            volume_point_contains(&volume, Point::from_f64(0.5, 0.0, 1.0)).unwrap(),
+            VolumePointContains::Inside
+        ));
+        assert!(matches!(
+            volume_point_contains(&volume, Point::from_f64(2.0, 0.0, 1.0)).unwrap(),
+            VolumePointContains::Outside
+        ));
+    }
+
+    #[test]
+    fn test_extrude_nurbs_curve() {
+        // Circles with normal -z run clockwise, such that the faces bound the plane with normal -z
+        let plane = Rc::new(Surface::Plane(Plane::new(
+            Point::zero(),
+            Point::unit_y(),
+            Point::unit_x(),
+        )));
+        let circle = Circle::try_new(Point::zero(), -Point::unit_z(), EFloat64::one()).unwrap();
+
+        // A full circle as a closed rational curve
+        let nurbs = NurbsCurve::try_new_arc(&circle, None, None).unwrap();
+        let face = Face::new(
+            vec![Contour::new(vec![Edge::new(
+                None,
+                None,
+                Curve::Nurbs(nurbs),
+            )])],
+            plane.clone(),
+        );
+        let volume = extrude(face, Point::unit_z() * EFloat64::two());
+        assert_eq!(volume.all_faces().len(), 3);
+        let side = &volume.all_faces()[0];
+        let p = Point::from_f64(0.0, 1.0, 1.0);
+        assert!(side.surface().on_surface(p));
+        assert!(side.surface().normal(p).dot(Point::unit_y()) > 0.9);
+        assert!(is_inside(&volume, Point::from_f64(0.5, 0.0, 1.0)));
+        assert!(!is_inside(&volume, Point::from_f64(2.0, 0.0, 1.0)));
+
+        // A half disc, where the arc has end points and the side face is bounded by lines
+        let (top, bottom) = (Point::unit_y(), -Point::unit_y());
+        let arc = NurbsCurve::try_new_arc(&circle, Some(top), Some(bottom)).unwrap();
+        let face = Face::new(
+            vec![Contour::new(vec![
+                Edge::new(Some(top), Some(bottom), Curve::Nurbs(arc)),
+                primitive_line(bottom, top).unwrap(),
+            ])],
+            plane,
+        );
+        let volume = extrude(face, Point::unit_z() * EFloat64::two());
+        assert_eq!(volume.all_faces().len(), 4);
+        let side = volume
+            .all_faces()
+            .into_iter()
+            .find(|face| matches!(face.surface().as_ref(), Surface::Nurbs(_)))
+            .unwrap();
+        let p = Point::from_f64(1.0, 0.0, 1.0);
+        assert!(side.surface().on_surface(p));
+        assert!(side.surface().normal(p).dot(Point::unit_x()) > 0.9);
+        assert!(is_inside(&volume, Point::from_f64(0.5, 0.0, 1.0)));
+        assert!(!is_inside(&volume, Point::from_f64(-0.5, 0.0, 1.0)));
+    }
+}
@@ -0,0 +1,175 @@
+use geop_geometry::{color::Category10Color, efloat::EFloat64};
+
+use crate::{
+    topology::{edge::Edge, volume::Volume},
+    topology_error::{TopologyError, TopologyResult, WithContext},
+    topology_scene::TopologyScene,
+};
+
+use super::blend::{blend, Profile};
+
+// Rounds the edges of a volume with a constant radius.
+// Line edges between two planes, or between a plane and a cylinder along the edge, are blended by a cylinder. They have
+// to end in planes perpendicular to the edge, which get an arc instead of the corner. Closed circles between a plane and
+// a coaxial cylinder are blended by a torus.
+// Convex edges lose material and concave edges gain material.
+pub fn fillet(volume: &Volume, edges: &[Edge], radius: EFloat64) -> TopologyResult<Volume> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            format!("Fillet {} edges with radius {}", edges.len(), radius),
+            TopologyScene::with_edges(
+                edges
+                    .iter()
+                    .map(|edge| (edge.clone(), Category10Color::Red))
+                    .collect(),
+            ),
+        )
+    };
+
+    if radius <= 0.0 {
+        return Err(TopologyError::new("Radius must be positive".to_string()))
+            .with_context(&context);
+    }
+    blend(volume, edges, Profile::Round(radius)).with_context(&context)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::{
+        curves::curve::Curve,
+        point::Point,
+        surfaces::{plane::Plane, surface::Surface},
+    };
+
+    use crate::{
+        operations::extrude::extrude,
+        primitive_objects::edges::{circle::primitive_circle, line::primitive_line},
+        test_fixtures::{cube, find_edge, is_inside},
+        topology::{contour::Contour, face::Face},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_fillet_cube_edge() {
+        let volume = cube();
+        let edge = find_edge(&volume, Point::from_f64(1.0, 0.0, 1.0));
+        let filleted = fillet(&volume, &[edge], EFloat64::from(0.5)).unwrap();
+        assert_eq!(filleted.boundary().faces().len(), 7);
+        assert_eq!(
+            filleted
+                .boundary()
+                .faces()
+                .iter()
+                .filter(|f| matches!(**f.surface(), Surface::Cylinder(_)))
+                .count(),
+            1
+        );
+        // The arc is centered at (0.5, y, 0.5)
+        assert!(is_inside(&filleted, Point::from_f64(0.85, 0.0, 0.85)));
+        assert!(!is_inside(&filleted, Point::from_f64(0.9, 0.0, 0.9)));
+        assert!(is_inside(&filleted, Point::from_f64(0.9, 0.0, -0.9)));
+    }
+
+    #[test]
+    fn test_fillet_cube_edges() {
+        let volume = cube();
+        let edges = [(1.0, 1.0), (1.0, -1.0), (-1.0, 1.0), (-1.0, -1.0)]
+            .map(|(x, y)| find_edge(&volume, Point::from_f64(x, y, 0.0)));
+        let filleted = fillet(&volume, &edges, EFloat64::from(0.3)).unwrap();
+        assert_eq!(filleted.boundary().faces().len(), 10);
+        assert!(!is_inside(&filleted, Point::from_f64(0.95, 0.95, 0.0)));
+        assert!(is_inside(&filleted, Point::from_f64(0.75, 0.75, 0.0)));
+    }
+
+    #[test]
+    fn test_fillet_concave_edge() {
+        // An L shaped prism, the edge at (1, 1) is concave
+        let p = [
+            (0.0, 0.0),
+            (0.0, 2.0),
+            (1.0, 2.0),
+            (1.0, 1.0),
+            (2.0, 1.0),
+            (2.0, 0.0),
+        ]
+        .map(|(x, y)| Point::from_f64(x, y, 0.0));
+        let face = Face::new(
+            vec![Contour::new(
+                (0..6)
+                    .map(|i| primitive_line(p[i], p[(i + 1) % 6]).unwrap())
+                    .collect(),
+            )],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_x(),
+                -Point::unit_y(),
+            ))),
+        );
+        let volume = extrude(face, Point::from_f64(0.0, 0.0, 1.0));
+        let edge = find_edge(&volume, Point::from_f64(1.0, 1.0, 0.5));
+        let filleted = fillet(&volume, &[edge], EFloat64::from(0.3)).unwrap();
+        assert_eq!(filleted.boundary().faces().len(), 9);
+        // The arc is centered at (1.3, 1.3, z) and fills the corner
+        assert!(is_inside(&filleted, Point::from_f64(1.05, 1.05, 0.5)));
+        assert!(!is_inside(&filleted, Point::from_f64(1.2, 1.2, 0.5)));
+    }
+
+    #[test]
+    fn test_fillet_cylinder_rim() {
+        let disc = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::zero(),
+                -Point::unit_z(),
+                EFloat64::one(),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let volume = extrude(disc, Point::from_f64(0.0, 0.0, 2.0));
+        let edge = volume
+            .all_faces()
+            .iter()
+            .flat_map(|face| face.all_edges())
+            .find(|edge| match &edge.curve {
+                Curve::Circle(circle) => circle.basis == Point::from_f64(0.0, 0.0, 2.0),
+                _ => false,
+            })
+            .unwrap();
+        let filleted = fillet(&volume, &[edge], EFloat64::from(0.25)).unwrap();
+        assert_eq!(filleted.boundary().faces().len(), 4);
+        assert!(filleted
+            .boundary()
+            .faces()
+            .iter()
+            .any(|f| matches!(**f.surface(), Surface::Torus(_))));
+        assert!(!is_inside(&filleted, Point::from_f64(0.95, 0.0, 1.95)));
+        assert!(is_inside(&filleted, Point::from_f64(0.8, 0.0, 1.8)));
+
+        let edge = filleted
+            .all_faces()
+            .iter()
+            .flat_map(|face| face.all_edges())
+            .find(|edge| match &edge.curve {
+                Curve::Circle(circle) => circle.basis == Point::zero(),
+                _ => false,
+            })
+            .unwrap();
+        assert!(fillet(&filleted, &[edge], EFloat64::from(2.5)).is_err());
+    }
+
+    #[test]
+    fn test_fillet_errors() {
+        let volume = cube();
+        let edge1 = find_edge(&volume, Point::from_f64(1.0, 0.0, 1.0));
+        let edge2 = find_edge(&volume, Point::from_f64(1.0, 1.0, 0.0));
+        assert!(fillet(&volume, std::slice::from_ref(&edge1), EFloat64::from(2.5)).is_err());
+        assert!(fillet(&volume, std::slice::from_ref(&edge1), EFloat64::zero()).is_err());
+        assert!(fillet(&volume, &[edge1, edge2], EFloat64::from(0.5)).is_err());
+    }
+}
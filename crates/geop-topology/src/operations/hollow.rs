@@ -0,0 +1,386 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    color::Category10Color,
+    curve_surface_intersection::curve_surface::{
+        curve_surface_intersection, CurveSurfaceIntersection,
+    },
+    curves::{curve::Curve, CurveLike},
+    efloat::EFloat64,
+    point::Point,
+    surface_surface_intersection::surface_surface::{
+        surface_surface_intersection, FaceSurfaceIntersection,
+    },
+    surfaces::{surface::Surface, SurfaceLike},
+};
+
+use crate::{
+    contains::face_point::{face_point_contains, FacePointContains},
+    topology::{contour::Contour, edge::Edge, face::Face, shell::Shell, volume::Volume},
+    topology_error::{ElevateToTopology, TopologyError, TopologyResult, WithContext},
+    topology_scene::TopologyScene,
+};
+
+// Turns a volume into a thin walled volume by offsetting every face inwards by thickness.
+// Without open faces, the offset faces form a cavity. Open faces are removed instead of offset,
+// they keep a rim between the outer and the inner wall and the result is a single open shell.
+// Only planes, spheres and cylinders can be offset. Open faces must not be adjacent to each other.
+pub fn hollow(volume: &Volume, thickness: EFloat64, open_faces: &[Face]) -> TopologyResult<Volume> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            format!(
+                "Hollow volume with thickness {} and {} open faces",
+                thickness,
+                open_faces.len()
+            ),
+            TopologyScene::with_faces(
+                open_faces
+                    .iter()
+                    .map(|face| (face.clone(), Category10Color::Red))
+                    .collect(),
+            ),
+        )
+    };
+
+    if thickness <= 0.0 {
+        return Err(TopologyError::new("Thickness must be positive".to_string()))
+            .with_context(&context);
+    }
+    if !volume.cavities().is_empty() {
+        return Err(TopologyError::new(
+            "Volumes with cavities cannot be hollowed".to_string(),
+        ))
+        .with_context(&context);
+    }
+
+    let faces = &volume.boundary().faces();
+    let open = faces
+        .iter()
+        .map(|face| open_faces.iter().any(|other| same_face(face, other)))
+        .collect::<Vec<bool>>();
+    if open.iter().filter(|open| **open).count() != open_faces.len() {
+        return Err(TopologyError::new(
+            "Open faces have to be faces of the volume".to_string(),
+        ))
+        .with_context(&context);
+    }
+
+    // Open faces are not offset, so the inner wall ends on them
+    let targets = faces
+        .iter()
+        .zip(open.iter())
+        .map(|(face, open)| match open {
+            true => Ok(face.surface().as_ref().clone()),
+            false => offset_inwards(face.surface(), thickness),
+        })
+        .collect::<TopologyResult<Vec<Surface>>>()
+        .with_context(&context)?;
+    let offset_contour = |i: usize, contour: &Contour| {
+        contour
+            .edges
+            .iter()
+            .map(|edge| offset_edge(faces, &open, &targets, i, edge))
+            .collect::<TopologyResult<Vec<Edge>>>()
+    };
+
+    let mut outer = Vec::<Face>::new();
+    let mut inner = Vec::<Face>::new();
+    for (i, face) in faces.iter().enumerate() {
+        if !open[i] {
+            let boundaries = face
+                .boundaries()
+                .iter()
+                .map(|contour| Ok(Contour::new(offset_contour(i, contour)?)))
+                .collect::<TopologyResult<Vec<Contour>>>()
+                .with_context(&context)?;
+            // The inner wall faces away from the material, which is towards the cavity
+            inner.push(Face::new(boundaries, Rc::new(targets[i].clone())).flip());
+            outer.push(face.clone());
+            continue;
+        }
+
+        // The open face keeps its boundary and gets a hole where the inner wall ends
+        let mut boundaries = face.boundaries().to_vec();
+        for contour in face.boundaries().iter() {
+            let edges = offset_contour(i, contour).with_context(&context)?;
+            for edge in edges.iter() {
+                let points = edge.start.into_iter().chain([edge.get_midpoint()]);
+                for p in points {
+                    if face_point_contains(face, p) != FacePointContains::Inside {
+                        return Err(TopologyError::new(
+                            "Thickness is too large for the open face".to_string(),
+                        ))
+                        .with_context(&context);
+                    }
+                }
+            }
+            boundaries.push(Contour::new(
+                edges.iter().rev().map(|edge| edge.flip()).collect(),
+            ));
+        }
+        outer.push(Face::new(boundaries, face.surface().clone()));
+    }
+
+    match open.iter().any(|open| *open) {
+        true => {
+            outer.extend(inner);
+            Ok(Volume::new(Shell::new(outer), vec![]))
+        }
+        false => Ok(Volume::new(Shell::new(outer), vec![Shell::new(inner)])),
+    }
+}
+
+fn same_face(face: &Face, other: &Face) -> bool {
+    let edges = face.all_edges();
+    let other_edges = other.all_edges();
+    face.surface() == other.surface()
+        && edges.len() == other_edges.len()
+        && edges.iter().all(|edge| other_edges.contains(edge))
+}
+
+// Moves the surface by distance against its normal. The inner wall is found by intersecting
+// the offset surfaces, which is only available for planes, spheres and cylinders.
+fn offset_inwards(surface: &Surface, distance: EFloat64) -> TopologyResult<Surface> {
+    let context = |err: TopologyError| err.with_context(format!("Offset surface by {}", distance));
+    match surface {
+        Surface::Plane(_) | Surface::Sphere(_) | Surface::Cylinder(_) => {
+            surface.offset(-distance).elevate(&context)
+        }
+        _ => Err(TopologyError::new(
+            "Only planes, spheres and cylinders can be offset".to_string(),
+        ))
+        .with_context(&context),
+    }
+}
+
+// The other face of the volume that contains the edge of face i.
+fn adjacent(faces: &[Face], i: usize, edge: &Edge) -> TopologyResult<usize> {
+    let adjacent = (0..faces.len())
+        .filter(|j| *j != i && faces[*j].all_edges().contains(edge))
+        .collect::<Vec<usize>>();
+    match adjacent.len() {
+        1 => Ok(adjacent[0]),
+        _ => Err(TopologyError::new(
+            "Edge has to be shared by exactly two faces".to_string(),
+        ))
+        .with_context(&|err: TopologyError| {
+            err.with_context_scene(
+                "Find faces next to edge".to_string(),
+                TopologyScene::with_edges(vec![(edge.clone(), Category10Color::Red)]),
+            )
+        }),
+    }
+}
+
+// The intersection of the offset surfaces of the faces i and j that is closest to their common edge.
+// It runs in the same direction as the edge.
+fn offset_curve(targets: &[Surface], i: usize, j: usize, edge: &Edge) -> TopologyResult<Curve> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            "Intersect offset surfaces next to edge".to_string(),
+            TopologyScene::with_edges(vec![(edge.clone(), Category10Color::Red)]),
+        )
+    };
+    let curves = match surface_surface_intersection(&targets[i], &targets[j]).elevate(&context)? {
+        FaceSurfaceIntersection::CurvesAndPoints(curves, _) => curves,
+        _ => vec![],
+    };
+    let m = edge.get_midpoint();
+    let Some(curve) = curves.into_iter().min_by(|a, b| {
+        let distance_a = (a.project(m) - m).norm().to_f64();
+        let distance_b = (b.project(m) - m).norm().to_f64();
+        distance_a.total_cmp(&distance_b)
+    }) else {
+        return Err(TopologyError::new(
+            "Offset surfaces do not intersect".to_string(),
+        ))
+        .with_context(&context);
+    };
+    let tangent = curve.tangent(curve.project(m)).elevate(&context)?;
+    match tangent.dot(edge.tangent(m)) > 0.0 {
+        true => Ok(curve),
+        false => Ok(curve.neg()),
+    }
+}
+
+// The vertex where the offset surfaces of all faces around v meet.
+fn offset_vertex(faces: &[Face], targets: &[Surface], v: Point) -> TopologyResult<Point> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            format!("Offset vertex {}", v),
+            TopologyScene::with_points(vec![(v, Category10Color::Red)]),
+        )
+    };
+    let around = (0..faces.len())
+        .filter(|i| faces[*i].all_points().contains(&v))
+        .collect::<Vec<usize>>();
+    let i = around[0];
+    let edge = faces[i]
+        .all_edges()
+        .into_iter()
+        .find(|edge| edge.start == Some(v) || edge.end == Some(v))
+        .expect("Vertex is part of the face");
+    let j = adjacent(faces, i, &edge).with_context(&context)?;
+    let curve = offset_curve(targets, i, j, &edge).with_context(&context)?;
+    for k in around {
+        if targets[k] == targets[i] || targets[k] == targets[j] {
+            continue;
+        }
+        if let CurveSurfaceIntersection::Points(points) =
+            curve_surface_intersection(&curve, &targets[k]).elevate(&context)?
+        {
+            if let Some(p) = points.into_iter().min_by(|a, b| {
+                let distance_a = (*a - v).norm().to_f64();
+                let distance_b = (*b - v).norm().to_f64();
+                distance_a.total_cmp(&distance_b)
+            }) {
+                return Ok(p);
+            }
+        }
+    }
+    Err(TopologyError::new(
+        "Offset surfaces around the vertex do not meet in a point".to_string(),
+    ))
+    .with_context(&context)
+}
+
+// The edge of the inner wall that belongs to the edge of face i.
+fn offset_edge(
+    faces: &[Face],
+    open: &[bool],
+    targets: &[Surface],
+    i: usize,
+    edge: &Edge,
+) -> TopologyResult<Edge> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            "Offset edge".to_string(),
+            TopologyScene::with_edges(vec![(edge.clone(), Category10Color::Red)]),
+        )
+    };
+    let j = adjacent(faces, i, edge).with_context(&context)?;
+    if open[i] && open[j] {
+        return Err(TopologyError::new(
+            "Open faces must not be adjacent".to_string(),
+        ))
+        .with_context(&context);
+    }
+    let curve = offset_curve(targets, i, j, edge).with_context(&context)?;
+    let start = match edge.start {
+        Some(start) => Some(offset_vertex(faces, targets, start).with_context(&context)?),
+        None => None,
+    };
+    let end = match edge.end {
+        Some(end) => Some(offset_vertex(faces, targets, end).with_context(&context)?),
+        None => None,
+    };
+    // If the walls are too thick, the edges of the inner wall turn around
+    if let (Curve::Line(_), Some(start), Some(end)) = (&curve, start, end) {
+        let forward = (end - start).dot(edge.tangent(edge.get_midpoint())) > 0.0;
+        if !forward {
+            return Err(TopologyError::new(
+                "Thickness is too large for the edges of the face".to_string(),
+            ))
+            .with_context(&context);
+        }
+    }
+    Ok(Edge::new(start, end, curve))
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::surfaces::plane::Plane;
+
+    use crate::{
+        operations::extrude::extrude,
+        primitive_objects::{edges::circle::primitive_circle, faces::sphere::primitive_sphere},
+        test_fixtures::{cube, is_inside},
+    };
+
+    use super::*;
+
+    fn find_face(volume: &Volume, normal: Point) -> Face {
+        volume
+            .boundary()
+            .faces()
+            .iter()
+            .find(|face| match &**face.surface() {
+                Surface::Plane(plane) => {
+                    plane.u_slope.cross(plane.v_slope).normalize().unwrap() == normal
+                }
+                _ => false,
+            })
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn test_hollow_cube() {
+        let hollowed = hollow(&cube(), EFloat64::from(0.2), &[]).unwrap();
+        assert_eq!(hollowed.boundary().faces().len(), 6);
+        assert_eq!(hollowed.cavities().len(), 1);
+        assert_eq!(hollowed.cavities()[0].faces().len(), 6);
+        assert!(!is_inside(&hollowed, Point::zero()));
+        assert!(!is_inside(&hollowed, Point::from_f64(0.7, 0.3, -0.7)));
+        assert!(is_inside(&hollowed, Point::from_f64(0.9, 0.3, 0.0)));
+        assert!(is_inside(&hollowed, Point::from_f64(0.0, 0.3, -0.9)));
+    }
+
+    #[test]
+    fn test_hollow_open_cube() {
+        let volume = cube();
+        let top = find_face(&volume, Point::unit_z());
+        let hollowed = hollow(&volume, EFloat64::from(0.2), &[top]).unwrap();
+        assert_eq!(hollowed.boundary().faces().len(), 11);
+        assert!(hollowed.cavities().is_empty());
+        assert!(!is_inside(&hollowed, Point::zero()));
+        assert!(!is_inside(&hollowed, Point::from_f64(0.3, 0.2, 1.5)));
+        assert!(is_inside(&hollowed, Point::from_f64(0.9, 0.3, 0.5)));
+        assert!(is_inside(&hollowed, Point::from_f64(0.3, 0.2, -0.9)));
+    }
+
+    #[test]
+    fn test_hollow_open_cylinder() {
+        let disc = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::zero(),
+                -Point::unit_z(),
+                EFloat64::one(),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let volume = extrude(disc, Point::from_f64(0.0, 0.0, 2.0));
+        let top = find_face(&volume, Point::unit_z());
+        let hollowed = hollow(&volume, EFloat64::from(0.2), &[top]).unwrap();
+        assert_eq!(hollowed.boundary().faces().len(), 5);
+        assert!(!is_inside(&hollowed, Point::from_f64(0.1, 0.2, 1.0)));
+        assert!(is_inside(&hollowed, Point::from_f64(0.9, 0.0, 1.0)));
+        assert!(is_inside(&hollowed, Point::from_f64(0.1, 0.2, 0.1)));
+    }
+
+    #[test]
+    fn test_hollow_sphere() {
+        let volume = Volume::new(
+            Shell::new(vec![primitive_sphere(Point::zero(), EFloat64::one())]),
+            vec![],
+        );
+        let hollowed = hollow(&volume, EFloat64::from(0.25), &[]).unwrap();
+        assert_eq!(hollowed.cavities().len(), 1);
+        assert!(!is_inside(&hollowed, Point::from_f64(0.1, 0.2, 0.3)));
+        assert!(is_inside(&hollowed, Point::from_f64(0.0, 0.9, 0.0)));
+    }
+
+    #[test]
+    fn test_hollow_errors() {
+        let volume = cube();
+        let top = find_face(&volume, Point::unit_z());
+        let side = find_face(&volume, Point::unit_x());
+        assert!(hollow(&volume, EFloat64::zero(), &[]).is_err());
+        assert!(hollow(&volume, EFloat64::from(1.5), &[]).is_err());
+        assert!(hollow(&volume, EFloat64::from(0.2), &[top, side]).is_err());
+    }
+}
@@ -0,0 +1,412 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    color::Category10Color,
+    curves::{curve::Curve, nurb::NurbsCurve, CurveLike},
+    efloat::EFloat64,
+    geometry_error::{GeometryError, GeometryResult},
+    point::Point,
+    surfaces::{nurbs::NurbsSurface, plane::Plane, surface::Surface, SurfaceLike},
+};
+
+use crate::{
+    topology::{contour::Contour, edge::Edge, face::Face, shell::Shell, volume::Volume},
+    topology_error::{ElevateToTopology, TopologyError, TopologyResult, WithContext},
+    topology_scene::TopologyScene,
+};
+
+// Number of points per edge used to find the plane and the orientation of a section.
+const SAMPLES_PER_EDGE: usize = 16;
+
+// Lofts a smooth volume through the planar sections, where the side faces are b-spline surfaces of up to cubic degree
+// between the sections. The sections must have the same number of edges, except for sections that are a single circle,
+// which are split to match the other sections. The first and the last section are closed with planar caps.
+pub fn loft(sections: &[Contour]) -> TopologyResult<Volume> {
+    loft_with_degree(sections, 3)
+}
+
+// Same as loft, but the side faces are ruled surfaces, i.e. straight between neighbouring sections.
+pub fn ruled_loft(sections: &[Contour]) -> TopologyResult<Volume> {
+    loft_with_degree(sections, 1)
+}
+
+// Centroid and area vector of a section, which is perpendicular to its plane.
+fn section_plane(section: &Contour) -> TopologyResult<(Point, Point)> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            "Find plane of section".to_string(),
+            TopologyScene::with_points(
+                section
+                    .all_points()
+                    .into_iter()
+                    .map(|p| (p, Category10Color::Orange))
+                    .collect(),
+            ),
+        )
+    };
+    let samples = section
+        .edges
+        .iter()
+        .flat_map(|edge| {
+            (0..SAMPLES_PER_EDGE).map(|i| edge.interpolate(i as f64 / SAMPLES_PER_EDGE as f64))
+        })
+        .collect::<Vec<Point>>();
+    let centroid = (samples.iter().fold(Point::zero(), |acc, p| acc + *p)
+        / EFloat64::from(samples.len() as f64))
+    .elevate(&context)?;
+    let mut area = Point::zero();
+    for i in 0..samples.len() {
+        let next = samples[(i + 1) % samples.len()];
+        area = area + (samples[i] - centroid).cross(next - centroid);
+    }
+    let normal = area.normalize().elevate(&context)?;
+    if samples
+        .iter()
+        .any(|p| (*p - centroid).dot(normal).abs() > 1e-6)
+    {
+        return Err(TopologyError::new("Sections must be planar".to_string()))
+            .with_context(&context);
+    }
+    Ok((centroid, area))
+}
+
+fn loft_with_degree(sections: &[Contour], degree: usize) -> TopologyResult<Volume> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            format!("Loft through {} sections", sections.len()),
+            TopologyScene::with_points(
+                sections
+                    .iter()
+                    .flat_map(|section| section.all_points())
+                    .map(|p| (p, Category10Color::Orange))
+                    .collect(),
+            ),
+        )
+    };
+    if sections.len() < 2 {
+        return Err(TopologyError::new(
+            "Loft needs at least two sections".to_string(),
+        ))
+        .with_context(&context);
+    }
+    let planes = sections
+        .iter()
+        .map(section_plane)
+        .collect::<TopologyResult<Vec<(Point, Point)>>>()
+        .with_context(&context)?;
+    let direction = planes[planes.len() - 1].0 - planes[0].0;
+    if direction.norm() == 0.0 {
+        return Err(TopologyError::new(
+            "First and last section must not coincide".to_string(),
+        ))
+        .with_context(&context);
+    }
+
+    // All sections run counter-clockwise when looked at against the direction of the loft
+    let mut sections = sections
+        .iter()
+        .zip(planes.iter())
+        .map(|(section, (_, area))| match area.dot(direction) > 0.0 {
+            true => section.flip(),
+            false => section.clone(),
+        })
+        .collect::<Vec<Contour>>();
+
+    // Full circles are split at the corners of a reference section with corners
+    if let Some(reference) = sections
+        .iter()
+        .position(|section| section.edges.iter().all(|edge| edge.start.is_some()))
+    {
+        let corners = sections[reference].all_points();
+        for (k, section) in sections.iter_mut().enumerate() {
+            if let [Edge {
+                start: None,
+                curve: Curve::Circle(circle),
+                ..
+            }] = section.edges.clone().as_slice()
+            {
+                let points = corners
+                    .iter()
+                    .map(|p| circle.project(*p - planes[reference].0 + planes[k].0))
+                    .collect::<Vec<Point>>();
+                let curve = Curve::Circle(circle.clone());
+                *section = Contour::new(
+                    (0..points.len())
+                        .map(|i| {
+                            Edge::new(
+                                Some(points[i]),
+                                Some(points[(i + 1) % points.len()]),
+                                curve.clone(),
+                            )
+                        })
+                        .collect(),
+                );
+            }
+        }
+    }
+    let n = sections[0].edges.len();
+    if sections.iter().any(|section| section.edges.len() != n) {
+        return Err(TopologyError::new(
+            "All sections must have the same number of edges".to_string(),
+        ))
+        .with_context(&context);
+    }
+
+    // Rotate the edges of each section such that corresponding corners are close to each other
+    for k in 1..sections.len() {
+        let corners = |section: &Contour, centroid: Point| {
+            section
+                .edges
+                .iter()
+                .map(|edge| edge.start.map(|p| p - centroid))
+                .collect::<Vec<Option<Point>>>()
+        };
+        let previous = corners(&sections[k - 1], planes[k - 1].0);
+        let current = corners(&sections[k], planes[k].0);
+        let shift = (0..n)
+            .min_by(|a, b| {
+                let cost = |shift: usize| {
+                    (0..n)
+                        .map(|i| match (previous[i], current[(i + shift) % n]) {
+                            (Some(p), Some(q)) => (p - q).norm().to_f64(),
+                            _ => 0.0,
+                        })
+                        .sum::<f64>()
+                };
+                cost(*a).total_cmp(&cost(*b))
+            })
+            .expect("Sections are not empty");
+        sections[k].edges.rotate_left(shift);
+    }
+
+    let mut faces = Vec::<Face>::new();
+    for i in 0..n {
+        let edges = sections
+            .iter()
+            .map(|section| section.edges[i].clone())
+            .collect::<Vec<Edge>>();
+        faces.push(loft_edges(&edges, &planes, degree).with_context(&context)?);
+    }
+
+    // The caps are bounded by the original edges of the first and the last section
+    let (c0, a0) = planes[0];
+    let (c1, a1) = planes[planes.len() - 1];
+    let n0 = a0.normalize().elevate(&context)?;
+    let n1 = a1.normalize().elevate(&context)?;
+    let n0 = match n0.dot(direction) > 0.0 {
+        true => -n0,
+        false => n0,
+    };
+    let n1 = match n1.dot(direction) > 0.0 {
+        true => n1,
+        false => -n1,
+    };
+    faces.push(cap(&sections[0], c0, n0).with_context(&context)?);
+    faces.push(cap(&sections[sections.len() - 1].flip(), c1, n1).with_context(&context)?);
+
+    Ok(Volume::new(Shell::new(faces), vec![]))
+}
+
+// Planar face bounded by the section with the given normal.
+fn cap(section: &Contour, centroid: Point, normal: Point) -> TopologyResult<Face> {
+    let helper = match normal.x.abs() < 0.5 {
+        true => Point::unit_x(),
+        false => Point::unit_y(),
+    };
+    let u = normal.cross(helper).normalize()?;
+    let v = normal.cross(u);
+    Ok(Face::new(
+        vec![section.clone()],
+        Rc::new(Surface::Plane(Plane::new(centroid, u, v))),
+    ))
+}
+
+// Creates the side face through the corresponding edges of all sections as a skinned nurbs surface.
+fn loft_edges(edges: &[Edge], planes: &[(Point, Point)], degree: usize) -> TopologyResult<Face> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            "Loft edges".to_string(),
+            TopologyScene::with_points(
+                edges
+                    .iter()
+                    .flat_map(|edge| edge.start.into_iter().chain(edge.end))
+                    .map(|p| (p, Category10Color::Blue))
+                    .collect(),
+            ),
+        )
+    };
+
+    let curves = compatible_curves(edges).elevate(&context)?;
+    let surface = NurbsSurface::try_new_skin(&curves, degree).elevate(&context)?;
+
+    // The outwards normal is the direction of the loft crossed with the direction of the edge
+    let midpoint = edges[0].get_midpoint();
+    let outwards = (planes[1].0 - planes[0].0).cross(edges[0].tangent(midpoint));
+    let flipped = surface.normal(midpoint).dot(outwards) < 0.0;
+    let surface = match flipped {
+        true => surface.neg(),
+        false => surface,
+    };
+
+    // The curves that run through the end points of the edges, in direction of the loft
+    let ((_, _), (v0, v1)) = surface.domain();
+    let path = |v: f64| match flipped {
+        true => Curve::Nurbs(surface.iso_curve_v(v).neg()),
+        false => Curve::Nurbs(surface.iso_curve_v(v)),
+    };
+    let (first, last) = (&edges[0], &edges[edges.len() - 1]);
+    let top = Edge::new(first.end, first.start, Curve::Nurbs(curves[0].neg()));
+    let bottom = Edge::new(
+        last.start,
+        last.end,
+        Curve::Nurbs(curves[curves.len() - 1].clone()),
+    );
+    let boundaries = match (first.start, first.end, last.start, last.end) {
+        (Some(s0), Some(e0), Some(s1), Some(e1)) => vec![Contour::new(vec![
+            Edge::new(Some(e1), Some(e0), path(v1).neg()),
+            top,
+            Edge::new(Some(s0), Some(s1), path(v0)),
+            bottom,
+        ])],
+        _ => vec![Contour::new(vec![top]), Contour::new(vec![bottom])],
+    };
+
+    Ok(Face::new(boundaries, Rc::new(Surface::Nurbs(surface))))
+}
+
+// Converts the edges into nurbs curves with the same degree and knot vector. Lines stay lines and circles
+// are split into the same number of segments. Closed circles start at the same angle.
+fn compatible_curves(edges: &[Edge]) -> GeometryResult<Vec<NurbsCurve>> {
+    let bounds = |edge: &Edge| match (&edge.curve, edge.start, edge.end) {
+        (Curve::Circle(circle), None, None) => {
+            let seam = circle.project(circle.basis + circle.radius);
+            (Some(seam), Some(seam))
+        }
+        (_, start, end) => (start, end),
+    };
+    if edges
+        .iter()
+        .all(|edge| matches!(edge.curve, Curve::Line(_)))
+    {
+        return edges
+            .iter()
+            .map(|edge| match bounds(edge) {
+                (Some(start), Some(end)) => NurbsCurve::try_new_polyline(vec![start, end]),
+                _ => Err(GeometryError::new("Lines must be bounded".to_string())),
+            })
+            .collect();
+    }
+
+    let mut segments = 1;
+    for edge in edges.iter() {
+        match (&edge.curve, bounds(edge)) {
+            (Curve::Circle(circle), (start, end)) => {
+                let arc = NurbsCurve::try_new_arc(circle, start, end)?;
+                segments = segments.max((arc.control_points.len() - 1) / 2);
+            }
+            (Curve::Line(_), _) => {}
+            _ => {
+                return Err(GeometryError::new(
+                    "Only lines and circles can be lofted".to_string(),
+                ))
+            }
+        }
+    }
+
+    // Lines become quadratic curves with evenly spaced control points, which are parameterized like the arcs
+    let mut knot_vector = vec![EFloat64::zero(); 3];
+    for k in 1..segments {
+        let knot = EFloat64::from(k as f64 / segments as f64);
+        knot_vector.extend([knot, knot]);
+    }
+    knot_vector.extend(vec![EFloat64::one(); 3]);
+    edges
+        .iter()
+        .map(|edge| match (&edge.curve, bounds(edge)) {
+            (Curve::Circle(circle), (start, end)) => {
+                NurbsCurve::try_new_arc_with_segments(circle, start, end, segments)
+            }
+            (_, (Some(start), Some(end))) => NurbsCurve::try_new(
+                2,
+                (0..=2 * segments)
+                    .map(|i| {
+                        start + (end - start) * EFloat64::from(i as f64 / (2 * segments) as f64)
+                    })
+                    .collect(),
+                vec![EFloat64::one(); 2 * segments + 1],
+                knot_vector.clone(),
+            ),
+            _ => Err(GeometryError::new("Lines must be bounded".to_string())),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primitive_objects::edges::{circle::primitive_circle, line::primitive_line};
+
+    use super::*;
+
+    fn square(z: f64, size: f64) -> Contour {
+        let p = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+            .map(|(x, y)| Point::from_f64(x * size, y * size, z));
+        Contour::new(
+            (0..4)
+                .map(|i| primitive_line(p[i], p[(i + 1) % 4]).unwrap())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_ruled_loft() {
+        // A frustum of a pyramid, where the top section runs the other way round
+        let volume = ruled_loft(&[square(0.0, 1.0), square(1.0, 0.5).flip()]).unwrap();
+        assert_eq!(volume.boundary().faces().len(), 6);
+        for face in volume.boundary().faces().iter() {
+            let p = face.inner_point();
+            let outwards = match face.surface().as_ref() {
+                Surface::Plane(_) => p - Point::from_f64(0.0, 0.0, 0.5),
+                _ => Point::from_f64(p.x.to_f64(), p.y.to_f64(), 0.0),
+            };
+            assert!(face.normal(p).dot(outwards) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_loft() {
+        // The middle section is larger, so the smooth loft bulges outwards
+        let sections = [square(0.0, 1.0), square(1.0, 2.0), square(2.0, 1.0)];
+        let volume = loft(&sections).unwrap();
+        assert_eq!(volume.boundary().faces().len(), 6);
+        let side = volume
+            .boundary()
+            .faces()
+            .iter()
+            .find(|face| face.surface().on_surface(Point::from_f64(2.0, 0.0, 1.0)))
+            .unwrap();
+        let q = side.surface().project(Point::from_f64(1.5, 0.0, 0.5));
+        assert!(q.x > 1.5);
+
+        // A circle is split at the corners of the square
+        let circle = Contour::new(vec![primitive_circle(
+            Point::from_f64(0.0, 0.0, 1.0),
+            Point::unit_z(),
+            EFloat64::one(),
+        )]);
+        let volume = loft(&[square(0.0, 1.0), circle]).unwrap();
+        assert_eq!(volume.boundary().faces().len(), 6);
+    }
+
+    #[test]
+    fn test_loft_errors() {
+        assert!(loft(&[square(0.0, 1.0)]).is_err());
+        assert!(loft(&[square(0.0, 1.0), square(0.0, 2.0)]).is_err());
+        let p = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)].map(|(x, y)| Point::from_f64(x, y, 1.0));
+        let triangle = Contour::new(
+            (0..3)
+                .map(|i| primitive_line(p[i], p[(i + 1) % 3]).unwrap())
+                .collect(),
+        );
+        assert!(loft(&[square(0.0, 1.0), triangle]).is_err());
+    }
+}
@@ -1 +1,8 @@
+pub mod blend;
+pub mod chamfer;
 pub mod extrude;
+pub mod fillet;
+pub mod hollow;
+pub mod loft;
+pub mod revolve;
+pub mod sweep;
@@ -0,0 +1,403 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    color::Category10Color,
+    curves::{circle::Circle, curve::Curve},
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{
+        cone::Cone, cylinder::Cylinder, plane::Plane, sphere::Sphere, surface::Surface,
+        torus::Torus, SurfaceLike,
+    },
+    transforms::Transform,
+};
+
+use crate::{
+    topology::{contour::Contour, edge::Edge, face::Face, shell::Shell, volume::Volume},
+    topology_error::{ElevateToTopology, TopologyError, TopologyResult, WithContext},
+    topology_scene::TopologyScene,
+};
+
+// Revolves a planar face around an axis that lies in the plane of the face.
+// The face is rotated counter-clockwise when looked at from the axis direction.
+// A full revolution (angle of 2 pi) results in a closed volume without end caps.
+pub fn revolve(
+    face: Face,
+    axis_origin: Point,
+    axis_dir: Point,
+    angle: EFloat64,
+) -> TopologyResult<Volume> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            format!(
+                "Revolve face around axis at {} with direction {} by {}",
+                axis_origin, axis_dir, angle
+            ),
+            TopologyScene::with_points(vec![
+                (axis_origin, Category10Color::Orange),
+                (axis_origin + axis_dir, Category10Color::Green),
+            ]),
+        )
+    };
+
+    let axis = axis_dir.normalize().elevate(&context)?;
+    if angle <= 0.0 || angle > EFloat64::two_pi() {
+        return Err(TopologyError::new(
+            "Angle must be in the range (0, 2 pi]".to_string(),
+        ))
+        .with_context(&context);
+    }
+    let full = angle == EFloat64::two_pi();
+
+    let (plane_basis, plane_normal) = match &**face.surface() {
+        Surface::Plane(plane) => (plane.basis, plane.normal(plane.basis)),
+        _ => {
+            return Err(TopologyError::new(
+                "Only planar faces can be revolved".to_string(),
+            ))
+            .with_context(&context)
+        }
+    };
+    if plane_normal.dot(axis) != 0.0 || (axis_origin - plane_basis).dot(plane_normal) != 0.0 {
+        return Err(TopologyError::new(
+            "Axis has to lie in the plane of the face".to_string(),
+        ))
+        .with_context(&context);
+    }
+
+    // The profile is not allowed to cross the axis, but it may touch it
+    let side = axis.cross(plane_normal);
+    let distances = face
+        .all_edges()
+        .iter()
+        .flat_map(|edge| (0..=16).map(|i| edge.interpolate(i as f64 / 16.0)))
+        .map(|p| (p - axis_origin).dot(side))
+        .collect::<Vec<EFloat64>>();
+    let positive = distances.iter().any(|d| *d > 0.0);
+    if positive && distances.iter().any(|d| *d < 0.0) {
+        return Err(TopologyError::new(
+            "Face must not cross the axis".to_string(),
+        ))
+        .with_context(&context);
+    }
+
+    // The start face has to point against the direction of rotation
+    let rotation_direction = match positive {
+        true => axis.cross(side),
+        false => -axis.cross(side),
+    };
+    let start_face = match plane_normal.dot(rotation_direction) > 0.0 {
+        true => face.flip(),
+        false => face,
+    };
+
+    let rotation = Transform::from_translation(axis_origin)
+        * Transform::from_axis_angle(axis, angle).elevate(&context)?
+        * Transform::from_translation(-axis_origin);
+
+    let mut faces = Vec::<Face>::new();
+    for edge in start_face.all_edges() {
+        if let Some(face) =
+            revolve_edge(&edge, axis_origin, axis, rotation, full).with_context(&context)?
+        {
+            faces.push(face);
+        }
+    }
+    if !full {
+        faces.push(start_face.transform(rotation).flip());
+        faces.push(start_face);
+    }
+
+    Ok(Volume::new(Shell::new(faces), vec![]))
+}
+
+// Creates the face that is swept by a single edge of the profile, or None if the edge lies on the axis.
+pub(crate) fn revolve_edge(
+    edge: &Edge,
+    axis_origin: Point,
+    axis: Point,
+    rotation: Transform,
+    full: bool,
+) -> TopologyResult<Option<Face>> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            "Revolve edge".to_string(),
+            TopologyScene::with_points(
+                edge.start
+                    .into_iter()
+                    .chain(edge.end)
+                    .map(|p| (p, Category10Color::Blue))
+                    .collect(),
+            ),
+        )
+    };
+    let foot = |p: Point| axis_origin + axis * (p - axis_origin).dot(axis);
+
+    let midpoint = edge.get_midpoint();
+    let radial = midpoint - foot(midpoint);
+    if radial.norm() <= 0.0 {
+        return Ok(None);
+    }
+    let rotation_direction = axis.cross(radial);
+    let tangent = edge.tangent(midpoint);
+    let outwards = rotation_direction.cross(tangent);
+
+    let surface = match &edge.curve {
+        Curve::Line(line) => {
+            if line.direction.is_parallel(axis) {
+                let cylinder = Cylinder::new(foot(midpoint), axis, radial.norm(), true);
+                let normal_outwards = cylinder.normal(midpoint).dot(outwards) > 0.0;
+                Surface::Cylinder(Cylinder::new(
+                    foot(midpoint),
+                    axis,
+                    radial.norm(),
+                    normal_outwards,
+                ))
+            } else if line.direction.dot(axis) == 0.0 {
+                Surface::Plane(Plane::new(midpoint, rotation_direction, tangent))
+            } else {
+                // The apex is the point where the line meets the axis
+                let perpendicular = |v: Point| v - axis * v.dot(axis);
+                let offset = perpendicular(line.basis - axis_origin);
+                let direction = perpendicular(line.direction);
+                let s = -(offset.dot(direction) / direction.norm_sq()).unwrap();
+                let apex = line.basis + line.direction * s;
+                let cone_axis = match (midpoint - apex).dot(axis) > 0.0 {
+                    true => axis,
+                    false => -axis,
+                };
+                let half_angle = line.direction.dot(cone_axis).abs().acos();
+                let cone = Cone::try_new(apex, cone_axis, half_angle, true).elevate(&context)?;
+                let normal_outwards = cone.normal(midpoint).dot(outwards) > 0.0;
+                Surface::Cone(
+                    Cone::try_new(apex, cone_axis, half_angle, normal_outwards)
+                        .elevate(&context)?,
+                )
+            }
+        }
+        Curve::Circle(circle) => {
+            let normal_outwards = outwards.dot(midpoint - circle.basis) > 0.0;
+            let center_foot = foot(circle.basis);
+            let major_radius = (circle.basis - center_foot).norm();
+            if major_radius <= 0.0 {
+                Surface::Sphere(Sphere::new(
+                    circle.basis,
+                    circle.radius.norm(),
+                    normal_outwards,
+                ))
+            } else {
+                Surface::Torus(
+                    Torus::try_new(
+                        center_foot,
+                        axis,
+                        major_radius,
+                        circle.radius.norm(),
+                        normal_outwards,
+                    )
+                    .elevate(&context)?,
+                )
+            }
+        }
+        _ => {
+            return Err(TopologyError::new(
+                "Only lines and circles can be revolved".to_string(),
+            ))
+            .with_context(&context)
+        }
+    };
+
+    // The circle that is swept by a point of the profile, or None if the point is on the axis
+    let sweep = |p: Point, forward: bool| -> TopologyResult<Option<Edge>> {
+        let radius = (p - foot(p)).norm();
+        if radius <= 0.0 {
+            return Ok(None);
+        }
+        let normal = match forward {
+            true => axis,
+            false => -axis,
+        };
+        let circle = Curve::Circle(Circle::try_new(foot(p), normal, radius).elevate(&context)?);
+        Ok(Some(match (full, forward) {
+            (true, _) => Edge::new(None, None, circle),
+            (false, true) => Edge::new(Some(p), Some(rotation * p), circle),
+            (false, false) => Edge::new(Some(rotation * p), Some(p), circle),
+        }))
+    };
+
+    let boundaries = match (edge.start, edge.end) {
+        (Some(start), Some(end)) if full => vec![sweep(start, true)?, sweep(end, false)?]
+            .into_iter()
+            .flatten()
+            .map(|edge| Contour::new(vec![edge]))
+            .collect(),
+        (Some(start), Some(end)) => {
+            let top = edge.flip();
+            let bottom = edge.transform(rotation);
+            vec![Contour::new(
+                vec![
+                    sweep(end, false)?,
+                    Some(top),
+                    sweep(start, true)?,
+                    Some(bottom),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            )]
+        }
+        _ if full => vec![],
+        _ => vec![
+            Contour::new(vec![edge.flip()]),
+            Contour::new(vec![edge.transform(rotation)]),
+        ],
+    };
+
+    Ok(Some(Face::new(boundaries, Rc::new(surface))))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        primitive_objects::edges::line::primitive_line,
+        test_fixtures::{assert_normals_outwards, is_inside},
+    };
+
+    use super::*;
+
+    fn rectangle(x0: f64, x1: f64, z0: f64, z1: f64) -> Face {
+        let p = [
+            Point::from_f64(x0, 0.0, z0),
+            Point::from_f64(x1, 0.0, z0),
+            Point::from_f64(x1, 0.0, z1),
+            Point::from_f64(x0, 0.0, z1),
+        ];
+        let edges = (0..4)
+            .map(|i| primitive_line(p[i], p[(i + 1) % 4]).unwrap())
+            .collect();
+        Face::new(
+            vec![Contour::new(edges)],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_x(),
+                Point::unit_z(),
+            ))),
+        )
+    }
+
+    #[test]
+    fn test_revolve_rectangle() {
+        let face = rectangle(1.0, 2.0, 0.0, 1.0);
+        let volume = revolve(
+            face.clone(),
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::two_pi(),
+        )
+        .unwrap();
+        assert_eq!(volume.boundary().faces().len(), 4);
+        assert_normals_outwards(&volume);
+        assert!(is_inside(&volume, Point::from_f64(-1.5, 0.0, 0.5)));
+        assert!(is_inside(&volume, Point::from_f64(0.0, 1.5, 0.5)));
+        assert!(!is_inside(&volume, Point::from_f64(0.0, 0.0, 0.5)));
+        assert!(!is_inside(&volume, Point::from_f64(2.5, 0.0, 0.5)));
+
+        let volume = revolve(face, Point::zero(), Point::unit_z(), EFloat64::half_pi()).unwrap();
+        assert_eq!(volume.boundary().faces().len(), 6);
+        let surfaces = volume
+            .boundary()
+            .faces()
+            .iter()
+            .filter(|f| matches!(**f.surface(), Surface::Cylinder(_)))
+            .count();
+        assert_eq!(surfaces, 2);
+        assert_normals_outwards(&volume);
+        assert!(is_inside(&volume, Point::from_f64(1.0, 1.0, 0.5)));
+        assert!(!is_inside(&volume, Point::from_f64(0.0, 0.0, 0.5)));
+        assert!(!is_inside(&volume, Point::from_f64(-1.0, -1.0, 0.5)));
+    }
+
+    #[test]
+    fn test_revolve_triangle() {
+        // A triangle touching the axis with one edge gives a cone and a disc
+        let p1 = Point::from_f64(0.0, 0.0, 0.0);
+        let p2 = Point::from_f64(1.0, 0.0, 0.0);
+        let p3 = Point::from_f64(0.0, 0.0, 1.0);
+        let face = Face::new(
+            vec![Contour::new(vec![
+                primitive_line(p1, p2).unwrap(),
+                primitive_line(p2, p3).unwrap(),
+                primitive_line(p3, p1).unwrap(),
+            ])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_x(),
+                Point::unit_z(),
+            ))),
+        );
+        let volume = revolve(face, Point::zero(), Point::unit_z(), EFloat64::two_pi()).unwrap();
+        assert_eq!(volume.boundary().faces().len(), 2);
+        assert!(volume
+            .boundary()
+            .faces()
+            .iter()
+            .any(|f| matches!(**f.surface(), Surface::Cone(_))));
+        assert_normals_outwards(&volume);
+        assert!(is_inside(&volume, Point::from_f64(0.0, 0.3, 0.3)));
+        assert!(!is_inside(&volume, Point::from_f64(0.0, 0.6, 0.6)));
+    }
+
+    #[test]
+    fn test_revolve_half_disc() {
+        let p1 = Point::from_f64(1.0, 0.0, 0.0);
+        let p2 = Point::from_f64(3.0, 0.0, 0.0);
+        let circle = Circle::try_new(
+            Point::from_f64(2.0, 0.0, 0.0),
+            -Point::unit_y(),
+            EFloat64::one(),
+        )
+        .unwrap();
+        let face = Face::new(
+            vec![Contour::new(vec![
+                primitive_line(p1, p2).unwrap(),
+                Edge::new(Some(p2), Some(p1), Curve::Circle(circle)),
+            ])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_x(),
+                Point::unit_z(),
+            ))),
+        );
+        let volume = revolve(face, Point::zero(), Point::unit_z(), EFloat64::pi()).unwrap();
+        assert_eq!(volume.boundary().faces().len(), 4);
+        assert!(volume
+            .boundary()
+            .faces()
+            .iter()
+            .any(|f| matches!(**f.surface(), Surface::Torus(_))));
+        assert_normals_outwards(&volume);
+        assert!(is_inside(&volume, Point::from_f64(0.0, 2.0, 0.5)));
+        assert!(!is_inside(&volume, Point::from_f64(0.0, -2.0, 0.5)));
+        assert!(!is_inside(&volume, Point::from_f64(0.0, 0.0, 0.5)));
+    }
+
+    #[test]
+    fn test_revolve_errors() {
+        let face = rectangle(1.0, 2.0, 0.0, 1.0);
+        assert!(revolve(face.clone(), Point::zero(), Point::unit_x(), EFloat64::pi()).is_ok());
+        assert!(revolve(
+            face.clone(),
+            Point::from_f64(1.5, 0.0, 0.0),
+            Point::unit_z(),
+            EFloat64::pi()
+        )
+        .is_err());
+        assert!(revolve(
+            face.clone(),
+            Point::from_f64(0.0, 1.0, 0.0),
+            Point::unit_z(),
+            EFloat64::pi()
+        )
+        .is_err());
+        assert!(revolve(face, Point::zero(), Point::unit_z(), EFloat64::zero()).is_err());
+    }
+}
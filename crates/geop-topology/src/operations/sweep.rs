@@ -0,0 +1,475 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    color::Category10Color,
+    curves::{curve::Curve, helix::Helix, nurb::NurbsCurve, CurveLike},
+    efloat::EFloat64,
+    geometry_error::GeometryResult,
+    point::Point,
+    surfaces::{nurbs::NurbsSurface, surface::Surface, SurfaceLike},
+    transforms::Transform,
+};
+
+use crate::{
+    topology::{contour::Contour, edge::Edge, face::Face, shell::Shell, volume::Volume},
+    topology_error::{ElevateToTopology, TopologyError, TopologyResult, WithContext},
+    topology_scene::TopologyScene,
+};
+
+// Number of frames per edge of the path and per turn of a helical sweep.
+const SAMPLES_PER_EDGE: usize = 16;
+const SAMPLES_PER_TURN: usize = 32;
+
+// Sweeps a planar face along a closed path. The profile is carried along the path with a rotation minimizing frame,
+// which is twisted slightly such that it closes up again, so the result is a ring without end caps.
+// The path has to be tangent continuous and the profile is expected to be placed at the start of the path.
+pub fn sweep(profile: Face, path: &Contour) -> TopologyResult<Volume> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            "Sweep face along path".to_string(),
+            TopologyScene::with_points(
+                path.all_points()
+                    .into_iter()
+                    .map(|p| (p, Category10Color::Orange))
+                    .collect(),
+            ),
+        )
+    };
+
+    for (i, edge) in path.edges.iter().enumerate() {
+        let next = &path.edges[(i + 1) % path.edges.len()];
+        if let (Some(end), Some(start)) = (edge.end, next.start) {
+            if (edge.tangent(end) - next.tangent(start)).norm() > 1e-6 {
+                return Err(TopologyError::new(
+                    "Path has to be tangent continuous".to_string(),
+                ))
+                .with_context(&context);
+            }
+        }
+    }
+
+    // Points and tangents along the path, where the last sample is the first one again
+    let mut samples = Vec::<(Point, Point)>::new();
+    for edge in path.edges.iter() {
+        for i in 0..SAMPLES_PER_EDGE {
+            let p = edge.interpolate(i as f64 / SAMPLES_PER_EDGE as f64);
+            samples.push((p, edge.tangent(p)));
+        }
+    }
+    samples.push(samples[0]);
+
+    let (x0, t0) = samples[0];
+    match &**profile.surface() {
+        Surface::Plane(plane) => {
+            let normal = plane.normal(plane.basis);
+            if (x0 - plane.basis).dot(normal) != 0.0 || normal.dot(t0) == 0.0 {
+                return Err(TopologyError::new(
+                    "Profile has to cross the path at its start".to_string(),
+                ))
+                .with_context(&context);
+            }
+        }
+        _ => {
+            return Err(TopologyError::new(
+                "Only planar faces can be swept".to_string(),
+            ))
+            .with_context(&context)
+        }
+    }
+
+    // Rotation minimizing frame by the double reflection method
+    let helper = match t0.x.abs() < 0.5 {
+        true => Point::unit_x(),
+        false => Point::unit_y(),
+    };
+    let mut normals = vec![t0.cross(helper).normalize().elevate(&context)?];
+    for i in 0..samples.len() - 1 {
+        let ((x, t), (x_next, t_next)) = (samples[i], samples[i + 1]);
+        let r = normals[i];
+        let v1 = x_next - x;
+        let c1 = v1.dot(v1);
+        let (r_l, t_l) = match c1 > 0.0 {
+            true => (
+                r - v1 * (EFloat64::two() * v1.dot(r) / c1).unwrap(),
+                t - v1 * (EFloat64::two() * v1.dot(t) / c1).unwrap(),
+            ),
+            false => (r, t),
+        };
+        let v2 = t_next - t_l;
+        let c2 = v2.dot(v2);
+        let r_next = match c2 > 0.0 {
+            true => r_l - v2 * (EFloat64::two() * v2.dot(r_l) / c2).unwrap(),
+            false => r_l,
+        };
+        normals.push(r_next.normalize().elevate(&context)?);
+    }
+
+    // Distribute the remaining twist evenly, such that the last frame matches the first one
+    let n = samples.len() - 1;
+    let twist = normals[0]
+        .angle2(normals[n], t0)
+        .elevate(&context)?
+        .to_f64();
+    let mut frames = Vec::<Transform>::new();
+    for (i, ((x, t), r)) in samples.iter().zip(normals.iter()).enumerate() {
+        let beta = EFloat64::from(-twist * i as f64 / n as f64);
+        let r = *r * beta.cos() + t.cross(*r) * beta.sin();
+        frames.push(frame_transform((x0, t0, normals[0]), (*x, *t, r)));
+    }
+    frames[n] = frames[0];
+
+    sweep_frames(profile, &frames, true).with_context(&context)
+}
+
+// Sweeps a planar face along a helix around the axis, which is right handed for a positive pitch. The profile is moved
+// by the screw motion of the helix, so every point of the profile keeps its distance to the axis, like for threads.
+pub fn helical_sweep(
+    profile: Face,
+    axis_origin: Point,
+    axis_dir: Point,
+    pitch: EFloat64,
+    turns: EFloat64,
+) -> TopologyResult<Volume> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            format!(
+                "Helical sweep around axis at {} with direction {}, pitch {} and {} turns",
+                axis_origin, axis_dir, pitch, turns
+            ),
+            TopologyScene::with_points(vec![
+                (axis_origin, Category10Color::Orange),
+                (axis_origin + axis_dir, Category10Color::Green),
+            ]),
+        )
+    };
+    let axis = axis_dir.normalize().elevate(&context)?;
+    if turns <= 0.0 {
+        return Err(TopologyError::new("Turns must be positive".to_string()))
+            .with_context(&context);
+    }
+
+    // Neighbouring turns must not overlap
+    if turns > 1.0 {
+        let heights = profile
+            .all_edges()
+            .iter()
+            .flat_map(|edge| (0..=16).map(|i| edge.interpolate(i as f64 / 16.0)))
+            .map(|p| (p - axis_origin).dot(axis).to_f64())
+            .collect::<Vec<f64>>();
+        let height = heights.iter().cloned().fold(f64::NEG_INFINITY, f64::max)
+            - heights.iter().cloned().fold(f64::INFINITY, f64::min);
+        if pitch.abs() <= height {
+            return Err(TopologyError::new(
+                "Profile must be narrower than the pitch".to_string(),
+            ))
+            .with_context(&context);
+        }
+    }
+
+    if pitch == 0.0 {
+        return Err(TopologyError::new("Pitch must not be zero".to_string()))
+            .with_context(&context);
+    }
+
+    // The path is the helix through the profile. Its tangent and the direction away from the axis are carried along by
+    // the screw motion, so the frames between them move the profile rigidly.
+    let center = profile.inner_point();
+    let basis = axis_origin + axis * (center - axis_origin).dot(axis);
+    if (center - basis).is_zero() {
+        return Err(TopologyError::new(
+            "Profile must not be centered on the axis".to_string(),
+        ))
+        .with_context(&context);
+    }
+    let helix = Helix::new(basis, axis * pitch, center - basis, pitch > 0.0);
+    let frame = |t: EFloat64| -> GeometryResult<(Point, Point, Point)> {
+        let x = helix.point_at_pitch(t);
+        let radial = (x - basis - helix.pitch * t).normalize()?;
+        Ok((x, helix.tangent(x)?, radial))
+    };
+
+    let n = ((turns.to_f64() * SAMPLES_PER_TURN as f64).ceil() as usize).max(3);
+    let start = frame(EFloat64::zero()).elevate(&context)?;
+    let mut frames = Vec::<Transform>::new();
+    for i in 0..=n {
+        let t = turns * EFloat64::from(i as f64 / n as f64);
+        frames.push(frame_transform(start, frame(t).elevate(&context)?));
+    }
+
+    sweep_frames(profile, &frames, false).with_context(&context)
+}
+
+// The rigid transform that moves the frame (origin, tangent, normal) to the other one.
+fn frame_transform(from: (Point, Point, Point), to: (Point, Point, Point)) -> Transform {
+    let (x0, t0, r0) = from;
+    let (x1, t1, r1) = to;
+    let (s0, s1) = (t0.cross(r0), t1.cross(r1));
+    let components = |p: Point| [p.x, p.y, p.z];
+    let (t0, r0, s0) = (components(t0), components(r0), components(s0));
+    let (t1, r1, s1) = (components(t1), components(r1), components(s1));
+    let mut rotation = Transform::from_translation(Point::zero());
+    for i in 0..3 {
+        for j in 0..3 {
+            rotation.matrix[i][j] = t1[i] * t0[j] + r1[i] * r0[j] + s1[i] * s0[j];
+        }
+    }
+    Transform::from_translation(x1) * rotation * Transform::from_translation(-x0)
+}
+
+// Sweeps the face through the given rigid transforms, where the first one is expected to be the identity.
+// For a closed sweep, the last transform is the first one again and no end caps are created.
+fn sweep_frames(profile: Face, frames: &[Transform], closed: bool) -> TopologyResult<Volume> {
+    let normal = match &**profile.surface() {
+        Surface::Plane(plane) => plane.normal(plane.basis),
+        _ => {
+            return Err(TopologyError::new(
+                "Only planar faces can be swept".to_string(),
+            ))
+        }
+    };
+
+    // The start face has to point against the direction of the sweep
+    let inner_point = profile.inner_point();
+    let start_face = match normal.dot(frames[1] * inner_point - inner_point) > 0.0 {
+        true => profile.flip(),
+        false => profile,
+    };
+
+    let mut faces = Vec::<Face>::new();
+    for edge in start_face.all_edges() {
+        faces.push(sweep_edge(&edge, frames, closed)?);
+    }
+    if !closed {
+        faces.push(start_face.transform(frames[frames.len() - 1]).flip());
+        faces.push(start_face);
+    }
+
+    Ok(Volume::new(Shell::new(faces), vec![]))
+}
+
+// Creates the face that is swept by a single edge of the profile as a nurbs surface.
+fn sweep_edge(edge: &Edge, frames: &[Transform], closed: bool) -> TopologyResult<Face> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            "Sweep edge".to_string(),
+            TopologyScene::with_points(
+                edge.start
+                    .into_iter()
+                    .chain(edge.end)
+                    .map(|p| (p, Category10Color::Blue))
+                    .collect(),
+            ),
+        )
+    };
+    let profile = match (&edge.curve, edge.start, edge.end) {
+        (Curve::Line(_), Some(start), Some(end)) => {
+            NurbsCurve::try_new_polyline(vec![start, end]).elevate(&context)?
+        }
+        (Curve::Circle(circle), start, end) => {
+            NurbsCurve::try_new_arc(circle, start, end).elevate(&context)?
+        }
+        _ => {
+            return Err(TopologyError::new(
+                "Only lines and circles can be swept".to_string(),
+            ))
+            .with_context(&context)
+        }
+    };
+
+    // The surface interpolates the profile at every frame, which are connected by a cubic b-spline in between
+    let sections = frames
+        .iter()
+        .map(|frame| profile.transform(*frame))
+        .collect::<Vec<NurbsCurve>>();
+    let surface = NurbsSurface::try_new_skin(&sections, 3).elevate(&context)?;
+    let n = frames.len();
+
+    // The outwards normal is the direction of the sweep crossed with the direction of the edge
+    let midpoint = edge.get_midpoint();
+    let outwards = (frames[1] * midpoint - midpoint).cross(edge.tangent(midpoint));
+    let flipped = surface.normal(midpoint).dot(outwards) < 0.0;
+    let surface = match flipped {
+        true => surface.neg(),
+        false => surface,
+    };
+
+    // The curves that are swept by the end points of the edge, in direction of the sweep
+    let ((_, _), (v0, v1)) = surface.domain();
+    let path = |v: f64| match flipped {
+        true => Curve::Nurbs(surface.iso_curve_v(v).neg()),
+        false => Curve::Nurbs(surface.iso_curve_v(v)),
+    };
+    // Lines and circles are not bounded, so the edges of the profile are taken as nurbs curves as well
+    let last = frames[n - 1];
+    let top = Edge::new(edge.end, edge.start, Curve::Nurbs(profile.neg()));
+    let bottom = Edge::new(
+        last * edge.start,
+        last * edge.end,
+        Curve::Nurbs(profile.transform(last)),
+    );
+
+    let boundaries = match (edge.start, edge.end) {
+        (Some(_), Some(_)) if closed => vec![
+            Contour::new(vec![Edge::new(None, None, path(v0))]),
+            Contour::new(vec![Edge::new(None, None, path(v1).neg())]),
+        ],
+        (Some(start), Some(end)) => vec![Contour::new(vec![
+            Edge::new(Some(last * end), Some(end), path(v1).neg()),
+            top,
+            Edge::new(Some(start), Some(last * start), path(v0)),
+            bottom,
+        ])],
+        _ if closed => vec![],
+        _ => vec![Contour::new(vec![top]), Contour::new(vec![bottom])],
+    };
+
+    Ok(Face::new(boundaries, Rc::new(Surface::Nurbs(surface))))
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::surfaces::plane::Plane;
+
+    use crate::{
+        primitive_objects::edges::{circle::primitive_circle, line::primitive_line},
+        test_fixtures::{assert_normals_outwards, is_inside},
+    };
+
+    use super::*;
+
+    fn square(center: Point, normal: Point, u: Point, size: f64) -> Face {
+        let v = normal.cross(u);
+        let size = EFloat64::from(size);
+        let p = [
+            center - u * size - v * size,
+            center + u * size - v * size,
+            center + u * size + v * size,
+            center - u * size + v * size,
+        ];
+        let edges = (0..4)
+            .map(|i| primitive_line(p[i], p[(i + 1) % 4]).unwrap())
+            .collect();
+        Face::new(
+            vec![Contour::new(edges)],
+            Rc::new(Surface::Plane(Plane::new(center, u, v))),
+        )
+    }
+
+    #[test]
+    fn test_helical_sweep() {
+        // A square thread around the z axis
+        let profile = square(
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_y(),
+            Point::unit_z(),
+            0.25,
+        );
+        let volume = helical_sweep(
+            profile,
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::one(),
+            EFloat64::from(1.25),
+        )
+        .unwrap();
+        assert_eq!(volume.boundary().faces().len(), 6);
+
+        // The end cap is the start face moved up by a turn and a quarter
+        let end_cap = volume
+            .boundary()
+            .faces()
+            .iter()
+            .find(|f| matches!(**f.surface(), Surface::Plane(_)) && f.inner_point().z > 0.5)
+            .unwrap();
+        assert!((end_cap.inner_point().y.to_f64() - 2.0).abs() < 0.3);
+
+        // Every frame moves the profile by the screw motion onto the side faces, whose normals point away from the
+        // square. The sides of the square are given by their midpoints and outwards directions.
+        let sides = [
+            ((2.0, -0.25), (0.0, -1.0)),
+            ((2.25, 0.0), (1.0, 0.0)),
+            ((2.0, 0.25), (0.0, 1.0)),
+            ((1.75, 0.0), (-1.0, 0.0)),
+        ];
+        let n = 40;
+        for i in 0..=n {
+            let t = EFloat64::from(1.25 * i as f64 / n as f64);
+            let rotation =
+                Transform::from_axis_angle(Point::unit_z(), EFloat64::two_pi() * t).unwrap();
+            let screw = Transform::from_translation(Point::unit_z() * t) * rotation;
+            for ((x, z), (dx, dz)) in sides {
+                let p = screw * Point::from_f64(x, 0.0, z);
+                let outwards = rotation * Point::from_f64(dx, 0.0, dz);
+                let side = volume
+                    .boundary()
+                    .faces()
+                    .iter()
+                    .find(|f| {
+                        matches!(**f.surface(), Surface::Nurbs(_)) && f.surface().on_surface(p)
+                    })
+                    .unwrap();
+                assert!(side.surface().normal(p).dot(outwards) > 0.9);
+            }
+        }
+
+        let profile = square(
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_y(),
+            Point::unit_z(),
+            0.25,
+        );
+        assert!(helical_sweep(
+            profile,
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(0.3),
+            EFloat64::two()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_sweep_along_circle() {
+        // Sweeping a circle along a circle gives a torus like ring without caps
+        let path = Contour::new(vec![primitive_circle(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::two(),
+        )]);
+        let profile = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::from_f64(2.0, 0.0, 0.0),
+                Point::unit_y(),
+                EFloat64::from(0.5),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::from_f64(2.0, 0.0, 0.0),
+                Point::unit_z(),
+                Point::unit_x(),
+            ))),
+        );
+        let volume = sweep(profile, &path).unwrap();
+        assert_eq!(volume.boundary().faces().len(), 1);
+
+        // The opposite side of the ring, where the normal points away from the center
+        let surface = &volume.boundary().faces()[0].surface();
+        let p = Point::from_f64(0.0, 2.5, 0.0);
+        let q = surface.project(p);
+        assert!((q - p).norm() < 0.1);
+        assert!(surface.normal(q).dot(Point::unit_y()) > 0.9);
+
+        // Along a planar circle, the frames are rotations around its axis, which move the profile onto the surface
+        for i in 0..16 {
+            let angle = EFloat64::two_pi() * EFloat64::from(i as f64 / 16.0);
+            let rotation = Transform::from_axis_angle(Point::unit_z(), angle).unwrap();
+            for j in 0..8 {
+                let a = 2.0 * std::f64::consts::PI * j as f64 / 8.0;
+                let p = Point::from_f64(2.0 + 0.5 * a.cos(), 0.0, 0.5 * a.sin());
+                assert!(surface.on_surface(rotation * p));
+            }
+        }
+        assert_normals_outwards(&volume);
+        assert!(is_inside(&volume, Point::from_f64(-2.0, 0.0, 0.25)));
+        assert!(!is_inside(&volume, Point::zero()));
+    }
+}
@@ -0,0 +1,14 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{cone::Cone, surface::Surface},
+};
+
+use crate::topology::face::Face;
+
+pub fn primitive_cone(apex: Point, axis: Point, half_angle: EFloat64) -> Face {
+    let cone = Cone::try_new(apex, axis, half_angle, true).expect("Cone is valid");
+    Face::new(vec![], Rc::new(Surface::Cone(cone)))
+}
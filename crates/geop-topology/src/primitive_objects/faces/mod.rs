@@ -1,4 +1,7 @@
+pub mod cone;
 pub mod cylinder;
+pub mod nurbs;
 pub mod plane;
 pub mod rectangle;
 pub mod sphere;
+pub mod torus;
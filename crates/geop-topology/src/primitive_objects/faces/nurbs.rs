@@ -0,0 +1,39 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    curves::curve::Curve,
+    surfaces::{nurbs::NurbsSurface, surface::Surface},
+};
+
+use crate::topology::{contour::Contour, edge::Edge, face::Face};
+
+// A face covering the whole nurbs surface, bounded by the iso curves at the ends of the parameter domain.
+// Boundary curves that collapse to a point, like at the pole of a sphere, are left out.
+pub fn primitive_nurbs_face(surface: NurbsSurface) -> Face {
+    let ((u0, u1), (v0, v1)) = surface.domain();
+    let corners = [
+        surface.point_at(u0, v0),
+        surface.point_at(u1, v0),
+        surface.point_at(u1, v1),
+        surface.point_at(u0, v1),
+    ];
+    let curves = [
+        surface.iso_curve_v(v0),
+        surface.iso_curve_u(u1),
+        surface.iso_curve_v(v1).neg(),
+        surface.iso_curve_u(u0).neg(),
+    ];
+    let edges = curves
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| corners[*i] != corners[(i + 1) % 4])
+        .map(|(i, curve)| {
+            Edge::new(
+                Some(corners[i]),
+                Some(corners[(i + 1) % 4]),
+                Curve::Nurbs(curve),
+            )
+        })
+        .collect();
+    Face::new(vec![Contour::new(edges)], Rc::new(Surface::Nurbs(surface)))
+}
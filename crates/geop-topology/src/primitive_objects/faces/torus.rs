@@ -0,0 +1,20 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{surface::Surface, torus::Torus},
+};
+
+use crate::topology::face::Face;
+
+pub fn primitive_torus(
+    basis: Point,
+    axis: Point,
+    major_radius: EFloat64,
+    minor_radius: EFloat64,
+) -> Face {
+    let torus =
+        Torus::try_new(basis, axis, major_radius, minor_radius, true).expect("Torus is valid");
+    Face::new(vec![], Rc::new(Surface::Torus(torus)))
+}
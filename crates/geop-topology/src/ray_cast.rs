@@ -0,0 +1,169 @@
+use geop_geometry::{
+    color::Category10Color,
+    curve_surface_intersection::curve_surface::{
+        curve_surface_intersection, CurveSurfaceIntersection,
+    },
+    curves::{curve::Curve, line::Line},
+    efloat::EFloat64,
+    point::Point,
+    surfaces::SurfaceLike,
+};
+
+use crate::{
+    contains::face_point::{face_point_contains, FacePointContains},
+    topology::{face::Face, volume::Volume},
+    topology_error::{ElevateToTopology, TopologyError, TopologyResult, WithContext},
+    topology_scene::TopologyScene,
+};
+
+#[derive(Clone, Debug)]
+pub struct RayHit {
+    pub point: Point,
+    pub face: Face,
+    pub normal: Point,
+    // The hit is at origin + parameter * direction.
+    pub parameter: EFloat64,
+}
+
+// Intersects the ray with the face. Hits on the boundary of the face are included, hits behind the origin are not.
+// A ray that runs within the surface of the face does not hit it.
+pub fn face_ray_cast(face: &Face, origin: Point, direction: Point) -> TopologyResult<Vec<RayHit>> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            format!("Cast ray from {} in direction {}", origin, direction),
+            TopologyScene::with_faces(vec![(face.clone(), Category10Color::Red)]),
+        )
+    };
+    let line = Line::new(origin, direction.normalize().elevate(&context)?).elevate(&context)?;
+
+    let mut hits = Vec::<RayHit>::new();
+    if let CurveSurfaceIntersection::Points(points) =
+        curve_surface_intersection(&Curve::Line(line), face.surface()).elevate(&context)?
+    {
+        for point in points {
+            if (point - origin).dot(direction) < 0.0 {
+                continue;
+            }
+            if face_point_contains(face, point) == FacePointContains::Outside {
+                continue;
+            }
+            hits.push(RayHit {
+                point,
+                face: face.clone(),
+                normal: face.surface().normal(point),
+                parameter: ((point - origin).dot(direction) / direction.norm_sq())
+                    .map_err(|_| TopologyError::new("Direction is zero".to_string()))
+                    .with_context(&context)?,
+            });
+        }
+    }
+    Ok(hits)
+}
+
+// Returns all hits of the ray with the faces of the volume, sorted by their distance to the origin.
+// Faces whose bounding boxes the ray misses are skipped. A ray through an edge or a vertex hits all faces there at
+// the same point, which is reported once with the first of these faces.
+pub fn ray_cast(volume: &Volume, origin: Point, direction: Point) -> TopologyResult<Vec<RayHit>> {
+    let mut hits = Vec::<RayHit>::new();
+    let faces = volume.all_faces();
+    for i in volume.face_bvh().query_ray(origin, direction) {
+        hits.extend(face_ray_cast(&faces[i], origin, direction)?);
+    }
+    hits.sort_by(|a, b| a.parameter.to_f64().total_cmp(&b.parameter.to_f64()));
+    hits.dedup_by(|hit, previous| {
+        hit.parameter == previous.parameter && hit.point == previous.point
+    });
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::surfaces::{plane::Plane, surface::Surface};
+
+    use crate::{
+        operations::extrude::extrude,
+        primitive_objects::{edges::circle::primitive_circle, volumes::cube::primitive_cube},
+        topology::contour::Contour,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_ray_cast_cube() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let hits = ray_cast(
+            &cube,
+            Point::from_f64(-3.0, 0.5, 0.5),
+            Point::from_f64(2.0, 0.0, 0.0),
+        )
+        .unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].point, Point::from_f64(-1.0, 0.5, 0.5));
+        assert_eq!(hits[0].normal, -Point::unit_x());
+        assert!(hits[0].parameter == 1.0);
+        assert_eq!(hits[1].point, Point::from_f64(1.0, 0.5, 0.5));
+        assert_eq!(hits[1].normal, Point::unit_x());
+        assert!(hits[1].parameter == 2.0);
+
+        // The ray points away from the cube
+        let hits = ray_cast(&cube, Point::from_f64(-3.0, 0.5, 0.5), -Point::unit_x()).unwrap();
+        assert!(hits.is_empty());
+
+        // The ray starts inside of the cube
+        let hits = ray_cast(&cube, Point::zero(), Point::unit_z()).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].point, Point::unit_z());
+    }
+
+    #[test]
+    fn test_ray_cast_cube_edges() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+
+        // The ray enters and leaves through edges, where it hits two faces each
+        let hits = ray_cast(
+            &cube,
+            Point::from_f64(-3.0, -3.0, 0.5),
+            Point::from_f64(1.0, 1.0, 0.0),
+        )
+        .unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].point, Point::from_f64(-1.0, -1.0, 0.5));
+        assert!(hits[0].parameter == 2.0);
+        assert_eq!(hits[1].point, Point::from_f64(1.0, 1.0, 0.5));
+        assert!(hits[1].parameter == 4.0);
+
+        // The diagonal enters and leaves through vertices, where it hits three faces each
+        let hits = ray_cast(&cube, Point::from_f64(-3.0, -3.0, -3.0), Point::ones()).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].point, -Point::ones());
+        assert_eq!(hits[1].point, Point::ones());
+    }
+
+    #[test]
+    fn test_ray_cast_cylinder() {
+        let face = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::zero(),
+                -Point::unit_z(),
+                EFloat64::one(),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let cylinder = extrude(face, Point::unit_z());
+        let hits = ray_cast(&cylinder, Point::from_f64(-2.0, 0.0, 0.5), Point::unit_x()).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(matches!(**hits[0].face.surface(), Surface::Cylinder(_)));
+        assert_eq!(hits[0].point, Point::from_f64(-1.0, 0.0, 0.5));
+        assert_eq!(hits[0].normal, -Point::unit_x());
+
+        // The ray misses the cylinder
+        let hits = ray_cast(&cylinder, Point::from_f64(-2.0, 2.0, 0.5), Point::unit_x()).unwrap();
+        assert!(hits.is_empty());
+    }
+}
@@ -0,0 +1,473 @@
+use std::{collections::HashMap, rc::Rc};
+
+use geop_geometry::{curves::curve::Curve, point::Point, surfaces::surface::Surface};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    topology::{
+        contour::Contour,
+        edge::Edge,
+        face::Face,
+        scene::{Color, Scene},
+        shell::Shell,
+        volume::Volume,
+    },
+    topology_error::{TopologyError, TopologyResult},
+};
+
+const FORMAT: &str = "geop";
+
+// The version of the format that is written. Files of older versions can still be read.
+pub const FORMAT_VERSION: u32 = 1;
+
+// The surfaces of all faces in a document, each stored once, such that faces that share a surface still share it after loading.
+pub struct SurfaceTable {
+    surfaces: Vec<Surface>,
+    indices: HashMap<*const Surface, usize>,
+}
+
+impl SurfaceTable {
+    fn new() -> Self {
+        SurfaceTable {
+            surfaces: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    fn index(&mut self, surface: &Rc<Surface>) -> usize {
+        let surfaces = &mut self.surfaces;
+        *self.indices.entry(Rc::as_ptr(surface)).or_insert_with(|| {
+            surfaces.push((**surface).clone());
+            surfaces.len() - 1
+        })
+    }
+}
+
+fn surface(surfaces: &[Rc<Surface>], index: usize) -> TopologyResult<Rc<Surface>> {
+    match surfaces.get(index) {
+        Some(surface) => Ok(surface.clone()),
+        None => Err(TopologyError::new(format!(
+            "Surface {} is not in the document",
+            index
+        ))),
+    }
+}
+
+// Values that can be saved and loaded. Faces refer to their surface by its index in the surface table.
+pub trait Storable: Sized {
+    type Stored: Serialize + DeserializeOwned;
+
+    fn store(&self, surfaces: &mut SurfaceTable) -> Self::Stored;
+    fn restore(stored: Self::Stored, surfaces: &[Rc<Surface>]) -> TopologyResult<Self>;
+}
+
+// Geometry and edges do not refer to surfaces, so they are stored as they are.
+macro_rules! storable_as_is {
+    ($($t:ty),*) => {
+        $(
+            impl Storable for $t {
+                type Stored = $t;
+
+                fn store(&self, _surfaces: &mut SurfaceTable) -> $t {
+                    self.clone()
+                }
+
+                fn restore(stored: $t, _surfaces: &[Rc<Surface>]) -> TopologyResult<$t> {
+                    Ok(stored)
+                }
+            }
+        )*
+    };
+}
+
+storable_as_is!(Point, Curve, Surface, Edge, Contour, Color);
+
+fn store_all<T: Storable>(values: &[T], surfaces: &mut SurfaceTable) -> Vec<T::Stored> {
+    values.iter().map(|value| value.store(surfaces)).collect()
+}
+
+impl<T: Storable> Storable for Vec<T> {
+    type Stored = Vec<T::Stored>;
+
+    fn store(&self, surfaces: &mut SurfaceTable) -> Self::Stored {
+        store_all(self, surfaces)
+    }
+
+    fn restore(stored: Self::Stored, surfaces: &[Rc<Surface>]) -> TopologyResult<Self> {
+        stored
+            .into_iter()
+            .map(|value| T::restore(value, surfaces))
+            .collect()
+    }
+}
+
+impl<T: Storable> Storable for (T, Color) {
+    type Stored = (T::Stored, Color);
+
+    fn store(&self, surfaces: &mut SurfaceTable) -> Self::Stored {
+        (self.0.store(surfaces), self.1)
+    }
+
+    fn restore(stored: Self::Stored, surfaces: &[Rc<Surface>]) -> TopologyResult<Self> {
+        Ok((T::restore(stored.0, surfaces)?, stored.1))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StoredFace {
+    surface: usize,
+    boundaries: Vec<Contour>,
+}
+
+impl Storable for Face {
+    type Stored = StoredFace;
+
+    fn store(&self, surfaces: &mut SurfaceTable) -> StoredFace {
+        StoredFace {
+            surface: surfaces.index(self.surface()),
+            boundaries: self.boundaries().to_vec(),
+        }
+    }
+
+    // Files can be edited or corrupted, so the face is checked like any new face.
+    fn restore(stored: StoredFace, surfaces: &[Rc<Surface>]) -> TopologyResult<Face> {
+        Face::try_new(stored.boundaries, surface(surfaces, stored.surface)?)
+    }
+}
+
+impl Storable for Shell {
+    type Stored = Vec<StoredFace>;
+
+    fn store(&self, surfaces: &mut SurfaceTable) -> Vec<StoredFace> {
+        store_all(self.faces(), surfaces)
+    }
+
+    fn restore(stored: Vec<StoredFace>, surfaces: &[Rc<Surface>]) -> TopologyResult<Shell> {
+        if stored.is_empty() {
+            return Err(TopologyError::new("Shell has no faces".to_string()));
+        }
+        Ok(Shell::new(Vec::<Face>::restore(stored, surfaces)?))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StoredVolume {
+    boundary: Vec<StoredFace>,
+    cavities: Vec<Vec<StoredFace>>,
+}
+
+impl Storable for Volume {
+    type Stored = StoredVolume;
+
+    fn store(&self, surfaces: &mut SurfaceTable) -> StoredVolume {
+        StoredVolume {
+            boundary: self.boundary().store(surfaces),
+            cavities: store_all(self.cavities(), surfaces),
+        }
+    }
+
+    fn restore(stored: StoredVolume, surfaces: &[Rc<Surface>]) -> TopologyResult<Volume> {
+        Ok(Volume::new(
+            Shell::restore(stored.boundary, surfaces)?,
+            Vec::<Shell>::restore(stored.cavities, surfaces)?,
+        ))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StoredScene {
+    volumes: Vec<(StoredVolume, Color)>,
+    faces: Vec<(StoredFace, Color)>,
+    edges: Vec<(Edge, Color)>,
+    points: Vec<(Point, Color)>,
+}
+
+impl Storable for Scene {
+    type Stored = StoredScene;
+
+    fn store(&self, surfaces: &mut SurfaceTable) -> StoredScene {
+        StoredScene {
+            volumes: self.volumes.store(surfaces),
+            faces: self.faces.store(surfaces),
+            edges: self.edges.clone(),
+            points: self.points.clone(),
+        }
+    }
+
+    fn restore(stored: StoredScene, surfaces: &[Rc<Surface>]) -> TopologyResult<Scene> {
+        Ok(Scene::new(
+            Vec::<(Volume, Color)>::restore(stored.volumes, surfaces)?,
+            Vec::<(Face, Color)>::restore(stored.faces, surfaces)?,
+            stored.edges,
+            stored.points,
+        ))
+    }
+}
+
+// The start of every document, which is read first to reject files of newer versions.
+#[derive(Serialize, Deserialize)]
+struct Header {
+    format: String,
+    version: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Document<T> {
+    format: String,
+    version: u32,
+    surfaces: Vec<Surface>,
+    content: T,
+}
+
+fn document<T: Storable>(value: &T) -> Document<T::Stored> {
+    let mut surfaces = SurfaceTable::new();
+    let content = value.store(&mut surfaces);
+    Document {
+        format: FORMAT.to_string(),
+        version: FORMAT_VERSION,
+        surfaces: surfaces.surfaces,
+        content,
+    }
+}
+
+fn check_header(header: Header) -> TopologyResult<()> {
+    if header.format != FORMAT {
+        return Err(TopologyError::new(format!(
+            "Expected a {} document, but found {}",
+            FORMAT, header.format
+        )));
+    }
+    if header.version > FORMAT_VERSION {
+        return Err(TopologyError::new(format!(
+            "The document has version {}, but only versions up to {} are supported",
+            header.version, FORMAT_VERSION
+        )));
+    }
+    Ok(())
+}
+
+fn restore<T: Storable>(document: Document<T::Stored>) -> TopologyResult<T> {
+    let surfaces = document
+        .surfaces
+        .into_iter()
+        .map(Rc::new)
+        .collect::<Vec<Rc<Surface>>>();
+    T::restore(document.content, &surfaces)
+}
+
+// Saves the value as human readable JSON.
+pub fn to_json<T: Storable>(value: &T) -> TopologyResult<String> {
+    serde_json::to_string_pretty(&document(value))
+        .map_err(|e| TopologyError::new(format!("Cannot write JSON: {}", e)))
+}
+
+pub fn from_json<T: Storable>(json: &str) -> TopologyResult<T> {
+    let error = |e: serde_json::Error| TopologyError::new(format!("Cannot read JSON: {}", e));
+    check_header(serde_json::from_str::<Header>(json).map_err(error)?)?;
+    restore(serde_json::from_str::<Document<T::Stored>>(json).map_err(error)?)
+}
+
+// Saves the value in a compact binary encoding.
+pub fn to_binary<T: Storable>(value: &T) -> TopologyResult<Vec<u8>> {
+    bincode::serialize(&document(value))
+        .map_err(|e| TopologyError::new(format!("Cannot write binary: {}", e)))
+}
+
+pub fn from_binary<T: Storable>(bytes: &[u8]) -> TopologyResult<T> {
+    let error = |e: bincode::Error| TopologyError::new(format!("Cannot read binary: {}", e));
+    // The header is a prefix of the document
+    check_header(bincode::deserialize::<Header>(bytes).map_err(error)?)?;
+    restore(bincode::deserialize::<Document<T::Stored>>(bytes).map_err(error)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::{
+        curves::{line::Line, nurb::NurbsCurve, sampled_curve::SampledCurve},
+        efloat::EFloat64,
+        surfaces::{plane::Plane, sphere::Sphere, torus::Torus},
+    };
+
+    use crate::{
+        primitive_objects::{edges::line::primitive_line, volumes::cube::primitive_cube},
+        topology::scene::Color,
+    };
+
+    use super::*;
+
+    fn volume(volume: &Volume) -> f64 {
+        volume
+            .mass_properties(EFloat64::one())
+            .unwrap()
+            .volume
+            .to_f64()
+    }
+
+    #[test]
+    fn test_json() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let json = to_json(&cube).unwrap();
+        assert!(json.contains("\"version\": 1"));
+
+        let loaded = from_json::<Volume>(&json).unwrap();
+        assert_eq!(loaded.boundary().faces().len(), 6);
+        assert_eq!(volume(&loaded), volume(&cube));
+        for (face, loaded) in cube.all_faces().iter().zip(loaded.all_faces().iter()) {
+            assert!(**face.surface() == **loaded.surface());
+            assert_eq!(face.all_edges().len(), loaded.all_edges().len());
+        }
+    }
+
+    #[test]
+    fn test_binary() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let bytes = to_binary(&vec![cube.clone(), cube.clone()]).unwrap();
+        assert!(bytes.len() < to_json(&cube).unwrap().len());
+
+        let loaded = from_binary::<Vec<Volume>>(&bytes).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(volume(&loaded[1]), volume(&cube));
+    }
+
+    #[test]
+    fn test_shared_surfaces() {
+        // The clones of the cube share the surfaces of its faces
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let faces = cube.all_faces();
+        let shared = vec![faces[0].clone(), faces[0].clone(), faces[1].clone()];
+
+        let loaded = from_json::<Vec<Face>>(&to_json(&shared).unwrap()).unwrap();
+        assert!(Rc::ptr_eq(loaded[0].surface(), loaded[1].surface()));
+        assert!(!Rc::ptr_eq(loaded[0].surface(), loaded[2].surface()));
+
+        let loaded = from_binary::<Vec<Face>>(&to_binary(&shared).unwrap()).unwrap();
+        assert!(Rc::ptr_eq(loaded[0].surface(), loaded[1].surface()));
+    }
+
+    #[test]
+    fn test_scene() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let edge = primitive_line(Point::zero(), Point::unit_x()).unwrap();
+        let scene = Scene::new(
+            vec![(cube.clone(), Color::red())],
+            vec![(cube.all_faces()[0].clone(), Color::blue())],
+            vec![(edge, Color::black())],
+            vec![(Point::unit_z(), Color::green())],
+        );
+        let loaded = from_binary::<Scene>(&to_binary(&scene).unwrap()).unwrap();
+        assert_eq!(loaded.volumes.len(), 1);
+        assert_eq!(loaded.faces.len(), 1);
+        assert!(loaded.edges[0].0.end == Some(Point::unit_x()));
+        assert!(loaded.points[0].0 == Point::unit_z());
+        assert_eq!(loaded.points[0].1.g, Color::green().g);
+
+        // The face of the scene and the face of the volume share their surface
+        assert!(Rc::ptr_eq(
+            loaded.faces[0].0.surface(),
+            loaded.volumes[0].0.boundary().faces()[0].surface()
+        ));
+    }
+
+    #[test]
+    fn test_version() {
+        let json = to_json(&Point::unit_x()).unwrap();
+        assert!(from_json::<Point>(&json).unwrap() == Point::unit_x());
+
+        let newer = json.replace("\"version\": 1", "\"version\": 2");
+        assert!(from_json::<Point>(&newer).is_err());
+        assert!(from_json::<Point>("{\"format\": \"other\", \"version\": 1}").is_err());
+    }
+
+    #[test]
+    fn test_malformed_json() {
+        let polyline =
+            NurbsCurve::try_new_polyline(vec![Point::zero(), Point::unit_x(), Point::unit_y()])
+                .unwrap();
+        let json = to_json(&Curve::Nurbs(polyline)).unwrap();
+        assert!(from_json::<Curve>(&json).is_ok());
+        // The knot vector does not fit a curve of higher degree
+        let malformed = json.replace("\"degree\": 1", "\"degree\": 5");
+        assert_ne!(malformed, json);
+        assert!(from_json::<Curve>(&malformed).is_err());
+
+        let sampled =
+            SampledCurve::try_new(vec![Point::zero(), Point::unit_x(), Point::unit_y()], 0.5)
+                .unwrap();
+        let json = to_json(&Curve::SampledCurve(sampled)).unwrap();
+        assert!(!json.contains("arc_lengths"));
+        assert!(from_json::<Curve>(&json).is_ok());
+        let malformed = json.replace("\"error_bound\": 0.5", "\"error_bound\": -0.5");
+        assert_ne!(malformed, json);
+        assert!(from_json::<Curve>(&malformed).is_err());
+
+        let torus = Torus::try_new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(3.0),
+            EFloat64::one(),
+            true,
+        )
+        .unwrap();
+        let json = to_json(&Surface::Nurbs(torus.to_nurbs())).unwrap();
+        assert!(from_json::<Surface>(&json).is_ok());
+        let malformed = json.replace("\"u_degree\": 2", "\"u_degree\": 7");
+        assert_ne!(malformed, json);
+        assert!(from_json::<Surface>(&malformed).is_err());
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        // The fields are public, so invalid values can be saved, but they are rejected when they are loaded
+        let nan = Point::new(
+            EFloat64 {
+                upper_bound: f64::NAN,
+                lower_bound: f64::NAN,
+            },
+            EFloat64::zero(),
+            EFloat64::zero(),
+        );
+        assert!(from_binary::<Point>(&to_binary(&nan).unwrap()).is_err());
+        let empty = Point::new(
+            EFloat64 {
+                upper_bound: 0.0,
+                lower_bound: 1.0,
+            },
+            EFloat64::zero(),
+            EFloat64::zero(),
+        );
+        assert!(from_json::<Point>(&to_json(&empty).unwrap()).is_err());
+
+        let plane = Surface::Plane(Plane {
+            basis: Point::zero(),
+            u_slope: Point::unit_x(),
+            v_slope: Point::unit_x(),
+        });
+        assert!(from_json::<Surface>(&to_json(&plane).unwrap()).is_err());
+        let sphere = Surface::Sphere(Sphere {
+            basis: Point::zero(),
+            radius: -EFloat64::one(),
+            normal_outwards: true,
+        });
+        assert!(from_json::<Surface>(&to_json(&sphere).unwrap()).is_err());
+        let line = Curve::Line(Line {
+            basis: Point::zero(),
+            direction: Point::zero(),
+        });
+        assert!(from_json::<Curve>(&to_json(&line).unwrap()).is_err());
+
+        let edge = Edge {
+            start: Some(Point::unit_y()),
+            end: Some(Point::unit_x()),
+            curve: primitive_line(Point::zero(), Point::unit_x())
+                .unwrap()
+                .curve,
+        };
+        assert!(from_json::<Edge>(&to_json(&edge).unwrap()).is_err());
+
+        // The boundary of one face of the cube is not on the surface of another
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let faces = cube.all_faces();
+        let face = Face::new_unchecked(faces[0].boundaries().to_vec(), faces[1].surface().clone());
+        assert!(from_json::<Face>(&to_json(&face).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,45 @@
+pub mod parser;
+pub mod reader;
+pub mod writer;
+
+// Errors in reading STEP files, which follow ISO 10303-21.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepError {
+    // The file does not follow the syntax of ISO 10303-21.
+    Syntax {
+        line: usize,
+        message: String,
+    },
+    // An entity refers to an entity that is not defined in the file.
+    MissingEntity {
+        id: usize,
+    },
+    // The entity cannot be mapped onto geop, like a B-spline surface.
+    UnsupportedEntity {
+        id: usize,
+        name: String,
+    },
+    // The entity has unexpected parameters or describes invalid geometry.
+    InvalidEntity {
+        id: usize,
+        name: String,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for StepError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            StepError::Syntax { line, message } => {
+                write!(f, "Syntax error in line {}: {}", line, message)
+            }
+            StepError::MissingEntity { id } => write!(f, "Entity #{} is not defined", id),
+            StepError::UnsupportedEntity { id, name } => {
+                write!(f, "Entity #{} of type {} is not supported", id, name)
+            }
+            StepError::InvalidEntity { id, name, message } => {
+                write!(f, "Entity #{} of type {} is invalid: {}", id, name, message)
+            }
+        }
+    }
+}
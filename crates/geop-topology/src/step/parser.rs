@@ -0,0 +1,375 @@
+use std::collections::BTreeMap;
+
+use super::StepError;
+
+// A parameter of an entity instance in an ISO 10303-21 file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepValue {
+    Integer(i64),
+    Real(f64),
+    String(String),
+    Enumeration(String),
+    Reference(usize),
+    List(Vec<StepValue>),
+    // A value with an explicit type, like LENGTH_MEASURE(1.0)
+    Typed(String, Vec<StepValue>),
+    // $
+    Unset,
+    // *
+    Derived,
+}
+
+// The name and parameters of an entity, like CARTESIAN_POINT('', (0., 0., 0.)).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepRecord {
+    pub name: String,
+    pub parameters: Vec<StepValue>,
+}
+
+// An entity instance, like #12 = CARTESIAN_POINT(...). Complex instances consist of several records.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepEntity {
+    pub id: usize,
+    pub line: usize,
+    pub records: Vec<StepRecord>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StepFile {
+    pub header: Vec<StepRecord>,
+    pub entities: BTreeMap<usize, StepEntity>,
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    position: usize,
+    line: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, message: &str) -> Result<T, StepError> {
+        Err(StepError::Syntax {
+            line: self.line,
+            message: message.to_string(),
+        })
+    }
+
+    // Skips whitespace and comments.
+    fn skip(&mut self) {
+        while self.position < self.text.len() {
+            match self.text[self.position] {
+                b'\n' => {
+                    self.line += 1;
+                    self.position += 1;
+                }
+                c if c.is_ascii_whitespace() => self.position += 1,
+                b'/' if self.text.get(self.position + 1) == Some(&b'*') => {
+                    self.position += 2;
+                    while self.position < self.text.len()
+                        && !self.text[self.position..].starts_with(b"*/")
+                    {
+                        if self.text[self.position] == b'\n' {
+                            self.line += 1;
+                        }
+                        self.position += 1;
+                    }
+                    self.position += 2;
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip();
+        self.text.get(self.position).copied()
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), StepError> {
+        match self.peek() {
+            Some(d) if d == c => {
+                self.position += 1;
+                Ok(())
+            }
+            _ => self.error(&format!("Expected '{}'", c as char)),
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(u8) -> bool) -> &'a str {
+        let start = self.position;
+        while self.position < self.text.len() && f(self.text[self.position]) {
+            self.position += 1;
+        }
+        std::str::from_utf8(&self.text[start..self.position]).unwrap()
+    }
+
+    // Whether the next keyword is the given one, without consuming it.
+    fn at_keyword(&mut self, keyword: &str) -> bool {
+        self.skip();
+        self.text[self.position..].starts_with(keyword.as_bytes())
+    }
+
+    // A keyword like CARTESIAN_POINT or END-ISO-10303-21.
+    fn keyword(&mut self) -> Result<&'a str, StepError> {
+        self.skip();
+        let keyword = self.take_while(|c| c.is_ascii_alphanumeric() || c == b'_' || c == b'-');
+        match keyword.is_empty() {
+            true => self.error("Expected a keyword"),
+            false => Ok(keyword),
+        }
+    }
+
+    fn number(&mut self) -> Result<usize, StepError> {
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        match digits.parse::<usize>() {
+            Ok(n) => Ok(n),
+            Err(_) => self.error("Expected an entity number"),
+        }
+    }
+
+    fn value(&mut self) -> Result<StepValue, StepError> {
+        match self.peek() {
+            Some(b'$') => {
+                self.position += 1;
+                Ok(StepValue::Unset)
+            }
+            Some(b'*') => {
+                self.position += 1;
+                Ok(StepValue::Derived)
+            }
+            Some(b'#') => {
+                self.position += 1;
+                Ok(StepValue::Reference(self.number()?))
+            }
+            Some(b'(') => Ok(StepValue::List(self.values()?)),
+            Some(b'\'') => {
+                self.position += 1;
+                let mut string = Vec::<u8>::new();
+                loop {
+                    match self.text.get(self.position) {
+                        Some(b'\'') if self.text.get(self.position + 1) == Some(&b'\'') => {
+                            string.push(b'\'');
+                            self.position += 2;
+                        }
+                        Some(b'\'') => {
+                            self.position += 1;
+                            break;
+                        }
+                        Some(c) => {
+                            if *c == b'\n' {
+                                self.line += 1;
+                            }
+                            string.push(*c);
+                            self.position += 1;
+                        }
+                        None => return self.error("Unterminated string"),
+                    }
+                }
+                Ok(StepValue::String(
+                    String::from_utf8_lossy(&string).to_string(),
+                ))
+            }
+            Some(b'.') => {
+                self.position += 1;
+                let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == b'_');
+                self.expect(b'.')?;
+                Ok(StepValue::Enumeration(name.to_string()))
+            }
+            Some(b'"') => {
+                // Binary values are not needed for geometry
+                self.position += 1;
+                self.take_while(|c| c != b'"');
+                self.expect(b'"')?;
+                Ok(StepValue::Unset)
+            }
+            Some(c) if c == b'-' || c == b'+' || c.is_ascii_digit() => {
+                let number = self.take_while(|c| {
+                    c.is_ascii_digit() || matches!(c, b'-' | b'+' | b'.' | b'E' | b'e')
+                });
+                if let Ok(integer) = number.parse::<i64>() {
+                    return Ok(StepValue::Integer(integer));
+                }
+                match number.parse::<f64>() {
+                    Ok(real) => Ok(StepValue::Real(real)),
+                    Err(_) => self.error(&format!("Invalid number {}", number)),
+                }
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                let name = self.keyword()?.to_string();
+                Ok(StepValue::Typed(name, self.values()?))
+            }
+            _ => self.error("Expected a parameter"),
+        }
+    }
+
+    // A parenthesized, comma separated list of values.
+    fn values(&mut self) -> Result<Vec<StepValue>, StepError> {
+        self.expect(b'(')?;
+        let mut values = Vec::<StepValue>::new();
+        if self.peek() == Some(b')') {
+            self.position += 1;
+            return Ok(values);
+        }
+        loop {
+            values.push(self.value()?);
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b')') => {
+                    self.position += 1;
+                    return Ok(values);
+                }
+                _ => return self.error("Expected ',' or ')'"),
+            }
+        }
+    }
+
+    fn record(&mut self) -> Result<StepRecord, StepError> {
+        let name = self.keyword()?.to_string();
+        let parameters = self.values()?;
+        Ok(StepRecord { name, parameters })
+    }
+
+    fn entity(&mut self) -> Result<StepEntity, StepError> {
+        let line = self.line;
+        self.expect(b'#')?;
+        let id = self.number()?;
+        self.expect(b'=')?;
+        let records = match self.peek() {
+            Some(b'(') => {
+                self.position += 1;
+                let mut records = Vec::<StepRecord>::new();
+                while self.peek() != Some(b')') {
+                    records.push(self.record()?);
+                }
+                self.position += 1;
+                records
+            }
+            _ => vec![self.record()?],
+        };
+        self.expect(b';')?;
+        Ok(StepEntity { id, line, records })
+    }
+}
+
+// Parses the header and the data section of an ISO 10303-21 file.
+pub fn parse_step(text: &str) -> Result<StepFile, StepError> {
+    let mut parser = Parser {
+        text: text.as_bytes(),
+        position: 0,
+        line: 1,
+    };
+    if parser.keyword()? != "ISO-10303-21" {
+        return parser.error("Expected ISO-10303-21");
+    }
+    parser.expect(b';')?;
+
+    let mut file = StepFile {
+        header: Vec::new(),
+        entities: BTreeMap::new(),
+    };
+    loop {
+        match parser.keyword()? {
+            "HEADER" => {
+                parser.expect(b';')?;
+                while !parser.at_keyword("ENDSEC") {
+                    file.header.push(parser.record()?);
+                    parser.expect(b';')?;
+                }
+                if parser.keyword()? != "ENDSEC" {
+                    return parser.error("Expected ENDSEC");
+                }
+                parser.expect(b';')?;
+            }
+            "DATA" => {
+                // The data section may have parameters in later editions of the standard
+                if parser.peek() == Some(b'(') {
+                    parser.values()?;
+                }
+                parser.expect(b';')?;
+                while parser.peek() == Some(b'#') {
+                    let entity = parser.entity()?;
+                    if file.entities.contains_key(&entity.id) {
+                        return parser.error(&format!("Entity #{} is defined twice", entity.id));
+                    }
+                    file.entities.insert(entity.id, entity);
+                }
+                if parser.keyword()? != "ENDSEC" {
+                    return parser.error("Expected ENDSEC");
+                }
+                parser.expect(b';')?;
+            }
+            "END-ISO-10303-21" => {
+                parser.expect(b';')?;
+                return Ok(file);
+            }
+            keyword => return parser.error(&format!("Unexpected section {}", keyword)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_step() {
+        let text = "ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION(('A part'),'2;1');
+ENDSEC;
+DATA;
+/* A comment */
+#1=CARTESIAN_POINT('it''s',(1.,-2.5E-1,3));
+#2=( LENGTH_UNIT() NAMED_UNIT(*) SI_UNIT(.MILLI.,.METRE.) );
+#3=ORIENTED_EDGE('',*,*,#1,.T.);
+#4=MEASURE_REPRESENTATION_ITEM('',LENGTH_MEASURE(1.E-07),$);
+ENDSEC;
+END-ISO-10303-21;
+";
+        let file = parse_step(text).unwrap();
+        assert_eq!(file.header[0].name, "FILE_DESCRIPTION");
+        assert_eq!(file.entities.len(), 4);
+        assert_eq!(
+            file.entities[&1].records[0].parameters,
+            vec![
+                StepValue::String("it's".to_string()),
+                StepValue::List(vec![
+                    StepValue::Real(1.0),
+                    StepValue::Real(-0.25),
+                    StepValue::Integer(3)
+                ])
+            ]
+        );
+        assert_eq!(file.entities[&1].line, 7);
+        assert_eq!(file.entities[&2].records.len(), 3);
+        assert_eq!(
+            file.entities[&2].records[2].parameters[0],
+            StepValue::Enumeration("MILLI".to_string())
+        );
+        assert_eq!(
+            file.entities[&3].records[0].parameters[1..],
+            [
+                StepValue::Derived,
+                StepValue::Derived,
+                StepValue::Reference(1),
+                StepValue::Enumeration("T".to_string())
+            ]
+        );
+        assert_eq!(
+            file.entities[&4].records[0].parameters[1],
+            StepValue::Typed("LENGTH_MEASURE".to_string(), vec![StepValue::Real(1e-7)])
+        );
+    }
+
+    #[test]
+    fn test_syntax_error() {
+        let text = "ISO-10303-21;\nDATA;\n#1=CARTESIAN_POINT('',(1.,2.,3.);\nENDSEC;\n";
+        assert_eq!(
+            parse_step(text).unwrap_err(),
+            StepError::Syntax {
+                line: 3,
+                message: "Expected ',' or ')'".to_string()
+            }
+        );
+    }
+}
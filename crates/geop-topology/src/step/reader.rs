@@ -0,0 +1,823 @@
+use std::{collections::HashMap, rc::Rc};
+
+use geop_geometry::{
+    curves::{circle::Circle, curve::Curve, ellipse::Ellipse, line::Line, CurveLike},
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{
+        cone::Cone, cylinder::Cylinder, plane::Plane, sphere::Sphere, surface::Surface,
+        torus::Torus, SurfaceLike,
+    },
+};
+
+use crate::{
+    topology::{contour::Contour, edge::Edge, face::Face, shell::Shell, volume::Volume},
+    topology_error::{TopologyError, TopologyResult, WithContext},
+};
+
+use super::{
+    parser::{parse_step, StepFile, StepRecord, StepValue},
+    StepError,
+};
+
+// Vertices that are this close to their curve are moved onto it. Exporters often round coordinates.
+const VERTEX_TOLERANCE: f64 = 1e-6;
+
+// The parameters of a simple entity instance.
+struct Parameters<'a> {
+    id: usize,
+    record: &'a StepRecord,
+}
+
+impl<'a> Parameters<'a> {
+    fn name(&self) -> &'a str {
+        &self.record.name
+    }
+
+    fn invalid(&self, message: &str) -> StepError {
+        StepError::InvalidEntity {
+            id: self.id,
+            name: self.record.name.clone(),
+            message: message.to_string(),
+        }
+    }
+
+    fn unsupported(&self) -> StepError {
+        StepError::UnsupportedEntity {
+            id: self.id,
+            name: self.record.name.clone(),
+        }
+    }
+
+    fn get(&self, i: usize) -> Result<&'a StepValue, StepError> {
+        self.record
+            .parameters
+            .get(i)
+            .ok_or_else(|| self.invalid(&format!("Missing parameter {}", i)))
+    }
+
+    fn reference(&self, i: usize) -> Result<usize, StepError> {
+        match self.get(i)? {
+            StepValue::Reference(id) => Ok(*id),
+            _ => Err(self.invalid(&format!("Parameter {} is not a reference", i))),
+        }
+    }
+
+    fn optional_reference(&self, i: usize) -> Result<Option<usize>, StepError> {
+        match self.get(i)? {
+            StepValue::Unset => Ok(None),
+            _ => Ok(Some(self.reference(i)?)),
+        }
+    }
+
+    fn references(&self, i: usize) -> Result<Vec<usize>, StepError> {
+        match self.get(i)? {
+            StepValue::List(values) => values
+                .iter()
+                .map(|value| match value {
+                    StepValue::Reference(id) => Ok(*id),
+                    _ => Err(self.invalid(&format!("Parameter {} is not a list of references", i))),
+                })
+                .collect(),
+            _ => Err(self.invalid(&format!("Parameter {} is not a list", i))),
+        }
+    }
+
+    fn real(&self, i: usize) -> Result<f64, StepError> {
+        real(self.get(i)?).ok_or_else(|| self.invalid(&format!("Parameter {} is not a number", i)))
+    }
+
+    fn reals(&self, i: usize) -> Result<Vec<f64>, StepError> {
+        match self.get(i)? {
+            StepValue::List(values) => values
+                .iter()
+                .map(|value| {
+                    real(value).ok_or_else(|| {
+                        self.invalid(&format!("Parameter {} is not a list of numbers", i))
+                    })
+                })
+                .collect(),
+            _ => Err(self.invalid(&format!("Parameter {} is not a list", i))),
+        }
+    }
+
+    fn boolean(&self, i: usize) -> Result<bool, StepError> {
+        match self.get(i)? {
+            StepValue::Enumeration(value) if value == "T" => Ok(true),
+            StepValue::Enumeration(value) if value == "F" => Ok(false),
+            _ => Err(self.invalid(&format!("Parameter {} is not a boolean", i))),
+        }
+    }
+}
+
+// Numbers may be written with an explicit type, like LENGTH_MEASURE(1.0).
+fn real(value: &StepValue) -> Option<f64> {
+    match value {
+        StepValue::Integer(n) => Some(*n as f64),
+        StepValue::Real(x) => Some(*x),
+        StepValue::Typed(_, values) if values.len() == 1 => real(&values[0]),
+        _ => None,
+    }
+}
+
+// A right handed coordinate system, as given by AXIS2_PLACEMENT_3D.
+struct Placement {
+    location: Point,
+    axis: Point,
+    ref_direction: Point,
+}
+
+impl Placement {
+    fn y_direction(&self) -> Point {
+        self.axis.cross(self.ref_direction)
+    }
+}
+
+struct StepReader<'a> {
+    file: &'a StepFile,
+    // Faces that share a surface share it in geop as well.
+    surfaces: HashMap<usize, Rc<Surface>>,
+}
+
+impl<'a> StepReader<'a> {
+    fn new(file: &'a StepFile) -> Self {
+        StepReader {
+            file,
+            surfaces: HashMap::new(),
+        }
+    }
+
+    fn parameters(&self, id: usize) -> Result<Parameters<'a>, StepError> {
+        let entity = self
+            .file
+            .entities
+            .get(&id)
+            .ok_or(StepError::MissingEntity { id })?;
+        match entity.records.as_slice() {
+            [record] => Ok(Parameters { id, record }),
+            records => Err(StepError::UnsupportedEntity {
+                id,
+                name: records
+                    .iter()
+                    .map(|record| record.name.clone())
+                    .collect::<Vec<String>>()
+                    .join(" "),
+            }),
+        }
+    }
+
+    // Like `parameters`, but only accepts the given entity types.
+    fn expect(&self, id: usize, names: &[&str]) -> Result<Parameters<'a>, StepError> {
+        let parameters = self.parameters(id)?;
+        match names.contains(&parameters.name()) {
+            true => Ok(parameters),
+            false => Err(parameters.unsupported()),
+        }
+    }
+
+    fn point(&self, id: usize) -> Result<Point, StepError> {
+        let p = self.expect(id, &["CARTESIAN_POINT"])?;
+        match p.reals(1)?.as_slice() {
+            [x, y] => Ok(Point::from_f64(*x, *y, 0.0)),
+            [x, y, z] => Ok(Point::from_f64(*x, *y, *z)),
+            _ => Err(p.invalid("Expected two or three coordinates")),
+        }
+    }
+
+    fn direction(&self, id: usize) -> Result<Point, StepError> {
+        let p = self.expect(id, &["DIRECTION"])?;
+        let direction = match p.reals(1)?.as_slice() {
+            [x, y] => Point::from_f64(*x, *y, 0.0),
+            [x, y, z] => Point::from_f64(*x, *y, *z),
+            _ => return Err(p.invalid("Expected two or three coordinates")),
+        };
+        direction
+            .normalize()
+            .map_err(|_| p.invalid("Direction has no length"))
+    }
+
+    fn vector(&self, id: usize) -> Result<Point, StepError> {
+        let p = self.expect(id, &["VECTOR"])?;
+        Ok(self.direction(p.reference(1)?)? * EFloat64::from(p.real(2)?))
+    }
+
+    fn placement(&self, id: usize) -> Result<Placement, StepError> {
+        let p = self.expect(id, &["AXIS2_PLACEMENT_3D"])?;
+        let location = self.point(p.reference(1)?)?;
+        let axis = match p.optional_reference(2)? {
+            Some(id) => self.direction(id)?,
+            None => Point::unit_z(),
+        };
+        // The reference direction only has to be roughly perpendicular to the axis.
+        let mut candidates = vec![Point::unit_x(), Point::unit_y()];
+        if let Some(id) = p.optional_reference(3)? {
+            candidates.insert(0, self.direction(id)?);
+        }
+        for candidate in candidates {
+            if let Ok(ref_direction) = (candidate - axis * candidate.dot(axis)).normalize() {
+                return Ok(Placement {
+                    location,
+                    axis,
+                    ref_direction,
+                });
+            }
+        }
+        Err(p.invalid("Reference direction is parallel to the axis"))
+    }
+
+    fn positive(&self, p: &Parameters, i: usize) -> Result<EFloat64, StepError> {
+        let value = p.real(i)?;
+        match value > 0.0 {
+            true => Ok(EFloat64::from(value)),
+            false => Err(p.invalid(&format!("Parameter {} is not positive", i))),
+        }
+    }
+
+    fn surface(&mut self, id: usize) -> Result<Rc<Surface>, StepError> {
+        if let Some(surface) = self.surfaces.get(&id) {
+            return Ok(surface.clone());
+        }
+        let p = self.parameters(id)?;
+        let surface = match p.name() {
+            "PLANE" => {
+                let placement = self.placement(p.reference(1)?)?;
+                Surface::Plane(Plane::new(
+                    placement.location,
+                    placement.ref_direction,
+                    placement.y_direction(),
+                ))
+            }
+            "CYLINDRICAL_SURFACE" => {
+                let placement = self.placement(p.reference(1)?)?;
+                Surface::Cylinder(Cylinder::new(
+                    placement.location,
+                    placement.axis,
+                    self.positive(&p, 2)?,
+                    true,
+                ))
+            }
+            "SPHERICAL_SURFACE" => {
+                let placement = self.placement(p.reference(1)?)?;
+                Surface::Sphere(Sphere::new(placement.location, self.positive(&p, 2)?, true))
+            }
+            // The placement is where the cone has the given radius, while geop places cones at their apex.
+            "CONICAL_SURFACE" => {
+                let placement = self.placement(p.reference(1)?)?;
+                let radius = p.real(2)?;
+                if radius < 0.0 {
+                    return Err(p.invalid("Radius is negative"));
+                }
+                let half_angle = self.positive(&p, 3)?;
+                let apex = placement.location
+                    - placement.axis * EFloat64::from(radius / half_angle.to_f64().tan());
+                Cone::try_new(apex, placement.axis, half_angle, true)
+                    .map(Surface::Cone)
+                    .map_err(|_| p.invalid("Semi angle must be less than pi / 2"))?
+            }
+            "TOROIDAL_SURFACE" => {
+                let placement = self.placement(p.reference(1)?)?;
+                Torus::try_new(
+                    placement.location,
+                    placement.axis,
+                    self.positive(&p, 2)?,
+                    self.positive(&p, 3)?,
+                    true,
+                )
+                .map(Surface::Torus)
+                .map_err(|_| p.invalid("Major radius must be larger than the minor radius"))?
+            }
+            _ => return Err(p.unsupported()),
+        };
+        let surface = Rc::new(surface);
+        self.surfaces.insert(id, surface.clone());
+        Ok(surface)
+    }
+
+    fn curve(&self, id: usize) -> Result<Curve, StepError> {
+        let p = self.parameters(id)?;
+        match p.name() {
+            "LINE" => {
+                let basis = self.point(p.reference(1)?)?;
+                let direction = self.vector(p.reference(2)?)?;
+                Line::new(basis, direction)
+                    .map(Curve::Line)
+                    .map_err(|_| p.invalid("Line has no direction"))
+            }
+            "CIRCLE" => {
+                let placement = self.placement(p.reference(1)?)?;
+                Circle::try_new(placement.location, placement.axis, self.positive(&p, 2)?)
+                    .map(Curve::Circle)
+                    .map_err(|_| p.invalid("Circle is degenerate"))
+            }
+            "ELLIPSE" => {
+                let placement = self.placement(p.reference(1)?)?;
+                Ellipse::try_new(
+                    placement.location,
+                    placement.axis,
+                    placement.ref_direction * self.positive(&p, 2)?,
+                    placement.y_direction() * self.positive(&p, 3)?,
+                )
+                .map(Curve::Ellipse)
+                .map_err(|_| p.invalid("Ellipse is degenerate"))
+            }
+            // The 3D curve is the first representation, the curves in parameter space are not needed.
+            "SURFACE_CURVE" | "SEAM_CURVE" => self.curve(p.reference(1)?),
+            _ => Err(p.unsupported()),
+        }
+    }
+
+    fn vertex(&self, id: usize) -> Result<Point, StepError> {
+        let p = self.expect(id, &["VERTEX_POINT"])?;
+        self.point(p.reference(1)?)
+    }
+
+    fn edge_curve(&self, id: usize) -> Result<Edge, StepError> {
+        let p = self.expect(id, &["EDGE_CURVE"])?;
+        let curve = self.curve(p.reference(3)?)?;
+        let curve = match p.boolean(4)? {
+            true => curve,
+            false => curve.neg(),
+        };
+        let on_curve = |point: Point| -> Result<Point, StepError> {
+            if curve.on_curve(point) {
+                return Ok(point);
+            }
+            let projection = curve.project(point);
+            match (projection - point).norm() < VERTEX_TOLERANCE && curve.on_curve(projection) {
+                true => Ok(projection),
+                false => Err(p.invalid("Vertex is not on the curve")),
+            }
+        };
+        let start = on_curve(self.vertex(p.reference(1)?)?)?;
+        let end = on_curve(self.vertex(p.reference(2)?)?)?;
+        // geop represents edges around a whole closed curve without vertices.
+        if start == end {
+            return match curve {
+                Curve::Circle(_) | Curve::Ellipse(_) => Ok(Edge::new(None, None, curve)),
+                _ => Err(p.invalid("Edge starts where it ends")),
+            };
+        }
+        Ok(Edge::new(Some(start), Some(end), curve))
+    }
+
+    // The contours of an EDGE_LOOP or VERTEX_LOOP.
+    fn edge_loop(&self, id: usize) -> Result<Vec<Contour>, StepError> {
+        let p = self.parameters(id)?;
+        match p.name() {
+            // A loop around a single vertex, like the pole of a sphere, does not bound anything.
+            "VERTEX_LOOP" => return Ok(Vec::new()),
+            "EDGE_LOOP" => {}
+            _ => return Err(p.unsupported()),
+        }
+
+        let mut oriented_edges = Vec::<(usize, bool)>::new();
+        for oriented_edge in p.references(1)? {
+            let oe = self.expect(oriented_edge, &["ORIENTED_EDGE"])?;
+            oriented_edges.push((oe.reference(3)?, oe.boolean(4)?));
+        }
+
+        // Seams of periodic surfaces are used twice in opposite directions. geop does not need them.
+        let mut edges = Vec::<Edge>::new();
+        for (edge_id, orientation) in oriented_edges.iter() {
+            if oriented_edges.iter().filter(|(e, _)| e == edge_id).count() > 1 {
+                continue;
+            }
+            let edge = self.edge_curve(*edge_id)?;
+            edges.push(match orientation {
+                true => edge,
+                false => edge.flip(),
+            });
+        }
+
+        // Removing seams can split the loop into several closed contours.
+        let mut contours = Vec::<Contour>::new();
+        let mut current = Vec::<Edge>::new();
+        for edge in edges {
+            if let Some(last) = current.last() {
+                if last.end != edge.start {
+                    return Err(p.invalid("Edges are not connected"));
+                }
+            }
+            current.push(edge);
+            if current[0].start == current[current.len() - 1].end {
+                contours.push(Contour::new(current));
+                current = Vec::new();
+            }
+        }
+        match current.is_empty() {
+            true => Ok(contours),
+            false => Err(p.invalid("Edge loop is not closed")),
+        }
+    }
+
+    fn face(&mut self, id: usize) -> TopologyResult<Face> {
+        let p = self.expect(id, &["ADVANCED_FACE", "FACE_SURFACE"])?;
+        let mut outer = Vec::<Contour>::new();
+        let mut inner = Vec::<Contour>::new();
+        for bound in p.references(1)? {
+            let b = self.expect(bound, &["FACE_OUTER_BOUND", "FACE_BOUND"])?;
+            let orientation = b.boolean(2)?;
+            for contour in self.edge_loop(b.reference(1)?)? {
+                let contour = match orientation {
+                    true => contour,
+                    false => contour.flip(),
+                };
+                match b.name() {
+                    "FACE_OUTER_BOUND" => outer.push(contour),
+                    _ => inner.push(contour),
+                }
+            }
+        }
+        outer.extend(inner);
+
+        let surface = self.surface(p.reference(2)?)?;
+        let surface = match p.boolean(3)? {
+            true => surface,
+            false => Rc::new(surface.neg()),
+        };
+        let context = |err: TopologyError| err.with_context(format!("Read face #{}", id));
+        Face::try_new(outer, surface).with_context(&context)
+    }
+
+    fn shell(&mut self, id: usize) -> TopologyResult<Shell> {
+        let p = self.expect(id, &["CLOSED_SHELL", "ORIENTED_CLOSED_SHELL"])?;
+        if p.name() == "ORIENTED_CLOSED_SHELL" {
+            let shell = self.shell(p.reference(2)?)?;
+            return match p.boolean(3)? {
+                true => Ok(shell),
+                false => Ok(Shell::new(
+                    shell.faces().iter().map(|face| face.flip()).collect(),
+                )),
+            };
+        }
+        let face_ids = p.references(1)?;
+        if face_ids.is_empty() {
+            return Err(p.invalid("Shell has no faces").into());
+        }
+        let mut faces = Vec::<Face>::new();
+        for face in face_ids {
+            faces.push(self.face(face)?);
+        }
+        Ok(Shell::new(faces))
+    }
+
+    fn volume(&mut self, id: usize) -> TopologyResult<Volume> {
+        let p = self.expect(id, &["MANIFOLD_SOLID_BREP", "BREP_WITH_VOIDS"])?;
+        let context = |err: TopologyError| err.with_context(format!("Read solid #{}", id));
+        let boundary = self.shell(p.reference(1)?).with_context(&context)?;
+        let mut cavities = Vec::<Shell>::new();
+        if p.name() == "BREP_WITH_VOIDS" {
+            for void in p.references(2)? {
+                cavities.push(self.shell(void).with_context(&context)?);
+            }
+        }
+        Ok(Volume::new(boundary, cavities))
+    }
+}
+
+// Reads the solids of a STEP AP203 or AP214 file. Only analytic geometry is supported: planes, cylinders, spheres, cones and tori bounded by lines, circles and ellipses.
+// Files without solids are read as one volume per closed shell.
+pub fn read_step(text: &str) -> TopologyResult<Vec<Volume>> {
+    let file = parse_step(text)?;
+    let mut reader = StepReader::new(&file);
+
+    let ids_of = |names: &[&str]| {
+        file.entities
+            .values()
+            .filter(|entity| {
+                entity.records.len() == 1 && names.contains(&entity.records[0].name.as_str())
+            })
+            .map(|entity| entity.id)
+            .collect::<Vec<usize>>()
+    };
+    let solids = ids_of(&["MANIFOLD_SOLID_BREP", "BREP_WITH_VOIDS"]);
+    if !solids.is_empty() {
+        return solids.into_iter().map(|id| reader.volume(id)).collect();
+    }
+
+    let shells = ids_of(&["CLOSED_SHELL"]);
+    if shells.is_empty() {
+        return Err(TopologyError::new(
+            "The STEP file contains no solids".to_string(),
+        ));
+    }
+    shells
+        .into_iter()
+        .map(|id| Ok(Volume::new(reader.shell(id)?, Vec::new())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::topology_error::TopologyErrorRoot;
+
+    use super::*;
+
+    const CUBE: &str = "ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION((''),'2;1');
+FILE_NAME('','',(''),(''),'','','');
+FILE_SCHEMA(('AUTOMOTIVE_DESIGN'));
+ENDSEC;
+DATA;
+#1=CARTESIAN_POINT('',(0.,0.,0.));
+#2=VERTEX_POINT('',#1);
+#3=CARTESIAN_POINT('',(0.,0.,1.));
+#4=VERTEX_POINT('',#3);
+#5=CARTESIAN_POINT('',(0.,1.,0.));
+#6=VERTEX_POINT('',#5);
+#7=CARTESIAN_POINT('',(0.,1.,1.));
+#8=VERTEX_POINT('',#7);
+#9=CARTESIAN_POINT('',(1.,0.,0.));
+#10=VERTEX_POINT('',#9);
+#11=CARTESIAN_POINT('',(1.,0.,1.));
+#12=VERTEX_POINT('',#11);
+#13=CARTESIAN_POINT('',(1.,1.,0.));
+#14=VERTEX_POINT('',#13);
+#15=CARTESIAN_POINT('',(1.,1.,1.));
+#16=VERTEX_POINT('',#15);
+#17=CARTESIAN_POINT('',(0.,0.,0.));
+#18=DIRECTION('',(0.,1.,0.));
+#19=VECTOR('',#18,1.);
+#20=LINE('',#17,#19);
+#21=EDGE_CURVE('',#2,#6,#20,.T.);
+#22=ORIENTED_EDGE('',*,*,#21,.T.);
+#23=CARTESIAN_POINT('',(0.,1.,0.));
+#24=DIRECTION('',(1.,0.,0.));
+#25=VECTOR('',#24,1.);
+#26=LINE('',#23,#25);
+#27=EDGE_CURVE('',#6,#14,#26,.T.);
+#28=ORIENTED_EDGE('',*,*,#27,.T.);
+#29=CARTESIAN_POINT('',(1.,1.,0.));
+#30=DIRECTION('',(0.,-1.,0.));
+#31=VECTOR('',#30,1.);
+#32=LINE('',#29,#31);
+#33=EDGE_CURVE('',#14,#10,#32,.T.);
+#34=ORIENTED_EDGE('',*,*,#33,.T.);
+#35=CARTESIAN_POINT('',(1.,0.,0.));
+#36=DIRECTION('',(-1.,0.,0.));
+#37=VECTOR('',#36,1.);
+#38=LINE('',#35,#37);
+#39=EDGE_CURVE('',#10,#2,#38,.T.);
+#40=ORIENTED_EDGE('',*,*,#39,.T.);
+#41=EDGE_LOOP('',(#22,#28,#34,#40));
+#42=FACE_OUTER_BOUND('',#41,.T.);
+#43=CARTESIAN_POINT('',(0.,0.,0.));
+#44=DIRECTION('',(0.,0.,-1.));
+#45=DIRECTION('',(1.,0.,0.));
+#46=AXIS2_PLACEMENT_3D('',#43,#44,#45);
+#47=PLANE('',#46);
+#48=ADVANCED_FACE('',(#42),#47,.T.);
+#49=CARTESIAN_POINT('',(0.,0.,1.));
+#50=DIRECTION('',(1.,0.,0.));
+#51=VECTOR('',#50,1.);
+#52=LINE('',#49,#51);
+#53=EDGE_CURVE('',#4,#12,#52,.T.);
+#54=ORIENTED_EDGE('',*,*,#53,.T.);
+#55=CARTESIAN_POINT('',(1.,0.,1.));
+#56=DIRECTION('',(0.,1.,0.));
+#57=VECTOR('',#56,1.);
+#58=LINE('',#55,#57);
+#59=EDGE_CURVE('',#12,#16,#58,.T.);
+#60=ORIENTED_EDGE('',*,*,#59,.T.);
+#61=CARTESIAN_POINT('',(1.,1.,1.));
+#62=DIRECTION('',(-1.,0.,0.));
+#63=VECTOR('',#62,1.);
+#64=LINE('',#61,#63);
+#65=EDGE_CURVE('',#16,#8,#64,.T.);
+#66=ORIENTED_EDGE('',*,*,#65,.T.);
+#67=CARTESIAN_POINT('',(0.,1.,1.));
+#68=DIRECTION('',(0.,-1.,0.));
+#69=VECTOR('',#68,1.);
+#70=LINE('',#67,#69);
+#71=EDGE_CURVE('',#8,#4,#70,.T.);
+#72=ORIENTED_EDGE('',*,*,#71,.T.);
+#73=EDGE_LOOP('',(#54,#60,#66,#72));
+#74=FACE_OUTER_BOUND('',#73,.T.);
+#75=CARTESIAN_POINT('',(0.,0.,1.));
+#76=DIRECTION('',(0.,0.,1.));
+#77=DIRECTION('',(1.,0.,0.));
+#78=AXIS2_PLACEMENT_3D('',#75,#76,#77);
+#79=PLANE('',#78);
+#80=ADVANCED_FACE('',(#74),#79,.T.);
+#81=ORIENTED_EDGE('',*,*,#39,.F.);
+#82=CARTESIAN_POINT('',(1.,0.,0.));
+#83=DIRECTION('',(0.,0.,1.));
+#84=VECTOR('',#83,1.);
+#85=LINE('',#82,#84);
+#86=EDGE_CURVE('',#10,#12,#85,.T.);
+#87=ORIENTED_EDGE('',*,*,#86,.T.);
+#88=ORIENTED_EDGE('',*,*,#53,.F.);
+#89=CARTESIAN_POINT('',(0.,0.,1.));
+#90=DIRECTION('',(0.,0.,-1.));
+#91=VECTOR('',#90,1.);
+#92=LINE('',#89,#91);
+#93=EDGE_CURVE('',#4,#2,#92,.T.);
+#94=ORIENTED_EDGE('',*,*,#93,.T.);
+#95=EDGE_LOOP('',(#81,#87,#88,#94));
+#96=FACE_OUTER_BOUND('',#95,.T.);
+#97=CARTESIAN_POINT('',(0.,0.,0.));
+#98=DIRECTION('',(0.,-1.,0.));
+#99=DIRECTION('',(1.,0.,0.));
+#100=AXIS2_PLACEMENT_3D('',#97,#98,#99);
+#101=PLANE('',#100);
+#102=ADVANCED_FACE('',(#96),#101,.T.);
+#103=CARTESIAN_POINT('',(0.,1.,0.));
+#104=DIRECTION('',(0.,0.,1.));
+#105=VECTOR('',#104,1.);
+#106=LINE('',#103,#105);
+#107=EDGE_CURVE('',#6,#8,#106,.T.);
+#108=ORIENTED_EDGE('',*,*,#107,.T.);
+#109=ORIENTED_EDGE('',*,*,#65,.F.);
+#110=CARTESIAN_POINT('',(1.,1.,1.));
+#111=DIRECTION('',(0.,0.,-1.));
+#112=VECTOR('',#111,1.);
+#113=LINE('',#110,#112);
+#114=EDGE_CURVE('',#16,#14,#113,.T.);
+#115=ORIENTED_EDGE('',*,*,#114,.T.);
+#116=ORIENTED_EDGE('',*,*,#27,.F.);
+#117=EDGE_LOOP('',(#108,#109,#115,#116));
+#118=FACE_OUTER_BOUND('',#117,.T.);
+#119=CARTESIAN_POINT('',(0.,1.,0.));
+#120=DIRECTION('',(0.,1.,0.));
+#121=DIRECTION('',(1.,0.,0.));
+#122=AXIS2_PLACEMENT_3D('',#119,#120,#121);
+#123=PLANE('',#122);
+#124=ADVANCED_FACE('',(#118),#123,.T.);
+#125=ORIENTED_EDGE('',*,*,#93,.F.);
+#126=ORIENTED_EDGE('',*,*,#71,.F.);
+#127=ORIENTED_EDGE('',*,*,#107,.F.);
+#128=ORIENTED_EDGE('',*,*,#21,.F.);
+#129=EDGE_LOOP('',(#125,#126,#127,#128));
+#130=FACE_OUTER_BOUND('',#129,.T.);
+#131=CARTESIAN_POINT('',(0.,0.,0.));
+#132=DIRECTION('',(-1.,0.,0.));
+#133=DIRECTION('',(0.,1.,0.));
+#134=AXIS2_PLACEMENT_3D('',#131,#132,#133);
+#135=PLANE('',#134);
+#136=ADVANCED_FACE('',(#130),#135,.T.);
+#137=ORIENTED_EDGE('',*,*,#33,.F.);
+#138=ORIENTED_EDGE('',*,*,#114,.F.);
+#139=ORIENTED_EDGE('',*,*,#59,.F.);
+#140=ORIENTED_EDGE('',*,*,#86,.F.);
+#141=EDGE_LOOP('',(#137,#138,#139,#140));
+#142=FACE_OUTER_BOUND('',#141,.T.);
+#143=CARTESIAN_POINT('',(1.,0.,0.));
+#144=DIRECTION('',(1.,0.,0.));
+#145=DIRECTION('',(0.,1.,0.));
+#146=AXIS2_PLACEMENT_3D('',#143,#144,#145);
+#147=PLANE('',#146);
+#148=ADVANCED_FACE('',(#142),#147,.T.);
+#149=CLOSED_SHELL('',(#48,#80,#102,#124,#136,#148));
+#150=MANIFOLD_SOLID_BREP('Cube',#149);
+ENDSEC;
+END-ISO-10303-21;
+";
+
+    const CYLINDER: &str = "ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION((''),'2;1');
+FILE_NAME('','',(''),(''),'','','');
+FILE_SCHEMA(('AUTOMOTIVE_DESIGN'));
+ENDSEC;
+DATA;
+#1=CARTESIAN_POINT('',(1.,0.,0.));
+#2=VERTEX_POINT('',#1);
+#3=CARTESIAN_POINT('',(1.,0.,2.));
+#4=VERTEX_POINT('',#3);
+#5=CARTESIAN_POINT('',(0.,0.,0.));
+#6=DIRECTION('',(0.,0.,1.));
+#7=DIRECTION('',(1.,0.,0.));
+#8=AXIS2_PLACEMENT_3D('',#5,#6,#7);
+#9=CIRCLE('',#8,1.);
+#10=CARTESIAN_POINT('',(0.,0.,2.));
+#11=DIRECTION('',(0.,0.,1.));
+#12=DIRECTION('',(1.,0.,0.));
+#13=AXIS2_PLACEMENT_3D('',#10,#11,#12);
+#14=CIRCLE('',#13,1.);
+#15=EDGE_CURVE('',#2,#2,#9,.T.);
+#16=EDGE_CURVE('',#4,#4,#14,.T.);
+#17=CARTESIAN_POINT('',(1.,0.,0.));
+#18=DIRECTION('',(0.,0.,1.));
+#19=VECTOR('',#18,1.);
+#20=LINE('',#17,#19);
+#21=EDGE_CURVE('',#2,#4,#20,.T.);
+#22=ORIENTED_EDGE('',*,*,#15,.F.);
+#23=EDGE_LOOP('',(#22));
+#24=FACE_OUTER_BOUND('',#23,.T.);
+#25=CARTESIAN_POINT('',(0.,0.,0.));
+#26=DIRECTION('',(0.,0.,-1.));
+#27=DIRECTION('',(1.,0.,0.));
+#28=AXIS2_PLACEMENT_3D('',#25,#26,#27);
+#29=PLANE('',#28);
+#30=ADVANCED_FACE('',(#24),#29,.T.);
+#31=ORIENTED_EDGE('',*,*,#16,.T.);
+#32=EDGE_LOOP('',(#31));
+#33=FACE_OUTER_BOUND('',#32,.T.);
+#34=CARTESIAN_POINT('',(0.,0.,2.));
+#35=DIRECTION('',(0.,0.,1.));
+#36=DIRECTION('',(1.,0.,0.));
+#37=AXIS2_PLACEMENT_3D('',#34,#35,#36);
+#38=PLANE('',#37);
+#39=ADVANCED_FACE('',(#33),#38,.T.);
+#40=ORIENTED_EDGE('',*,*,#15,.T.);
+#41=ORIENTED_EDGE('',*,*,#21,.T.);
+#42=ORIENTED_EDGE('',*,*,#16,.F.);
+#43=ORIENTED_EDGE('',*,*,#21,.F.);
+#44=EDGE_LOOP('',(#40,#41,#42,#43));
+#45=FACE_OUTER_BOUND('',#44,.T.);
+#46=CARTESIAN_POINT('',(0.,0.,0.));
+#47=DIRECTION('',(0.,0.,1.));
+#48=DIRECTION('',(1.,0.,0.));
+#49=AXIS2_PLACEMENT_3D('',#46,#47,#48);
+#50=CYLINDRICAL_SURFACE('',#49,1.);
+#51=ADVANCED_FACE('',(#45),#50,.T.);
+#52=CLOSED_SHELL('',(#30,#39,#51));
+#53=MANIFOLD_SOLID_BREP('Cylinder',#52);
+ENDSEC;
+END-ISO-10303-21;
+";
+
+    // A file without a solid, where the sphere is bounded by a loop around its pole.
+    const SPHERE: &str = "ISO-10303-21;
+HEADER;
+FILE_SCHEMA(('CONFIG_CONTROL_DESIGN'));
+ENDSEC;
+DATA;
+#1=CARTESIAN_POINT('',(0.,0.,0.));
+#2=AXIS2_PLACEMENT_3D('',#1,$,$);
+#3=SPHERICAL_SURFACE('',#2,2.);
+#4=CARTESIAN_POINT('',(0.,0.,2.));
+#5=VERTEX_POINT('',#4);
+#6=VERTEX_LOOP('',#5);
+#7=FACE_BOUND('',#6,.T.);
+#8=ADVANCED_FACE('',(#7),#3,.T.);
+#9=CLOSED_SHELL('',(#8));
+ENDSEC;
+END-ISO-10303-21;
+";
+
+    fn step_error(text: &str) -> StepError {
+        match read_step(text).unwrap_err().root() {
+            TopologyErrorRoot::FromStepError { step_error } => step_error.clone(),
+            _ => panic!("Expected a STEP error"),
+        }
+    }
+
+    #[test]
+    fn test_cube() {
+        let volumes = read_step(CUBE).unwrap();
+        assert_eq!(volumes.len(), 1);
+        assert_eq!(volumes[0].boundary().faces().len(), 6);
+        let properties = volumes[0].mass_properties(EFloat64::one()).unwrap();
+        assert!((properties.volume.to_f64() - 1.0).abs() < 1e-6);
+        assert!((properties.center_of_mass.z.to_f64() - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_cylinder() {
+        let volumes = read_step(CYLINDER).unwrap();
+        // The seam is removed, so the lateral face is bounded by the two circles
+        let lateral = &volumes[0].boundary().faces()[2];
+        assert_eq!(lateral.boundaries().len(), 2);
+        assert!(lateral.boundaries()[0].edges[0].start.is_none());
+        let properties = volumes[0].mass_properties(EFloat64::one()).unwrap();
+        assert!((properties.volume.to_f64() - 2.0 * PI).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sphere() {
+        let volumes = read_step(SPHERE).unwrap();
+        assert_eq!(volumes.len(), 1);
+        assert!(volumes[0].boundary().faces()[0].boundaries().is_empty());
+        let properties = volumes[0].mass_properties(EFloat64::one()).unwrap();
+        assert!((properties.volume.to_f64() - 32.0 / 3.0 * PI).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_errors() {
+        let text = SPHERE.replace(
+            "#3=SPHERICAL_SURFACE('',#2,2.);",
+            "#3=SURFACE_OF_REVOLUTION('',#2,#2);",
+        );
+        assert_eq!(
+            step_error(&text),
+            StepError::UnsupportedEntity {
+                id: 3,
+                name: "SURFACE_OF_REVOLUTION".to_string()
+            }
+        );
+
+        let text = SPHERE.replace("(#8)", "(#8,#10)");
+        assert_eq!(step_error(&text), StepError::MissingEntity { id: 10 });
+
+        // A vertex that is not on its curve
+        let text = CUBE.replace(
+            "#1=CARTESIAN_POINT('',(0.,0.,0.));",
+            "#1=CARTESIAN_POINT('',(0.,0.,0.1));",
+        );
+        assert!(matches!(step_error(&text), StepError::InvalidEntity { .. }));
+    }
+}
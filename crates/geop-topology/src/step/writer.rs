@@ -0,0 +1,720 @@
+use std::{collections::HashMap, rc::Rc};
+
+use geop_geometry::{
+    curves::{circle::Circle, curve::Curve, line::Line, CurveLike},
+    efloat::EFloat64,
+    point::Point,
+    surfaces::surface::Surface,
+};
+
+use crate::{
+    contains::face_point::{face_point_contains, FacePointContains},
+    topology::{edge::Edge, face::Face, shell::Shell, volume::Volume},
+    topology_error::{TopologyError, TopologyResult, WithContext},
+};
+
+// Formats a real with a decimal point, as ISO 10303-21 requires, like 1. or 2.5E-7.
+fn real(x: f64) -> String {
+    let s = format!("{:?}", x);
+    match s.split_once('e') {
+        Some((mantissa, exponent)) if mantissa.contains('.') => {
+            format!("{}E{}", mantissa, exponent)
+        }
+        Some((mantissa, exponent)) => format!("{}.E{}", mantissa, exponent),
+        None => match s.strip_suffix(".0") {
+            Some(integer) => format!("{}.", integer),
+            None => s,
+        },
+    }
+}
+
+// The value with the fewest decimals within the bounds, so that rounding errors like 1.0000000000000002 do not end up in the file.
+fn shortest(value: EFloat64) -> f64 {
+    for decimals in 0..16 {
+        let scale = 10f64.powi(decimals);
+        let rounded = (value.to_f64() * scale).round() / scale;
+        if value.lower_bound <= rounded && rounded <= value.upper_bound {
+            return rounded;
+        }
+    }
+    value.to_f64()
+}
+
+fn string(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+fn boolean(b: bool) -> &'static str {
+    match b {
+        true => ".T.",
+        false => ".F.",
+    }
+}
+
+fn references(ids: &[usize]) -> String {
+    let ids = ids
+        .iter()
+        .map(|id| format!("#{}", id))
+        .collect::<Vec<String>>();
+    format!("({})", ids.join(","))
+}
+
+// Some unit vector perpendicular to the axis, for placements where the reference direction does not matter.
+fn perpendicular(axis: Point) -> Point {
+    [Point::unit_x(), Point::unit_y()]
+        .into_iter()
+        .find_map(|candidate| (candidate - axis * candidate.dot(axis)).normalize().ok())
+        .expect("One of the unit vectors is not parallel to the axis")
+}
+
+// The vertex of a closed edge. Circles start in the direction perpendicular to their normal, which does not depend
+// on their orientation, so that coaxial circles start on a common line.
+fn closed_start(curve: &Curve) -> TopologyResult<Point> {
+    match curve {
+        Curve::Circle(circle) => {
+            Ok(circle.basis + perpendicular(circle.normal) * circle.radius.norm())
+        }
+        Curve::Ellipse(ellipse) => Ok(ellipse.basis + ellipse.major_radius),
+        _ => Err(TopologyError::new(
+            "Only circles and ellipses can be closed edges".to_string(),
+        )),
+    }
+}
+
+// The seam that cuts a face between two closed edges on a periodic surface open, from the start of the first to the
+// start of the second edge. It is None if the face is not periodic or the edges do not start on a common seam.
+fn seam(face: &Face, first: &Curve, second: &Curve) -> TopologyResult<Option<Edge>> {
+    let (from, to) = (closed_start(first)?, closed_start(second)?);
+    // A meridian through the start of the first edge, around the center in the plane of the axis
+    let meridian = |center: Point, axis: Point| -> Vec<Curve> {
+        let normal = match axis.cross(from - center).normalize() {
+            Ok(normal) => normal,
+            Err(_) => return vec![],
+        };
+        match Circle::try_new(center, normal, (from - center).norm()) {
+            Ok(circle) => vec![Curve::Circle(circle.clone()), Curve::Circle(circle).neg()],
+            Err(_) => vec![],
+        }
+    };
+    let curves = match (&**face.surface(), first) {
+        (Surface::Cylinder(_) | Surface::Cone(_), _) => (to - from)
+            .normalize()
+            .ok()
+            .and_then(|direction| Line::new(from, direction).ok())
+            .map(|line| vec![Curve::Line(line)])
+            .unwrap_or_default(),
+        (Surface::Sphere(sphere), Curve::Circle(circle)) => meridian(sphere.basis, circle.normal),
+        (Surface::Torus(torus), _) => {
+            let outwards = from - torus.basis;
+            match (outwards - torus.axis * outwards.dot(torus.axis)).normalize() {
+                Ok(d) => meridian(torus.basis + d * torus.major_radius, torus.axis),
+                Err(_) => vec![],
+            }
+        }
+        _ => vec![],
+    };
+    // Circles run both ways between the points, the seam is the one that runs through the face
+    for curve in curves {
+        if from == to || !curve.on_curve(from) || !curve.on_curve(to) {
+            continue;
+        }
+        let edge = Edge::new(Some(from), Some(to), curve);
+        if face_point_contains(face, edge.get_midpoint()) == FacePointContains::Inside {
+            return Ok(Some(edge));
+        }
+    }
+    Ok(None)
+}
+
+// An edge that is already written, with the id of its EDGE_CURVE.
+struct WrittenEdge {
+    edge: Edge,
+    id: usize,
+}
+
+struct StepWriter {
+    entities: Vec<String>,
+    vertices: Vec<(Point, usize)>,
+    edges: Vec<WrittenEdge>,
+    // Faces that share a surface share it in the file as well.
+    surfaces: HashMap<*const Surface, (usize, bool)>,
+}
+
+impl StepWriter {
+    fn new() -> Self {
+        StepWriter {
+            entities: Vec::new(),
+            vertices: Vec::new(),
+            edges: Vec::new(),
+            surfaces: HashMap::new(),
+        }
+    }
+
+    // Adds an entity and returns its id.
+    fn add(&mut self, entity: String) -> usize {
+        self.entities.push(entity);
+        self.entities.len()
+    }
+
+    fn point(&mut self, p: Point) -> usize {
+        self.add(format!(
+            "CARTESIAN_POINT('',({},{},{}))",
+            real(shortest(p.x)),
+            real(shortest(p.y)),
+            real(shortest(p.z))
+        ))
+    }
+
+    fn direction(&mut self, d: Point) -> usize {
+        self.add(format!(
+            "DIRECTION('',({},{},{}))",
+            real(shortest(d.x)),
+            real(shortest(d.y)),
+            real(shortest(d.z))
+        ))
+    }
+
+    fn placement(&mut self, location: Point, axis: Point, ref_direction: Point) -> usize {
+        let location = self.point(location);
+        let axis = self.direction(axis);
+        let ref_direction = self.direction(ref_direction);
+        self.add(format!(
+            "AXIS2_PLACEMENT_3D('',#{},#{},#{})",
+            location, axis, ref_direction
+        ))
+    }
+
+    fn vertex(&mut self, p: Point) -> usize {
+        if let Some((_, id)) = self.vertices.iter().find(|(q, _)| *q == p) {
+            return *id;
+        }
+        let point = self.point(p);
+        let id = self.add(format!("VERTEX_POINT('',#{})", point));
+        self.vertices.push((p, id));
+        id
+    }
+
+    fn curve(&mut self, curve: &Curve) -> TopologyResult<usize> {
+        match curve {
+            Curve::Line(line) => {
+                let basis = self.point(line.basis);
+                let direction = self.direction(line.direction);
+                let vector = self.add(format!("VECTOR('',#{},1.)", direction));
+                Ok(self.add(format!("LINE('',#{},#{})", basis, vector)))
+            }
+            Curve::Circle(circle) => {
+                let ref_direction = circle.radius.normalize()?;
+                let placement = self.placement(circle.basis, circle.normal, ref_direction);
+                Ok(self.add(format!(
+                    "CIRCLE('',#{},{})",
+                    placement,
+                    real(shortest(circle.radius.norm()))
+                )))
+            }
+            Curve::Ellipse(ellipse) => {
+                // STEP ellipses run from the first to the second semi axis
+                let axis = ellipse
+                    .major_radius
+                    .cross(ellipse.minor_radius)
+                    .normalize()?;
+                let ref_direction = ellipse.major_radius.normalize()?;
+                let placement = self.placement(ellipse.basis, axis, ref_direction);
+                Ok(self.add(format!(
+                    "ELLIPSE('',#{},{},{})",
+                    placement,
+                    real(shortest(ellipse.major_radius.norm())),
+                    real(shortest(ellipse.minor_radius.norm()))
+                )))
+            }
+            _ => Err(TopologyError::new(format!(
+                "STEP export does not support the curve {:?}",
+                curve
+            ))),
+        }
+    }
+
+    // Returns the id of the EDGE_CURVE and whether the edge runs along it.
+    // An edge that is shared by two faces is written once, as each face runs along it in the opposite direction.
+    fn edge(&mut self, edge: &Edge) -> TopologyResult<(usize, bool)> {
+        for written in self.edges.iter() {
+            if written.edge.start == edge.start
+                && written.edge.end == edge.end
+                && written.edge.curve == edge.curve
+            {
+                return Ok((written.id, true));
+            }
+            if written.edge.start == edge.end
+                && written.edge.end == edge.start
+                && written.edge.curve == edge.curve.neg()
+            {
+                return Ok((written.id, false));
+            }
+        }
+
+        // STEP edges always have vertices, so closed edges start and end at some point of the curve.
+        let (start, end) = match (edge.start, edge.end) {
+            (Some(start), Some(end)) => (start, end),
+            (None, None) => {
+                let p = closed_start(&edge.curve)?;
+                (p, p)
+            }
+            _ => {
+                return Err(TopologyError::new(
+                    "Edge has only one end point".to_string(),
+                ))
+            }
+        };
+        let id = self.edge_curve(start, end, &edge.curve)?;
+        self.edges.push(WrittenEdge {
+            edge: edge.clone(),
+            id,
+        });
+        Ok((id, true))
+    }
+
+    fn edge_curve(&mut self, start: Point, end: Point, curve: &Curve) -> TopologyResult<usize> {
+        let start = self.vertex(start);
+        let end = self.vertex(end);
+        let curve = self.curve(curve)?;
+        Ok(self.add(format!("EDGE_CURVE('',#{},#{},#{},.T.)", start, end, curve)))
+    }
+
+    fn edge_loop(&mut self, oriented_edges: &[(usize, bool)]) -> usize {
+        let oriented_edges = oriented_edges
+            .iter()
+            .map(|(id, orientation)| {
+                self.add(format!(
+                    "ORIENTED_EDGE('',*,*,#{},{})",
+                    id,
+                    boolean(*orientation)
+                ))
+            })
+            .collect::<Vec<usize>>();
+        self.add(format!("EDGE_LOOP('',{})", references(&oriented_edges)))
+    }
+
+    // Returns the id of the surface and whether its normal is the normal of geop's surface.
+    fn surface(&mut self, surface: &Rc<Surface>) -> TopologyResult<(usize, bool)> {
+        if let Some(written) = self.surfaces.get(&Rc::as_ptr(surface)) {
+            return Ok(*written);
+        }
+        let written = match &**surface {
+            Surface::Plane(plane) => {
+                let axis = plane.u_slope.cross(plane.v_slope).normalize()?;
+                let ref_direction = plane.u_slope.normalize()?;
+                let placement = self.placement(plane.basis, axis, ref_direction);
+                (self.add(format!("PLANE('',#{})", placement)), true)
+            }
+            Surface::Cylinder(cylinder) => {
+                let ref_direction = cylinder.radius.normalize()?;
+                let placement = self.placement(cylinder.basis, cylinder.extend_dir, ref_direction);
+                let id = self.add(format!(
+                    "CYLINDRICAL_SURFACE('',#{},{})",
+                    placement,
+                    real(shortest(cylinder.radius.norm()))
+                ));
+                (id, cylinder.normal_outwards)
+            }
+            Surface::Sphere(sphere) => {
+                let placement = self.placement(sphere.basis, Point::unit_z(), Point::unit_x());
+                let id = self.add(format!(
+                    "SPHERICAL_SURFACE('',#{},{})",
+                    placement,
+                    real(shortest(sphere.radius))
+                ));
+                (id, sphere.normal_outwards)
+            }
+            Surface::Cone(cone) => {
+                // The cone opens along its axis, so it has no radius at the apex
+                let placement = self.placement(cone.apex, cone.axis, perpendicular(cone.axis));
+                let id = self.add(format!(
+                    "CONICAL_SURFACE('',#{},0.,{})",
+                    placement,
+                    real(shortest(cone.half_angle))
+                ));
+                (id, cone.normal_outwards)
+            }
+            Surface::Torus(torus) => {
+                let placement = self.placement(torus.basis, torus.axis, perpendicular(torus.axis));
+                let id = self.add(format!(
+                    "TOROIDAL_SURFACE('',#{},{},{})",
+                    placement,
+                    real(shortest(torus.major_radius)),
+                    real(shortest(torus.minor_radius))
+                ));
+                (id, torus.normal_outwards)
+            }
+            Surface::Nurbs(_) => {
+                return Err(TopologyError::new(
+                    "STEP export does not support NURBS surfaces".to_string(),
+                ))
+            }
+        };
+        self.surfaces.insert(Rc::as_ptr(surface), written);
+        Ok(written)
+    }
+
+    fn face(&mut self, face: &Face) -> TopologyResult<usize> {
+        let context = |err: TopologyError| err.with_context("Write a face".to_string());
+        let mut loops = face
+            .boundaries()
+            .iter()
+            .map(|contour| contour.edges.clone())
+            .collect::<Vec<Vec<Edge>>>();
+        // A face between two closed edges on a periodic surface is one loop that runs along the seam in both directions.
+        if let [first, second] = face.boundaries() {
+            if let ([a], [b]) = (first.edges.as_slice(), second.edges.as_slice()) {
+                if a.start.is_none() && b.start.is_none() {
+                    if let Some(seam) = seam(face, &a.curve, &b.curve).with_context(&context)? {
+                        loops = vec![vec![a.clone(), seam.clone(), b.clone(), seam.flip()]];
+                    }
+                }
+            }
+        }
+
+        let mut edge_loops = Vec::<usize>::new();
+        for edges in loops.iter() {
+            let mut oriented_edges = Vec::<(usize, bool)>::new();
+            for edge in edges.iter() {
+                oriented_edges.push(self.edge(edge).with_context(&context)?);
+            }
+            edge_loops.push(self.edge_loop(&oriented_edges));
+        }
+
+        // Closed surfaces without boundaries need a bound. It is a single vertex for spheres, and a meridian and a
+        // circle around the axis that both run along themselves in both directions for tori.
+        if face.boundaries().is_empty() {
+            match &**face.surface() {
+                Surface::Sphere(sphere) => {
+                    let vertex = self.vertex(sphere.basis + Point::unit_z() * sphere.radius);
+                    edge_loops.push(self.add(format!("VERTEX_LOOP('',#{})", vertex)));
+                }
+                Surface::Torus(torus) => {
+                    let d = perpendicular(torus.axis);
+                    let p = torus.basis + d * (torus.major_radius + torus.minor_radius);
+                    let around_axis = Circle::try_new(
+                        torus.basis,
+                        torus.axis,
+                        torus.major_radius + torus.minor_radius,
+                    )?;
+                    let around_tube = Circle::try_new(
+                        torus.basis + d * torus.major_radius,
+                        torus.axis.cross(d),
+                        torus.minor_radius,
+                    )?;
+                    let around_axis = self.edge_curve(p, p, &Curve::Circle(around_axis))?;
+                    let around_tube = self.edge_curve(p, p, &Curve::Circle(around_tube))?;
+                    edge_loops.push(self.edge_loop(&[
+                        (around_tube, true),
+                        (around_axis, true),
+                        (around_tube, false),
+                        (around_axis, false),
+                    ]));
+                }
+                _ => {
+                    return Err(TopologyError::new(
+                        "STEP export only supports spheres and tori as faces without boundaries"
+                            .to_string(),
+                    ))
+                    .with_context(&context)
+                }
+            }
+        }
+
+        // The first contour of a face is its outer boundary
+        let bounds = edge_loops
+            .iter()
+            .enumerate()
+            .map(|(i, edge_loop)| {
+                let bound = match i {
+                    0 => "FACE_OUTER_BOUND",
+                    _ => "FACE_BOUND",
+                };
+                self.add(format!("{}('',#{},.T.)", bound, edge_loop))
+            })
+            .collect::<Vec<usize>>();
+
+        let (surface, same_sense) = self.surface(face.surface()).with_context(&context)?;
+        Ok(self.add(format!(
+            "ADVANCED_FACE('',{},#{},{})",
+            references(&bounds),
+            surface,
+            boolean(same_sense)
+        )))
+    }
+
+    fn shell(&mut self, shell: &Shell) -> TopologyResult<usize> {
+        let mut faces = Vec::<usize>::new();
+        for face in shell.faces().iter() {
+            faces.push(self.face(face)?);
+        }
+        Ok(self.add(format!("CLOSED_SHELL('',{})", references(&faces))))
+    }
+
+    fn volume(&mut self, volume: &Volume, name: &str) -> TopologyResult<usize> {
+        let boundary = self.shell(volume.boundary())?;
+        if volume.cavities().is_empty() {
+            return Ok(self.add(format!(
+                "MANIFOLD_SOLID_BREP({},#{})",
+                string(name),
+                boundary
+            )));
+        }
+        // Voids are closed shells around the empty space, which are used in the opposite orientation.
+        let mut voids = Vec::<usize>::new();
+        for cavity in volume.cavities().iter() {
+            let shell = Shell::new(cavity.faces().iter().map(|face| face.flip()).collect());
+            let shell = self.shell(&shell)?;
+            voids.push(self.add(format!("ORIENTED_CLOSED_SHELL('',*,#{},.F.)", shell)));
+        }
+        Ok(self.add(format!(
+            "BREP_WITH_VOIDS({},#{},{})",
+            string(name),
+            boundary,
+            references(&voids)
+        )))
+    }
+}
+
+// Writes the volumes as the solids of one part in a STEP AP214 file, in millimeters.
+// Vertices and edges that are shared by several faces are written once. NURBS and helices are not supported yet.
+pub fn write_step(volumes: &[Volume], name: &str) -> TopologyResult<String> {
+    let mut writer = StepWriter::new();
+
+    let application = writer.add("APPLICATION_CONTEXT('automotive design')".to_string());
+    writer.add(format!(
+        "APPLICATION_PROTOCOL_DEFINITION('international standard','automotive_design',2000,#{})",
+        application
+    ));
+    let product_context = writer.add(format!("PRODUCT_CONTEXT('',#{},'mechanical')", application));
+    let product = writer.add(format!(
+        "PRODUCT({},{},'',(#{}))",
+        string(name),
+        string(name),
+        product_context
+    ));
+    writer.add(format!(
+        "PRODUCT_RELATED_PRODUCT_CATEGORY('part',$,(#{}))",
+        product
+    ));
+    let formation = writer.add(format!("PRODUCT_DEFINITION_FORMATION('','',#{})", product));
+    let definition_context = writer.add(format!(
+        "PRODUCT_DEFINITION_CONTEXT('part definition',#{},'design')",
+        application
+    ));
+    let definition = writer.add(format!(
+        "PRODUCT_DEFINITION('design','',#{},#{})",
+        formation, definition_context
+    ));
+    let shape = writer.add(format!("PRODUCT_DEFINITION_SHAPE('','',#{})", definition));
+
+    let length = writer.add("(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.))".to_string());
+    let angle = writer.add("(NAMED_UNIT(*)PLANE_ANGLE_UNIT()SI_UNIT($,.RADIAN.))".to_string());
+    let solid_angle =
+        writer.add("(NAMED_UNIT(*)SI_UNIT($,.STERADIAN.)SOLID_ANGLE_UNIT())".to_string());
+    let uncertainty = writer.add(format!(
+        "UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE(1.E-07),#{},'distance_accuracy_value','confusion accuracy')",
+        length
+    ));
+    let representation_context = writer.add(format!(
+        "(GEOMETRIC_REPRESENTATION_CONTEXT(3)GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT((#{}))GLOBAL_UNIT_ASSIGNED_CONTEXT((#{},#{},#{}))REPRESENTATION_CONTEXT('',''))",
+        uncertainty, length, angle, solid_angle
+    ));
+
+    let mut items = vec![writer.placement(Point::zero(), Point::unit_z(), Point::unit_x())];
+    for (i, volume) in volumes.iter().enumerate() {
+        let context = |err: TopologyError| err.with_context(format!("Write volume {}", i));
+        items.push(writer.volume(volume, name).with_context(&context)?);
+    }
+    let representation = writer.add(format!(
+        "ADVANCED_BREP_SHAPE_REPRESENTATION({},{},#{})",
+        string(name),
+        references(&items),
+        representation_context
+    ));
+    writer.add(format!(
+        "SHAPE_DEFINITION_REPRESENTATION(#{},#{})",
+        shape, representation
+    ));
+
+    let mut text = String::new();
+    text += "ISO-10303-21;\nHEADER;\n";
+    text += "FILE_DESCRIPTION((''),'2;1');\n";
+    text += &format!(
+        "FILE_NAME({},'',(''),(''),'geop','geop','');\n",
+        string(name)
+    );
+    text += "FILE_SCHEMA(('AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }'));\n";
+    text += "ENDSEC;\nDATA;\n";
+    for (i, entity) in writer.entities.iter().enumerate() {
+        text += &format!("#{}={};\n", i + 1, entity);
+    }
+    text += "ENDSEC;\nEND-ISO-10303-21;\n";
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::surfaces::plane::Plane;
+
+    use crate::{
+        operations::{extrude::extrude, hollow::hollow, revolve::revolve},
+        primitive_objects::{
+            edges::{circle::primitive_circle, line::primitive_line},
+            faces::{sphere::primitive_sphere, torus::primitive_torus},
+            volumes::cube::primitive_cube,
+        },
+        step::{parser::parse_step, reader::read_step},
+        topology::contour::Contour,
+    };
+
+    use super::*;
+
+    fn count(text: &str, name: &str) -> usize {
+        parse_step(text)
+            .unwrap()
+            .entities
+            .values()
+            .filter(|entity| entity.records[0].name == name)
+            .count()
+    }
+
+    fn volume(volume: &Volume) -> f64 {
+        volume
+            .mass_properties(EFloat64::one())
+            .unwrap()
+            .volume
+            .to_f64()
+    }
+
+    #[test]
+    fn test_real() {
+        assert_eq!(real(1.0), "1.");
+        assert_eq!(real(10.0), "10.");
+        assert_eq!(real(-0.25), "-0.25");
+        assert_eq!(real(1e-7), "1.E-7");
+        assert_eq!(real(1.5e20), "1.5E20");
+    }
+
+    #[test]
+    fn test_cube() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let text = write_step(std::slice::from_ref(&cube), "It's a cube").unwrap();
+        assert!(text.contains("PRODUCT('It''s a cube'"));
+
+        // Shared vertices and edges are written once
+        assert_eq!(count(&text, "VERTEX_POINT"), 8);
+        assert_eq!(count(&text, "EDGE_CURVE"), 12);
+        assert_eq!(count(&text, "ORIENTED_EDGE"), 24);
+        assert_eq!(count(&text, "ADVANCED_FACE"), 6);
+
+        let volumes = read_step(&text).unwrap();
+        assert_eq!(volumes.len(), 1);
+        assert!((volume(&volumes[0]) - volume(&cube)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_hollow_cube() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let hollow = hollow(&cube, EFloat64::from(0.5), &[]).unwrap();
+        let text = write_step(&[hollow], "Hollow cube").unwrap();
+        assert_eq!(count(&text, "BREP_WITH_VOIDS"), 1);
+
+        let volumes = read_step(&text).unwrap();
+        assert_eq!(volumes[0].cavities().len(), 1);
+        assert!((volume(&volumes[0]) - 7.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_cylinder() {
+        let circle = primitive_circle(Point::zero(), -Point::unit_z(), EFloat64::one());
+        let face = Face::new(
+            vec![Contour::new(vec![circle])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let cylinder = extrude(face, Point::unit_z() * EFloat64::two());
+        let text = write_step(std::slice::from_ref(&cylinder), "Cylinder").unwrap();
+        assert_eq!(count(&text, "CYLINDRICAL_SURFACE"), 1);
+        // The two circles and the seam of the cylinder
+        assert_eq!(count(&text, "EDGE_CURVE"), 3);
+        assert_eq!(count(&text, "FACE_OUTER_BOUND"), 3);
+        assert_eq!(count(&text, "FACE_BOUND"), 0);
+
+        let volumes = read_step(&text).unwrap();
+        assert!((volume(&volumes[0]) - volume(&cylinder)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sphere() {
+        let sphere = Volume::new(
+            Shell::new(vec![primitive_sphere(Point::zero(), EFloat64::one())]),
+            vec![],
+        );
+        let text = write_step(std::slice::from_ref(&sphere), "Sphere").unwrap();
+        assert_eq!(count(&text, "VERTEX_LOOP"), 1);
+
+        let volumes = read_step(&text).unwrap();
+        assert!((volume(&volumes[0]) - volume(&sphere)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_cone() {
+        // A frustum, whose conical face is bounded by two circles
+        let points = [
+            Point::from_f64(0.0, 0.0, 0.0),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(1.0, 0.0, 1.0),
+            Point::from_f64(0.0, 0.0, 1.0),
+        ];
+        let face = Face::new(
+            vec![Contour::new(
+                (0..4)
+                    .map(|i| primitive_line(points[i], points[(i + 1) % 4]).unwrap())
+                    .collect(),
+            )],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_x(),
+                Point::unit_z(),
+            ))),
+        );
+        let frustum = revolve(face, Point::zero(), Point::unit_z(), EFloat64::two_pi()).unwrap();
+        let text = write_step(std::slice::from_ref(&frustum), "Frustum").unwrap();
+        assert_eq!(count(&text, "CONICAL_SURFACE"), 1);
+        assert_eq!(count(&text, "LINE"), 1);
+
+        let volumes = read_step(&text).unwrap();
+        assert!(volumes[0]
+            .all_faces()
+            .iter()
+            .any(|face| matches!(**face.surface(), Surface::Cone(_))));
+        assert!((volume(&volumes[0]) - 7.0 * std::f64::consts::PI / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_torus() {
+        let torus = Volume::new(
+            Shell::new(vec![primitive_torus(
+                Point::zero(),
+                Point::unit_z(),
+                EFloat64::from(3.0),
+                EFloat64::one(),
+            )]),
+            vec![],
+        );
+        let text = write_step(std::slice::from_ref(&torus), "Torus").unwrap();
+        assert_eq!(count(&text, "TOROIDAL_SURFACE"), 1);
+        assert_eq!(count(&text, "EDGE_CURVE"), 2);
+
+        let volumes = read_step(&text).unwrap();
+        assert!(matches!(
+            **volumes[0].boundary().faces()[0].surface(),
+            Surface::Torus(_)
+        ));
+        assert!((volume(&volumes[0]) - volume(&torus)).abs() < 1e-6);
+    }
+}
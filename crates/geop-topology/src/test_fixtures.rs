@@ -0,0 +1,38 @@
+use geop_geometry::{efloat::EFloat64, point::Point};
+
+use crate::{
+    contains::volume_point::{volume_point_contains, VolumePointContains},
+    primitive_objects::volumes::cube::primitive_cube,
+    topology::{edge::Edge, volume::Volume},
+};
+
+// The cube from (-1, -1, -1) to (1, 1, 1).
+pub fn cube() -> Volume {
+    primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two())
+}
+
+pub fn find_edge(volume: &Volume, midpoint: Point) -> Edge {
+    volume
+        .all_faces()
+        .iter()
+        .flat_map(|face| face.all_edges())
+        .find(|edge| edge.get_midpoint() == midpoint)
+        .unwrap()
+}
+
+pub fn is_inside(volume: &Volume, p: Point) -> bool {
+    matches!(
+        volume_point_contains(volume, p).unwrap(),
+        VolumePointContains::Inside
+    )
+}
+
+// Slightly in front of every face is outside of the volume and slightly behind it is inside.
+pub fn assert_normals_outwards(volume: &Volume) {
+    for face in volume.all_faces() {
+        let p = face.inner_point();
+        let n = face.normal(p) * EFloat64::from(1e-3);
+        assert!(!is_inside(volume, p + n));
+        assert!(is_inside(volume, p - n));
+    }
+}
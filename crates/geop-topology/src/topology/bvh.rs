@@ -0,0 +1,103 @@
+use geop_geometry::{bvh::Bvh, curves::CurveLike};
+
+use super::{edge::Edge, face::Face};
+
+// Edges whose bounding box cannot be computed, like lines without an end, are returned by every query.
+pub fn edge_bvh(edges: &[Edge]) -> Bvh {
+    Bvh::new(
+        edges
+            .iter()
+            .map(|edge| edge.curve.get_bounding_box(edge.start, edge.end).ok())
+            .collect(),
+    )
+}
+
+// Faces whose bounding box cannot be computed, like unbounded planes, are returned by every query.
+// The faces are taken by reference, such that their bounding boxes stay cached on them.
+pub fn face_bvh<'a>(faces: impl IntoIterator<Item = &'a Face>) -> Bvh {
+    Bvh::new(
+        faces
+            .into_iter()
+            .map(|face| face.bounding_box().ok())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::{
+        efloat::EFloat64,
+        point::Point,
+        surfaces::{plane::Plane, surface::Surface},
+    };
+
+    use crate::{
+        contains::face_point::{face_point_contains, FacePointContains},
+        primitive_objects::{edges::line::primitive_line, volumes::cube::primitive_cube},
+        topology::contour::Contour,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_face_bvh() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let faces = cube.all_faces();
+        let bvh = face_bvh(&faces);
+        assert_eq!(bvh.query_point(Point::from_f64(1.0, 1.0, 1.0)).len(), 3);
+        assert_eq!(bvh.query_point(Point::from_f64(1.0, 0.0, 0.0)).len(), 1);
+        assert!(bvh.query_point(Point::from_f64(3.0, 0.0, 0.0)).is_empty());
+    }
+
+    #[test]
+    fn test_cached_bvh() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        assert!(std::ptr::eq(cube.face_bvh(), cube.face_bvh()));
+        assert_eq!(
+            cube.face_bvh().query_point(Point::from_f64(1.0, 1.0, 1.0)),
+            face_bvh(&cube.all_faces()).query_point(Point::from_f64(1.0, 1.0, 1.0))
+        );
+        let face = &cube.boundary().faces()[0];
+        assert!(std::ptr::eq(face.edge_bvh(), face.edge_bvh()));
+        assert_eq!(face.edge_bvh().len(), face.all_edges().len());
+    }
+
+    #[test]
+    fn test_many_edges() {
+        // A polygon with enough edges to use the hierarchy in face_point_contains
+        let n = 64;
+        let corners = (0..n)
+            .map(|i| {
+                let angle = 2.0 * std::f64::consts::PI * i as f64 / n as f64;
+                Point::from_f64(angle.cos(), angle.sin(), 0.0)
+            })
+            .collect::<Vec<Point>>();
+        let edges = (0..n)
+            .map(|i| primitive_line(corners[i], corners[(i + 1) % n]).unwrap())
+            .collect::<Vec<Edge>>();
+        assert_eq!(edge_bvh(&edges).query_point(corners[3]), vec![2, 3]);
+
+        let face = Face::new(
+            vec![Contour::new(edges)],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_x(),
+                Point::unit_y(),
+            ))),
+        );
+        assert_eq!(
+            face_point_contains(&face, Point::from_f64(0.3, 0.4, 0.0)),
+            FacePointContains::Inside
+        );
+        assert_eq!(
+            face_point_contains(&face, Point::from_f64(1.2, 0.4, 0.0)),
+            FacePointContains::Outside
+        );
+        assert!(matches!(
+            face_point_contains(&face, corners[5]),
+            FacePointContains::OnPoint(_)
+        ));
+    }
+}
@@ -51,10 +51,36 @@ impl ContourTangent {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "ContourRaw"))]
 pub struct Contour {
     pub edges: Vec<Edge>,
 }
 
+// Contour::new panics on edges that do not form a loop, so deserialized contours are checked first.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ContourRaw {
+    edges: Vec<Edge>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ContourRaw> for Contour {
+    type Error = crate::topology_error::TopologyError;
+
+    fn try_from(raw: ContourRaw) -> crate::topology_error::TopologyResult<Contour> {
+        let edges = raw.edges;
+        if edges.is_empty()
+            || (0..edges.len()).any(|i| edges[i].end != edges[(i + 1) % edges.len()].start)
+        {
+            return Err(crate::topology_error::TopologyError::new(
+                "Edges of the contour do not form a loop".to_string(),
+            ));
+        }
+        Ok(Contour::new(edges))
+    }
+}
+
 // An Contour is a closed loop of edges which is not self intersecting (because otherwise project would not be defined for self intersection point).
 // It has a defined inside and outside, which is determined by the direction of the edges.
 // The points of edges are not part of the contour, e.g. the intersection of two contours at the same point is empty.
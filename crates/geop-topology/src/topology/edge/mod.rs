@@ -9,6 +9,8 @@ use geop_geometry::{
 
 use crate::contains::edge_point::{edge_point_contains, EdgePointContains};
 
+use super::integral::{arc_length, edge_length};
+
 #[derive(Clone, Debug)]
 pub enum EdgeBounds {
     NoBounds,
@@ -17,12 +19,46 @@ pub enum EdgeBounds {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "EdgeRaw"))]
 pub struct Edge {
     // pub bounds: EdgeBounds, // TODO: Use this instead of start and end
     pub start: Option<Point>,
     pub end: Option<Point>,
     pub curve: Curve,
 }
+
+// Edge::new panics if the end points are not on the curve, so deserialized edges are checked first.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct EdgeRaw {
+    start: Option<Point>,
+    end: Option<Point>,
+    curve: Curve,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<EdgeRaw> for Edge {
+    type Error = crate::topology_error::TopologyError;
+
+    fn try_from(raw: EdgeRaw) -> crate::topology_error::TopologyResult<Edge> {
+        if raw.start.is_some() && raw.start == raw.end {
+            return Err(crate::topology_error::TopologyError::new(
+                "Edge starts where it ends".to_string(),
+            ));
+        }
+        if [raw.start, raw.end]
+            .iter()
+            .flatten()
+            .any(|p| !raw.curve.on_curve(*p))
+        {
+            return Err(crate::topology_error::TopologyError::new(
+                "End point of the edge is not on its curve".to_string(),
+            ));
+        }
+        Ok(Edge::new(raw.start, raw.end, raw.curve))
+    }
+}
 // Represents an Edge, defined by a curve, and a start and end point.
 // It is important to know that the start and end point are not considered a part of the edge.
 // E.g. "intersection" between two edges at end points are not considered intersections.
@@ -72,18 +108,26 @@ impl Edge {
         self.curve.interpolate(self.start, self.end, t).unwrap()
     }
 
+    // Returns None for edges that are not bounded, like a line without a start or an end. Lines and circles are
+    // measured exactly, other curves by quadrature, where the width of the result estimates the error.
     pub fn length(&self) -> Option<EFloat64> {
-        match (self.start, self.end) {
-            (Some(start), Some(end)) => Some(self.curve.distance(start, end).unwrap()),
-            _ => None,
+        match &self.curve {
+            Curve::Line(_) => match (self.start, self.end) {
+                (Some(start), Some(end)) => Some((end - start).norm()),
+                _ => None,
+            },
+            Curve::Helix(_) if self.start.is_none() || self.end.is_none() => None,
+            Curve::Circle(circle) => Some(arc_length(circle, self.start, self.end)),
+            _ => Some(edge_length(self)),
         }
     }
 }
 
 impl PartialEq for Edge {
     fn eq(&self, other: &Edge) -> bool {
-        if self.start == other.start && self.end == other.end {
-            return self.curve == other.curve;
+        // Closed edges have no start and end, so both directions have to be checked
+        if self.start == other.start && self.end == other.end && self.curve == other.curve {
+            return true;
         }
         if self.start == other.end && self.end == other.start {
             return self.curve == other.curve.neg();
@@ -103,6 +147,8 @@ impl Display for Edge {
             ),
             Curve::Ellipse(_) => write!(f, "Ellipse {:?} - {:?}", self.start, self.end),
             Curve::Helix(_) => write!(f, "Helix {:?} - {:?}", self.start, self.end),
+            Curve::SampledCurve(_) => write!(f, "Sampled curve {:?} - {:?}", self.start, self.end),
+            Curve::Nurbs(_) => write!(f, "Nurbs curve {:?} - {:?}", self.start, self.end),
         }
     }
 }
@@ -1,28 +1,40 @@
-use std::rc::Rc;
+use std::{cell::OnceCell, rc::Rc};
 
 use geop_geometry::{
+    bounding_box::BoundingBox,
+    bvh::Bvh,
+    color::Category10Color,
     curve_surface_intersection::curve_surface::curve_surface_intersection,
+    curves::CurveLike,
     efloat::EFloat64,
     point::Point,
     surfaces::{surface::Surface, SurfaceLike},
     transforms::Transform,
 };
 
-use crate::contains::{
-    contour_point::contour_point_contains,
-    edge_point::EdgePointContains,
-    face_point::{face_point_contains, FacePointContains},
+use crate::{
+    contains::{
+        contour_point::contour_point_contains,
+        edge_point::EdgePointContains,
+        face_point::{face_point_contains, FacePointContains},
+    },
+    topology_error::{ElevateToTopology, TopologyError, TopologyResult, WithContext},
+    topology_scene::TopologyScene,
 };
 
 use super::{
+    bvh::edge_bvh,
     contour::ContourTangent,
+    integral::{face_integral, with_error_estimate},
     {contour::Contour, edge::Edge},
 };
 
 #[derive(Clone, Debug)]
 pub struct Face {
-    pub boundaries: Vec<Contour>, // Outer boundary is Coutner-clockwise, inner ones are Clockwise. However, each of theh inner ones can also be the outside. So the only condition that applies to them is that they are not self-intersecting.
-    pub surface: Rc<Surface>,
+    boundaries: Vec<Contour>, // Outer boundary is Coutner-clockwise, inner ones are Clockwise. However, each of theh inner ones can also be the outside. So the only condition that applies to them is that they are not self-intersecting.
+    surface: Rc<Surface>,
+    bounding_box: OnceCell<BoundingBox>,
+    edge_bvh: OnceCell<Bvh>,
 }
 
 // Implements a Face. A Face is bounded by the outer_loop and might have holes in inner_loops.
@@ -33,33 +45,72 @@ impl Face {
     pub fn new(boundaries: Vec<Contour>, surface: Rc<Surface>) -> Face {
         for contour in boundaries.iter() {
             for edge in contour.edges.iter() {
-                assert!(curve_surface_intersection(&edge.curve, &*surface).is_curve());
+                assert!(curve_surface_intersection(&edge.curve, &*surface)
+                    .is_ok_and(|intersection| intersection.is_curve()));
             }
         }
 
         let f = Face {
             boundaries,
             surface,
+            bounding_box: OnceCell::new(),
+            edge_bvh: OnceCell::new(),
         };
         f.inner_point(); // Check if inner point exists
         f
     }
 
     pub fn try_new_face(boundaries: Vec<Contour>, surface: Rc<Surface>) -> Option<Face> {
-        for contour in boundaries.iter() {
-            for edge in contour.edges.iter() {
-                assert!(curve_surface_intersection(&edge.curve, &*surface).is_curve());
+        Face::try_new(boundaries, surface).ok()
+    }
+
+    // Creates a face like `new`, but returns an error instead of panicking if an edge is not on the surface or the face has no inner point.
+    pub fn try_new(boundaries: Vec<Contour>, surface: Rc<Surface>) -> TopologyResult<Face> {
+        let face = Face::new_unchecked(boundaries, surface);
+        let context = |err: TopologyError| {
+            err.with_context_scene(
+                "Create a face".to_string(),
+                TopologyScene::with_edges(
+                    face.all_edges()
+                        .into_iter()
+                        .map(|edge| (edge, Category10Color::Red))
+                        .collect(),
+                ),
+            )
+        };
+        for edge in face.all_edges() {
+            if !curve_surface_intersection(&edge.curve, &face.surface)
+                .elevate(&context)?
+                .is_curve()
+            {
+                return Err(TopologyError::new("Edge is not on the surface".to_string()))
+                    .with_context(&context);
             }
         }
+        if face.try_inner_point().is_none() {
+            return Err(TopologyError::new("Face has no inner point".to_string()))
+                .with_context(&context);
+        }
+        Ok(face)
+    }
 
-        let f = Face {
+    // Creates a face without checking that it has an inner point, e.g. to test the orientation of its boundaries.
+    pub fn new_unchecked(boundaries: Vec<Contour>, surface: Rc<Surface>) -> Face {
+        Face {
             boundaries,
             surface,
-        };
-        if f.try_inner_point().is_some() {
-            return Some(f);
+            bounding_box: OnceCell::new(),
+            edge_bvh: OnceCell::new(),
         }
-        None
+    }
+
+    // The fields are private, so that the cached bounding box and hierarchy always belong to the current boundaries.
+    pub fn boundaries(&self) -> &[Contour] {
+        &self.boundaries
+    }
+
+    pub fn surface(&self) -> &Rc<Surface> {
+        &self.surface
     }
 
     pub fn transform(&self, transform: Transform) -> Face {
@@ -90,52 +141,54 @@ impl Face {
         return edges;
     }
 
-    pub fn try_inner_point(&self) -> Option<Point> {
-        for e1 in self.boundaries[0].edges.iter() {
-            for e2 in self.boundaries[0].edges.iter() {
-                if e1 != e2 {
-                    let geodesic = self.edge_from_to(e1.get_midpoint(), e2.get_midpoint());
-                    let p = geodesic.get_midpoint();
-                    if face_point_contains(self, p) == FacePointContains::Inside {
-                        return Some(p);
-                    }
-                }
-            }
+    pub fn inner_point(&self) -> Point {
+        if let Some(p) = self.try_inner_point() {
+            return p;
         }
-        None
+        println!("Error creating face");
+        for c in self.boundaries.iter() {
+            println!("{}", c);
+        }
+        panic!("No inner point found");
     }
 
-    pub fn inner_point(&self) -> Point {
-        if self.boundaries.len() == 0 {
-            return self.surface.point_grid(1.0)[0];
+    // Steps a little into the face from the midpoint of the first edge. If that fails, e.g. for narrow faces, the
+    // midpoints of geodesics between the midpoints of any two edges are tried, and at last steps from all other edges.
+    pub fn try_inner_point(&self) -> Option<Point> {
+        if self.boundaries.is_empty() {
+            return Some(self.surface.point_grid(1.0)[0]);
         }
 
-        let p = self.boundaries[0].edges[0].get_midpoint();
-        let dist = EFloat64::from(0.01);
-        let normal = self.normal(p);
-        let tangent = self.boundary_tangent(p);
-        let extend_dir = normal.cross(*tangent.expect_on_edge()) * dist;
-        let inner_point = self.surface.exp(p, extend_dir);
-        if face_point_contains(self, inner_point) == FacePointContains::Inside {
-            return inner_point;
+        let edges = self.all_edges();
+        if let Some(p) = self.step_into(&edges[0]) {
+            return Some(p);
         }
-        for e1 in self.all_edges().iter() {
-            for e2 in self.all_edges().iter() {
+        for e1 in edges.iter() {
+            for e2 in edges.iter() {
                 if e1 != e2 {
                     let geodesic = self.edge_from_to(e1.get_midpoint(), e2.get_midpoint());
                     let p = geodesic.get_midpoint();
-                    // println!("Checking {:?}", p);
                     if face_point_contains(self, p) == FacePointContains::Inside {
-                        return p;
+                        return Some(p);
                     }
                 }
             }
         }
-        println!("Error creating face");
-        for c in self.boundaries.iter() {
-            println!("{}", c);
+        edges[1..].iter().find_map(|edge| self.step_into(edge))
+    }
+
+    // The point a small step away from the midpoint of the edge towards the inside of the face, if it is inside.
+    fn step_into(&self, edge: &Edge) -> Option<Point> {
+        let p = edge.get_midpoint();
+        let ContourTangent::OnEdge(tangent) = self.boundary_tangent(p) else {
+            return None;
+        };
+        let extend_dir = self.normal(p).cross(tangent) * EFloat64::from(0.01);
+        let inner_point = self.surface.exp(p, extend_dir);
+        match face_point_contains(self, inner_point) {
+            FacePointContains::Inside => Some(inner_point),
+            _ => None,
         }
-        panic!("No inner point found");
     }
 
     pub fn edge_from_to(&self, from: Point, to: Point) -> Edge {
@@ -171,10 +224,124 @@ impl Face {
         }
     }
 
+    // The area of the face, integrated over the parameter domain of the surface. The width of the result estimates
+    // the quadrature error.
+    pub fn area(&self) -> TopologyResult<EFloat64> {
+        let (coarse, fine) = face_integral(self, &|_, n| vec![n.norm().to_f64()])?;
+        Ok(with_error_estimate(coarse[0], fine[0], 0.0))
+    }
+
+    // The smallest axis aligned box that contains the face. It is computed once and cached.
+    pub fn bounding_box(&self) -> TopologyResult<BoundingBox> {
+        if let Some(bounding_box) = self.bounding_box.get() {
+            return Ok(bounding_box.clone());
+        }
+        let bounding_box = self.compute_bounding_box()?;
+        Ok(self.bounding_box.get_or_init(|| bounding_box).clone())
+    }
+
+    // Hierarchy of the bounding boxes of the edges in the order of all_edges. It is built once and cached.
+    pub fn edge_bvh(&self) -> &Bvh {
+        self.edge_bvh.get_or_init(|| edge_bvh(&self.all_edges()))
+    }
+
+    // Each coordinate takes its extremes either on the boundary or where the surface normal is parallel to its axis.
+    fn compute_bounding_box(&self) -> TopologyResult<BoundingBox> {
+        let context = |err: TopologyError| {
+            err.with_context_scene(
+                "Bounding box of face".to_string(),
+                TopologyScene::with_faces(vec![(self.clone(), Category10Color::Red)]),
+            )
+        };
+        let mut bounding_box: Option<BoundingBox> = None;
+        let add_box =
+            |bounding_box: &mut Option<BoundingBox>, b: BoundingBox| match bounding_box.as_mut() {
+                Some(bounding_box) => {
+                    bounding_box.add_point(b.min);
+                    bounding_box.add_point(b.max);
+                }
+                None => *bounding_box = Some(b),
+            };
+        for edge in self.all_edges() {
+            add_box(
+                &mut bounding_box,
+                edge.curve
+                    .get_bounding_box(edge.start, edge.end)
+                    .elevate(&context)?,
+            );
+        }
+
+        let axes = [Point::unit_x(), Point::unit_y(), Point::unit_z()];
+        let candidates = match &*self.surface {
+            // The normal is parallel to an axis only at the poles of the sphere along that axis
+            Surface::Sphere(sphere) => axes
+                .iter()
+                .flat_map(|e| {
+                    [
+                        sphere.basis + *e * sphere.radius,
+                        sphere.basis - *e * sphere.radius,
+                    ]
+                })
+                .collect::<Vec<Point>>(),
+            // The normal is parallel to an axis where the plane through the torus axis and the axis cuts the tube
+            Surface::Torus(torus) => {
+                let mut candidates = Vec::<Point>::new();
+                for (k, e) in axes.iter().enumerate() {
+                    let (radial, offset) = match (*e - torus.axis * e.dot(torus.axis)).normalize() {
+                        Ok(radial) if !torus.axis.is_parallel(*e) => (radial, *e),
+                        // If the axis is parallel to the torus axis, the extremes are whole circles, so one point of each suffices
+                        _ => (
+                            torus.axis.cross(axes[(k + 1) % 3]).normalize().unwrap(),
+                            torus.axis,
+                        ),
+                    };
+                    for r in [radial, -radial] {
+                        for o in [offset, -offset] {
+                            candidates.push(
+                                torus.basis + r * torus.major_radius + o * torus.minor_radius,
+                            );
+                        }
+                    }
+                }
+                candidates
+            }
+            // Along the lines of a cone the normal is constant, so the lines reach the boundary or the apex
+            Surface::Cone(cone) => vec![cone.apex],
+            // The extremes of a nurbs surface can only be found numerically
+            Surface::Nurbs(nurbs) => nurbs.extreme_points(),
+            // Planes have no extremes inside of a face, and on cylinders they are lines that reach the boundary
+            _ => vec![],
+        };
+        for point in candidates {
+            // Points inside of the box so far cannot extend it, so their containment is not checked
+            let point_box = BoundingBox::new(point, point);
+            if bounding_box
+                .as_ref()
+                .is_some_and(|bounding_box| bounding_box.intersects(&point_box))
+            {
+                continue;
+            }
+            match face_point_contains(self, point) {
+                FacePointContains::Outside | FacePointContains::NotOnSurface => {}
+                _ => add_box(&mut bounding_box, point_box),
+            }
+        }
+
+        match bounding_box {
+            Some(bounding_box) => Ok(bounding_box),
+            None => Err(TopologyError::new(
+                "Face without boundaries on an unbounded surface has no bounding box".to_string(),
+            ))
+            .with_context(&context),
+        }
+    }
+
     pub fn neg(&self) -> Face {
         Face {
             boundaries: self.boundaries.iter().rev().map(|l| l.flip()).collect(),
             surface: self.surface.clone(),
+            bounding_box: OnceCell::new(),
+            edge_bvh: OnceCell::new(),
         }
     }
 
@@ -182,6 +349,10 @@ impl Face {
         Face {
             boundaries: self.boundaries.iter().map(|l| l.flip()).collect(),
             surface: Rc::new(self.surface.neg()),
+            // Flipping the surface and the boundaries keeps the same set of points
+            bounding_box: self.bounding_box.clone(),
+            // The flipped contours list their edges in a different order
+            edge_bvh: OnceCell::new(),
         }
     }
 }
@@ -216,7 +387,189 @@ impl std::fmt::Display for Face {
                     }
                 }
             }
+            Surface::Cone(c) => {
+                writeln!(f, "Cone at apex = {:?} with axis = {:?}, half angle = {:?} and normal direction = {:?}", c.apex, c.axis, c.half_angle, c.normal_outwards)?;
+                for contour in self.boundaries.iter() {
+                    writeln!(f, "Boundary:")?;
+                    for edge in contour.edges.iter() {
+                        writeln!(f, "  {}", edge)?;
+                    }
+                }
+            }
+            Surface::Torus(t) => {
+                writeln!(f, "Torus at basis = {:?} with axis = {:?}, radii = {:?}, {:?} and normal direction = {:?}", t.basis, t.axis, t.major_radius, t.minor_radius, t.normal_outwards)?;
+                for contour in self.boundaries.iter() {
+                    writeln!(f, "Boundary:")?;
+                    for edge in contour.edges.iter() {
+                        writeln!(f, "  {}", edge)?;
+                    }
+                }
+            }
+            Surface::Nurbs(n) => {
+                writeln!(f, "{}", n)?;
+                for contour in self.boundaries.iter() {
+                    writeln!(f, "Boundary:")?;
+                    for edge in contour.edges.iter() {
+                        writeln!(f, "  {}", edge)?;
+                    }
+                }
+            }
         };
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::surfaces::{nurbs::NurbsSurface, plane::Plane, sphere::Sphere};
+
+    use crate::{
+        primitive_objects::{
+            edges::circle::primitive_circle,
+            faces::{nurbs::primitive_nurbs_face, torus::primitive_torus},
+            volumes::cube::primitive_cube,
+        },
+        topology::shell::Shell,
+    };
+
+    use super::*;
+
+    fn assert_box(bounding_box: BoundingBox, min: [f64; 3], max: [f64; 3]) {
+        let actual = [
+            bounding_box.min.x,
+            bounding_box.min.y,
+            bounding_box.min.z,
+            bounding_box.max.x,
+            bounding_box.max.y,
+            bounding_box.max.z,
+        ];
+        for (value, expected) in actual.iter().zip(min.iter().chain(max.iter())) {
+            assert!(
+                (value.to_f64() - expected).abs() < 1e-9,
+                "{} is not {}",
+                value,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_sphere_patch() {
+        let equator = primitive_circle(Point::zero(), Point::unit_z(), EFloat64::one());
+        let hemisphere = Face::new(
+            vec![Contour::new(vec![equator])],
+            Rc::new(Surface::Sphere(Sphere::new(
+                Point::zero(),
+                EFloat64::one(),
+                true,
+            ))),
+        );
+        assert_box(
+            hemisphere.bounding_box().unwrap(),
+            [-1.0, -1.0, 0.0],
+            [1.0, 1.0, 1.0],
+        );
+        assert_box(
+            hemisphere.flip().bounding_box().unwrap(),
+            [-1.0, -1.0, 0.0],
+            [1.0, 1.0, 1.0],
+        );
+        assert_box(
+            hemisphere.neg().bounding_box().unwrap(),
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 0.0],
+        );
+    }
+
+    #[test]
+    fn test_torus() {
+        let torus = primitive_torus(
+            Point::unit_x(),
+            Point::unit_z(),
+            EFloat64::two(),
+            EFloat64::from(0.5),
+        );
+        assert_box(
+            torus.bounding_box().unwrap(),
+            [-1.5, -2.5, -0.5],
+            [3.5, 2.5, 0.5],
+        );
+    }
+
+    #[test]
+    fn test_nurbs_patch() {
+        // The middle control point is raised to z = 2, but the surface only reaches a quarter of it
+        let knots = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0].map(EFloat64::from).to_vec();
+        let control_points = (0..3)
+            .map(|i| {
+                (0..3)
+                    .map(|j| {
+                        let z = if (i, j) == (1, 1) { 2.0 } else { 0.0 };
+                        Point::from_f64(i as f64 / 2.0, j as f64 / 2.0, z)
+                    })
+                    .collect()
+            })
+            .collect();
+        let bump = NurbsSurface::try_new(
+            2,
+            2,
+            control_points,
+            vec![vec![EFloat64::one(); 3]; 3],
+            knots.clone(),
+            knots,
+        )
+        .unwrap();
+        assert_box(
+            primitive_nurbs_face(bump).bounding_box().unwrap(),
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 0.5],
+        );
+    }
+
+    #[test]
+    fn test_volume() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        assert_box(cube.bounding_box().unwrap(), [-1.0; 3], [1.0; 3]);
+        let moved = cube.transform(Transform::from_translation(Point::unit_x()));
+        assert_box(
+            moved.bounding_box().unwrap(),
+            [0.0, -1.0, -1.0],
+            [2.0, 1.0, 1.0],
+        );
+    }
+
+    #[test]
+    fn test_push_cavity() {
+        let mut cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        assert_eq!(cube.face_bvh().len(), 6);
+        let inner = primitive_cube(EFloat64::one(), EFloat64::one(), EFloat64::one());
+        cube.push_cavity(Shell::new(
+            inner.boundary().faces().iter().map(|f| f.flip()).collect(),
+        ));
+        assert_eq!(cube.face_bvh().len(), 12);
+        let p = Point::from_f64(0.25, 0.25, 0.5);
+        assert_eq!(cube.face_bvh().query_point(p).len(), 1);
+    }
+
+    #[test]
+    fn test_try_new_face() {
+        let plane = Rc::new(Surface::Plane(Plane::new(
+            Point::zero(),
+            Point::unit_x(),
+            Point::unit_y(),
+        )));
+
+        // A disc is bounded by a single closed edge
+        let circle = primitive_circle(Point::zero(), Point::unit_z(), EFloat64::one());
+        let disc = Face::try_new_face(vec![Contour::new(vec![circle])], plane.clone()).unwrap();
+        let inner_point = disc.try_inner_point().unwrap();
+        assert_eq!(
+            face_point_contains(&disc, inner_point),
+            FacePointContains::Inside
+        );
+
+        // An edge that is not on the surface is rejected instead of panicking
+        let circle = primitive_circle(Point::unit_z(), Point::unit_z(), EFloat64::one());
+        assert!(Face::try_new_face(vec![Contour::new(vec![circle])], plane).is_none());
+    }
+}
@@ -0,0 +1,589 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use geop_geometry::{
+    color::Category10Color,
+    curves::circle::Circle,
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{nurbs::NurbsSurface, surface::Surface, SurfaceLike},
+};
+
+use crate::{
+    contains::face_point::{face_point_contains, FacePointContains},
+    topology_error::{TopologyError, TopologyResult, WithContext},
+    topology_scene::TopologyScene,
+};
+
+use super::{edge::Edge, face::Face};
+
+// Nodes and weights of the 8 point Gauss-Legendre rule on [-1, 1], which is exact for polynomials up to degree 15.
+const GAUSS_NODES: [f64; 8] = [
+    -0.960_289_856_497_536_3,
+    -0.796_666_477_413_626_7,
+    -0.525_532_409_916_329,
+    -0.183_434_642_495_649_8,
+    0.183_434_642_495_649_8,
+    0.525_532_409_916_329,
+    0.796_666_477_413_626_7,
+    0.960_289_856_497_536_3,
+];
+const GAUSS_WEIGHTS: [f64; 8] = [
+    0.101_228_536_290_376_3,
+    0.222_381_034_453_374_5,
+    0.313_706_645_877_887_3,
+    0.362_683_783_378_362,
+    0.362_683_783_378_362,
+    0.313_706_645_877_887_3,
+    0.222_381_034_453_374_5,
+    0.101_228_536_290_376_3,
+];
+
+// Integrals are computed with this many segments and with twice as many. The difference estimates the quadrature error.
+const SEGMENTS: usize = 4;
+// Step of the central differences that are used for derivatives along edges.
+const STEP: f64 = 1e-5;
+// Relative error that is added to every estimate to account for the central differences and rounding.
+const RELATIVE_ERROR: f64 = 1e-8;
+// Samples per edge that are used to find the parameter range covered by the boundary of a face.
+const BOUNDARY_SAMPLES: usize = 64;
+
+// Composite Gauss-Legendre quadrature of a vector valued function from a to b.
+fn gauss(a: f64, b: f64, segments: usize, mut f: impl FnMut(f64) -> Vec<f64>) -> Vec<f64> {
+    let mut result = Vec::<f64>::new();
+    let width = (b - a) / segments as f64;
+    for segment in 0..segments {
+        let center = a + width * (segment as f64 + 0.5);
+        for (node, weight) in GAUSS_NODES.iter().zip(GAUSS_WEIGHTS.iter()) {
+            let values = f(center + node * width / 2.0);
+            result.resize(values.len(), 0.0);
+            for (r, v) in result.iter_mut().zip(values) {
+                *r += v * weight * width / 2.0;
+            }
+        }
+    }
+    result
+}
+
+// Turns an integral at two resolutions into an interval around the finer one, whose width is an estimate of the error.
+// This is not a guaranteed enclosure, which would need bounds on the derivatives of the integrand, so integrands with
+// features that neither resolution resolves can lie outside of it.
+pub(crate) fn with_error_estimate(coarse: f64, fine: f64, scale: f64) -> EFloat64 {
+    let error = (fine - coarse).abs() + RELATIVE_ERROR * (fine.abs() + scale);
+    EFloat64::new(fine + error, fine - error)
+}
+
+fn wrap_angle(angle: f64) -> f64 {
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
+
+// A unit vector perpendicular to the axis.
+fn perpendicular(axis: Point) -> Point {
+    let helper = match axis.x.abs() < 0.9 {
+        true => Point::unit_x(),
+        false => Point::unit_y(),
+    };
+    axis.cross(helper).normalize().unwrap()
+}
+
+// Parametrization of a surface by (u, v). u is periodic for surfaces of revolution, v is periodic for tori.
+enum Chart<'a> {
+    Plane {
+        origin: Point,
+        e1: Point,
+        e2: Point,
+    },
+    Cylinder {
+        basis: Point,
+        axis: Point,
+        e1: Point,
+        e2: Point,
+        radius: EFloat64,
+    },
+    Cone {
+        apex: Point,
+        axis: Point,
+        e1: Point,
+        e2: Point,
+        half_angle: EFloat64,
+    },
+    Sphere {
+        center: Point,
+        radius: EFloat64,
+    },
+    Torus {
+        center: Point,
+        axis: Point,
+        e1: Point,
+        e2: Point,
+        major_radius: EFloat64,
+        minor_radius: EFloat64,
+    },
+    Nurbs(&'a NurbsSurface),
+}
+
+impl<'a> Chart<'a> {
+    fn new(surface: &'a Surface) -> Chart<'a> {
+        match surface {
+            Surface::Plane(plane) => {
+                let e1 = plane.u_slope.normalize().unwrap();
+                let normal = plane.u_slope.cross(plane.v_slope).normalize().unwrap();
+                Chart::Plane {
+                    origin: plane.basis,
+                    e1,
+                    e2: normal.cross(e1),
+                }
+            }
+            Surface::Cylinder(cylinder) => {
+                let e1 = cylinder.radius.normalize().unwrap();
+                Chart::Cylinder {
+                    basis: cylinder.basis,
+                    axis: cylinder.extend_dir,
+                    e1,
+                    e2: cylinder.extend_dir.cross(e1),
+                    radius: cylinder.radius.norm(),
+                }
+            }
+            Surface::Cone(cone) => {
+                let e1 = perpendicular(cone.axis);
+                Chart::Cone {
+                    apex: cone.apex,
+                    axis: cone.axis,
+                    e1,
+                    e2: cone.axis.cross(e1),
+                    half_angle: cone.half_angle,
+                }
+            }
+            Surface::Sphere(sphere) => Chart::Sphere {
+                center: sphere.basis,
+                radius: sphere.radius,
+            },
+            Surface::Torus(torus) => {
+                let e1 = perpendicular(torus.axis);
+                Chart::Torus {
+                    center: torus.basis,
+                    axis: torus.axis,
+                    e1,
+                    e2: torus.axis.cross(e1),
+                    major_radius: torus.major_radius,
+                    minor_radius: torus.minor_radius,
+                }
+            }
+            Surface::Nurbs(nurbs) => Chart::Nurbs(nurbs),
+        }
+    }
+
+    fn periodic(&self) -> (bool, bool) {
+        match self {
+            Chart::Plane { .. } | Chart::Nurbs(_) => (false, false),
+            Chart::Cylinder { .. } | Chart::Cone { .. } | Chart::Sphere { .. } => (true, false),
+            Chart::Torus { .. } => (true, true),
+        }
+    }
+
+    // The value of v where the inner integrals start. The sphere also has a pole at the other end of v.
+    fn v_bounds(&self) -> (f64, Option<f64>) {
+        match self {
+            Chart::Sphere { .. } => (-FRAC_PI_2, Some(FRAC_PI_2)),
+            Chart::Nurbs(nurbs) => (nurbs.domain().1 .0, None),
+            _ => (0.0, None),
+        }
+    }
+
+    // A point where the surface is not degenerate.
+    fn sample(&self) -> (f64, f64) {
+        match self {
+            Chart::Nurbs(nurbs) => {
+                let ((u0, u1), (v0, v1)) = nurbs.domain();
+                ((u0 + u1) / 2.0, (v0 + v1) / 2.0)
+            }
+            _ => (0.3, 0.2),
+        }
+    }
+
+    // The point at (u, v) and the partial derivatives in u and v.
+    fn evaluate(&self, u: f64, v: f64) -> (Point, Point, Point) {
+        let (cos_u, sin_u) = (EFloat64::from(u.cos()), EFloat64::from(u.sin()));
+        let (cos_v, sin_v) = (EFloat64::from(v.cos()), EFloat64::from(v.sin()));
+        let u = EFloat64::from(u);
+        let v = EFloat64::from(v);
+        match self {
+            Chart::Plane { origin, e1, e2 } => (*origin + *e1 * u + *e2 * v, *e1, *e2),
+            Chart::Cylinder {
+                basis,
+                axis,
+                e1,
+                e2,
+                radius,
+            } => {
+                let around = *e1 * cos_u + *e2 * sin_u;
+                let around_u = *e2 * cos_u - *e1 * sin_u;
+                (
+                    *basis + around * *radius + *axis * v,
+                    around_u * *radius,
+                    *axis,
+                )
+            }
+            Chart::Cone {
+                apex,
+                axis,
+                e1,
+                e2,
+                half_angle,
+            } => {
+                let around = *e1 * cos_u + *e2 * sin_u;
+                let around_u = *e2 * cos_u - *e1 * sin_u;
+                let generator = *axis * half_angle.cos() + around * half_angle.sin();
+                (
+                    *apex + generator * v,
+                    around_u * half_angle.sin() * v,
+                    generator,
+                )
+            }
+            Chart::Sphere { center, radius } => {
+                let around = Point::unit_x() * cos_u + Point::unit_y() * sin_u;
+                let around_u = Point::unit_y() * cos_u - Point::unit_x() * sin_u;
+                (
+                    *center + (around * cos_v + Point::unit_z() * sin_v) * *radius,
+                    around_u * cos_v * *radius,
+                    (Point::unit_z() * cos_v - around * sin_v) * *radius,
+                )
+            }
+            Chart::Torus {
+                center,
+                axis,
+                e1,
+                e2,
+                major_radius,
+                minor_radius,
+            } => {
+                let around = *e1 * cos_u + *e2 * sin_u;
+                let around_u = *e2 * cos_u - *e1 * sin_u;
+                let distance = *major_radius + *minor_radius * cos_v;
+                (
+                    *center + around * distance + *axis * *minor_radius * sin_v,
+                    around_u * distance,
+                    (*axis * cos_v - around * sin_v) * *minor_radius,
+                )
+            }
+            Chart::Nurbs(nurbs) => {
+                let ((u0, u1), (v0, v1)) = nurbs.domain();
+                let (u, v) = (u.to_f64(), v.to_f64());
+                let (hu, hv) = (STEP * (u1 - u0), STEP * (v1 - v0));
+                let derivative = |a: Point, b: Point, h: f64| (b - a) * EFloat64::from(0.5 / h);
+                (
+                    nurbs.point_at(u, v),
+                    derivative(nurbs.point_at(u - hu, v), nurbs.point_at(u + hu, v), hu),
+                    derivative(nurbs.point_at(u, v - hv), nurbs.point_at(u, v + hv), hv),
+                )
+            }
+        }
+    }
+
+    // The parameters of a point on the surface. Periodic parameters are in [-pi, pi].
+    fn parameters(&self, p: Point) -> (f64, f64) {
+        let angle = |d: Point, e1: Point, e2: Point| d.dot(e2).atan2(d.dot(e1)).to_f64();
+        match self {
+            Chart::Plane { origin, e1, e2 } => (
+                (p - *origin).dot(*e1).to_f64(),
+                (p - *origin).dot(*e2).to_f64(),
+            ),
+            Chart::Cylinder {
+                basis,
+                axis,
+                e1,
+                e2,
+                ..
+            } => {
+                let d = p - *basis;
+                (angle(d, *e1, *e2), d.dot(*axis).to_f64())
+            }
+            Chart::Cone { apex, e1, e2, .. } => {
+                let d = p - *apex;
+                (angle(d, *e1, *e2), d.norm().to_f64())
+            }
+            Chart::Sphere { center, radius } => {
+                let d = p - *center;
+                let height = (d.z / *radius).unwrap().to_f64().clamp(-1.0, 1.0);
+                (angle(d, Point::unit_x(), Point::unit_y()), height.asin())
+            }
+            Chart::Torus {
+                center,
+                axis,
+                e1,
+                e2,
+                major_radius,
+                ..
+            } => {
+                let d = p - *center;
+                let height = d.dot(*axis);
+                let radial = d - *axis * height;
+                (
+                    angle(d, *e1, *e2),
+                    height.atan2(radial.norm() - *major_radius).to_f64(),
+                )
+            }
+            Chart::Nurbs(nurbs) => nurbs.parameter(p),
+        }
+    }
+}
+
+// Finds a value of a periodic parameter that the boundary does not reach and which is far from it.
+// The samples are consecutive values along each edge.
+fn find_cut(samples: &[Vec<f64>]) -> Option<f64> {
+    let mut intervals = Vec::<(f64, f64)>::new();
+    for edge in samples.iter() {
+        for pair in edge.windows(2) {
+            let start = pair[0].rem_euclid(2.0 * PI);
+            let end = start + wrap_angle(pair[1] - pair[0]);
+            let (start, end) = (start.min(end), start.max(end));
+            match (start < 0.0, end > 2.0 * PI) {
+                (true, _) => intervals.extend([(start + 2.0 * PI, 2.0 * PI), (0.0, end)]),
+                (_, true) => intervals.extend([(start, 2.0 * PI), (0.0, end - 2.0 * PI)]),
+                _ => intervals.push((start, end)),
+            }
+        }
+    }
+    if intervals.is_empty() {
+        return Some(0.0);
+    }
+    intervals.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut best: Option<(f64, f64)> = None;
+    let mut covered = intervals[0].1;
+    for interval in intervals.iter().skip(1) {
+        if interval.0 > covered && best.is_none_or(|(_, gap)| interval.0 - covered > gap) {
+            best = Some(((interval.0 + covered) / 2.0, interval.0 - covered));
+        }
+        covered = covered.max(interval.1);
+    }
+    let wrap_gap = intervals[0].0 + 2.0 * PI - covered;
+    if wrap_gap > 0.0 && best.is_none_or(|(_, gap)| wrap_gap > gap) {
+        best = Some(((covered + intervals[0].0 + 2.0 * PI) / 2.0, wrap_gap));
+    }
+    best.map(|(cut, _)| cut)
+}
+
+// Integrates a vector valued function f(x, n dA) over the face, where n dA is the oriented area element.
+// The integral over the parameter domain is turned into integrals along the boundary by Green's theorem.
+// The inner parameter is integrated from a reference value up to the boundary, the outer one runs along the edges.
+// Returns the integrals with SEGMENTS and with twice as many segments.
+pub(crate) fn face_integral(
+    face: &Face,
+    f: &dyn Fn(Point, Point) -> Vec<f64>,
+) -> TopologyResult<(Vec<f64>, Vec<f64>)> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            "Integrate over face".to_string(),
+            TopologyScene::with_faces(vec![(face.clone(), Category10Color::Red)]),
+        )
+    };
+    let chart = Chart::new(face.surface());
+    let edges = face.all_edges();
+
+    // The chart may be oriented against the normal of the surface
+    let (u, v) = chart.sample();
+    let (p, p_u, p_v) = chart.evaluate(u, v);
+    let orientation = match p_u.cross(p_v).dot(face.surface().normal(p)) > 0.0 {
+        true => 1.0,
+        false => -1.0,
+    };
+
+    // Periodic inner parameters are cut where the boundary does not reach, so the face lies within one period
+    let samples = edges
+        .iter()
+        .map(|edge| {
+            (0..=BOUNDARY_SAMPLES)
+                .map(|i| chart.parameters(edge.interpolate(i as f64 / BOUNDARY_SAMPLES as f64)))
+                .collect::<Vec<(f64, f64)>>()
+        })
+        .collect::<Vec<Vec<(f64, f64)>>>();
+    let select = |swap: bool| {
+        samples
+            .iter()
+            .map(|edge| {
+                edge.iter()
+                    .map(|(u, v)| match swap {
+                        true => *u,
+                        false => *v,
+                    })
+                    .collect::<Vec<f64>>()
+            })
+            .collect::<Vec<Vec<f64>>>()
+    };
+    let (u_periodic, v_periodic) = chart.periodic();
+    let (swap, reference, top) = match v_periodic {
+        false => {
+            let (reference, top) = chart.v_bounds();
+            (false, reference, top)
+        }
+        true => match (find_cut(&select(false)), find_cut(&select(true))) {
+            (Some(cut), _) => (false, cut, Some(cut + 2.0 * PI)),
+            (None, Some(cut)) => (true, cut, Some(cut + 2.0 * PI)),
+            (None, None) => {
+                return Err(TopologyError::new(
+                    "Face wraps around the surface in both directions".to_string(),
+                ))
+                .with_context(&context)
+            }
+        },
+    };
+    let inner_periodic = match swap {
+        true => u_periodic,
+        false => v_periodic,
+    };
+    let outer_periodic = match swap {
+        true => v_periodic,
+        false => u_periodic,
+    };
+    // Parameters as (outer, inner), with the inner one in the period that starts at the reference
+    let split = |p: Point| {
+        let (u, v) = chart.parameters(p);
+        let (outer, inner) = match swap {
+            true => (v, u),
+            false => (u, v),
+        };
+        match inner_periodic {
+            true => (outer, reference + (inner - reference).rem_euclid(2.0 * PI)),
+            false => (outer, inner),
+        }
+    };
+    let integrand = |outer: f64, inner: f64| {
+        let (u, v) = match swap {
+            true => (inner, outer),
+            false => (outer, inner),
+        };
+        let (p, p_u, p_v) = chart.evaluate(u, v);
+        f(p, p_u.cross(p_v) * EFloat64::from(orientation))
+    };
+    let inner_integral = |outer: f64, inner: f64, segments: usize| {
+        gauss(reference, inner, segments, |s| integrand(outer, s))
+    };
+
+    // By Green's theorem, the integral is -sign * (boundary integral of the inner integral along the outer parameter).
+    // Swapping the parameters changes the orientation of the parameter plane.
+    let sign = match swap {
+        true => -1.0,
+        false => 1.0,
+    } * orientation;
+    let integrate = |segments: usize| {
+        let mut result = Vec::<f64>::new();
+        let mut add = |values: Vec<f64>, factor: f64| {
+            result.resize(values.len(), 0.0);
+            for (r, v) in result.iter_mut().zip(values) {
+                *r += v * factor;
+            }
+        };
+        for edge in edges.iter() {
+            let values = gauss(0.0, 1.0, segments, |t| {
+                let (outer, inner) = split(edge.interpolate(t));
+                let (t0, t1) = ((t - STEP).max(0.0), (t + STEP).min(1.0));
+                let difference = split(edge.interpolate(t1)).0 - split(edge.interpolate(t0)).0;
+                let speed = match outer_periodic {
+                    true => wrap_angle(difference),
+                    false => difference,
+                } / (t1 - t0);
+                inner_integral(outer, inner, segments)
+                    .into_iter()
+                    .map(|value| value * speed)
+                    .collect()
+            });
+            add(values, -sign);
+        }
+        // The face may contain the end of the inner parameter range, which is a pole of a sphere or the cut of a torus
+        if let Some(top) = top {
+            let (u, v) = match swap {
+                true => (top, 0.0),
+                false => (0.0, top),
+            };
+            let (p, _, _) = chart.evaluate(u, v);
+            if face_point_contains(face, p) == FacePointContains::Inside {
+                add(
+                    gauss(0.0, 2.0 * PI, segments, |outer| {
+                        inner_integral(outer, top, segments)
+                    }),
+                    1.0,
+                );
+            }
+        }
+        result
+    };
+    Ok((integrate(SEGMENTS), integrate(2 * SEGMENTS)))
+}
+
+// Arc length of a bounded edge by quadrature of its speed.
+pub(crate) fn edge_length(edge: &Edge) -> EFloat64 {
+    let speed = |t: f64| {
+        let (t0, t1) = ((t - STEP).max(0.0), (t + STEP).min(1.0));
+        let difference = edge.interpolate(t1) - edge.interpolate(t0);
+        vec![difference.norm().to_f64() / (t1 - t0)]
+    };
+    let coarse = gauss(0.0, 1.0, SEGMENTS, speed)[0];
+    let fine = gauss(0.0, 1.0, 2 * SEGMENTS, speed)[0];
+    with_error_estimate(coarse, fine, 0.0)
+}
+
+// The length of a circular arc from start to end, counter-clockwise around the normal.
+pub(crate) fn arc_length(circle: &Circle, start: Option<Point>, end: Option<Point>) -> EFloat64 {
+    let angle = match (start, end) {
+        (Some(start), Some(end)) => {
+            let (start, end) = (start - circle.basis, end - circle.basis);
+            let angle = start.cross(end).dot(circle.normal).atan2(start.dot(end));
+            match angle.upper_bound <= 0.0 {
+                true => angle + EFloat64::two_pi(),
+                false => angle,
+            }
+        }
+        _ => EFloat64::two_pi(),
+    };
+    circle.radius.norm() * angle
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::curves::{curve::Curve, line::Line};
+
+    use crate::primitive_objects::{
+        edges::circle::primitive_circle,
+        faces::{rectangle::primitive_rectangle, torus::primitive_torus},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_edge_length() {
+        let circle = primitive_circle(Point::zero(), Point::unit_z(), EFloat64::two());
+        assert!(circle.length().unwrap() == 4.0 * PI);
+
+        // The arc runs counter-clockwise from angle 0 to angle -pi / 2
+        let arc = Edge::new(
+            Some(Point::unit_x() * EFloat64::two()),
+            Some(-Point::unit_y() * EFloat64::two()),
+            circle.curve.clone(),
+        );
+        assert!(arc.length().unwrap() == 3.0 * PI);
+
+        let ray = Edge::new(
+            Some(Point::zero()),
+            None,
+            Curve::Line(Line::new(Point::zero(), Point::unit_x()).unwrap()),
+        );
+        assert!(ray.length().is_none());
+    }
+
+    #[test]
+    fn test_face_area() {
+        let rectangle = primitive_rectangle(
+            Point::zero(),
+            Point::unit_x(),
+            Point::unit_y() * EFloat64::two(),
+        );
+        assert!(rectangle.area().unwrap() == 8.0);
+
+        let torus = primitive_torus(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::two(),
+            EFloat64::from(0.5),
+        );
+        assert!(torus.area().unwrap() == 4.0 * PI * PI);
+    }
+}
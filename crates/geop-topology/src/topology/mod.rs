@@ -1,6 +1,8 @@
+pub mod bvh;
 pub mod contour;
 pub mod edge;
 pub mod face;
+pub(crate) mod integral;
 pub mod scene;
 pub mod shell;
 pub mod volume;
@@ -5,6 +5,7 @@ use geop_geometry::{color::Category10Color, point::Point};
 use super::{edge::Edge, face::Face, volume::Volume};
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     pub r: f32,
     pub g: f32,
@@ -1,12 +1,19 @@
-use geop_geometry::{point::Point, transforms::Transform};
+use std::cell::OnceCell;
 
-use crate::contains::face_point::{face_point_contains, FacePointContains};
+use geop_geometry::{bounding_box::BoundingBox, bvh::Bvh, point::Point, transforms::Transform};
 
-use super::face::Face;
+use crate::{
+    contains::face_point::{face_point_contains, FacePointContains},
+    topology_error::TopologyResult,
+};
+
+use super::{bvh::face_bvh, face::Face};
 
 #[derive(Clone, Debug)]
 pub struct Shell {
-    pub faces: Vec<Face>,
+    faces: Vec<Face>,
+    bounding_box: OnceCell<BoundingBox>,
+    face_bvh: OnceCell<Bvh>,
 }
 
 pub enum ShellNormal {
@@ -49,15 +56,44 @@ impl ShellNormal {
 impl Shell {
     pub fn new(faces: Vec<Face>) -> Shell {
         assert!(faces.len() > 0, "Shell must have at least one face");
-        Shell { faces }
+        Shell {
+            faces,
+            bounding_box: OnceCell::new(),
+            face_bvh: OnceCell::new(),
+        }
+    }
+
+    pub fn faces(&self) -> &[Face] {
+        &self.faces
     }
 
     pub fn transform(&self, transform: Transform) -> Shell {
         Shell {
             faces: self.faces.iter().map(|f| f.transform(transform)).collect(),
+            bounding_box: OnceCell::new(),
+            face_bvh: OnceCell::new(),
         }
     }
 
+    // The union of the bounding boxes of the faces. It is computed once and cached.
+    pub fn bounding_box(&self) -> TopologyResult<BoundingBox> {
+        if let Some(bounding_box) = self.bounding_box.get() {
+            return Ok(bounding_box.clone());
+        }
+        let mut bounding_box = self.faces[0].bounding_box()?;
+        for face in self.faces.iter().skip(1) {
+            let face_box = face.bounding_box()?;
+            bounding_box.add_point(face_box.min);
+            bounding_box.add_point(face_box.max);
+        }
+        Ok(self.bounding_box.get_or_init(|| bounding_box).clone())
+    }
+
+    // Hierarchy of the bounding boxes of the faces in their order. It is built once and cached.
+    pub fn face_bvh(&self) -> &Bvh {
+        self.face_bvh.get_or_init(|| face_bvh(&self.faces))
+    }
+
     pub fn normal(&self, point: Point) -> ShellNormal {
         let mut relevant_faces = Vec::<&Face>::new();
         for face in self.faces.iter() {
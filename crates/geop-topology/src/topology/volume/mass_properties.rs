@@ -0,0 +1,221 @@
+use geop_geometry::{color::Category10Color, efloat::EFloat64, point::Point};
+
+use crate::{
+    topology::integral::{face_integral, with_error_estimate},
+    topology_error::{ElevateToTopology, TopologyError, TopologyResult, WithContext},
+    topology_scene::TopologyScene,
+};
+
+use super::Volume;
+
+#[derive(Debug, Clone)]
+pub struct MassProperties {
+    pub volume: EFloat64,
+    pub surface_area: EFloat64,
+    pub mass: EFloat64,
+    pub center_of_mass: Point,
+    // Inertia tensor about the center of mass, with the products of inertia negated.
+    pub inertia: [[EFloat64; 3]; 3],
+}
+
+impl Volume {
+    // Integrates over the faces with the divergence theorem. The width of every value estimates the quadrature error.
+    pub fn mass_properties(&self, density: EFloat64) -> TopologyResult<MassProperties> {
+        let context = |err: TopologyError| {
+            err.with_context_scene(
+                format!("Compute mass properties with density {}", density),
+                TopologyScene::with_faces(
+                    self.all_faces()
+                        .into_iter()
+                        .map(|face| (face, Category10Color::Gray))
+                        .collect(),
+                ),
+            )
+        };
+
+        // Integrate relative to a point close to the volume to keep the integrands small
+        let faces = self.all_faces();
+        if faces.is_empty() {
+            return Err(TopologyError::new("Volume has no faces".to_string()))
+                .with_context(&context);
+        }
+        let origin = faces[0].inner_point();
+
+        // Area, volume, first moments and second moments, followed by the same integrals of absolute values
+        let integrand = |p: Point, n: Point| {
+            let d = p - origin;
+            let (x, y, z) = (d.x.to_f64(), d.y.to_f64(), d.z.to_f64());
+            let (nx, ny, nz) = (n.x.to_f64(), n.y.to_f64(), n.z.to_f64());
+            let values = [
+                n.norm().to_f64(),
+                (x * nx + y * ny + z * nz) / 3.0,
+                x * x * nx / 2.0,
+                y * y * ny / 2.0,
+                z * z * nz / 2.0,
+                x * x * x * nx / 3.0,
+                y * y * y * ny / 3.0,
+                z * z * z * nz / 3.0,
+                x * x * y * nx / 2.0,
+                y * y * z * ny / 2.0,
+                z * z * x * nz / 2.0,
+            ];
+            let mut result = values.to_vec();
+            result.extend(values.iter().map(|v| v.abs()));
+            result
+        };
+        let mut coarse = [0.0; 22];
+        let mut fine = [0.0; 22];
+        for face in faces.iter() {
+            let (c, f) = face_integral(face, &integrand).with_context(&context)?;
+            for i in 0..22 {
+                coarse[i] += c[i];
+                fine[i] += f[i];
+            }
+        }
+        let integral = |i: usize| with_error_estimate(coarse[i], fine[i], fine[i + 11]);
+
+        let volume = integral(1);
+        if volume <= 0.0 {
+            return Err(TopologyError::new(
+                "Volume is not positive, the faces may be oriented inwards".to_string(),
+            ))
+            .with_context(&context);
+        }
+        let mass = density * volume;
+        let moment = Point::new(integral(2), integral(3), integral(4));
+        let center = (moment / volume).elevate(&context)?;
+
+        // Second moments about the integration origin, shifted to the center of mass by the parallel axis theorem
+        let second = [
+            [integral(5), integral(8), integral(10)],
+            [integral(8), integral(6), integral(9)],
+            [integral(10), integral(9), integral(7)],
+        ];
+        let c = [center.x, center.y, center.z];
+        let central = |i: usize, j: usize| density * second[i][j] - mass * c[i] * c[j];
+        let mut inertia = [[EFloat64::zero(); 3]; 3];
+        for (i, row) in inertia.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = match i == j {
+                    true => central((i + 1) % 3, (i + 1) % 3) + central((i + 2) % 3, (i + 2) % 3),
+                    false => -central(i, j),
+                };
+            }
+        }
+
+        Ok(MassProperties {
+            volume,
+            surface_area: integral(0),
+            mass,
+            center_of_mass: origin + center,
+            inertia,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f64::consts::PI, rc::Rc};
+
+    use geop_geometry::surfaces::{plane::Plane, surface::Surface};
+
+    use crate::{
+        operations::{extrude::extrude, fillet::fillet, hollow::hollow},
+        primitive_objects::{
+            edges::circle::primitive_circle, faces::sphere::primitive_sphere,
+            volumes::cube::primitive_cube,
+        },
+        topology::{contour::Contour, face::Face, shell::Shell},
+    };
+
+    use super::*;
+
+    fn assert_close(value: EFloat64, expected: f64) {
+        assert!(
+            (value.to_f64() - expected).abs() < 1e-6,
+            "{} is not {}",
+            value,
+            expected
+        );
+        assert!(value.upper_bound - value.lower_bound < 1e-4);
+    }
+
+    #[test]
+    fn test_cube() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let properties = cube.mass_properties(EFloat64::two()).unwrap();
+        assert_close(properties.volume, 8.0);
+        assert_close(properties.surface_area, 24.0);
+        assert_close(properties.mass, 16.0);
+        assert_close(properties.center_of_mass.x, 0.0);
+        assert_close(properties.center_of_mass.y, 0.0);
+        assert_close(properties.center_of_mass.z, 0.0);
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_close(
+                    properties.inertia[i][j],
+                    match i == j {
+                        true => 32.0 / 3.0,
+                        false => 0.0,
+                    },
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_cylinder() {
+        let circle = primitive_circle(Point::zero(), -Point::unit_z(), EFloat64::one());
+        let face = Face::new(
+            vec![Contour::new(vec![circle])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let cylinder = extrude(face, Point::unit_z() * EFloat64::two());
+        let properties = cylinder.mass_properties(EFloat64::one()).unwrap();
+        assert_close(properties.volume, 2.0 * PI);
+        assert_close(properties.surface_area, 6.0 * PI);
+        assert_close(properties.center_of_mass.z, 1.0);
+        assert_close(properties.inertia[2][2], PI);
+        assert_close(properties.inertia[0][0], PI * (3.0 + 4.0) / 6.0);
+    }
+
+    #[test]
+    fn test_sphere() {
+        let sphere = Volume::new(
+            Shell::new(vec![primitive_sphere(Point::unit_x(), EFloat64::one())]),
+            vec![],
+        );
+        let properties = sphere.mass_properties(EFloat64::one()).unwrap();
+        assert_close(properties.volume, 4.0 * PI / 3.0);
+        assert_close(properties.surface_area, 4.0 * PI);
+        assert_close(properties.center_of_mass.x, 1.0);
+        assert_close(properties.inertia[1][1], 8.0 * PI / 15.0);
+    }
+
+    #[test]
+    fn test_hollow_cube() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let hollow = hollow(&cube, EFloat64::from(0.5), &[]).unwrap();
+        let properties = hollow.mass_properties(EFloat64::one()).unwrap();
+        assert_close(properties.volume, 7.0);
+        assert_close(properties.surface_area, 30.0);
+    }
+
+    #[test]
+    fn test_filleted_cube() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let edge = cube
+            .all_faces()
+            .iter()
+            .flat_map(|face| face.all_edges())
+            .find(|edge| edge.get_midpoint() == Point::from_f64(1.0, 0.0, 1.0))
+            .unwrap();
+        let filleted = fillet(&cube, &[edge], EFloat64::from(0.5)).unwrap();
+        let properties = filleted.mass_properties(EFloat64::one()).unwrap();
+        assert_close(properties.volume, 8.0 - (0.25 - PI / 16.0) * 2.0);
+    }
+}
@@ -1,21 +1,48 @@
-use geop_geometry::{point::Point, transforms::Transform};
+use std::cell::OnceCell;
 
-use crate::contains::{face_point::FacePointContains, shell_point::shell_point_contains};
+use geop_geometry::{bounding_box::BoundingBox, bvh::Bvh, point::Point, transforms::Transform};
+
+pub mod mass_properties;
+
+use crate::{
+    contains::{face_point::FacePointContains, shell_point::shell_point_contains},
+    topology_error::TopologyResult,
+};
 
 use super::{
+    bvh::face_bvh,
     face::Face,
     shell::{Shell, ShellNormal},
 };
 
 #[derive(Clone, Debug)]
 pub struct Volume {
-    pub boundary: Shell,      // Normal pointing outwards
-    pub cavities: Vec<Shell>, // Normal pointing inwards
+    boundary: Shell,      // Normal pointing outwards
+    cavities: Vec<Shell>, // Normal pointing inwards
+    face_bvh: OnceCell<Bvh>,
 }
 
 impl Volume {
     pub fn new(boundary: Shell, cavities: Vec<Shell>) -> Volume {
-        Volume { boundary, cavities }
+        Volume {
+            boundary,
+            cavities,
+            face_bvh: OnceCell::new(),
+        }
+    }
+
+    pub fn boundary(&self) -> &Shell {
+        &self.boundary
+    }
+
+    pub fn cavities(&self) -> &[Shell] {
+        &self.cavities
+    }
+
+    // Adds a cavity and drops the cached hierarchy, which does not contain its faces.
+    pub fn push_cavity(&mut self, cavity: Shell) {
+        self.cavities.push(cavity);
+        self.face_bvh = OnceCell::new();
     }
 
     pub fn transform(&self, transform: Transform) -> Volume {
@@ -26,15 +53,33 @@ impl Volume {
                 .iter()
                 .map(|h| h.transform(transform))
                 .collect(),
+            face_bvh: OnceCell::new(),
         }
     }
 
+    // The cavities lie inside of the boundary, so the cached box of the boundary contains the volume.
+    pub fn bounding_box(&self) -> TopologyResult<BoundingBox> {
+        self.boundary.bounding_box()
+    }
+
+    // Hierarchy of the bounding boxes of the faces in the order of all_faces. It is built once and cached, and so are
+    // the bounding boxes of the faces themselves.
+    pub fn face_bvh(&self) -> &Bvh {
+        self.face_bvh.get_or_init(|| {
+            face_bvh(
+                std::iter::once(&self.boundary)
+                    .chain(self.cavities.iter())
+                    .flat_map(|shell| shell.faces().iter()),
+            )
+        })
+    }
+
     pub fn all_faces(&self) -> Vec<Face> {
         let mut faces = Vec::<Face>::new();
 
-        faces.extend(self.boundary.faces.clone());
+        faces.extend(self.boundary.faces().iter().cloned());
         for hole in self.cavities.iter() {
-            faces.extend(hole.faces.clone());
+            faces.extend(hole.faces().iter().cloned());
         }
         return faces;
     }
@@ -2,7 +2,7 @@ use std::backtrace::Backtrace;
 
 use geop_geometry::geometry_error::{GeometryError, GeometryResult};
 
-use crate::topology_scene::TopologyScene;
+use crate::{step::StepError, topology_scene::TopologyScene};
 
 pub enum TopologyErrorRoot {
     InTopologyCrate {
@@ -12,6 +12,9 @@ pub enum TopologyErrorRoot {
     FromGeometryError {
         geometry_error: GeometryError,
     },
+    FromStepError {
+        step_error: StepError,
+    },
 }
 
 pub enum TopologyError {
@@ -33,6 +36,18 @@ impl TopologyError {
         TopologyError::Root(TopologyErrorRoot::FromGeometryError { geometry_error })
     }
 
+    pub fn from_step_error(step_error: StepError) -> TopologyError {
+        TopologyError::Root(TopologyErrorRoot::FromStepError { step_error })
+    }
+
+    // The error at the bottom of the chain of contexts.
+    pub fn root(&self) -> &TopologyErrorRoot {
+        match self {
+            TopologyError::Context { inner_error, .. } => inner_error.root(),
+            TopologyError::Root(root) => root,
+        }
+    }
+
     pub fn with_context(self, message: String) -> TopologyError {
         TopologyError::Context {
             message,
@@ -70,6 +85,9 @@ impl std::fmt::Display for TopologyError {
                 TopologyErrorRoot::FromGeometryError { geometry_error } => {
                     write!(f, "{}", geometry_error)
                 }
+                TopologyErrorRoot::FromStepError { step_error } => {
+                    writeln!(f, "StepError: {}", step_error)
+                }
             },
         }
     }
@@ -99,6 +117,12 @@ impl From<GeometryError> for TopologyError {
     }
 }
 
+impl From<StepError> for TopologyError {
+    fn from(error: StepError) -> Self {
+        TopologyError::from_step_error(error)
+    }
+}
+
 pub type TopologyResult<T> = Result<T, TopologyError>;
 
 pub trait WithContext<T> {
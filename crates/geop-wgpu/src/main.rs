@@ -105,16 +105,20 @@ async fn run() {
         );
         let face2 = face1.transform(Transform::from_translation(Point::from_f64(0.2, 0.2, 0.0)));
 
-        let union_face = face_face_difference(&face2, &face1)[1].clone();
+        let union_face = face_face_difference(&face2, &face1).unwrap()[1].clone();
 
         let _object = extrude(union_face.clone(), Point::from_f64(0.0, 0.0, -0.5));
 
-        let mut sphere = primitive_sphere(Point::zero(), EFloat64::one());
-        sphere.boundaries.push(Contour::new(vec![primitive_circle(
-            Point::zero(),
-            Point::from_f64(0.5, 0.5, 0.5),
-            EFloat64::one(),
-        )]));
+        let _sphere = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::zero(),
+                Point::from_f64(0.5, 0.5, 0.5),
+                EFloat64::one(),
+            )])],
+            primitive_sphere(Point::zero(), EFloat64::one())
+                .surface()
+                .clone(),
+        );
 
         let p1 = Point::from_f64(-1.0, 0.0, 1.0);
         let p2 = Point::from_f64(-1.0, 0.0, -1.0);
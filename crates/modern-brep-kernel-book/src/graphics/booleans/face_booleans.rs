@@ -80,7 +80,7 @@ mod tests {
             scene.edges.push((edge.clone(), Color::white()));
         }
 
-        let split_points = face_split_points(&face1, &face2);
+        let split_points = face_split_points(&face1, &face2).unwrap();
         for point in split_points {
             scene.points.push((point, Color::gray()));
         }
@@ -102,7 +102,7 @@ mod tests {
         let (face1, face2) = generate_scene();
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
 
-        let face_splits = face_split(&face1, &face2);
+        let face_splits = face_split(&face1, &face2).unwrap();
         for split in face_splits {
             let (edge, color) = match split {
                 FaceSplit::AinB(edge) => (edge, Color::ten_different_colors(0)),
@@ -137,6 +137,7 @@ mod tests {
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
 
         let edges = face_split(&face1, &face2)
+            .unwrap()
             .drain(..)
             .filter(|mode| match mode {
                 FaceSplit::AinB(_) => true,
@@ -184,7 +185,7 @@ mod tests {
             scene.edges.push((edge.clone(), Color::white()));
         }
 
-        let intersection_face = face_face_intersection(&face1, &face2);
+        let intersection_face = face_face_intersection(&face1, &face2).unwrap();
         match intersection_face {
             FaceFaceIntersection::Faces(faces) => {
                 assert!(faces.len() == 1);
@@ -227,7 +228,7 @@ mod tests {
             scene.edges.push((edge.clone(), Color::white()));
         }
 
-        let faces = face_face_difference(&face1, &face2);
+        let faces = face_face_difference(&face1, &face2).unwrap();
         for face in faces {
             let face = face.transform(Transform::from_translation(Point::from_f64(
                 0.0, 0.001, 0.0,
@@ -262,7 +263,7 @@ mod tests {
             scene.edges.push((edge.clone(), Color::white()));
         }
 
-        let faces = face_face_union(&face1, &face2);
+        let faces = face_face_union(&face1, &face2).unwrap();
         for face in faces {
             let face = face.transform(Transform::from_translation(Point::from_f64(
                 0.0, 0.001, 0.0,
@@ -71,7 +71,7 @@ mod tests {
     #[rstest]
     async fn test_volume_split_edges(#[future] renderer: Box<HeadlessRenderer>) {
         let (volume1, volume2) = generate_secene_1();
-        let split_edges = volume_split_edges(&volume1, &volume2);
+        let split_edges = volume_split_edges(&volume1, &volume2).unwrap();
         assert!(split_edges.len() == 4);
 
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
@@ -104,7 +104,7 @@ mod tests {
     #[rstest]
     async fn test_face_subdivision(#[future] renderer: Box<HeadlessRenderer>) {
         let (volume1, volume2) = generate_secene_1();
-        let split_edges = volume_split_edges(&volume1, &volume2);
+        let split_edges = volume_split_edges(&volume1, &volume2).unwrap();
         assert!(split_edges.len() == 4);
 
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
@@ -112,11 +112,11 @@ mod tests {
         let faces = split_faces_by_edges_if_necessary(volume1.all_faces(), &split_edges);
         for f in faces {
             let mut midpoint = Point::zero();
-            for e in f.boundaries[0].clone().edges.iter() {
+            for e in f.boundaries()[0].clone().edges.iter() {
                 midpoint = midpoint + e.get_midpoint();
             }
             midpoint =
-                (midpoint / EFloat64::from(f.boundaries[0].clone().edges.len() as f64)).unwrap();
+                (midpoint / EFloat64::from(f.boundaries()[0].clone().edges.len() as f64)).unwrap();
             let f = f.transform(Transform::from_translation(midpoint * EFloat64::from(0.2)));
             scene.faces.push((f, Color::white()));
         }
@@ -124,11 +124,11 @@ mod tests {
         let faces = split_faces_by_edges_if_necessary(volume2.all_faces(), &split_edges);
         for f in faces {
             let mut midpoint = Point::zero();
-            for e in f.boundaries[0].clone().edges.iter() {
+            for e in f.boundaries()[0].clone().edges.iter() {
                 midpoint = midpoint + e.get_midpoint();
             }
             midpoint =
-                (midpoint / EFloat64::from(f.boundaries[0].clone().edges.len() as f64)).unwrap();
+                (midpoint / EFloat64::from(f.boundaries()[0].clone().edges.len() as f64)).unwrap();
             midpoint = midpoint + Point::from_f64(0.5, 0.0, 0.0);
             let f = f.transform(Transform::from_translation(midpoint * EFloat64::from(0.2)));
             scene.faces.push((f, Color::white()));
@@ -149,20 +149,20 @@ mod tests {
     #[rstest]
     async fn test_face_classification(#[future] renderer: Box<HeadlessRenderer>) {
         let (volume1, volume2) = generate_secene_1();
-        let split_edges = volume_split_edges(&volume1, &volume2);
+        let split_edges = volume_split_edges(&volume1, &volume2).unwrap();
         assert!(split_edges.len() == 4);
 
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
 
-        let splits = volume_split(&volume1, &volume2);
+        let splits = volume_split(&volume1, &volume2).unwrap();
         for split in splits {
             let f = split.face();
             let mut midpoint = Point::zero();
-            for e in f.boundaries[0].clone().edges.iter() {
+            for e in f.boundaries()[0].clone().edges.iter() {
                 midpoint = midpoint + e.get_midpoint();
             }
             midpoint =
-                (midpoint / EFloat64::from(f.boundaries[0].clone().edges.len() as f64)).unwrap();
+                (midpoint / EFloat64::from(f.boundaries()[0].clone().edges.len() as f64)).unwrap();
             let f = f.transform(Transform::from_translation(midpoint * EFloat64::from(0.2)));
 
             let color = match split {
@@ -194,12 +194,12 @@ mod tests {
     #[rstest]
     async fn test_union_splits(#[future] renderer: Box<HeadlessRenderer>) {
         let (volume1, volume2) = generate_secene_1();
-        let split_edges = volume_split_edges(&volume1, &volume2);
+        let split_edges = volume_split_edges(&volume1, &volume2).unwrap();
         assert!(split_edges.len() == 4);
 
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
 
-        let splits = volume_split(&volume1, &volume2);
+        let splits = volume_split(&volume1, &volume2).unwrap();
         let splits = splits.iter().filter(|split| match split {
             VolumeSplit::AinB(_) => false,
             VolumeSplit::AonBSameSide(_) => true,
@@ -213,11 +213,11 @@ mod tests {
         for split in splits {
             let f = split.face();
             let mut midpoint = Point::zero();
-            for e in f.boundaries[0].clone().edges.iter() {
+            for e in f.boundaries()[0].clone().edges.iter() {
                 midpoint = midpoint + e.get_midpoint();
             }
             midpoint =
-                (midpoint / EFloat64::from(f.boundaries[0].clone().edges.len() as f64)).unwrap();
+                (midpoint / EFloat64::from(f.boundaries()[0].clone().edges.len() as f64)).unwrap();
             let f = f.transform(Transform::from_translation(midpoint * EFloat64::from(0.2)));
 
             let color = match split {
@@ -249,12 +249,12 @@ mod tests {
     #[rstest]
     async fn test_union_splits2(#[future] renderer: Box<HeadlessRenderer>) {
         let (volume1, volume2) = generate_secene_2();
-        let split_edges = volume_split_edges(&volume1, &volume2);
+        let split_edges = volume_split_edges(&volume1, &volume2).unwrap();
         assert!(split_edges.len() == 4);
 
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
 
-        let splits = volume_split(&volume1, &volume2);
+        let splits = volume_split(&volume1, &volume2).unwrap();
         // let splits = splits.iter().filter(|split| match split {
         //     VolumeSplit::AinB(_) => false,
         //     VolumeSplit::AonBSameSide(_) => true,
@@ -268,11 +268,11 @@ mod tests {
         for split in splits {
             let f = split.face();
             let mut midpoint = Point::zero();
-            for e in f.boundaries[0].clone().edges.iter() {
+            for e in f.boundaries()[0].clone().edges.iter() {
                 midpoint = midpoint + e.get_midpoint();
             }
             midpoint =
-                (midpoint / EFloat64::from(f.boundaries[0].clone().edges.len() as f64)).unwrap();
+                (midpoint / EFloat64::from(f.boundaries()[0].clone().edges.len() as f64)).unwrap();
             let f = f.transform(Transform::from_translation(midpoint * EFloat64::from(0.2)));
 
             let color = match split {
@@ -201,7 +201,7 @@ mod tests {
         }
 
         for (p1, p2) in points.iter().zip(points.iter().skip(1)) {
-            let geodesic = match face.surface.borrow() {
+            let geodesic = match face.surface().borrow() {
                 Surface::Cylinder(cylinder) => cylinder.geodesic(*p1, *p2),
                 _ => panic!(),
             };
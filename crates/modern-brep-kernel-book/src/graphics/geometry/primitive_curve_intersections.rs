@@ -36,10 +36,10 @@ mod tests {
         ];
         let mut scene_points = vec![];
         for intersection in [
-            edge_edge_intersection(&line1, &line1),
-            edge_edge_intersection(&line2, &line3),
-            edge_edge_intersection(&line1, &line3),
-            edge_edge_intersection(&line1, &line2),
+            edge_edge_intersection(&line1, &line1).unwrap(),
+            edge_edge_intersection(&line2, &line3).unwrap(),
+            edge_edge_intersection(&line1, &line3).unwrap(),
+            edge_edge_intersection(&line1, &line2).unwrap(),
         ] {
             match intersection {
                 EdgeEdgeIntersection::Edges(edges) => {
@@ -95,10 +95,10 @@ mod tests {
         ];
         let mut scene_points = vec![];
         for intersection in [
-            edge_edge_intersection(&circle1, &circle1),
-            edge_edge_intersection(&circle2, &circle3),
-            edge_edge_intersection(&circle1, &circle3),
-            edge_edge_intersection(&circle1, &circle2),
+            edge_edge_intersection(&circle1, &circle1).unwrap(),
+            edge_edge_intersection(&circle2, &circle3).unwrap(),
+            edge_edge_intersection(&circle1, &circle3).unwrap(),
+            edge_edge_intersection(&circle1, &circle2).unwrap(),
         ] {
             match intersection {
                 EdgeEdgeIntersection::Edges(edges) => {
@@ -164,10 +164,10 @@ mod tests {
         ];
         let mut scene_points = vec![];
         for intersection in [
-            edge_edge_intersection(&circle1, &line1),
-            edge_edge_intersection(&circle1, &line2),
-            edge_edge_intersection(&circle1, &line3),
-            edge_edge_intersection(&circle1, &line4),
+            edge_edge_intersection(&circle1, &line1).unwrap(),
+            edge_edge_intersection(&circle1, &line2).unwrap(),
+            edge_edge_intersection(&circle1, &line3).unwrap(),
+            edge_edge_intersection(&circle1, &line4).unwrap(),
         ] {
             match intersection {
                 EdgeEdgeIntersection::Edges(edges) => {
@@ -218,7 +218,7 @@ mod tests {
         scene.edges.push((ellipse1.clone(), Color::white()));
         scene.edges.push((ellipse2.clone(), Color::white()));
 
-        let intersections = edge_edge_intersection(&ellipse1, &ellipse2);
+        let intersections = edge_edge_intersection(&ellipse1, &ellipse2).unwrap();
         match intersections {
             EdgeEdgeIntersection::Edges(edges) => {
                 panic!("Unexpected edges: {:?}", edges);
@@ -22,11 +22,11 @@ mod tests {
         let anchor = Point::from_f64(-0.5, -1.3, 0.5).normalize().unwrap();
         scene.points.push((anchor, Color::blue()));
 
-        for p in face.surface.point_grid(2.0) {
-            assert!(face.surface.on_surface(p));
+        for p in face.surface().point_grid(2.0) {
+            assert!(face.surface().on_surface(p));
             if face.edge_from_to(anchor, p).length().unwrap() < 1.0 {
                 scene.points.push((p, Color::green()));
-                let log = face.surface.log(anchor, p).unwrap() + anchor;
+                let log = face.surface().log(anchor, p).unwrap() + anchor;
                 scene
                     .edges
                     .push((primitive_line(log, p).unwrap(), Color::white()));
@@ -56,11 +56,11 @@ mod tests {
         anchor.z = EFloat64::from(-0.5);
         scene.points.push((anchor, Color::blue()));
 
-        for p in face.surface.point_grid(2.0) {
-            assert!(face.surface.on_surface(p));
+        for p in face.surface().point_grid(2.0) {
+            assert!(face.surface().on_surface(p));
             if (anchor - p).norm() < 1.5 && (anchor - p).norm() > 0.0001 {
                 scene.points.push((p, Color::green()));
-                let log = face.surface.log(anchor, p).unwrap() + anchor;
+                let log = face.surface().log(anchor, p).unwrap() + anchor;
                 if (log - p).norm() >= 0.0 {
                     scene
                         .edges
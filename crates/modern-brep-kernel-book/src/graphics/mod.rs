@@ -112,6 +112,9 @@ mod tests {
                 TopologyErrorRoot::FromGeometryError { geometry_error } => {
                     geometry_error_to_scene(geometry_error)
                 }
+                TopologyErrorRoot::FromStepError { step_error: _ } => {
+                    Scene::new(vec![], vec![], vec![], vec![])
+                }
             },
             TopologyError::Context {
                 message: _,
@@ -68,13 +68,17 @@ mod tests {
     fn half_sphere_scene() -> Scene {
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
 
-        let mut sphere = primitive_sphere(Point::zero(), EFloat64::from(1.0));
         let edge = primitive_circle(
             Point::zero(),
             -Point::from_f64(0.5, 3.0, 0.5).normalize().unwrap(),
             EFloat64::one(),
         );
-        sphere.boundaries.push(Contour::new(vec![edge.clone()]));
+        let sphere = Face::new(
+            vec![Contour::new(vec![edge.clone()])],
+            primitive_sphere(Point::zero(), EFloat64::from(1.0))
+                .surface()
+                .clone(),
+        );
 
         scene.faces.push((sphere, Color::light_gray()));
         scene
@@ -82,19 +86,23 @@ mod tests {
 
     fn cylinder_scene1() -> Scene {
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
-        let mut cylinder = primitive_cylinder(Point::zero(), Point::unit_z(), EFloat64::one());
-
-        cylinder.boundaries.push(Contour::new(vec![primitive_circle(
-            Point::from_f64(0.0, 0.0, -2.0),
-            Point::unit_z(),
-            EFloat64::one(),
-        )]));
-
-        cylinder.boundaries.push(Contour::new(vec![primitive_circle(
-            Point::from_f64(0.0, 0.0, 2.0),
-            -Point::unit_z(),
-            EFloat64::one(),
-        )]));
+        let cylinder = Face::new(
+            vec![
+                Contour::new(vec![primitive_circle(
+                    Point::from_f64(0.0, 0.0, -2.0),
+                    Point::unit_z(),
+                    EFloat64::one(),
+                )]),
+                Contour::new(vec![primitive_circle(
+                    Point::from_f64(0.0, 0.0, 2.0),
+                    -Point::unit_z(),
+                    EFloat64::one(),
+                )]),
+            ],
+            primitive_cylinder(Point::zero(), Point::unit_z(), EFloat64::one())
+                .surface()
+                .clone(),
+        );
 
         scene.faces.push((cylinder, Color::light_gray()));
         scene
@@ -102,21 +110,25 @@ mod tests {
 
     fn cylinder_scene2() -> Scene {
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
-        let mut cylinder = primitive_cylinder(Point::zero(), Point::unit_z(), EFloat64::one());
-
-        cylinder = cylinder.flip();
-        cylinder.boundaries.push(Contour::new(vec![primitive_circle(
-            Point::from_f64(0.0, 0.0, -2.0),
-            -Point::unit_z(),
-            EFloat64::one(),
-        )]));
-
-        cylinder.boundaries.push(Contour::new(vec![primitive_circle(
-            Point::from_f64(0.0, 0.0, 2.0),
-            Point::unit_z(),
-            EFloat64::one(),
-        )]));
-        cylinder = cylinder.transform(
+        let cylinder = Face::new(
+            vec![
+                Contour::new(vec![primitive_circle(
+                    Point::from_f64(0.0, 0.0, -2.0),
+                    -Point::unit_z(),
+                    EFloat64::one(),
+                )]),
+                Contour::new(vec![primitive_circle(
+                    Point::from_f64(0.0, 0.0, 2.0),
+                    Point::unit_z(),
+                    EFloat64::one(),
+                )]),
+            ],
+            primitive_cylinder(Point::zero(), Point::unit_z(), EFloat64::one())
+                .flip()
+                .surface()
+                .clone(),
+        );
+        let cylinder = cylinder.transform(
             Transform::from_translation(Point::from_f64(0.3, -0.45, 0.12))
                 * Transform::from_euler_angles(
                     EFloat64::from(-90.0 / 180.0 * f64::consts::PI),
@@ -13,6 +13,7 @@ mod tests {
         },
         topology::{
             contour::Contour,
+            face::Face,
             scene::{Color, Scene},
         },
     };
@@ -25,14 +26,17 @@ mod tests {
     async fn test_face_contains_sphere(#[future] renderer: Box<HeadlessRenderer>) {
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
 
-        let mut face = primitive_sphere(Point::zero(), EFloat64::one());
-        face.boundaries.push(Contour::new(vec![primitive_circle(
-            Point::zero(),
-            -Point::from_f64(0.5, 0.5, 0.5),
-            EFloat64::one(),
-        )]));
+        let sphere = primitive_sphere(Point::zero(), EFloat64::one());
+        let face = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::zero(),
+                -Point::from_f64(0.5, 0.5, 0.5),
+                EFloat64::one(),
+            )])],
+            sphere.surface().clone(),
+        );
 
-        for p in face.surface.point_grid(4.0) {
+        for p in face.surface().point_grid(4.0) {
             match face_point_contains(&face, p) {
                 FacePointContains::Inside => scene.points.push((p, Color::green())),
                 FacePointContains::OnEdge(_) => scene.points.push((p, Color::blue())),
@@ -59,14 +63,20 @@ mod tests {
     async fn test_face_contains_rectangle(#[future] renderer: Box<HeadlessRenderer>) {
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
 
-        let mut face = primitive_rectangle(Point::zero(), Point::unit_x(), Point::unit_y());
-        face.boundaries.push(Contour::new(vec![primitive_circle(
-            Point::zero(),
-            Point::from_f64(0.0, 0.0, -1.0),
-            EFloat64::from(0.5),
-        )]));
+        let rectangle = primitive_rectangle(Point::zero(), Point::unit_x(), Point::unit_y());
+        let face = Face::new(
+            vec![
+                rectangle.boundaries()[0].clone(),
+                Contour::new(vec![primitive_circle(
+                    Point::zero(),
+                    Point::from_f64(0.0, 0.0, -1.0),
+                    EFloat64::from(0.5),
+                )]),
+            ],
+            rectangle.surface().clone(),
+        );
 
-        let plane = match &*face.surface {
+        let plane = match &**face.surface() {
             Surface::Plane(p) => p,
             _ => panic!("Surface is not a plane"),
         };
@@ -102,18 +112,22 @@ mod tests {
     async fn test_face_contains_rectangle2(#[future] renderer: Box<HeadlessRenderer>) {
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
 
-        let mut face = primitive_rectangle(Point::zero(), Point::unit_x(), Point::unit_y());
-        face.boundaries.push(
-            primitive_rectangle(
-                Point::zero(),
-                (Point::unit_x() / EFloat64::two()).unwrap(),
-                (Point::unit_y() / EFloat64::two()).unwrap(),
-            )
-            .boundaries[0]
-                .flip(),
+        let rectangle = primitive_rectangle(Point::zero(), Point::unit_x(), Point::unit_y());
+        let face = Face::new(
+            vec![
+                rectangle.boundaries()[0].clone(),
+                primitive_rectangle(
+                    Point::zero(),
+                    (Point::unit_x() / EFloat64::two()).unwrap(),
+                    (Point::unit_y() / EFloat64::two()).unwrap(),
+                )
+                .boundaries()[0]
+                    .flip(),
+            ],
+            rectangle.surface().clone(),
         );
 
-        let plane = match &*face.surface {
+        let plane = match &**face.surface() {
             Surface::Plane(p) => p,
             _ => panic!("Surface is not a plane"),
         };